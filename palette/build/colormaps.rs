@@ -0,0 +1,61 @@
+#[cfg(feature = "colormaps")]
+pub fn build() {
+    use std::fs::File;
+    use std::io::{BufRead, BufReader, BufWriter, Write};
+    use std::path::Path;
+
+    let out_dir = ::std::env::var("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join("colormaps.rs");
+
+    let reader =
+        BufReader::new(File::open("build/colormaps.txt").expect("could not open colormaps.txt"));
+    let mut writer =
+        BufWriter::new(File::create(dest_path).expect("couldn't create colormaps.rs"));
+
+    let mut lines = reader.lines();
+    while let Some(line) = lines.next() {
+        let line = line.unwrap();
+
+        //Each table starts with a line with its name and length
+        if line.is_empty() {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let name = parts.next().expect("couldn't get the colormap name");
+        let length: usize = parts
+            .next()
+            .and_then(|length| length.parse().ok())
+            .unwrap_or_else(|| panic!("couldn't get the length of {}", name));
+
+        writeln!(
+            writer,
+            "const {}: [[f64; 3]; {}] = [",
+            name.to_uppercase(),
+            length
+        )
+        .unwrap();
+
+        for i in 0..length {
+            let line = lines
+                .next()
+                .unwrap_or_else(|| panic!("{} has fewer than {} colors", name, length))
+                .unwrap();
+            let mut rgb = line.split(',').map(|value| value.trim().parse::<f64>());
+            let mut component = |channel: &str| {
+                rgb.next()
+                    .and_then(|value| value.ok())
+                    .unwrap_or_else(|| panic!("couldn't get {} for color {} of {}", channel, i, name))
+            };
+            let red = component("red");
+            let green = component("green");
+            let blue = component("blue");
+
+            writeln!(writer, "    [{:?}, {:?}, {:?}],", red, green, blue).unwrap();
+        }
+
+        writeln!(writer, "];").unwrap();
+    }
+}
+
+#[cfg(not(feature = "colormaps"))]
+pub fn build() {}
@@ -1,8 +1,12 @@
 #[cfg(feature = "phf_codegen")]
 extern crate phf_codegen;
 
+mod colormaps;
 mod named;
+mod srgb;
 
 fn main() {
+    colormaps::build();
     named::build();
+    srgb::build();
 }
\ No newline at end of file
@@ -2,15 +2,25 @@ use std::fs::File;
 
 #[cfg(feature = "named")]
 pub fn build() {
+    gen_colors("build/svg_colors.txt", "named.rs");
+
+    #[cfg(feature = "named_x11")]
+    gen_colors("build/x11_colors.txt", "named_x11.rs");
+}
+
+#[cfg(feature = "named")]
+fn gen_colors(source: &str, destination: &str) {
     use std::path::Path;
     use std::io::{BufRead, BufReader, Write};
 
     let out_dir = ::std::env::var("OUT_DIR").unwrap();
-    let dest_path = Path::new(&out_dir).join("named.rs");
+    let dest_path = Path::new(&out_dir).join(destination);
 
-    let reader =
-        BufReader::new(File::open("build/svg_colors.txt").expect("could not open svg_colors.txt"));
-    let mut writer = File::create(dest_path).expect("couldn't create named.rs");
+    let reader = BufReader::new(
+        File::open(source).unwrap_or_else(|_| panic!("could not open {}", source)),
+    );
+    let mut writer =
+        File::create(dest_path).unwrap_or_else(|_| panic!("couldn't create {}", destination));
     let mut entries = vec![];
 
     for line in reader.lines() {
@@ -31,15 +41,28 @@ pub fn build() {
             .and_then(|r| r.trim().parse().ok())
             .expect(&format!("couldn't get blue for {}", name));
 
-        writeln!(writer, "\n///<div style=\"display: inline-block; width: 3em; height: 1em; border: 1px solid black; background: {0};\"></div>", name).unwrap();
+        writeln!(writer, "\n///<div style=\"display: inline-block; width: 3em; height: 1em; border: 1px solid black; background: rgb({}, {}, {});\"></div>", red, green, blue).unwrap();
         writeln!(writer, "pub const {}: ::rgb::Srgb<u8> = ::rgb::Srgb {{ red: {}, green: {}, blue: {}, standard: ::core::marker::PhantomData }};", name.to_uppercase(), red, green, blue).unwrap();
 
         entries.push((name.to_owned(), name.to_uppercase()));
     }
 
+    gen_list(&mut writer, &entries);
     gen_from_str(&mut writer, &entries)
 }
 
+#[cfg(feature = "named")]
+fn gen_list(writer: &mut File, entries: &[(String, String)]) {
+    use std::io::Write;
+
+    writeln!(writer, "\n///All of the colors, with their lower case names.").unwrap();
+    writeln!(writer, "pub static ALL: &[(&str, ::rgb::Srgb<u8>)] = &[").unwrap();
+    for (key, value) in entries {
+        writeln!(writer, "    (\"{}\", {}),", key, value).unwrap();
+    }
+    writeln!(writer, "];").unwrap();
+}
+
 #[cfg(feature = "named_from_str")]
 fn gen_from_str(writer: &mut File, entries: &[(String, String)]) {
     use std::io::Write;
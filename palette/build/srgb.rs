@@ -0,0 +1,32 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub fn build() {
+    let out_dir = ::std::env::var("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join("srgb_tables.rs");
+    let mut writer = BufWriter::new(
+        File::create(dest_path).expect("couldn't create srgb_tables.rs"),
+    );
+
+    gen_table(&mut writer, "U8_INTO_LINEAR", 256);
+    gen_table(&mut writer, "U16_INTO_LINEAR", 65536);
+}
+
+fn gen_table(writer: &mut BufWriter<File>, name: &str, size: usize) {
+    let max = (size - 1) as f64;
+
+    writeln!(writer, "static {}: [f32; {}] = [", name, size).unwrap();
+    for i in 0..size {
+        writeln!(writer, "    {:?},", into_linear(i as f64 / max) as f32).unwrap();
+    }
+    writeln!(writer, "];").unwrap();
+}
+
+fn into_linear(x: f64) -> f64 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
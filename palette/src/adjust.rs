@@ -0,0 +1,435 @@
+//!Levels and curves adjustments, as in image editors.
+//!
+//![`Levels`](struct.Levels.html) remaps a range of input values to a range of
+//!output values, with a gamma adjustment of the midtones in between, and a
+//![`Curve`](struct.Curve.html) maps values with a smooth curve through a set
+//!of control points. Both work on component values from `0.0` to `1.0`.
+//!
+//!The adjustments are applied to the components of RGB colors, in the space
+//!and encoding of the color type. Adjusting `Srgb` colors is what image
+//!editors usually do, while `LinSrgb` gives results that are proportional to
+//!the light intensity. An adjustment can either be applied to each component
+//!individually, which changes the contrast of each component and may shift
+//!the hue of saturated colors, or only to the luma of the color, which keeps
+//!the differences between the components.
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::adjust::{Adjustment, Levels};
+//!
+//!//Stretch the contrast of a dull image
+//!let levels = Levels::new(0.1, 0.9);
+//!
+//!let mut image = [Srgb::new(0.1, 0.5, 0.9), Srgb::new(0.3, 0.3, 0.3)];
+//!levels.apply_slice(&mut image);
+//!assert_eq!(image[0], Srgb::new(0.0, 0.5, 1.0));
+//!```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use float::Float;
+
+use matrix::rgb_to_xyz_matrix;
+use rgb::{Rgb, RgbStandard};
+use {clamp, Component};
+
+///An adjustment of component values.
+pub trait Adjustment<T: Float> {
+    ///Adjust a single component value.
+    fn map(&self, value: T) -> T;
+
+    ///Adjust each of the components of an RGB color.
+    fn apply<S>(&self, color: Rgb<S, T>) -> Rgb<S, T>
+    where
+        S: RgbStandard,
+        T: Component,
+    {
+        Rgb::new(
+            self.map(color.red),
+            self.map(color.green),
+            self.map(color.blue),
+        )
+    }
+
+    ///Adjust the luma of an RGB color.
+    ///
+    ///The luma is the weighted sum of the components, with the luminance
+    ///weights of the RGB space, and the adjustment of the luma is added to
+    ///all of the components. The result is not clamped.
+    fn apply_luma<S>(&self, color: Rgb<S, T>) -> Rgb<S, T>
+    where
+        S: RgbStandard,
+        T: Component,
+    {
+        adjust_luma(self, color, &luma_weights::<S, T>())
+    }
+
+    ///Adjust each of the components of the colors in a slice.
+    fn apply_slice<S>(&self, colors: &mut [Rgb<S, T>])
+    where
+        S: RgbStandard,
+        T: Component,
+    {
+        for color in colors {
+            *color = self.apply(*color);
+        }
+    }
+
+    ///Adjust the luma of the colors in a slice.
+    fn apply_luma_slice<S>(&self, colors: &mut [Rgb<S, T>])
+    where
+        S: RgbStandard,
+        T: Component,
+    {
+        let weights = luma_weights::<S, T>();
+        for color in colors {
+            *color = adjust_luma(self, *color, &weights);
+        }
+    }
+}
+
+///A levels adjustment.
+///
+///The input black and white points are mapped to the output black and white
+///points, and the values in between are adjusted with a gamma curve. Input
+///values outside of the input range are clamped to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Levels<T: Float = f32> {
+    ///The input value that becomes the output black point.
+    pub input_black: T,
+
+    ///The input value that becomes the output white point.
+    pub input_white: T,
+
+    ///The gamma of the midtones. Values above `1.0` make the midtones
+    ///brighter and values below `1.0` make them darker.
+    pub gamma: T,
+
+    ///The darkest output value.
+    pub output_black: T,
+
+    ///The brightest output value.
+    pub output_white: T,
+}
+
+impl<T: Float> Levels<T> {
+    ///Create a levels adjustment that maps the input range from
+    ///`input_black` to `input_white` to the full output range.
+    pub fn new(input_black: T, input_white: T) -> Levels<T> {
+        Levels {
+            input_black: input_black,
+            input_white: input_white,
+            gamma: T::one(),
+            output_black: T::zero(),
+            output_white: T::one(),
+        }
+    }
+
+    ///Set the gamma of the midtones.
+    pub fn with_gamma(mut self, gamma: T) -> Levels<T> {
+        self.gamma = gamma;
+        self
+    }
+
+    ///Set the output black and white points.
+    pub fn with_output(mut self, black: T, white: T) -> Levels<T> {
+        self.output_black = black;
+        self.output_white = white;
+        self
+    }
+}
+
+impl<T: Float> Default for Levels<T> {
+    fn default() -> Levels<T> {
+        Levels::new(T::zero(), T::one())
+    }
+}
+
+impl<T: Float> Adjustment<T> for Levels<T> {
+    fn map(&self, value: T) -> T {
+        let range = self.input_white - self.input_black;
+        let normalized = if range > T::zero() {
+            clamp((value - self.input_black) / range, T::zero(), T::one())
+        } else if value < self.input_black {
+            T::zero()
+        } else {
+            T::one()
+        };
+
+        let adjusted = if self.gamma > T::zero() {
+            normalized.powf(T::one() / self.gamma)
+        } else {
+            normalized
+        };
+
+        self.output_black + (self.output_white - self.output_black) * adjusted
+    }
+}
+
+///A curve through a set of control points.
+///
+///The curve is a monotone cubic spline, so it's smooth and doesn't overshoot
+///the control points. It's increasing wherever the control points are
+///increasing. Values before the first or after the last control point get
+///the value of that control point.
+///
+///```
+///use palette::adjust::{Adjustment, Curve};
+///
+/////An S-curve that increases the contrast
+///let curve = Curve::new(&[(0.0, 0.0), (0.25, 0.2), (0.75, 0.8), (1.0, 1.0)]);
+///
+///assert_eq!(curve.map(0.25), 0.2);
+///assert!(curve.map(0.1) < 0.1);
+///assert!(curve.map(0.9) > 0.9);
+///```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curve<T: Float = f32> {
+    points: Vec<(T, T)>,
+    tangents: Vec<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Float> Curve<T> {
+    ///Create a curve through the `(input, output)` control points, that may
+    ///be in any order.
+    ///
+    ///Panics if there are fewer than two control points, or if two of them
+    ///have the same input.
+    pub fn new(points: &[(T, T)]) -> Curve<T> {
+        assert!(points.len() >= 2, "a curve must have at least two points");
+
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .unwrap_or(::core::cmp::Ordering::Equal)
+        });
+        assert!(
+            points.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "the points of a curve must have different inputs"
+        );
+
+        //The Fritsch-Carlson method for monotone tangents
+        let slopes: Vec<T> = points
+            .windows(2)
+            .map(|pair| (pair[1].1 - pair[0].1) / (pair[1].0 - pair[0].0))
+            .collect();
+
+        let mut tangents = Vec::with_capacity(points.len());
+        tangents.push(slopes[0]);
+        for pair in slopes.windows(2) {
+            if pair[0] * pair[1] <= T::zero() {
+                tangents.push(T::zero());
+            } else {
+                tangents.push((pair[0] + pair[1]) / (T::one() + T::one()));
+            }
+        }
+        tangents.push(slopes[slopes.len() - 1]);
+
+        let three = T::one() + T::one() + T::one();
+        for (index, &slope) in slopes.iter().enumerate() {
+            if slope == T::zero() {
+                tangents[index] = T::zero();
+                tangents[index + 1] = T::zero();
+                continue;
+            }
+
+            let a = tangents[index] / slope;
+            let b = tangents[index + 1] / slope;
+            let length = (a * a + b * b).sqrt();
+            if length > three {
+                tangents[index] = three * a * slope / length;
+                tangents[index + 1] = three * b * slope / length;
+            }
+        }
+
+        Curve {
+            points: points,
+            tangents: tangents,
+        }
+    }
+
+    ///The control points of the curve, ordered by their input.
+    pub fn points(&self) -> &[(T, T)] {
+        &self.points
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Float> Adjustment<T> for Curve<T> {
+    fn map(&self, value: T) -> T {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if value <= first.0 {
+            return first.1;
+        }
+        if value >= last.0 {
+            return last.1;
+        }
+
+        let index = self
+            .points
+            .iter()
+            .position(|point| point.0 > value)
+            .unwrap_or(1)
+            - 1;
+        let (x0, y0) = self.points[index];
+        let (x1, y1) = self.points[index + 1];
+        let width = x1 - x0;
+        let t = (value - x0) / width;
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let two = T::one() + T::one();
+        let three = two + T::one();
+
+        (two * t3 - three * t2 + T::one()) * y0
+            + (t3 - two * t2 + t) * width * self.tangents[index]
+            + (three * t2 - two * t3) * y1
+            + (t3 - t2) * width * self.tangents[index + 1]
+    }
+}
+
+///The luminance weights of the RGB space of `S`.
+fn luma_weights<S, T>() -> [T; 3]
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let matrix = rgb_to_xyz_matrix::<S::Space, T>();
+    [matrix[3], matrix[4], matrix[5]]
+}
+
+fn adjust_luma<A, S, T>(adjustment: &A, color: Rgb<S, T>, weights: &[T; 3]) -> Rgb<S, T>
+where
+    A: Adjustment<T> + ?Sized,
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let luma = weights[0] * color.red + weights[1] * color.green + weights[2] * color.blue;
+    let difference = adjustment.map(luma) - luma;
+
+    Rgb::new(
+        color.red + difference,
+        color.green + difference,
+        color.blue + difference,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "alloc")]
+    use super::Curve;
+    use super::{Adjustment, Levels};
+    use {LinSrgb, Srgb};
+
+    #[test]
+    fn levels() {
+        let levels = Levels::new(0.2f64, 0.6).with_output(0.1, 0.9);
+
+        assert_relative_eq!(levels.map(0.0), 0.1);
+        assert_relative_eq!(levels.map(0.2), 0.1);
+        assert_relative_eq!(levels.map(0.4), 0.5);
+        assert_relative_eq!(levels.map(0.6), 0.9);
+        assert_relative_eq!(levels.map(1.0), 0.9);
+    }
+
+    #[test]
+    fn levels_gamma() {
+        let brighter = Levels::default().with_gamma(2.0f64);
+        let darker = Levels::default().with_gamma(0.5f64);
+
+        assert_relative_eq!(brighter.map(0.25), 0.5);
+        assert_relative_eq!(darker.map(0.5), 0.25);
+        assert_relative_eq!(brighter.map(0.0), 0.0);
+        assert_relative_eq!(brighter.map(1.0), 1.0);
+    }
+
+    #[test]
+    fn inverted_output() {
+        let invert = Levels::default().with_output(1.0f64, 0.0);
+        assert_eq!(
+            invert.apply(Srgb::new(0.2, 0.5, 1.0)),
+            Srgb::new(0.8, 0.5, 0.0)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn curve_through_points() {
+        let points = [(1.0f64, 0.9), (0.0, 0.1), (0.3, 0.2), (0.6, 0.7)];
+        let curve = Curve::new(&points);
+
+        assert_eq!(curve.points()[0], (0.0, 0.1));
+        for &(input, output) in &points {
+            assert_relative_eq!(curve.map(input), output, epsilon = 1e-12);
+        }
+        assert_eq!(curve.map(-1.0), 0.1);
+        assert_eq!(curve.map(2.0), 0.9);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn curve_is_monotonic() {
+        let curve = Curve::new(&[
+            (0.0f64, 0.0),
+            (0.1, 0.5),
+            (0.2, 0.55),
+            (0.9, 0.6),
+            (1.0, 1.0),
+        ]);
+
+        let mut previous = curve.map(0.0);
+        for i in 1..=1000 {
+            let current = curve.map(i as f64 / 1000.0);
+            assert!(current >= previous, "decreased at {}", i);
+            assert!(current <= 1.0);
+            previous = current;
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn curve_flat_segment() {
+        let curve = Curve::new(&[(0.0f64, 0.0), (0.4, 0.5), (0.6, 0.5), (1.0, 1.0)]);
+
+        for i in 0..=10 {
+            assert_eq!(curve.map(0.4 + i as f64 * 0.02), 0.5);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn linear_curve() {
+        let curve = Curve::new(&[(0.0f64, 0.2), (1.0, 0.8)]);
+        assert_relative_eq!(curve.map(0.5), 0.5, epsilon = 1e-12);
+        assert_relative_eq!(curve.map(0.25), 0.35, epsilon = 1e-12);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "the points of a curve must have different inputs")]
+    fn curve_same_inputs() {
+        Curve::new(&[(0.0, 0.0), (0.5, 0.2), (0.5, 0.8)]);
+    }
+
+    #[test]
+    fn luma() {
+        let levels = Levels::new(0.0f64, 0.5);
+        let color = LinSrgb::new(0.3, 0.2, 0.1);
+        let adjusted = levels.apply_luma(color);
+
+        assert_relative_eq!(adjusted.red - adjusted.green, 0.1, epsilon = 1e-12);
+        assert_relative_eq!(adjusted.green - adjusted.blue, 0.1, epsilon = 1e-12);
+
+        let luma =
+            0.2126729 * adjusted.red + 0.7151522 * adjusted.green + 0.0721750 * adjusted.blue;
+        let original = 0.2126729 * color.red + 0.7151522 * color.green + 0.0721750 * color.blue;
+        assert_relative_eq!(luma, original * 2.0, epsilon = 1e-6);
+
+        let mut colors = [color; 3];
+        levels.apply_luma_slice(&mut colors);
+        assert_eq!(colors[2], adjusted);
+    }
+}
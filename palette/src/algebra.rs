@@ -0,0 +1,123 @@
+//!Interoperability with [`nalgebra`](https://crates.io/crates/nalgebra).
+//!
+//!The color types can be converted to and from `Vector3` and, with an alpha
+//!component, `Vector4`, with the components in the same order as in the
+//!color types. The conversion matrices, such as the ones from
+//![`TransformMatrix`](../chromatic_adaptation/trait.TransformMatrix.html)
+//!and [`Deficiency`](../cvd/enum.Deficiency.html), are stored in row-major
+//!order and can be converted with [`to_matrix3`](fn.to_matrix3.html) and
+//![`from_matrix3`](fn.from_matrix3.html).
+//!
+//!```
+//!extern crate nalgebra;
+//!extern crate palette;
+//!
+//!use nalgebra::Vector3;
+//!use palette::LinSrgb;
+//!use palette::algebra::to_matrix3;
+//!use palette::cvd::Deficiency;
+//!
+//!fn main() {
+//!    let simulation = to_matrix3(Deficiency::Protan.matrix(1.0f32));
+//!    let color: Vector3<f32> = LinSrgb::new(0.8, 0.3, 0.1).into();
+//!
+//!    let simulated = LinSrgb::from(simulation * color);
+//!    assert_eq!(simulated, Deficiency::Protan.simulate(LinSrgb::new(0.8, 0.3, 0.1), 1.0));
+//!}
+//!```
+
+use nalgebra::{Matrix3, Scalar, Vector3, Vector4};
+
+use matrix::Mat3;
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {Alpha, Hsl, Hsv, Hwb, Lab, Lch, Pixel, Xyz, Yxy};
+
+///Convert a row-major conversion matrix to an `nalgebra` matrix.
+pub fn to_matrix3<T: Scalar>(matrix: Mat3<T>) -> Matrix3<T> {
+    Matrix3::from_row_slice(&matrix)
+}
+
+///Convert an `nalgebra` matrix to a row-major conversion matrix.
+pub fn from_matrix3<T: Scalar + Copy>(matrix: &Matrix3<T>) -> Mat3<T> {
+    let mut result = [matrix[(0, 0)]; 9];
+
+    for (index, value) in result.iter_mut().enumerate() {
+        *value = matrix[(index / 3, index % 3)];
+    }
+
+    result
+}
+
+macro_rules! impl_nalgebra {
+    ($ty:ident<$param:ident: $bound:ident>) => {
+        impl_nalgebra!(@vector $ty<$param: $bound>, f32);
+        impl_nalgebra!(@vector $ty<$param: $bound>, f64);
+    };
+
+    (@vector $ty:ident<$param:ident: $bound:ident>, $float:ty) => {
+        impl<$param: $bound> From<$ty<$param, $float>> for Vector3<$float> {
+            fn from(color: $ty<$param, $float>) -> Vector3<$float> {
+                Vector3::from_column_slice(color.as_raw())
+            }
+        }
+
+        impl<$param: $bound> From<Vector3<$float>> for $ty<$param, $float> {
+            fn from(vector: Vector3<$float>) -> $ty<$param, $float> {
+                *$ty::from_raw(vector.as_slice())
+            }
+        }
+
+        impl<$param: $bound> From<Alpha<$ty<$param, $float>, $float>> for Vector4<$float> {
+            fn from(color: Alpha<$ty<$param, $float>, $float>) -> Vector4<$float> {
+                Vector4::from_column_slice(color.as_raw())
+            }
+        }
+
+        impl<$param: $bound> From<Vector4<$float>> for Alpha<$ty<$param, $float>, $float> {
+            fn from(vector: Vector4<$float>) -> Alpha<$ty<$param, $float>, $float> {
+                *Alpha::from_raw(vector.as_slice())
+            }
+        }
+    };
+}
+
+impl_nalgebra!(Rgb<S: RgbStandard>);
+impl_nalgebra!(Hsl<S: RgbSpace>);
+impl_nalgebra!(Hsv<S: RgbSpace>);
+impl_nalgebra!(Hwb<S: RgbSpace>);
+impl_nalgebra!(Lab<Wp: WhitePoint>);
+impl_nalgebra!(Lch<Wp: WhitePoint>);
+impl_nalgebra!(Xyz<Wp: WhitePoint>);
+impl_nalgebra!(Yxy<Wp: WhitePoint>);
+
+#[cfg(test)]
+mod test {
+    use nalgebra::{Matrix3, Vector3, Vector4};
+
+    use super::{from_matrix3, to_matrix3};
+    use {Lab, LinSrgb, LinSrgba};
+
+    #[test]
+    fn matrices() {
+        let matrix = [1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let converted = to_matrix3(matrix);
+
+        assert_eq!(converted, Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0));
+        assert_eq!(from_matrix3(&converted), matrix);
+    }
+
+    #[test]
+    fn vectors() {
+        let vector: Vector3<f32> = LinSrgb::new(0.1, 0.2, 0.3).into();
+        assert_eq!(vector, Vector3::new(0.1, 0.2, 0.3));
+        assert_eq!(LinSrgb::from(vector), LinSrgb::new(0.1, 0.2, 0.3));
+
+        let vector: Vector4<f64> = LinSrgba::new(0.1, 0.2, 0.3, 0.4).into();
+        assert_eq!(vector, Vector4::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(LinSrgba::from(vector), LinSrgba::new(0.1, 0.2, 0.3, 0.4));
+
+        let lab: Lab<_, f64> = Vector3::new(50.0, 10.0, -20.0).into();
+        assert_eq!(lab, Lab::new(50.0, 10.0, -20.0));
+    }
+}
@@ -5,11 +5,119 @@ use float::Float;
 
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 
-use {clamp, Blend, Component, ComponentWise, GetHue, Hue, Limited, Mix, Pixel, Saturate, Shade};
+use {
+    clamp, Blend, Complement, Component, ComponentWise, GetHue, Hue, HueInterpolation, Invert,
+    Limited, Mix, Pixel, Saturate, SaturateFixed, Shade,
+};
 use blend::PreAlpha;
 use encoding::pixel::RawPixel;
 
+///A trait for color types that can have or be given transparency.
+///
+///Colors without an alpha component are wrapped in
+///[`Alpha`](struct.Alpha.html) when they are given one, while `Alpha` and
+///colors with their own alpha component just replace its value.
+///
+///```
+///use palette::{Srgb, Srgba, WithAlpha};
+///
+///let color = Srgb::new(1.0f32, 0.5, 0.0);
+///let transparent: Srgba = color.with_alpha(0.5);
+///assert_eq!(transparent.alpha, 0.5);
+///
+///let (opaque, alpha) = transparent.split();
+///assert_eq!(opaque, color);
+///assert_eq!(alpha, 0.5);
+///assert_eq!(transparent.without_alpha(), color);
+///```
+///
+///# Deriving
+///
+///`WithAlpha` can be derived for custom color types, so they work with the
+///rest of the library in the same way as the built in types. A type without
+///an alpha component is wrapped in `Alpha`, and a field with the
+///`#[palette_alpha]` attribute is used as the alpha component of a type
+///that has one. Its type has to implement `Component`.
+///
+///```
+///#[macro_use]
+///extern crate palette;
+///
+///use palette::{Alpha, WithAlpha};
+///
+///#[derive(Clone, Copy, PartialEq, Debug, WithAlpha)]
+///struct Gray(f32);
+///
+///#[derive(Clone, Copy, PartialEq, Debug, WithAlpha)]
+///struct CssRgb {
+///    red: u8,
+///    green: u8,
+///    blue: u8,
+///    #[palette_alpha]
+///    alpha: f32,
+///}
+///
+///fn main() {
+///    let gray: Alpha<Gray, u8> = Gray(0.5).with_alpha(128);
+///    assert_eq!(gray.alpha, 128);
+///
+///    let css = CssRgb { red: 255, green: 0, blue: 0, alpha: 1.0 };
+///    let transparent = css.with_alpha(0.25);
+///    assert_eq!(transparent.alpha, 0.25);
+///    assert_eq!(transparent.without_alpha(), css);
+///}
+///```
+pub trait WithAlpha<A: Component>: Sized {
+    ///The opaque color type, without any transparency.
+    type Color;
+
+    ///The color type with transparency.
+    type WithAlpha: WithAlpha<A, Color = Self::Color, WithAlpha = Self::WithAlpha>;
+
+    ///Give the color the transparency `alpha`, or replace its current
+    ///transparency.
+    fn with_alpha(self, alpha: A) -> Self::WithAlpha;
+
+    ///Remove the transparency of the color, making it opaque.
+    fn without_alpha(self) -> Self::Color;
+
+    ///Split the color into an opaque color and its transparency. Colors
+    ///without transparency are fully opaque.
+    fn split(self) -> (Self::Color, A);
+
+    ///Make the color fully opaque.
+    fn opaque(self) -> Self::WithAlpha {
+        self.with_alpha(A::max_intensity())
+    }
+
+    ///Make the color fully transparent.
+    fn transparent(self) -> Self::WithAlpha {
+        self.with_alpha(A::zero())
+    }
+}
+
+impl<C, A: Component> WithAlpha<A> for Alpha<C, A> {
+    type Color = C;
+    type WithAlpha = Self;
+
+    fn with_alpha(mut self, alpha: A) -> Self::WithAlpha {
+        self.alpha = alpha;
+        self
+    }
+
+    fn without_alpha(self) -> Self::Color {
+        self.color
+    }
+
+    fn split(self) -> (Self::Color, A) {
+        (self.color, self.alpha)
+    }
+}
+
 ///An alpha component wrapper for colors.
+///
+///`Alpha` is `#[repr(C)]`, so the alpha component is placed directly after
+///the components of the color in memory.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[repr(C)]
@@ -46,6 +154,18 @@ impl<C: Mix> Mix for Alpha<C, C::Scalar> {
             alpha: self.alpha + factor * (other.alpha - self.alpha),
         }
     }
+
+    fn mix_with_hue(
+        &self,
+        other: &Alpha<C, C::Scalar>,
+        factor: C::Scalar,
+        hue_interpolation: HueInterpolation,
+    ) -> Alpha<C, C::Scalar> {
+        Alpha {
+            color: self.color.mix_with_hue(&other.color, factor, hue_interpolation),
+            alpha: self.alpha + factor * (other.alpha - self.alpha),
+        }
+    }
 }
 
 impl<C: Shade> Shade for Alpha<C, C::Scalar> {
@@ -83,6 +203,24 @@ impl<C: Hue, T: Clone> Hue for Alpha<C, T> {
     }
 }
 
+impl<C: Invert, T: Clone> Invert for Alpha<C, T> {
+    fn invert(&self) -> Alpha<C, T> {
+        Alpha {
+            color: self.color.invert(),
+            alpha: self.alpha.clone(),
+        }
+    }
+}
+
+impl<C: Complement, T: Clone> Complement for Alpha<C, T> {
+    fn complement(&self) -> Alpha<C, T> {
+        Alpha {
+            color: self.color.complement(),
+            alpha: self.alpha.clone(),
+        }
+    }
+}
+
 impl<C: Saturate> Saturate for Alpha<C, C::Scalar> {
     type Scalar = C::Scalar;
 
@@ -94,6 +232,15 @@ impl<C: Saturate> Saturate for Alpha<C, C::Scalar> {
     }
 }
 
+impl<C: SaturateFixed> SaturateFixed for Alpha<C, C::Scalar> {
+    fn saturate_fixed(&self, amount: C::Scalar) -> Alpha<C, C::Scalar> {
+        Alpha {
+            color: self.color.saturate_fixed(amount),
+            alpha: self.alpha,
+        }
+    }
+}
+
 impl<C: Limited, T: Component> Limited for Alpha<C, T> {
     fn is_valid(&self) -> bool {
         self.color.is_valid() && self.alpha >= T::zero() && self.alpha <= T::max_intensity()
@@ -146,6 +293,24 @@ impl<C: ComponentWise<Scalar = T>, T: Clone> ComponentWise for Alpha<C, T> {
     }
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, T> ::bytemuck::Zeroable for Alpha<C, T>
+where
+    C: ::bytemuck::Zeroable,
+    T: ::bytemuck::Zeroable,
+{
+}
+
+// The color is made of `T` components when it implements `Pixel<T>`, so
+// there's no padding between the color and the alpha component.
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, T> ::bytemuck::Pod for Alpha<C, T>
+where
+    C: ::bytemuck::Pod + Pixel<T>,
+    T: ::bytemuck::Pod,
+{
+}
+
 unsafe impl<T, C: Pixel<T>> Pixel<T> for Alpha<C, T> {
     const CHANNELS: usize = C::CHANNELS + 1;
 }
@@ -388,12 +553,24 @@ impl<C, T: Component> From<C> for Alpha<C, T> {
     }
 }
 
+///Formats the color followed by the alpha component, as hexadecimal numbers.
+///The alternate flag adds a leading `#`.
+///
+///```
+///use palette::Srgba;
+///
+///assert_eq!(format!("{:#x}", Srgba::new(255u8, 136, 0, 128)), "#ff880080");
+///```
 impl<C, T> fmt::LowerHex for Alpha<C, T>
 where
     T: fmt::LowerHex,
     C: fmt::LowerHex,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(
             f,
@@ -411,6 +588,10 @@ where
     C: fmt::UpperHex,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(
             f,
@@ -422,11 +603,76 @@ where
     }
 }
 
+///Samples the color and the alpha independently, using their own
+///distributions.
+#[cfg(feature = "rand")]
+impl<C, T> ::rand::distributions::Distribution<Alpha<C, T>> for ::rand::distributions::Standard
+where
+    ::rand::distributions::Standard:
+        ::rand::distributions::Distribution<C> + ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Alpha<C, T> {
+        Alpha {
+            color: rng.gen(),
+            alpha: rng.gen(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rgb::Rgba;
     use encoding::Srgb;
 
+    #[test]
+    fn invert_and_complement_keep_alpha() {
+        use {Complement, Hsla, Invert};
+
+        let color = Rgba::<Srgb, u8>::new(255, 64, 0, 128);
+        assert_eq!(color.invert(), Rgba::new(0, 191, 255, 128));
+
+        let color = Hsla::new(350.0, 0.5, 0.5, 0.25);
+        assert_relative_eq!(color.complement(), Hsla::new(170.0, 0.5, 0.5, 0.25));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_cast() {
+        use Hsla;
+
+        let colors = [Rgba::<Srgb, u8>::new(1, 2, 3, 4), Rgba::new(5, 6, 7, 8)];
+        let bytes: &[u8] = ::bytemuck::cast_slice(&colors);
+        assert_eq!(bytes, &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let components = [90.0f32, 0.5, 0.25, 1.0];
+        let colors: &[Hsla] = ::bytemuck::cast_slice(&components);
+        assert_eq!(colors, &[Hsla::new(90.0, 0.5, 0.25, 1.0)]);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_sampling() {
+        use rand::rngs::mock::StepRng;
+        use rand::Rng;
+        use {Hsla, Hwb, Lab, Lch, Limited, Xyz};
+
+        let mut rng = StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0..1000 {
+            let hsla: Hsla<Srgb, f64> = rng.gen();
+            assert!(hsla.is_valid());
+            assert!(hsla.hue.to_positive_degrees() < 360.0);
+
+            assert!(rng.gen::<Rgba<Srgb, f32>>().is_valid());
+            assert!(rng.gen::<Hwb>().is_valid());
+            assert!(rng.gen::<Lab>().is_valid());
+            assert!(rng.gen::<Xyz>().is_valid());
+
+            let lch: Lch = rng.gen();
+            assert!(lch.is_valid() && lch.chroma < 128.0);
+        }
+    }
+
     #[test]
     fn lower_hex() {
         assert_eq!(
@@ -475,6 +721,13 @@ mod test {
         );
     }
 
+    #[test]
+    fn hex_alternate() {
+        let color = Rgba::<Srgb, u8>::new(171, 193, 35, 18);
+        assert_eq!(format!("{:#x}", color), "#abc12312");
+        assert_eq!(format!("{:#X}", color), "#ABC12312");
+    }
+
     #[test]
     fn upper_hex() {
         assert_eq!(
@@ -542,4 +795,49 @@ mod test {
 
         assert_eq!(deserialized, Rgba::<Srgb>::new(0.3, 0.8, 0.1, 0.5));
     }
+
+    #[test]
+    fn with_alpha() {
+        use white_point::D65;
+        use {Lab, Laba, LinLuma, WithAlpha};
+
+        let color = Lab::new(50.0f64, 10.0, -20.0);
+        assert_eq!(color.with_alpha(0.5), Laba::new(50.0, 10.0, -20.0, 0.5));
+        assert_eq!(color.with_alpha(0.5).opaque().alpha, 1.0);
+        assert_eq!(color.with_alpha(0.5).transparent().alpha, 0.0);
+        assert_eq!(WithAlpha::<f64>::split(color), (color, 1.0));
+
+        let luma = LinLuma::<D65, f32>::new(0.5).with_alpha(64u8);
+        assert_eq!(luma.with_alpha(255).alpha, 255);
+        assert_eq!(luma.split(), (LinLuma::new(0.5), 64));
+        assert_eq!(luma.without_alpha(), LinLuma::new(0.5));
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, WithAlpha)]
+    #[palette_internal]
+    struct Gray<T>(T);
+
+    #[derive(Clone, Copy, Debug, PartialEq, WithAlpha)]
+    #[palette_internal]
+    struct GrayAlpha<T> {
+        value: T,
+        #[palette_alpha]
+        alpha: T,
+    }
+
+    #[test]
+    fn derived_with_alpha() {
+        use WithAlpha;
+
+        let gray = Gray(0.5f64).with_alpha(0.25);
+        assert_eq!(gray.color, Gray(0.5));
+        assert_eq!(gray.alpha, 0.25);
+
+        let gray = GrayAlpha {
+            value: 100u8,
+            alpha: 50,
+        };
+        assert_eq!(gray.split(), (GrayAlpha { value: 100, alpha: 255 }, 50));
+        assert_eq!(gray.transparent().alpha, 0);
+    }
 }
@@ -4,7 +4,7 @@ use float::Float;
 use num_traits::float::FloatCore;
 
 use {cast, clamp, ComponentWise};
-use blend::{BlendFunction, PreAlpha};
+use blend::{BlendFunction, PorterDuff, PreAlpha};
 
 ///A trait for colors that can be blended together.
 ///
@@ -151,6 +151,29 @@ where
         Self::from_premultiplied(result)
     }
 
+    ///Place `other` over `self`. This is the reverse of `over`.
+    fn destination_over(self, other: Self) -> Self {
+        self.blend(other, PorterDuff::DestinationOver)
+    }
+
+    ///Results in the parts of `other` that overlaps the visible parts of
+    ///`self`. This is the reverse of `inside`.
+    fn destination_in(self, other: Self) -> Self {
+        self.blend(other, PorterDuff::DestinationIn)
+    }
+
+    ///Results in the parts of `other` that lies outside the visible parts of
+    ///`self`. This is the reverse of `outside`.
+    fn destination_out(self, other: Self) -> Self {
+        self.blend(other, PorterDuff::DestinationOut)
+    }
+
+    ///Place `other` over only the visible parts of `self`. This is the
+    ///reverse of `atop`.
+    fn destination_atop(self, other: Self) -> Self {
+        self.blend(other, PorterDuff::DestinationAtop)
+    }
+
     ///Add `self` and `other`. This uses the alpha component to regulate the
     ///effect, so it's not just plain component wise addition.
     fn plus(self, other: Self) -> Self {
@@ -0,0 +1,305 @@
+use num_traits::{One, Zero};
+use float::Float;
+#[cfg(not(feature = "std"))]
+use num_traits::float::FloatCore;
+
+use {cast, clamp, Blend, Component, ComponentWise};
+use blend::{BlendFunction, PreAlpha};
+use rgb::{Rgb, RgbStandard};
+
+///The separable blend modes from the W3C compositing specification, which
+///are also used in PDF and most image editors.
+///
+///A separable blend mode mixes each color component independently of the
+///others, so it works with any color type whose components can be blended
+///one by one, such as RGB and luma. The blended color is then placed over the
+///destination with regular source-over compositing.
+///
+///The blend modes can be passed directly to the `blend` function, from the
+///`Blend` trait. They operate on whatever values the color type stores, so
+///blending linear colors, like `LinSrgba`, is physically based, while
+///blending encoded colors, like `Srgba`, matches what web browsers and most
+///image editors do:
+///
+///```
+///use palette::{Blend, LinSrgba, Srgba};
+///use palette::blend::BlendMode;
+///
+///let a = Srgba::new(0.2, 0.5, 0.1, 0.8);
+///let b = Srgba::new(0.6, 0.3, 0.5, 1.0);
+///
+///let encoded = a.blend(b, BlendMode::SoftLight);
+///let linear = a.into_linear().blend(b.into_linear(), BlendMode::SoftLight);
+///```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlendMode {
+    ///Results in the source color.
+    Normal,
+    ///Multiplies the source and destination. The result is never lighter
+    ///than either of them.
+    Multiply,
+    ///Multiplies the complements of the source and destination. The result
+    ///is never darker than either of them.
+    Screen,
+    ///Multiplies or screens, depending on the destination. This is the same
+    ///as `HardLight`, with the source and destination swapped.
+    Overlay,
+    ///Selects the darker of the source and destination.
+    Darken,
+    ///Selects the lighter of the source and destination.
+    Lighten,
+    ///Brightens the destination to reflect the source.
+    ColorDodge,
+    ///Darkens the destination to reflect the source.
+    ColorBurn,
+    ///Multiplies or screens, depending on the source.
+    HardLight,
+    ///Darkens or lightens, depending on the source, like a diffused spotlight.
+    SoftLight,
+    ///The absolute difference between the source and destination.
+    Difference,
+    ///Similar to `Difference`, but with lower contrast.
+    Exclusion,
+}
+
+impl BlendMode {
+    ///Blend a single, non-premultiplied, component of the `backdrop` and the
+    ///`source`. The components are expected to be in the range `[0.0, 1.0]`.
+    pub fn apply<T: Float>(self, backdrop: T, source: T) -> T {
+        let zero = T::zero();
+        let one = T::one();
+        let two = one + one;
+        let half: T = cast(0.5);
+
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => backdrop * source,
+            BlendMode::Screen => backdrop + source - backdrop * source,
+            BlendMode::Overlay => BlendMode::HardLight.apply(source, backdrop),
+            BlendMode::Darken => backdrop.min(source),
+            BlendMode::Lighten => backdrop.max(source),
+            BlendMode::ColorDodge => {
+                if backdrop <= zero {
+                    zero
+                } else if source >= one {
+                    one
+                } else {
+                    one.min(backdrop / (one - source))
+                }
+            }
+            BlendMode::ColorBurn => {
+                if backdrop >= one {
+                    one
+                } else if source <= zero {
+                    zero
+                } else {
+                    one - one.min((one - backdrop) / source)
+                }
+            }
+            BlendMode::HardLight => {
+                if source <= half {
+                    BlendMode::Multiply.apply(backdrop, two * source)
+                } else {
+                    BlendMode::Screen.apply(backdrop, two * source - one)
+                }
+            }
+            BlendMode::SoftLight => {
+                if source <= half {
+                    backdrop - (one - two * source) * backdrop * (one - backdrop)
+                } else {
+                    let d = if backdrop <= cast(0.25) {
+                        ((backdrop * cast(16.0) - cast(12.0)) * backdrop + cast(4.0)) * backdrop
+                    } else {
+                        backdrop.sqrt()
+                    };
+
+                    backdrop + (two * source - one) * (d - backdrop)
+                }
+            }
+            BlendMode::Difference => (backdrop - source).abs(),
+            BlendMode::Exclusion => backdrop + source - two * backdrop * source,
+        }
+    }
+}
+
+impl<C: Blend<Color = C> + ComponentWise> BlendFunction<C> for BlendMode
+where
+    C::Scalar: Float,
+{
+    fn apply_to(
+        self,
+        source: PreAlpha<C, C::Scalar>,
+        destination: PreAlpha<C, C::Scalar>,
+    ) -> PreAlpha<C, C::Scalar> {
+        let zero = C::Scalar::zero();
+        let one = C::Scalar::one();
+
+        let src_alpha = source.alpha;
+        let dst_alpha = destination.alpha;
+        let unpremultiply = |value: C::Scalar, alpha: C::Scalar| {
+            if alpha.is_normal() {
+                value / alpha
+            } else {
+                zero
+            }
+        };
+
+        PreAlpha {
+            color: source.color.component_wise(&destination.color, |a, b| {
+                let blended = self.apply(unpremultiply(b, dst_alpha), unpremultiply(a, src_alpha));
+                a * (one - dst_alpha) + b * (one - src_alpha) + src_alpha * dst_alpha * blended
+            }),
+            alpha: clamp(src_alpha + dst_alpha - src_alpha * dst_alpha, zero, one),
+        }
+    }
+}
+
+///The non-separable blend modes from the W3C compositing specification.
+///
+///These blend modes treat the color as a whole, by mixing the hue,
+///saturation and luminosity of the source and destination, so they only work
+///with RGB. The luminosity and saturation are the same approximations as in
+///the specification, which makes the results match other implementations.
+///As with `BlendMode`, the blended color is placed over the destination with
+///regular source-over compositing.
+///
+///```
+///use palette::{Blend, Srgba};
+///use palette::blend::NonSeparableBlendMode;
+///
+///let a = Srgba::new(0.8, 0.2, 0.1, 1.0);
+///let b = Srgba::new(0.5, 0.5, 0.5, 0.5);
+///
+///let c = a.blend(b, NonSeparableBlendMode::Color);
+///```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NonSeparableBlendMode {
+    ///The hue of the source, with the saturation and luminosity of the
+    ///destination.
+    Hue,
+    ///The saturation of the source, with the hue and luminosity of the
+    ///destination.
+    Saturation,
+    ///The hue and saturation of the source, with the luminosity of the
+    ///destination.
+    Color,
+    ///The luminosity of the source, with the hue and saturation of the
+    ///destination.
+    Luminosity,
+}
+
+impl NonSeparableBlendMode {
+    ///Blend the non-premultiplied `backdrop` and `source` colors. The
+    ///components are expected to be in the range `[0.0, 1.0]`.
+    pub fn apply<S, T>(self, backdrop: Rgb<S, T>, source: Rgb<S, T>) -> Rgb<S, T>
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        match self {
+            NonSeparableBlendMode::Hue => {
+                set_lum(set_sat(source, sat(&backdrop)), lum(&backdrop))
+            }
+            NonSeparableBlendMode::Saturation => {
+                set_lum(set_sat(backdrop, sat(&source)), lum(&backdrop))
+            }
+            NonSeparableBlendMode::Color => set_lum(source, lum(&backdrop)),
+            NonSeparableBlendMode::Luminosity => set_lum(backdrop, lum(&source)),
+        }
+    }
+}
+
+impl<S, T> BlendFunction<Rgb<S, T>> for NonSeparableBlendMode
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    fn apply_to(
+        self,
+        source: PreAlpha<Rgb<S, T>, T>,
+        destination: PreAlpha<Rgb<S, T>, T>,
+    ) -> PreAlpha<Rgb<S, T>, T> {
+        let zero = T::zero();
+        let one = T::one();
+
+        let src_alpha = source.alpha;
+        let dst_alpha = destination.alpha;
+        let unpremultiply = |color: &Rgb<S, T>, alpha: T| {
+            color.component_wise_self(|value| {
+                if alpha.is_normal() {
+                    value / alpha
+                } else {
+                    zero
+                }
+            })
+        };
+
+        let blended = self.apply(
+            unpremultiply(&destination.color, dst_alpha),
+            unpremultiply(&source.color, src_alpha),
+        );
+
+        let color = source
+            .color
+            .component_wise(&destination.color, |a, b| {
+                a * (one - dst_alpha) + b * (one - src_alpha)
+            })
+            .component_wise(&blended, |a, blended| {
+                a + src_alpha * dst_alpha * blended
+            });
+
+        PreAlpha {
+            color,
+            alpha: clamp(src_alpha + dst_alpha - src_alpha * dst_alpha, zero, one),
+        }
+    }
+}
+
+fn lum<S: RgbStandard, T: Component + Float>(color: &Rgb<S, T>) -> T {
+    color.red * cast(0.3) + color.green * cast(0.59) + color.blue * cast(0.11)
+}
+
+fn sat<S: RgbStandard, T: Component + Float>(color: &Rgb<S, T>) -> T {
+    color.red.max(color.green).max(color.blue) - color.red.min(color.green).min(color.blue)
+}
+
+fn set_lum<S, T>(color: Rgb<S, T>, luminosity: T) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let difference = luminosity - lum(&color);
+    let color = color.component_wise_self(|c| c + difference);
+
+    //Bring the color back into range, while keeping its luminosity
+    let l = lum(&color);
+    let min = color.red.min(color.green).min(color.blue);
+    let max = color.red.max(color.green).max(color.blue);
+
+    let color = if min < T::zero() {
+        color.component_wise_self(|c| l + (c - l) * l / (l - min))
+    } else {
+        color
+    };
+
+    if max > T::one() {
+        color.component_wise_self(|c| l + (c - l) * (T::one() - l) / (max - l))
+    } else {
+        color
+    }
+}
+
+fn set_sat<S, T>(color: Rgb<S, T>, saturation: T) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let min = color.red.min(color.green).min(color.blue);
+    let max = color.red.max(color.green).max(color.blue);
+
+    if max > min {
+        color.component_wise_self(|c| (c - min) * saturation / (max - min))
+    } else {
+        color.component_wise_self(|_| T::zero())
+    }
+}
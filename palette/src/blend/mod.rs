@@ -32,6 +32,60 @@
 //!let c = a.blend(b, blend_mode);
 //!```
 //!
+//!All twelve Porter-Duff compositing operators are available through the
+//![`PorterDuff`](enum.PorterDuff.html) type, which can also be passed to the
+//!`blend` function:
+//!
+//!```
+//!use palette::{LinSrgba, Blend};
+//!use palette::blend::PorterDuff;
+//!
+//!let a = LinSrgba::new(0.2, 0.5, 0.1, 0.8);
+//!let b = LinSrgba::new(0.6, 0.3, 0.5, 0.1);
+//!let c = a.blend(b, PorterDuff::DestinationAtop);
+//!```
+//!
+//!The full set of separable blend modes from the W3C compositing
+//!specification is available through the [`BlendMode`](enum.BlendMode.html)
+//!type. Blending happens on the values that are stored in the color, so
+//!linear colors are blended in linear space, and encoded colors, like
+//!`Srgba`, are blended as they are, just like in web browsers:
+//!
+//!```
+//!use palette::{LinSrgba, Blend, Srgba};
+//!use palette::blend::BlendMode;
+//!
+//!let a = Srgba::new(0.2, 0.5, 0.1, 0.8);
+//!let b = Srgba::new(0.6, 0.3, 0.5, 0.1);
+//!let encoded = a.blend(b, BlendMode::Overlay);
+//!
+//!let a = LinSrgba::new(0.2, 0.5, 0.1, 0.8);
+//!let b = LinSrgba::new(0.6, 0.3, 0.5, 0.1);
+//!let linear = a.blend(b, BlendMode::Overlay);
+//!```
+//!
+//!The blend modes and compositing operators work just as well for grayscale
+//!colors, such as `LinLumaa` or `SrgbLumaa`, which is useful for masks and
+//!other single channel images.
+//!
+//!RGB and luma colors can also be blended in another encoding than the one they are
+//!stored in, using `blend_in`. The colors are converted before blending, and
+//!the result is converted back:
+//!
+//!```
+//!use palette::Srgba;
+//!use palette::blend::BlendMode;
+//!use palette::encoding::Linear;
+//!
+//!let a = Srgba::new(0.2, 0.5, 0.1, 0.8);
+//!let b = Srgba::new(0.6, 0.3, 0.5, 0.1);
+//!let c = a.blend_in::<Linear<_>, _>(b, BlendMode::Overlay);
+//!```
+//!
+//!The non-separable blend modes, that mix the hue, saturation and luminosity
+//!of RGB colors, are available through the
+//![`NonSeparableBlendMode`](enum.NonSeparableBlendMode.html) type.
+//!
 //!Note that blending will use [premultiplied alpha](struct.PreAlpha.html),
 //!which may result in loss of some color information in some cases. One such
 //!case is that a completely transparent resultant color will become black.
@@ -41,12 +95,18 @@ use float::Float;
 use ComponentWise;
 
 pub use self::equations::{Equation, Equations, Parameter, Parameters};
-pub use self::pre_alpha::PreAlpha;
+pub use self::pre_alpha::{
+    premultiply, premultiply_in_place, unpremultiply, unpremultiply_in_place, PreAlpha,
+};
 pub use self::blend::Blend;
+pub use self::porter_duff::PorterDuff;
+pub use self::blend_mode::{BlendMode, NonSeparableBlendMode};
 
 mod equations;
 mod pre_alpha;
 mod blend;
+mod porter_duff;
+mod blend_mode;
 
 #[cfg(test)]
 mod test;
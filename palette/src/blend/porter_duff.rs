@@ -0,0 +1,108 @@
+use num_traits::{One, Zero};
+use float::Float;
+
+use {clamp, Blend, ComponentWise};
+use blend::{BlendFunction, PreAlpha};
+
+///The twelve Porter-Duff compositing operators.
+///
+///Each operator decides how much of the source and destination colors, and
+///their alpha, ends up in the result. The colors are always premultiplied
+///while they are composed. An operator can be passed directly to the `blend`
+///function, from the `Blend` trait:
+///
+///```
+///#[macro_use]
+///extern crate approx;
+///extern crate palette;
+///
+///use palette::{Blend, LinSrgba};
+///use palette::blend::PorterDuff;
+///
+///fn main() {
+///    let a = LinSrgba::new(0.2, 0.5, 0.1, 0.8);
+///    let b = LinSrgba::new(0.6, 0.3, 0.5, 0.4);
+///
+///    assert_relative_eq!(a.blend(b, PorterDuff::SourceOver), a.over(b));
+///    assert_relative_eq!(a.blend(b, PorterDuff::DestinationOver), b.over(a));
+///}
+///```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PorterDuff {
+    ///Results in full transparency.
+    Clear,
+    ///Results in the source color.
+    Source,
+    ///Results in the destination color.
+    Destination,
+    ///Places the source over the destination. Same as `Blend::over`.
+    SourceOver,
+    ///Places the destination over the source.
+    DestinationOver,
+    ///Results in the parts of the source that overlaps the destination. Same
+    ///as `Blend::inside`.
+    SourceIn,
+    ///Results in the parts of the destination that overlaps the source.
+    DestinationIn,
+    ///Results in the parts of the source that lies outside the destination.
+    ///Same as `Blend::outside`.
+    SourceOut,
+    ///Results in the parts of the destination that lies outside the source.
+    DestinationOut,
+    ///Places the source over only the visible parts of the destination. Same
+    ///as `Blend::atop`.
+    SourceAtop,
+    ///Places the destination over only the visible parts of the source.
+    DestinationAtop,
+    ///Results in either the source or the destination, where they do not
+    ///overlap. Same as `Blend::xor`.
+    Xor,
+}
+
+impl PorterDuff {
+    ///Get the fractions of the source and destination that the operator keeps,
+    ///given their alpha values.
+    fn factors<T: Float>(self, source_alpha: T, destination_alpha: T) -> (T, T) {
+        let zero = T::zero();
+        let one = T::one();
+
+        match self {
+            PorterDuff::Clear => (zero, zero),
+            PorterDuff::Source => (one, zero),
+            PorterDuff::Destination => (zero, one),
+            PorterDuff::SourceOver => (one, one - source_alpha),
+            PorterDuff::DestinationOver => (one - destination_alpha, one),
+            PorterDuff::SourceIn => (destination_alpha, zero),
+            PorterDuff::DestinationIn => (zero, source_alpha),
+            PorterDuff::SourceOut => (one - destination_alpha, zero),
+            PorterDuff::DestinationOut => (zero, one - source_alpha),
+            PorterDuff::SourceAtop => (destination_alpha, one - source_alpha),
+            PorterDuff::DestinationAtop => (one - destination_alpha, source_alpha),
+            PorterDuff::Xor => (one - destination_alpha, one - source_alpha),
+        }
+    }
+}
+
+impl<C: Blend<Color = C> + ComponentWise> BlendFunction<C> for PorterDuff
+where
+    C::Scalar: Float,
+{
+    fn apply_to(
+        self,
+        source: PreAlpha<C, C::Scalar>,
+        destination: PreAlpha<C, C::Scalar>,
+    ) -> PreAlpha<C, C::Scalar> {
+        let (src_factor, dst_factor) = self.factors(source.alpha, destination.alpha);
+
+        PreAlpha {
+            color: source
+                .color
+                .component_wise(&destination.color, |a, b| a * src_factor + b * dst_factor),
+            alpha: clamp(
+                source.alpha * src_factor + destination.alpha * dst_factor,
+                C::Scalar::zero(),
+                C::Scalar::one(),
+            ),
+        }
+    }
+}
@@ -39,6 +39,24 @@ pub struct PreAlpha<C, T: Float> {
     pub alpha: T,
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, T> ::bytemuck::Zeroable for PreAlpha<C, T>
+where
+    C: ::bytemuck::Zeroable,
+    T: Float + ::bytemuck::Zeroable,
+{
+}
+
+// The color is made of `T` components when it implements `Pixel<T>`, so
+// there's no padding between the color and the alpha component.
+#[cfg(feature = "bytemuck")]
+unsafe impl<C, T> ::bytemuck::Pod for PreAlpha<C, T>
+where
+    C: ::bytemuck::Pod + Pixel<T>,
+    T: Float + ::bytemuck::Pod,
+{
+}
+
 impl<C, T> From<Alpha<C, T>> for PreAlpha<C, T>
 where
     C: ComponentWise<Scalar = T>,
@@ -77,6 +95,126 @@ where
     }
 }
 
+///Premultiply a slice of colors with their alpha, and write the results to
+///`output`.
+///
+///The alpha is clamped to `[0.0, 1.0]`, the same way as when converting a
+///single color.
+///
+///```
+///use palette::LinSrgba;
+///use palette::blend::{premultiply, PreAlpha};
+///
+///let colors = [LinSrgba::new(0.4, 0.2, 1.0, 0.5), LinSrgba::new(0.4, 0.2, 1.0, 0.0)];
+///let mut output = [PreAlpha::default(); 2];
+///premultiply(&colors, &mut output);
+///
+///assert_eq!(output[0], PreAlpha::from(LinSrgba::new(0.4, 0.2, 1.0, 0.5)));
+///```
+///
+///# Panics
+///
+///Panics if the slices don't have the same length.
+pub fn premultiply<C, T>(colors: &[Alpha<C, T>], output: &mut [PreAlpha<C, T>])
+where
+    C: ComponentWise<Scalar = T> + Clone,
+    T: Float,
+{
+    assert_eq!(
+        colors.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    for (color, output) in colors.iter().zip(output) {
+        *output = color.clone().into();
+    }
+}
+
+///Convert a slice of premultiplied colors back to separate alpha, and write
+///the results to `output`.
+///
+///Colors with zero alpha don't have any recoverable color information, so
+///they become black and fully transparent.
+///
+///# Panics
+///
+///Panics if the slices don't have the same length.
+pub fn unpremultiply<C, T>(colors: &[PreAlpha<C, T>], output: &mut [Alpha<C, T>])
+where
+    C: ComponentWise<Scalar = T> + Clone,
+    T: Float,
+{
+    assert_eq!(
+        colors.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    for (color, output) in colors.iter().zip(output) {
+        *output = color.clone().into();
+    }
+}
+
+///Premultiply a slice of colors with their alpha, in place, and return the
+///same memory as a slice of premultiplied colors.
+///
+///```
+///use palette::{LinSrgb, LinSrgba};
+///use palette::blend::premultiply_in_place;
+///
+///let mut colors = [LinSrgba::new(0.4, 0.2, 1.0, 0.5)];
+///let premultiplied = premultiply_in_place(&mut colors);
+///
+///assert_eq!(premultiplied[0].color, LinSrgb::new(0.2, 0.1, 0.5));
+///```
+pub fn premultiply_in_place<C, T>(colors: &mut [Alpha<C, T>]) -> &mut [PreAlpha<C, T>]
+where
+    C: ComponentWise<Scalar = T>,
+    T: Float,
+{
+    for color in colors.iter_mut() {
+        let alpha = clamp(color.alpha, T::zero(), T::one());
+        color.color = color.color.component_wise_self(|a| a * alpha);
+        color.alpha = alpha;
+    }
+
+    // Alpha and PreAlpha are both repr(C), with the same fields in the same
+    // order, so they have the same layout.
+    unsafe {
+        ::core::slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut PreAlpha<C, T>, colors.len())
+    }
+}
+
+///Convert a slice of premultiplied colors back to separate alpha, in place,
+///and return the same memory as a slice of non-premultiplied colors.
+///
+///Colors with zero alpha don't have any recoverable color information, so
+///they become black and fully transparent.
+pub fn unpremultiply_in_place<C, T>(colors: &mut [PreAlpha<C, T>]) -> &mut [Alpha<C, T>]
+where
+    C: ComponentWise<Scalar = T>,
+    T: Float,
+{
+    for color in colors.iter_mut() {
+        let alpha = clamp(color.alpha, T::zero(), T::one());
+        color.color = color.color.component_wise_self(|a| {
+            if alpha.is_normal() {
+                a / alpha
+            } else {
+                T::zero()
+            }
+        });
+        color.alpha = alpha;
+    }
+
+    // Alpha and PreAlpha are both repr(C), with the same fields in the same
+    // order, so they have the same layout.
+    unsafe {
+        ::core::slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut Alpha<C, T>, colors.len())
+    }
+}
+
 impl<C, T> Blend for PreAlpha<C, T>
 where
     C: Blend<Color = C> + ComponentWise<Scalar = T>,
@@ -1,7 +1,10 @@
-use {Blend, ComponentWise, LinSrgb, LinSrgba};
+use {Blend, ComponentWise, LinLuma, LinLumaa, LinSrgb, LinSrgba, Srgb, SrgbLuma, SrgbLumaa, Srgba};
 use rgb::Rgb;
-use encoding::Linear;
-use blend::PreAlpha;
+use encoding::{Linear, Srgb as SrgbEncoding};
+use blend::{
+    premultiply, premultiply_in_place, unpremultiply, unpremultiply_in_place, BlendMode,
+    NonSeparableBlendMode, PorterDuff, PreAlpha,
+};
 
 #[test]
 fn blend_color() {
@@ -139,6 +142,41 @@ fn xor() {
     assert_relative_eq!(LinSrgba::new(0.5, 0.0, 0.3, 1.0), a.xor(b));
 }
 
+#[test]
+fn porter_duff() {
+    let colors = [
+        LinSrgba::new(0.5, 0.0, 0.3, 1.0),
+        LinSrgba::new(1.0, 0.2, 0.0, 0.5),
+        LinSrgba::new(0.2, 0.8, 0.4, 0.25),
+        LinSrgba::new(1.0, 0.2, 0.0, 0.0),
+    ];
+
+    for &a in &colors {
+        for &b in &colors {
+            assert_relative_eq!(a.blend(b, PorterDuff::SourceOver), a.over(b));
+            assert_relative_eq!(a.blend(b, PorterDuff::SourceIn), a.inside(b));
+            assert_relative_eq!(a.blend(b, PorterDuff::SourceOut), a.outside(b));
+            assert_relative_eq!(a.blend(b, PorterDuff::SourceAtop), a.atop(b));
+            assert_relative_eq!(a.blend(b, PorterDuff::Xor), a.xor(b));
+
+            assert_relative_eq!(a.destination_over(b), b.over(a));
+            assert_relative_eq!(a.destination_in(b), b.inside(a));
+            assert_relative_eq!(a.destination_out(b), b.outside(a));
+            assert_relative_eq!(a.destination_atop(b), b.atop(a));
+        }
+    }
+
+    let a = LinSrgba::new(0.5, 0.0, 0.3, 0.5);
+    let b = LinSrgba::new(1.0, 0.2, 0.0, 0.25);
+
+    assert_relative_eq!(
+        LinSrgba::new(0.0, 0.0, 0.0, 0.0),
+        a.blend(b, PorterDuff::Clear)
+    );
+    assert_relative_eq!(a, a.blend(b, PorterDuff::Source));
+    assert_relative_eq!(b, a.blend(b, PorterDuff::Destination));
+}
+
 #[test]
 fn plus() {
     let a = LinSrgb::new(0.5, 0.0, 0.3);
@@ -385,3 +423,260 @@ fn exclusion() {
 
     assert_relative_eq!(LinSrgba::new(0.5, 0.0, 0.3, 1.0), a.difference(b));
 }
+
+#[test]
+fn blend_mode_components() {
+    let expected = [
+        (BlendMode::Normal, 0.75, 0.8),
+        (BlendMode::Multiply, 0.15, 0.6),
+        (BlendMode::Screen, 0.8, 0.95),
+        (BlendMode::Overlay, 0.3, 0.9),
+        (BlendMode::Darken, 0.2, 0.75),
+        (BlendMode::Lighten, 0.75, 0.8),
+        (BlendMode::ColorDodge, 0.8, 1.0),
+        (BlendMode::ColorBurn, 0.0, 0.6875),
+        (BlendMode::HardLight, 0.6, 0.9),
+        (BlendMode::SoftLight, 0.324, 0.75 + 0.6 * (0.75f64.sqrt() - 0.75)),
+        (BlendMode::Difference, 0.55, 0.05),
+        (BlendMode::Exclusion, 0.65, 0.35),
+    ];
+
+    for &(mode, first, second) in &expected {
+        assert_relative_eq!(mode.apply(0.2, 0.75), first, epsilon = 1e-12);
+        assert_relative_eq!(mode.apply(0.75, 0.8), second, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn blend_mode_opaque() {
+    let a = LinSrgb::new(0.5, 0.0, 0.3);
+    let b = LinSrgb::new(1.0, 0.2, 0.0);
+
+    assert_relative_eq!(a.blend(b, BlendMode::Multiply), LinSrgb::new(0.5, 0.0, 0.0));
+    assert_relative_eq!(a.blend(b, BlendMode::Screen), LinSrgb::new(1.0, 0.2, 0.3));
+    assert_relative_eq!(a.blend(b, BlendMode::Normal), a);
+}
+
+#[test]
+fn blend_mode_alpha() {
+    let a = LinSrgba::new(0.5, 0.0, 0.3, 0.5);
+    let b = LinSrgba::new(1.0, 0.2, 0.0, 0.5);
+
+    //The same as the premultiplied implementation in the `Blend` trait
+    assert_relative_eq!(a.blend(b, BlendMode::Multiply), a.multiply(b));
+    assert_relative_eq!(a.blend(b, BlendMode::Screen), a.screen(b));
+    assert_relative_eq!(a.blend(b, BlendMode::Normal), a.over(b));
+
+    //Fully transparent colors don't affect the other color
+    let transparent = LinSrgba::new(0.3, 0.3, 0.3, 0.0);
+    assert_relative_eq!(transparent.blend(b, BlendMode::Difference), b);
+    assert_relative_eq!(b.blend(transparent, BlendMode::Difference), b);
+}
+
+#[test]
+fn blend_mode_luma() {
+    let a: LinLuma = LinLuma::new(0.25);
+    let b = LinLuma::new(0.5);
+    assert_relative_eq!(a.blend(b, BlendMode::Screen), LinLuma::new(0.625));
+
+    let a: LinLumaa = LinLumaa::new(0.25, 0.5);
+    let b = LinLumaa::new(0.5, 1.0);
+    assert_relative_eq!(a.blend(b, BlendMode::Multiply), LinLumaa::new(0.3125, 1.0));
+}
+
+#[test]
+fn blend_mode_encoded() {
+    let a = Srgb::new(0.5, 0.5, 0.5);
+    let b = Srgb::new(0.5, 0.25, 1.0);
+
+    assert_relative_eq!(a.blend(b, BlendMode::Multiply), Srgb::new(0.25, 0.125, 0.5));
+
+    let linear = Srgb::from_linear(a.into_linear().blend(b.into_linear(), BlendMode::Multiply));
+    assert!(linear.red < 0.25);
+
+    let a = Srgba::new(0.5, 0.5, 0.5, 1.0);
+    let b = Srgba::new(0.5, 0.25, 1.0, 1.0);
+    assert_relative_eq!(
+        a.blend(b, BlendMode::Multiply),
+        Srgba::new(0.25, 0.125, 0.5, 1.0)
+    );
+}
+
+#[test]
+fn non_separable_blend_modes() {
+    let gray = LinSrgb::new(0.5, 0.5, 0.5);
+    let red = LinSrgb::new(0.8, 0.2, 0.1);
+
+    //Gray has no hue or saturation to give
+    assert_relative_eq!(NonSeparableBlendMode::Hue.apply(gray, red), gray);
+    assert_relative_eq!(NonSeparableBlendMode::Saturation.apply(gray, red), gray);
+    assert_relative_eq!(
+        NonSeparableBlendMode::Saturation.apply(red, gray),
+        LinSrgb::new(0.369, 0.369, 0.369),
+        epsilon = 1e-6
+    );
+
+    //The luminosity of the gray is 0.5, and 0.369 for the red
+    let expected = LinSrgb::new(0.931, 0.331, 0.231);
+    assert_relative_eq!(
+        NonSeparableBlendMode::Color.apply(gray, red),
+        expected,
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        NonSeparableBlendMode::Luminosity.apply(red, gray),
+        expected,
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        NonSeparableBlendMode::Hue.apply(red, LinSrgb::new(0.1, 0.2, 0.8)),
+        LinSrgb::new(0.233, 0.333, 0.933),
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn non_separable_blend_modes_clip() {
+    let backdrop = LinSrgb::new(0.9, 0.9, 0.9);
+    let source = LinSrgb::new(1.0, 0.0, 0.0);
+
+    let color = NonSeparableBlendMode::Color.apply(backdrop, source);
+    assert!(color.red <= 1.0 && color.green >= 0.0 && color.blue >= 0.0);
+    assert_relative_eq!(
+        color.red * 0.3 + color.green * 0.59 + color.blue * 0.11,
+        0.9,
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn non_separable_blend_mode_alpha() {
+    let a = LinSrgba::new(0.8, 0.2, 0.1, 1.0);
+    let b = LinSrgba::new(0.5, 0.5, 0.5, 0.0);
+    assert_relative_eq!(a.blend(b, NonSeparableBlendMode::Hue), a);
+    assert_relative_eq!(b.blend(a, NonSeparableBlendMode::Hue), a);
+
+    let a = LinSrgba::new(0.2, 0.4, 0.6, 1.0);
+    let b = LinSrgba::new(0.3, 0.3, 0.3, 1.0);
+    assert_relative_eq!(
+        a.blend(b, NonSeparableBlendMode::Luminosity),
+        LinSrgba::new(0.362, 0.362, 0.362, 1.0),
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn bulk_premultiply() {
+    let colors = [
+        LinSrgba::new(0.4, 0.2, 1.0, 0.5),
+        LinSrgba::new(0.4, 0.2, 1.0, 0.0),
+        LinSrgba::new(0.4, 0.2, 1.0, 1.5),
+    ];
+
+    let mut premultiplied = [PreAlpha::default(); 3];
+    premultiply(&colors, &mut premultiplied);
+    for (&color, &premultiplied) in colors.iter().zip(&premultiplied) {
+        assert_relative_eq!(PreAlpha::from(color), premultiplied);
+    }
+
+    let mut unpremultiplied = [LinSrgba::default(); 3];
+    unpremultiply(&premultiplied, &mut unpremultiplied);
+    assert_relative_eq!(unpremultiplied[0], colors[0]);
+    assert_relative_eq!(unpremultiplied[1], LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+    assert_relative_eq!(unpremultiplied[2], LinSrgba::new(0.4, 0.2, 1.0, 1.0));
+}
+
+#[test]
+fn bulk_premultiply_in_place() {
+    let mut colors = [
+        LinSrgba::new(0.4, 0.2, 1.0, 0.5),
+        LinSrgba::new(0.4, 0.2, 1.0, 0.0),
+    ];
+
+    {
+        let premultiplied = premultiply_in_place(&mut colors);
+        assert_relative_eq!(
+            premultiplied[0],
+            PreAlpha::from(LinSrgba::new(0.4, 0.2, 1.0, 0.5))
+        );
+        assert_relative_eq!(
+            premultiplied[1],
+            PreAlpha::from(LinSrgba::new(0.0, 0.0, 0.0, 0.0))
+        );
+
+        let unpremultiplied = unpremultiply_in_place(premultiplied);
+        assert_relative_eq!(unpremultiplied[0], LinSrgba::new(0.4, 0.2, 1.0, 0.5));
+    }
+
+    assert_relative_eq!(colors[1], LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn bulk_premultiply_length_mismatch() {
+    let colors = [LinSrgba::new(0.4, 0.2, 1.0, 0.5); 2];
+    let mut premultiplied = [PreAlpha::default(); 3];
+    premultiply(&colors, &mut premultiplied);
+}
+
+#[test]
+fn blend_in_encoding() {
+    let a = Srgba::new(0.5, 0.5, 0.5, 0.5);
+    let b = Srgba::new(0.5, 0.25, 1.0, 1.0);
+
+    let linear = a.blend_in::<Linear<_>, _>(b, BlendMode::Screen);
+    assert_relative_eq!(
+        linear,
+        Srgba::from_linear(a.into_linear().blend(b.into_linear(), BlendMode::Screen))
+    );
+
+    let encoded = a.blend_in::<SrgbEncoding, _>(b, BlendMode::Screen);
+    assert_relative_eq!(encoded, a.blend(b, BlendMode::Screen));
+
+    let a = LinSrgb::new(0.5, 0.5, 0.5);
+    let b = LinSrgb::new(0.5, 0.25, 1.0);
+    let encoded = Srgb::from_linear(a).blend(Srgb::from_linear(b), BlendMode::Multiply);
+    assert_relative_eq!(
+        a.blend_in::<SrgbEncoding, _>(b, BlendMode::Multiply),
+        encoded.into_linear(),
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn luma_compositing() {
+    let mask: LinLumaa = LinLumaa::new(1.0, 0.5);
+    let background = LinLumaa::new(0.2, 1.0);
+
+    assert_relative_eq!(mask.over(background), LinLumaa::new(0.6, 1.0));
+    assert_relative_eq!(mask.inside(background), LinLumaa::new(1.0, 0.5));
+    assert_relative_eq!(mask.outside(background), LinLumaa::new(0.0, 0.0));
+    assert_relative_eq!(background.destination_over(mask), mask.over(background));
+    assert_relative_eq!(
+        mask.blend(background, PorterDuff::SourceAtop),
+        mask.atop(background)
+    );
+
+    let a: LinLuma = LinLuma::new(0.25);
+    let b = LinLuma::new(0.5);
+    assert_relative_eq!(a.over(b), a);
+    assert_relative_eq!(a.multiply(b), LinLuma::new(0.125));
+}
+
+#[test]
+fn luma_blend_in_encoding() {
+    let a: SrgbLumaa = SrgbLumaa::new(0.5, 0.5);
+    let b = SrgbLumaa::new(0.25, 1.0);
+
+    assert_relative_eq!(a.blend(b, BlendMode::Normal), SrgbLumaa::new(0.375, 1.0));
+    assert_relative_eq!(
+        a.blend_in::<Linear<_>, _>(b, BlendMode::Normal),
+        SrgbLumaa::from_linear(a.into_linear().over(b.into_linear()))
+    );
+
+    let a: SrgbLuma = SrgbLuma::new(0.5);
+    assert_relative_eq!(
+        a.blend_in::<SrgbEncoding, _>(SrgbLuma::new(0.5), BlendMode::Multiply),
+        SrgbLuma::new(0.25)
+    );
+}
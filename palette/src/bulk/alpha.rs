@@ -0,0 +1,156 @@
+//!Splitting and joining the transparency of color buffers.
+//!
+//!Some codecs and formats keep the alpha channel separate from the colors,
+//!such as an RGB image with a separate mask. These functions move the alpha
+//!values between a slice of [`Alpha`](../../struct.Alpha.html) colors and a
+//!plane of their own, and work for any color type.
+//!
+//!```
+//!use palette::{Srgb, Srgba};
+//!use palette::bulk::alpha;
+//!
+//!let colors = [Srgba::new(1.0, 0.5, 0.0, 0.25), Srgba::new(0.0, 0.5, 1.0, 1.0)];
+//!let mut rgb = [Srgb::default(); 2];
+//!let mut mask = [0.0; 2];
+//!alpha::split_alpha(&colors, &mut rgb, &mut mask);
+//!assert_eq!(mask, [0.25, 1.0]);
+//!
+//!let mut joined = [Srgba::default(); 2];
+//!alpha::join_alpha(&rgb, &mask, &mut joined);
+//!assert_eq!(joined, colors);
+//!```
+
+use Alpha;
+
+///Split transparent colors into a slice of colors and a plane of alpha values.
+///
+///Panics if the slices don't have the same length.
+pub fn split_alpha<C: Copy, T: Copy>(input: &[Alpha<C, T>], colors: &mut [C], alpha: &mut [T]) {
+    assert_eq!(
+        input.len(),
+        colors.len(),
+        "the slices must have the same length"
+    );
+    assert_eq!(
+        input.len(),
+        alpha.len(),
+        "the slices must have the same length"
+    );
+
+    for ((input, color), alpha) in input.iter().zip(colors).zip(alpha) {
+        *color = input.color;
+        *alpha = input.alpha;
+    }
+}
+
+///Join a slice of colors and a plane of alpha values into transparent colors.
+///
+///Panics if the slices don't have the same length.
+pub fn join_alpha<C: Copy, T: Copy>(colors: &[C], alpha: &[T], output: &mut [Alpha<C, T>]) {
+    assert_eq!(
+        colors.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+    assert_eq!(
+        alpha.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    for ((&color, &alpha), output) in colors.iter().zip(alpha).zip(output) {
+        *output = Alpha {
+            color: color,
+            alpha: alpha,
+        };
+    }
+}
+
+///Add the same alpha value to each color in a slice.
+///
+///```
+///use palette::{Srgb, Srgba};
+///use palette::bulk::alpha;
+///
+///let mut output = [Srgba::default(); 2];
+///alpha::with_alpha(&[Srgb::new(1u8, 2, 3); 2], 255, &mut output);
+///assert_eq!(output, [Srgba::new(1, 2, 3, 255); 2]);
+///```
+///
+///Panics if the slices don't have the same length.
+pub fn with_alpha<C: Copy, T: Copy>(colors: &[C], alpha: T, output: &mut [Alpha<C, T>]) {
+    assert_eq!(
+        colors.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    for (&color, output) in colors.iter().zip(output) {
+        *output = Alpha {
+            color: color,
+            alpha: alpha,
+        };
+    }
+}
+
+///Remove the alpha value from each color in a slice.
+///
+///Panics if the slices don't have the same length.
+pub fn without_alpha<C: Copy, T>(input: &[Alpha<C, T>], output: &mut [C]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    for (input, output) in input.iter().zip(output) {
+        *output = input.color;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{join_alpha, split_alpha, with_alpha, without_alpha};
+    use luma::{Luma, Lumaa};
+    use {Hsv, Hsva};
+
+    #[test]
+    fn split_and_join() {
+        let input = [
+            Hsva::new(10.0, 0.5, 0.5, 0.0),
+            Hsva::new(20.0, 1.0, 0.0, 0.5),
+            Hsva::new(30.0, 0.0, 1.0, 1.0),
+        ];
+
+        let mut colors = [Hsv::default(); 3];
+        let mut alpha = [0.0; 3];
+        split_alpha(&input, &mut colors, &mut alpha);
+
+        assert_eq!(colors[1], Hsv::new(20.0, 1.0, 0.0));
+        assert_eq!(alpha, [0.0, 0.5, 1.0]);
+
+        let mut output = [Hsva::default(); 3];
+        join_alpha(&colors, &alpha, &mut output);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let colors = [Luma::<::encoding::Srgb, u8>::new(7); 4];
+
+        let mut transparent = [Lumaa::default(); 4];
+        with_alpha(&colors, 128u8, &mut transparent);
+        assert_eq!(transparent[3], Lumaa::new(7, 128));
+
+        let mut opaque = [Luma::default(); 4];
+        without_alpha(&transparent, &mut opaque);
+        assert_eq!(opaque, colors);
+    }
+
+    #[test]
+    #[should_panic(expected = "the slices must have the same length")]
+    fn different_lengths() {
+        let input: [Hsva; 2] = Default::default();
+        split_alpha(&input, &mut [Hsv::default(); 2], &mut [0.0; 1]);
+    }
+}
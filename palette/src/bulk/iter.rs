@@ -0,0 +1,101 @@
+use core::iter::Map;
+
+use convert::FromColor;
+
+///The iterator returned by `IntoColorIterator::into_colors` and
+///`IntoColorIterator::into_clamped_colors`.
+pub type Colors<I, C> = Map<<I as IntoIterator>::IntoIter, fn(<I as IntoIterator>::Item) -> C>;
+
+///Converts the items of an iterator into colors.
+///
+///It's implemented for everything that implements `IntoIterator`, so any
+///collection or iterator of colors can be converted with `into_colors`,
+///without having to write out the conversion in a closure. This is the
+///sequential version of `IntoParallelColorIterator`.
+///
+///```
+///use palette::{Lab, Srgb};
+///use palette::bulk::IntoColorIterator;
+///
+///let pixels = [Srgb::new(0.8, 0.2, 0.1), Srgb::new(0.1, 0.4, 0.9)];
+///let lab: Vec<Lab> = pixels.iter().cloned().into_colors().collect();
+///
+///assert_eq!(lab[1], Lab::from(pixels[1]));
+///```
+pub trait IntoColorIterator: IntoIterator {
+    ///Create an iterator that converts each item into `C`, without
+    ///clamping the results.
+    fn into_colors<C>(self) -> Colors<Self, C>
+    where
+        C: From<Self::Item>;
+
+    ///Create an iterator that converts each item into `C` and clamps the
+    ///results to the bounds of its color space, as with `FromColor`.
+    ///
+    ///```
+    ///use palette::{Lch, Limited, Srgb};
+    ///use palette::bulk::IntoColorIterator;
+    ///
+    ///let colors = vec![Lch::new(50.0, 100.0, -175.0), Lch::new(50.0, 10.0, 30.0)];
+    ///let rgb: Vec<Srgb> = colors.into_clamped_colors().collect();
+    ///
+    ///assert!(rgb.iter().all(Limited::is_valid));
+    ///```
+    fn into_clamped_colors<C>(self) -> Colors<Self, C>
+    where
+        C: FromColor<Self::Item>;
+}
+
+impl<I: IntoIterator> IntoColorIterator for I {
+    fn into_colors<C>(self) -> Colors<Self, C>
+    where
+        C: From<Self::Item>,
+    {
+        self.into_iter().map(C::from as fn(Self::Item) -> C)
+    }
+
+    fn into_clamped_colors<C>(self) -> Colors<Self, C>
+    where
+        C: FromColor<Self::Item>,
+    {
+        self.into_iter().map(C::from_color as fn(Self::Item) -> C)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntoColorIterator;
+    use {FromColor, Hsv, Lab, Lch, Limited, Srgb};
+
+    fn colors() -> Vec<Srgb> {
+        (0..100)
+            .map(|i| Srgb::new((i % 10) as f32 / 9.0, (i % 7) as f32 / 6.0, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn into_colors() {
+        let colors = colors();
+        let lab: Vec<Lab> = colors.iter().cloned().into_colors().collect();
+        let hsv: Vec<Hsv> = colors.clone().into_colors().collect();
+
+        assert_eq!(lab.len(), colors.len());
+        for ((color, lab), hsv) in colors.iter().zip(&lab).zip(&hsv) {
+            assert_eq!(*lab, Lab::from(*color));
+            assert_eq!(*hsv, Hsv::from(*color));
+        }
+    }
+
+    #[test]
+    fn into_clamped_colors() {
+        let colors: Vec<_> = (0..36)
+            .map(|i| Lch::new(60.0, 120.0, i as f32 * 10.0))
+            .collect();
+        let rgb: Vec<Srgb> = colors.iter().cloned().into_clamped_colors().collect();
+
+        for (color, rgb) in colors.iter().zip(&rgb) {
+            assert!(rgb.is_valid());
+            assert_eq!(*rgb, Srgb::from_color(*color));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//!Conversion of whole slices of colors.
+//!
+//!The functions in this module convert a slice of `f32` colors into an
+//!output slice of the same length. They give the same results as converting
+//!each color with `From` or `into_linear`, but the most common conversions
+//!are vectorized when the `"simd"` feature is enabled:
+//!
+//!* decoding and encoding of sRGB,
+//!* transforms with a 3x3 matrix, such as between linear sRGB and XYZ,
+//!* the cube root based function of CIE L\*a\*b\*.
+//!
+//!The vectorized conversions process four colors at a time, using the
+//![`wide`](https://crates.io/crates/wide) crate, and the remaining colors are
+//!converted one by one. Their results may differ from the one by one
+//!conversions in the last few bits.
+//!
+//!The [`planar`](planar/index.html) module has the same conversions for
+//!colors that are stored as separate planes of components, and the
+//![`alpha`](alpha/index.html) module splits the transparency from colors and
+//!joins it back.
+//!
+//!Colors can also be converted in place, with `convert_slice_in_place` and
+//!`map_slice_in_place`, when both color types have the same number of
+//!components.
+//!
+//!Iterators of colors can be converted with
+//![`IntoColorIterator`](trait.IntoColorIterator.html), as in
+//!`pixels.iter().cloned().into_colors::<Lab>()`, instead of mapping each
+//!color with `From`.
+//!
+//!The `"rayon"` feature adds `par_convert_slice` and
+//!`IntoParallelColorIterator`, for spreading the conversion of large
+//!collections over multiple threads.
+//!
+//!```
+//!use palette::{LinSrgb, Srgb, Lab};
+//!use palette::bulk;
+//!
+//!let colors = vec![Srgb::new(0.8, 0.2, 0.1); 100];
+//!let mut linear = vec![LinSrgb::default(); 100];
+//!bulk::srgb_to_linear(&colors, &mut linear);
+//!
+//!let mut xyz = vec![Default::default(); 100];
+//!bulk::linear_srgb_to_xyz(&linear, &mut xyz);
+//!
+//!let mut lab: Vec<Lab> = vec![Lab::default(); 100];
+//!bulk::xyz_to_lab(&xyz, &mut lab);
+//!```
+
+use encoding::pixel::Pixel;
+use encoding::TransferFn;
+use matrix::{matrix_inverse, rgb_to_xyz_matrix};
+use white_point::{WhitePoint, D65};
+use {Lab, LinSrgb, Mat3, Srgb, Xyz};
+
+pub use self::iter::{Colors, IntoColorIterator};
+#[cfg(feature = "rayon")]
+pub use self::parallel::{par_convert_slice, IntoParallelColorIterator, ParColors};
+
+pub mod alpha;
+mod iter;
+#[cfg(feature = "rayon")]
+mod parallel;
+pub mod planar;
+#[cfg(feature = "simd")]
+mod simd;
+
+///Decode non-linear sRGB colors into linear sRGB.
+///
+///Panics if the slices don't have the same length.
+pub fn srgb_to_linear(input: &[Srgb<f32>], output: &mut [LinSrgb<f32>]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    decode_srgb(
+        Pixel::into_raw_slice(input),
+        Pixel::into_raw_slice_mut(output),
+    );
+}
+
+///Encode linear sRGB colors as non-linear sRGB.
+///
+///Panics if the slices don't have the same length.
+pub fn linear_to_srgb(input: &[LinSrgb<f32>], output: &mut [Srgb<f32>]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    encode_srgb(
+        Pixel::into_raw_slice(input),
+        Pixel::into_raw_slice_mut(output),
+    );
+}
+
+///Multiply each triplet of components in `input` with the row major matrix
+///`matrix`, and write the results to `output`.
+///
+///Panics if the slices don't have the same length, or if their length is not
+///a multiple of 3.
+pub fn transform(matrix: &Mat3<f32>, input: &[f32], output: &mut [f32]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+    assert_eq!(input.len() % 3, 0, "the slices must contain whole triplets");
+
+    #[cfg(feature = "simd")]
+    let (input, output) = {
+        let matrix = simd::splat_matrix(matrix);
+        let done = simd::interleaved(input, output, |color| simd::transform(&matrix, color)) * 3;
+        (&input[done..], &mut output[done..])
+    };
+
+    for (color, result) in input.chunks(3).zip(output.chunks_mut(3)) {
+        result.copy_from_slice(&multiply(matrix, [color[0], color[1], color[2]]));
+    }
+}
+
+///Convert linear sRGB colors to CIE XYZ.
+///
+///Panics if the slices don't have the same length.
+pub fn linear_srgb_to_xyz(input: &[LinSrgb<f32>], output: &mut [Xyz<D65, f32>]) {
+    transform(
+        &rgb_to_xyz_matrix::<::encoding::Srgb, f32>(),
+        Pixel::into_raw_slice(input),
+        Pixel::into_raw_slice_mut(output),
+    );
+}
+
+///Convert CIE XYZ colors to linear sRGB.
+///
+///Panics if the slices don't have the same length.
+pub fn xyz_to_linear_srgb(input: &[Xyz<D65, f32>], output: &mut [LinSrgb<f32>]) {
+    transform(
+        &matrix_inverse(&rgb_to_xyz_matrix::<::encoding::Srgb, f32>()),
+        Pixel::into_raw_slice(input),
+        Pixel::into_raw_slice_mut(output),
+    );
+}
+
+///Convert CIE XYZ colors to CIE L\*a\*b\*.
+///
+///Panics if the slices don't have the same length.
+pub fn xyz_to_lab<Wp: WhitePoint>(input: &[Xyz<Wp, f32>], output: &mut [Lab<Wp, f32>]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    #[cfg(feature = "simd")]
+    let (input, output) = {
+        let white = simd::splat(white_point::<Wp>());
+        let done = simd::interleaved(
+            Pixel::into_raw_slice(input),
+            Pixel::into_raw_slice_mut(output),
+            |color| simd::xyz_to_lab(&white, color),
+        );
+        (&input[done..], &mut output[done..])
+    };
+
+    for (&color, result) in input.iter().zip(output) {
+        *result = color.into();
+    }
+}
+
+///Convert CIE L\*a\*b\* colors to CIE XYZ.
+///
+///Panics if the slices don't have the same length.
+pub fn lab_to_xyz<Wp: WhitePoint>(input: &[Lab<Wp, f32>], output: &mut [Xyz<Wp, f32>]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    #[cfg(feature = "simd")]
+    let (input, output) = {
+        let white = simd::splat(white_point::<Wp>());
+        let done = simd::interleaved(
+            Pixel::into_raw_slice(input),
+            Pixel::into_raw_slice_mut(output),
+            |color| simd::lab_to_xyz(&white, color),
+        );
+        (&input[done..], &mut output[done..])
+    };
+
+    for (&color, result) in input.iter().zip(output) {
+        *result = color.into();
+    }
+}
+
+///Decode raw sRGB components, regardless of how they are laid out.
+fn decode_srgb(input: &[f32], output: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let (input, output) = {
+        let done = simd::components(input, output, simd::srgb_into_linear);
+        (&input[done..], &mut output[done..])
+    };
+
+    for (&component, result) in input.iter().zip(output) {
+        *result = ::encoding::Srgb::into_linear(component);
+    }
+}
+
+///Encode raw linear components as sRGB, regardless of how they are laid out.
+fn encode_srgb(input: &[f32], output: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let (input, output) = {
+        let done = simd::components(input, output, simd::srgb_from_linear);
+        (&input[done..], &mut output[done..])
+    };
+
+    for (&component, result) in input.iter().zip(output) {
+        *result = ::encoding::Srgb::from_linear(component);
+    }
+}
+
+fn multiply(matrix: &Mat3<f32>, [a, b, c]: [f32; 3]) -> [f32; 3] {
+    [
+        matrix[0] * a + matrix[1] * b + matrix[2] * c,
+        matrix[3] * a + matrix[4] * b + matrix[5] * c,
+        matrix[6] * a + matrix[7] * b + matrix[8] * c,
+    ]
+}
+
+#[cfg(feature = "simd")]
+fn white_point<Wp: WhitePoint>() -> [f32; 3] {
+    let white: Xyz<Wp, f32> = Wp::get_xyz();
+    [white.x, white.y, white.z]
+}
+
+///Convert a slice of colors into another color type with the same number of
+///components, in place, and return the same memory as a slice of the new
+///type.
+///
+///```
+///use palette::{Hsv, Srgb};
+///use palette::bulk::convert_slice_in_place;
+///
+///let mut colors = [Srgb::new(1.0, 0.0, 0.0), Srgb::new(0.0, 0.5, 0.5)];
+///let hsv: &mut [Hsv] = convert_slice_in_place(&mut colors);
+///
+///assert_eq!(hsv[0], Hsv::new(0.0, 1.0, 1.0));
+///```
+///
+///Panics if the color types don't have the same number of components.
+pub fn convert_slice_in_place<'a, A, B, T: 'a>(colors: &'a mut [A]) -> &'a mut [B]
+where
+    A: Pixel<T> + Copy,
+    B: Pixel<T> + Copy + From<A>,
+{
+    map_slice_in_place(colors, B::from)
+}
+
+///Convert a slice of colors in place with `convert`, and return the same
+///memory as a slice of the new type. This makes it possible to use
+///conversions that aren't implemented as `From`, such as `into_linear`.
+///
+///```
+///use palette::{LinSrgb, Srgb};
+///use palette::bulk::map_slice_in_place;
+///
+///let mut colors = [Srgb::new(1.0, 0.5, 0.0)];
+///let linear: &mut [LinSrgb] = map_slice_in_place(&mut colors, Srgb::into_linear);
+///
+///assert_eq!(linear[0], Srgb::new(1.0, 0.5, 0.0).into_linear());
+///```
+///
+///Panics if the color types don't have the same number of components.
+pub fn map_slice_in_place<'a, A, B, T: 'a, F>(colors: &'a mut [A], mut convert: F) -> &'a mut [B]
+where
+    A: Pixel<T> + Copy,
+    B: Pixel<T> + Copy,
+    F: FnMut(A) -> B,
+{
+    assert_eq!(
+        A::CHANNELS,
+        B::CHANNELS,
+        "the color types must have the same number of components"
+    );
+
+    for color in colors.iter_mut() {
+        let converted = convert(*color);
+        *B::from_raw_mut::<[T]>(color.as_raw_mut()) = converted;
+    }
+
+    B::from_raw_slice_mut(A::into_raw_slice_mut(colors))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {Hsv, LinSrgba};
+
+    fn colors(len: usize) -> Vec<Srgb<f32>> {
+        (0..len)
+            .map(|i| {
+                let i = i as f32;
+                Srgb::new((i * 0.37) % 1.2 - 0.1, (i * 0.11) % 1.0, (i * 0.73) % 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_single_conversions() {
+        for &len in &[0, 1, 3, 4, 5, 11, 64] {
+            let srgb = colors(len);
+
+            let mut linear = vec![LinSrgb::default(); len];
+            srgb_to_linear(&srgb, &mut linear);
+
+            let mut xyz = vec![Xyz::default(); len];
+            linear_srgb_to_xyz(&linear, &mut xyz);
+
+            let mut lab = vec![Lab::default(); len];
+            xyz_to_lab(&xyz, &mut lab);
+
+            let mut xyz_back = vec![Xyz::default(); len];
+            lab_to_xyz(&lab, &mut xyz_back);
+
+            let mut linear_back = vec![LinSrgb::default(); len];
+            xyz_to_linear_srgb(&xyz_back, &mut linear_back);
+
+            let mut srgb_back = vec![Srgb::default(); len];
+            linear_to_srgb(&linear_back, &mut srgb_back);
+
+            for i in 0..len {
+                let expected_linear = srgb[i].into_linear();
+                let expected_xyz: Xyz<D65, f32> = expected_linear.into();
+                let expected_lab: Lab<D65, f32> = expected_xyz.into();
+
+                assert_relative_eq!(
+                    linear[i],
+                    expected_linear,
+                    epsilon = 1e-6,
+                    max_relative = 1e-5
+                );
+                assert_relative_eq!(xyz[i], expected_xyz, epsilon = 1e-6, max_relative = 1e-5);
+                assert_relative_eq!(lab[i], expected_lab, epsilon = 1e-4, max_relative = 1e-5);
+                assert_relative_eq!(xyz_back[i], xyz[i], epsilon = 1e-5, max_relative = 1e-4);
+                assert_relative_eq!(srgb_back[i], srgb[i], epsilon = 1e-5, max_relative = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn in_place() {
+        let srgb = colors(9);
+
+        let mut buffer = srgb.clone();
+        {
+            let linear: &mut [LinSrgb] = map_slice_in_place(&mut buffer, Srgb::into_linear);
+            for (color, linear) in srgb.iter().zip(linear.iter()) {
+                assert_eq!(*linear, color.into_linear());
+            }
+        }
+
+        let mut buffer = srgb.clone();
+        let hsv: &mut [Hsv] = convert_slice_in_place(&mut buffer);
+        assert_eq!(hsv.len(), 9);
+        for (color, hsv) in srgb.iter().zip(hsv.iter()) {
+            assert_eq!(*hsv, Hsv::from(*color));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same number of components")]
+    fn in_place_different_sizes() {
+        map_slice_in_place(&mut colors(3), |color| LinSrgba::from(color.into_linear()));
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn different_lengths() {
+        srgb_to_linear(&colors(3), &mut [LinSrgb::default(); 2]);
+    }
+}
@@ -0,0 +1,126 @@
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, Map, ParallelIterator};
+use rayon::slice::{ParallelSlice, ParallelSliceMut};
+
+///The number of colors each task converts in `par_convert_slice`. Smaller
+///chunks would spend more time on scheduling than on converting.
+const CHUNK_SIZE: usize = 4096;
+
+///Convert a slice of colors into another color type, in parallel.
+///
+///The slices are split into chunks of a few thousand colors that are
+///converted on the `rayon` thread pool.
+///
+///```
+///use palette::{Lab, Srgb};
+///use palette::bulk::par_convert_slice;
+///
+///let colors = vec![Srgb::new(1.0, 0.5, 0.0); 10_000];
+///let mut lab = vec![Lab::default(); 10_000];
+///par_convert_slice(&colors, &mut lab);
+///
+///assert_eq!(lab[9_999], Lab::from(Srgb::new(1.0, 0.5, 0.0)));
+///```
+///
+///Panics if the slices don't have the same length.
+pub fn par_convert_slice<A, B>(input: &[A], output: &mut [B])
+where
+    A: Clone + Sync,
+    B: From<A> + Send,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+
+    input
+        .par_chunks(CHUNK_SIZE)
+        .zip(output.par_chunks_mut(CHUNK_SIZE))
+        .for_each(|(input, output)| {
+            for (color, result) in input.iter().zip(output) {
+                *result = color.clone().into();
+            }
+        });
+}
+
+///The parallel iterator returned by `IntoParallelColorIterator::into_par_colors`.
+pub type ParColors<I, C> =
+    Map<<I as IntoParallelIterator>::Iter, fn(<I as IntoParallelIterator>::Item) -> C>;
+
+///Converts the items of a parallel iterator into colors.
+///
+///It's implemented for everything that implements `IntoParallelIterator`, so
+///any collection or parallel iterator of colors can be converted with
+///`into_par_colors`.
+///
+///```
+///extern crate palette;
+///extern crate rayon;
+///
+///use palette::{Lab, Srgb};
+///use palette::bulk::IntoParallelColorIterator;
+///use rayon::iter::ParallelIterator;
+///
+///# fn main() {
+///let colors = vec![Srgb::new(0.8, 0.2, 0.1); 10_000];
+///let lab: Vec<Lab> = colors.into_par_colors().collect();
+///
+///assert_eq!(lab.len(), 10_000);
+///# }
+///```
+pub trait IntoParallelColorIterator: IntoParallelIterator {
+    ///Create a parallel iterator that converts each item into `C`.
+    fn into_par_colors<C>(self) -> ParColors<Self, C>
+    where
+        C: From<Self::Item> + Send;
+}
+
+impl<I: IntoParallelIterator> IntoParallelColorIterator for I {
+    fn into_par_colors<C>(self) -> ParColors<Self, C>
+    where
+        C: From<Self::Item> + Send,
+    {
+        self.into_par_iter().map(C::from as fn(Self::Item) -> C)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+    use super::{par_convert_slice, IntoParallelColorIterator};
+    use {Hsv, Lab, Srgb};
+
+    fn colors() -> Vec<Srgb> {
+        (0..10_000)
+            .map(|i| Srgb::new((i % 256) as f32 / 255.0, (i % 7) as f32 / 6.0, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn convert_slice() {
+        let colors = colors();
+        let mut lab = vec![Lab::default(); colors.len()];
+        par_convert_slice(&colors, &mut lab);
+
+        for (color, lab) in colors.iter().zip(&lab) {
+            assert_eq!(*lab, Lab::from(*color));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn convert_slice_length_mismatch() {
+        par_convert_slice(&colors(), &mut vec![Lab::default(); 3]);
+    }
+
+    #[test]
+    fn into_par_colors() {
+        let colors = colors();
+        let hsv: Vec<Hsv> = colors.par_iter().cloned().into_par_colors().collect();
+
+        for (color, hsv) in colors.iter().zip(&hsv) {
+            assert_eq!(*hsv, Hsv::from(*color));
+        }
+    }
+}
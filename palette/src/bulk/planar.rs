@@ -0,0 +1,556 @@
+//!Colors stored as separate planes of components.
+//!
+//!A [`Planar`](struct.Planar.html) buffer keeps each of the three components
+//!in its own slice, such as separate R, G and B planes, or the Y, Cb and Cr
+//!planes of a video frame. The conversion functions in this module mirror the
+//!ones in the [`bulk`](../index.html) module, and they are vectorized in the
+//!same way when the `"simd"` feature is enabled. Planar data doesn't need to
+//!be shuffled between colors and vectors, which makes it the faster layout.
+//!
+//!```
+//!use palette::{LinSrgb, Srgb};
+//!use palette::bulk::planar::{self, Planar};
+//!
+//!let red = [1.0, 0.2];
+//!let green = [0.5, 0.4];
+//!let blue = [0.0, 0.6];
+//!let colors: Planar<Srgb, f32, &[f32]> = Planar::new(&red, &green, &blue);
+//!assert_eq!(colors.get(1), Some(Srgb::new(0.2, 0.4, 0.6)));
+//!
+//!let mut linear: Planar<LinSrgb, f32, _> = Planar::new([0.0; 2], [0.0; 2], [0.0; 2]);
+//!planar::srgb_to_linear(&colors, &mut linear);
+//!
+//!assert_eq!(linear.get(1), Some(Srgb::new(0.2, 0.4, 0.6).into_linear()));
+//!```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use encoding::pixel::Pixel;
+use matrix::{matrix_inverse, rgb_to_xyz_matrix};
+use white_point::{WhitePoint, D65};
+use {Lab, LinSrgb, Mat3, Srgb, Xyz};
+
+use super::{decode_srgb, encode_srgb, multiply};
+#[cfg(feature = "simd")]
+use super::{simd, white_point};
+
+///A buffer of three component colors, where each component is stored in a
+///separate plane.
+///
+///`C` is the color type, `T` is its component type, and `B` is the type of
+///the planes, such as `Vec<T>`, `&[T]` or `&mut [T]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Planar<C, T, B> {
+    planes: [B; 3],
+    color: PhantomData<C>,
+    component: PhantomData<T>,
+}
+
+impl<C, T, B> Planar<C, T, B>
+where
+    C: Pixel<T> + Copy,
+    T: Copy,
+    B: AsRef<[T]>,
+{
+    ///Create a planar buffer from the planes of the first, second and third
+    ///components of `C`.
+    ///
+    ///Panics if the planes don't have the same length, or if `C` doesn't
+    ///have three components.
+    pub fn new(first: B, second: B, third: B) -> Self {
+        assert_eq!(C::CHANNELS, 3, "the color type must have three components");
+        assert!(
+            first.as_ref().len() == second.as_ref().len()
+                && first.as_ref().len() == third.as_ref().len(),
+            "the planes must have the same length"
+        );
+
+        Planar {
+            planes: [first, second, third],
+            color: PhantomData,
+            component: PhantomData,
+        }
+    }
+
+    ///The number of colors in the buffer.
+    pub fn len(&self) -> usize {
+        self.planes[0].as_ref().len()
+    }
+
+    ///Check if the buffer has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///Get the planes as slices.
+    pub fn planes(&self) -> [&[T]; 3] {
+        [
+            self.planes[0].as_ref(),
+            self.planes[1].as_ref(),
+            self.planes[2].as_ref(),
+        ]
+    }
+
+    ///Take the planes out of the buffer.
+    pub fn into_planes(self) -> [B; 3] {
+        self.planes
+    }
+
+    ///Get the color at `index`, or `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<C> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let [first, second, third] = self.planes();
+        Some(*C::from_raw(&[first[index], second[index], third[index]]))
+    }
+
+    ///Iterate over the colors in the buffer.
+    pub fn iter<'a>(&'a self) -> Iter<'a, C, T, B> {
+        Iter {
+            planar: self,
+            index: 0,
+        }
+    }
+
+    ///Copy the colors into an interleaved slice of colors.
+    ///
+    ///Panics if `output` doesn't have the same length as the buffer.
+    pub fn copy_to_slice(&self, output: &mut [C]) {
+        assert_eq!(
+            self.len(),
+            output.len(),
+            "the slices must have the same length"
+        );
+
+        for (color, result) in self.iter().zip(output) {
+            *result = color;
+        }
+    }
+
+    ///Copy the colors into a `Vec` of interleaved colors.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<C> {
+        self.iter().collect()
+    }
+
+    ///Convert the colors into another color type and write them to `output`.
+    ///
+    ///Panics if `output` doesn't have the same length as the buffer.
+    pub fn convert_into<D, U, Bo>(&self, output: &mut Planar<D, U, Bo>)
+    where
+        D: Pixel<U> + Copy + From<C>,
+        U: Copy,
+        Bo: AsRef<[U]> + AsMut<[U]>,
+    {
+        self.map_into(output, D::from)
+    }
+
+    ///Convert the colors with `convert` and write them to `output`. This makes
+    ///it possible to use conversions that aren't implemented as `From`, such
+    ///as `into_linear` or `into_format`.
+    ///
+    ///Panics if `output` doesn't have the same length as the buffer.
+    pub fn map_into<D, U, Bo, F>(&self, output: &mut Planar<D, U, Bo>, mut convert: F)
+    where
+        D: Pixel<U> + Copy,
+        U: Copy,
+        Bo: AsRef<[U]> + AsMut<[U]>,
+        F: FnMut(C) -> D,
+    {
+        assert_eq!(
+            self.len(),
+            output.len(),
+            "the buffers must have the same length"
+        );
+
+        for (index, color) in self.iter().enumerate() {
+            output.set(index, convert(color));
+        }
+    }
+}
+
+impl<C, T, B> Planar<C, T, B>
+where
+    C: Pixel<T> + Copy,
+    T: Copy,
+    B: AsRef<[T]> + AsMut<[T]>,
+{
+    ///Get the planes as mutable slices.
+    pub fn planes_mut(&mut self) -> [&mut [T]; 3] {
+        let [ref mut first, ref mut second, ref mut third] = self.planes;
+        [first.as_mut(), second.as_mut(), third.as_mut()]
+    }
+
+    ///Replace the color at `index`.
+    ///
+    ///Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, color: C) {
+        let components: &[T] = color.as_raw();
+        for (plane, &component) in self.planes_mut().iter_mut().zip(components) {
+            plane[index] = component;
+        }
+    }
+
+    ///Copy the colors from an interleaved slice of colors.
+    ///
+    ///Panics if `colors` doesn't have the same length as the buffer.
+    pub fn copy_from_slice(&mut self, colors: &[C]) {
+        assert_eq!(
+            self.len(),
+            colors.len(),
+            "the slices must have the same length"
+        );
+
+        for (index, &color) in colors.iter().enumerate() {
+            self.set(index, color);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, T> Planar<C, T, Vec<T>>
+where
+    C: Pixel<T> + Copy,
+    T: Copy,
+{
+    ///Split an interleaved slice of colors into planes.
+    pub fn from_colors(colors: &[C]) -> Self {
+        let mut planes = [
+            Vec::with_capacity(colors.len()),
+            Vec::with_capacity(colors.len()),
+            Vec::with_capacity(colors.len()),
+        ];
+
+        for color in colors {
+            let components: &[T] = color.as_raw();
+            for (plane, &component) in planes.iter_mut().zip(components) {
+                plane.push(component);
+            }
+        }
+
+        let [first, second, third] = planes;
+        Planar::new(first, second, third)
+    }
+}
+
+///An iterator over the colors in a [`Planar`](struct.Planar.html) buffer.
+pub struct Iter<'a, C: 'a, T: 'a, B: 'a> {
+    planar: &'a Planar<C, T, B>,
+    index: usize,
+}
+
+impl<'a, C, T, B> Iterator for Iter<'a, C, T, B>
+where
+    C: Pixel<T> + Copy,
+    T: Copy,
+    B: AsRef<[T]>,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        let color = self.planar.get(self.index);
+        if color.is_some() {
+            self.index += 1;
+        }
+        color
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.planar.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, C, T, B> ExactSizeIterator for Iter<'a, C, T, B>
+where
+    C: Pixel<T> + Copy,
+    T: Copy,
+    B: AsRef<[T]>,
+{
+}
+
+///Decode non-linear sRGB colors into linear sRGB.
+///
+///Panics if the buffers don't have the same length.
+pub fn srgb_to_linear<A, B>(
+    input: &Planar<Srgb<f32>, f32, A>,
+    output: &mut Planar<LinSrgb<f32>, f32, B>,
+) where
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the buffers must have the same length"
+    );
+
+    for (input, output) in input.planes().iter().zip(output.planes_mut().iter_mut()) {
+        decode_srgb(input, output);
+    }
+}
+
+///Encode linear sRGB colors as non-linear sRGB.
+///
+///Panics if the buffers don't have the same length.
+pub fn linear_to_srgb<A, B>(
+    input: &Planar<LinSrgb<f32>, f32, A>,
+    output: &mut Planar<Srgb<f32>, f32, B>,
+) where
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the buffers must have the same length"
+    );
+
+    for (input, output) in input.planes().iter().zip(output.planes_mut().iter_mut()) {
+        encode_srgb(input, output);
+    }
+}
+
+///Multiply the components in `input` with the row major matrix `matrix`, and
+///write the results to `output`.
+///
+///Panics if the planes don't have the same length.
+pub fn transform(matrix: &Mat3<f32>, input: [&[f32]; 3], output: [&mut [f32]; 3]) {
+    let length = input[0].len();
+    assert!(
+        input.iter().all(|plane| plane.len() == length)
+            && output.iter().all(|plane| plane.len() == length),
+        "the planes must have the same length"
+    );
+
+    let [x, y, z] = output;
+
+    #[cfg(feature = "simd")]
+    let start = {
+        let matrix = simd::splat_matrix(matrix);
+        simd::planar(input, [&mut *x, &mut *y, &mut *z], |color| {
+            simd::transform(&matrix, color)
+        })
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+
+    for i in start..length {
+        let [a, b, c] = multiply(matrix, [input[0][i], input[1][i], input[2][i]]);
+        x[i] = a;
+        y[i] = b;
+        z[i] = c;
+    }
+}
+
+///Convert linear sRGB colors to CIE XYZ.
+///
+///Panics if the buffers don't have the same length.
+pub fn linear_srgb_to_xyz<A, B>(
+    input: &Planar<LinSrgb<f32>, f32, A>,
+    output: &mut Planar<Xyz<D65, f32>, f32, B>,
+) where
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    transform(
+        &rgb_to_xyz_matrix::<::encoding::Srgb, f32>(),
+        input.planes(),
+        output.planes_mut(),
+    );
+}
+
+///Convert CIE XYZ colors to linear sRGB.
+///
+///Panics if the buffers don't have the same length.
+pub fn xyz_to_linear_srgb<A, B>(
+    input: &Planar<Xyz<D65, f32>, f32, A>,
+    output: &mut Planar<LinSrgb<f32>, f32, B>,
+) where
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    transform(
+        &matrix_inverse(&rgb_to_xyz_matrix::<::encoding::Srgb, f32>()),
+        input.planes(),
+        output.planes_mut(),
+    );
+}
+
+///Convert CIE XYZ colors to CIE L\*a\*b\*.
+///
+///Panics if the buffers don't have the same length.
+pub fn xyz_to_lab<Wp, A, B>(
+    input: &Planar<Xyz<Wp, f32>, f32, A>,
+    output: &mut Planar<Lab<Wp, f32>, f32, B>,
+) where
+    Wp: WhitePoint,
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the buffers must have the same length"
+    );
+
+    #[cfg(feature = "simd")]
+    let start = {
+        let white = simd::splat(white_point::<Wp>());
+        simd::planar(input.planes(), output.planes_mut(), |color| {
+            simd::xyz_to_lab(&white, color)
+        })
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+
+    for i in start..input.len() {
+        let color = input.get(i).unwrap();
+        output.set(i, color.into());
+    }
+}
+
+///Convert CIE L\*a\*b\* colors to CIE XYZ.
+///
+///Panics if the buffers don't have the same length.
+pub fn lab_to_xyz<Wp, A, B>(
+    input: &Planar<Lab<Wp, f32>, f32, A>,
+    output: &mut Planar<Xyz<Wp, f32>, f32, B>,
+) where
+    Wp: WhitePoint,
+    A: AsRef<[f32]>,
+    B: AsRef<[f32]> + AsMut<[f32]>,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the buffers must have the same length"
+    );
+
+    #[cfg(feature = "simd")]
+    let start = {
+        let white = simd::splat(white_point::<Wp>());
+        simd::planar(input.planes(), output.planes_mut(), |color| {
+            simd::lab_to_xyz(&white, color)
+        })
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+
+    for i in start..input.len() {
+        let color = input.get(i).unwrap();
+        output.set(i, color.into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bulk;
+    use Hsv;
+
+    fn colors(len: usize) -> Vec<Srgb<f32>> {
+        (0..len)
+            .map(|i| {
+                let i = i as f32;
+                Srgb::new((i * 0.37) % 1.2 - 0.1, (i * 0.11) % 1.0, (i * 0.73) % 1.0)
+            })
+            .collect()
+    }
+
+    fn empty<C: Pixel<f32> + Copy>(len: usize) -> Planar<C, f32, Vec<f32>> {
+        Planar::new(vec![0.0; len], vec![0.0; len], vec![0.0; len])
+    }
+
+    fn planar<C: Pixel<f32> + Copy>(colors: &[C]) -> Planar<C, f32, Vec<f32>> {
+        let mut planar = empty(colors.len());
+        planar.copy_from_slice(colors);
+        planar
+    }
+
+    fn interleaved<C: Pixel<f32> + Copy>(planar: &Planar<C, f32, Vec<f32>>) -> Vec<C> {
+        planar.iter().collect()
+    }
+
+    #[test]
+    fn colors_and_planes() {
+        let colors = colors(5);
+        let mut planar = planar(&colors);
+
+        assert_eq!(planar.len(), 5);
+        assert_eq!(interleaved(&planar), colors);
+        assert_eq!(planar.get(5), None);
+
+        planar.set(2, Srgb::new(1.0, 2.0, 3.0));
+        assert_eq!(planar.get(2), Some(Srgb::new(1.0, 2.0, 3.0)));
+        assert_eq!(planar.planes()[1][2], 2.0);
+
+        let mut interleaved = vec![Srgb::default(); 5];
+        planar.copy_to_slice(&mut interleaved);
+        assert_eq!(interleaved[2], Srgb::new(1.0, 2.0, 3.0));
+
+        let mut hsv = empty::<Hsv>(5);
+        planar.convert_into(&mut hsv);
+        assert_eq!(hsv.get(4), Some(Hsv::from(colors[4])));
+    }
+
+    #[test]
+    fn matches_interleaved() {
+        for &len in &[0, 1, 3, 4, 5, 11, 64] {
+            let srgb = colors(len);
+            let planar_srgb = planar(&srgb);
+
+            let mut linear = vec![LinSrgb::default(); len];
+            let mut xyz = vec![Xyz::default(); len];
+            let mut lab = vec![Lab::default(); len];
+            bulk::srgb_to_linear(&srgb, &mut linear);
+            bulk::linear_srgb_to_xyz(&linear, &mut xyz);
+            bulk::xyz_to_lab(&xyz, &mut lab);
+
+            let mut planar_linear = empty(len);
+            let mut planar_xyz = empty(len);
+            let mut planar_lab = empty(len);
+            srgb_to_linear(&planar_srgb, &mut planar_linear);
+            linear_srgb_to_xyz(&planar_linear, &mut planar_xyz);
+            xyz_to_lab(&planar_xyz, &mut planar_lab);
+
+            for i in 0..len {
+                assert_relative_eq!(planar_linear.get(i).unwrap(), linear[i], epsilon = 1e-6);
+                assert_relative_eq!(planar_xyz.get(i).unwrap(), xyz[i], epsilon = 1e-6);
+                assert_relative_eq!(planar_lab.get(i).unwrap(), lab[i], epsilon = 1e-4);
+            }
+
+            let mut planar_xyz_back = empty(len);
+            let mut planar_linear_back = empty(len);
+            let mut planar_srgb_back = empty(len);
+            lab_to_xyz(&planar_lab, &mut planar_xyz_back);
+            xyz_to_linear_srgb(&planar_xyz_back, &mut planar_linear_back);
+            linear_to_srgb(&planar_linear_back, &mut planar_srgb_back);
+
+            for (color, expected) in planar_srgb_back.iter().zip(&srgb) {
+                assert_relative_eq!(color, expected, epsilon = 1e-5, max_relative = 1e-4);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_planes() {
+        let colors = colors(5);
+        let planar = Planar::from_colors(&colors);
+
+        let blue: Vec<_> = colors.iter().map(|color| color.blue).collect();
+        assert_eq!(planar.planes()[2], &blue[..]);
+        assert_eq!(planar.to_vec(), colors);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn different_plane_lengths() {
+        Planar::<Srgb, f32, _>::new(vec![0.0; 3], vec![0.0; 3], vec![0.0; 2]);
+    }
+}
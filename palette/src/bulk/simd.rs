@@ -0,0 +1,133 @@
+//!Vectorized conversions of raw components, four at a time. The kernels
+//!convert vectors of components, and the drivers apply them to as many whole
+//!chunks of components as possible and return how much they converted.
+
+use wide::{f32x4, CmpGt, CmpLe};
+
+use Mat3;
+
+///Apply `kernel` to each chunk of four components and return the number of
+///converted components.
+pub fn components<F>(input: &[f32], output: &mut [f32], kernel: F) -> usize
+where
+    F: Fn(f32x4) -> f32x4,
+{
+    for (input, output) in input.chunks_exact(4).zip(output.chunks_exact_mut(4)) {
+        let result = kernel(f32x4::from([input[0], input[1], input[2], input[3]]));
+        output.copy_from_slice(&result.to_array());
+    }
+
+    input.len() / 4 * 4
+}
+
+///Apply `kernel` to each chunk of four interleaved colors and return the
+///number of converted colors.
+pub fn interleaved<F>(input: &[f32], output: &mut [f32], kernel: F) -> usize
+where
+    F: Fn([f32x4; 3]) -> [f32x4; 3],
+{
+    for (input, output) in input.chunks_exact(12).zip(output.chunks_exact_mut(12)) {
+        let result = kernel([
+            f32x4::from([input[0], input[3], input[6], input[9]]),
+            f32x4::from([input[1], input[4], input[7], input[10]]),
+            f32x4::from([input[2], input[5], input[8], input[11]]),
+        ]);
+
+        for (offset, component) in result.iter().enumerate() {
+            for (index, &value) in component.to_array().iter().enumerate() {
+                output[index * 3 + offset] = value;
+            }
+        }
+    }
+
+    input.len() / 12 * 4
+}
+
+///Apply `kernel` to each chunk of four colors in separate planes and return
+///the number of converted colors.
+pub fn planar<F>(input: [&[f32]; 3], mut output: [&mut [f32]; 3], kernel: F) -> usize
+where
+    F: Fn([f32x4; 3]) -> [f32x4; 3],
+{
+    let length = input[0].len() / 4 * 4;
+    let load =
+        |plane: &[f32], i: usize| f32x4::from([plane[i], plane[i + 1], plane[i + 2], plane[i + 3]]);
+
+    for i in (0..length).step_by(4) {
+        let result = kernel([load(input[0], i), load(input[1], i), load(input[2], i)]);
+
+        for (plane, component) in output.iter_mut().zip(result.iter()) {
+            plane[i..i + 4].copy_from_slice(&component.to_array());
+        }
+    }
+
+    length
+}
+
+pub fn splat(values: [f32; 3]) -> [f32x4; 3] {
+    [
+        f32x4::splat(values[0]),
+        f32x4::splat(values[1]),
+        f32x4::splat(values[2]),
+    ]
+}
+
+pub fn splat_matrix(matrix: &Mat3<f32>) -> [f32x4; 9] {
+    let mut result = [f32x4::splat(0.0); 9];
+    for (vector, &value) in result.iter_mut().zip(matrix.iter()) {
+        *vector = f32x4::splat(value);
+    }
+    result
+}
+
+pub fn srgb_into_linear(x: f32x4) -> f32x4 {
+    let linear = x / f32x4::splat(12.92);
+    let curve = ((x + f32x4::splat(0.055)) / f32x4::splat(1.055)).powf(2.4);
+    x.cmp_le(f32x4::splat(0.04045)).blend(linear, curve)
+}
+
+pub fn srgb_from_linear(x: f32x4) -> f32x4 {
+    let linear = x * f32x4::splat(12.92);
+    let curve = x.powf(1.0 / 2.4) * f32x4::splat(1.055) - f32x4::splat(0.055);
+    x.cmp_le(f32x4::splat(0.0031308)).blend(linear, curve)
+}
+
+pub fn transform(m: &[f32x4; 9], [a, b, c]: [f32x4; 3]) -> [f32x4; 3] {
+    [
+        m[0] * a + m[1] * b + m[2] * c,
+        m[3] * a + m[4] * b + m[5] * c,
+        m[6] * a + m[7] * b + m[8] * c,
+    ]
+}
+
+pub fn xyz_to_lab(white: &[f32x4; 3], [x, y, z]: [f32x4; 3]) -> [f32x4; 3] {
+    let f = |c: f32x4| {
+        let epsilon = f32x4::splat((6.0f32 / 29.0).powi(3));
+        let linear = c * f32x4::splat(841.0 / 108.0) + f32x4::splat(4.0 / 29.0);
+        let curve = c.max(epsilon).powf(1.0 / 3.0);
+        c.cmp_gt(epsilon).blend(curve, linear)
+    };
+
+    let x = f(x / white[0]);
+    let y = f(y / white[1]);
+    let z = f(z / white[2]);
+
+    [
+        y * f32x4::splat(116.0) - f32x4::splat(16.0),
+        (x - y) * f32x4::splat(500.0),
+        (y - z) * f32x4::splat(200.0),
+    ]
+}
+
+pub fn lab_to_xyz(white: &[f32x4; 3], [l, a, b]: [f32x4; 3]) -> [f32x4; 3] {
+    let f = |c: f32x4| {
+        let linear = (c - f32x4::splat(4.0 / 29.0)) * f32x4::splat(108.0 / 841.0);
+        c.cmp_gt(f32x4::splat(6.0 / 29.0)).blend(c * c * c, linear)
+    };
+
+    let y = (l + f32x4::splat(16.0)) / f32x4::splat(116.0);
+    let x = y + a / f32x4::splat(500.0);
+    let z = y - b / f32x4::splat(200.0);
+
+    [f(x) * white[0], f(y) * white[1], f(z) * white[2]]
+}
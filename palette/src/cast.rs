@@ -0,0 +1,405 @@
+//!Conversion between colors and arrays, tuples and packed integers.
+//!
+//!Any color that implements [`Pixel`](../trait.Pixel.html), including the
+//![`Alpha`](../struct.Alpha.html) wrappers, can be converted to and from an
+//!array or a tuple of its components, in the same order as its fields:
+//!
+//!```
+//!use palette::{cast, Hsv, Srgba};
+//!
+//!let array: [f32; 4] = cast::into_array(Srgba::new(0.8, 0.2, 0.1, 1.0));
+//!assert_eq!(array, [0.8, 0.2, 0.1, 1.0]);
+//!
+//!let hsv: Hsv = cast::from_tuple((120.0, 0.5, 1.0));
+//!assert_eq!(hsv, Hsv::new(120.0, 0.5, 1.0));
+//!```
+//!
+//!8 bit RGB colors can also be packed into a `u32`, with the channel order
+//!chosen by a [`ComponentOrder`](trait.ComponentOrder.html):
+//!
+//!```
+//!use palette::Srgba;
+//!use palette::cast::{self, Argb, Rgba};
+//!
+//!let color = Srgba::new(0x11u8, 0x22, 0x33, 0xff);
+//!assert_eq!(cast::into_u32::<Rgba, _>(color), 0x112233ff);
+//!assert_eq!(cast::into_u32::<Argb, _>(color), 0xff112233);
+//!
+//!let unpacked: Srgba<u8> = cast::from_u32::<Argb, _>(0xff112233);
+//!assert_eq!(unpacked, color);
+//!```
+
+use core::marker::PhantomData;
+use core::{mem, ptr};
+
+use encoding::pixel::RawPixelSized;
+use rgb::{Rgb, RgbStandard};
+use {Alpha, Pixel};
+
+///Convert a color into an array of its components.
+///
+///# Panics
+///
+///Panics if the array doesn't have the same number of components as the color.
+#[inline]
+pub fn into_array<C, T, A>(color: C) -> A
+where
+    C: Pixel<T>,
+    A: RawPixelSized<T>,
+{
+    color.into_raw()
+}
+
+///Convert an array of components into a color.
+///
+///# Panics
+///
+///Panics if the array doesn't have the same number of components as the color.
+#[inline]
+pub fn from_array<C, T, A>(array: A) -> C
+where
+    C: Pixel<T>,
+    A: RawPixelSized<T>,
+{
+    assert_eq!(A::CHANNELS, C::CHANNELS);
+    assert_eq!(mem::size_of::<A>(), mem::size_of::<C>());
+    assert_eq!(mem::align_of::<A>(), mem::align_of::<C>());
+
+    let color = unsafe { ptr::read(&array as *const A as *const C) };
+    mem::forget(array);
+
+    color
+}
+
+///Convert a color into a tuple of its components.
+///
+///# Panics
+///
+///Panics if the tuple doesn't have the same number of components as the color.
+#[inline]
+pub fn into_tuple<C, T, U>(color: C) -> U
+where
+    C: Pixel<T>,
+    U: ComponentTuple<T>,
+{
+    U::from_array(into_array(color))
+}
+
+///Convert a tuple of components into a color.
+///
+///# Panics
+///
+///Panics if the tuple doesn't have the same number of components as the color.
+#[inline]
+pub fn from_tuple<C, T, U>(tuple: U) -> C
+where
+    C: Pixel<T>,
+    U: ComponentTuple<T>,
+{
+    from_array(tuple.into_array())
+}
+
+///A tuple of color components, with the same layout as an array.
+pub trait ComponentTuple<T>: Sized {
+    ///The array with the same number of components.
+    type Array: RawPixelSized<T>;
+
+    ///Create the tuple from an array.
+    fn from_array(array: Self::Array) -> Self;
+
+    ///Convert the tuple into an array.
+    fn into_array(self) -> Self::Array;
+}
+
+impl<T> ComponentTuple<T> for (T,) {
+    type Array = [T; 1];
+
+    #[inline]
+    fn from_array([a]: [T; 1]) -> Self {
+        (a,)
+    }
+
+    #[inline]
+    fn into_array(self) -> [T; 1] {
+        [self.0]
+    }
+}
+
+impl<T> ComponentTuple<T> for (T, T) {
+    type Array = [T; 2];
+
+    #[inline]
+    fn from_array([a, b]: [T; 2]) -> Self {
+        (a, b)
+    }
+
+    #[inline]
+    fn into_array(self) -> [T; 2] {
+        [self.0, self.1]
+    }
+}
+
+impl<T> ComponentTuple<T> for (T, T, T) {
+    type Array = [T; 3];
+
+    #[inline]
+    fn from_array([a, b, c]: [T; 3]) -> Self {
+        (a, b, c)
+    }
+
+    #[inline]
+    fn into_array(self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+impl<T> ComponentTuple<T> for (T, T, T, T) {
+    type Array = [T; 4];
+
+    #[inline]
+    fn from_array([a, b, c, d]: [T; 4]) -> Self {
+        (a, b, c, d)
+    }
+
+    #[inline]
+    fn into_array(self) -> [T; 4] {
+        [self.0, self.1, self.2, self.3]
+    }
+}
+
+///The order of the channels in a packed 8 bit RGBA color.
+pub trait ComponentOrder {
+    ///Pack the components, in the order `[red, green, blue, alpha]`, into a `u32`.
+    fn pack(rgba: [u8; 4]) -> u32;
+
+    ///Unpack a `u32` into its components, in the order `[red, green, blue, alpha]`.
+    fn unpack(packed: u32) -> [u8; 4];
+}
+
+///Red in the most significant byte and alpha in the least significant byte, or RGBA8888.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgba;
+
+impl ComponentOrder for Rgba {
+    #[inline]
+    fn pack([red, green, blue, alpha]: [u8; 4]) -> u32 {
+        u32::from_be_bytes([red, green, blue, alpha])
+    }
+
+    #[inline]
+    fn unpack(packed: u32) -> [u8; 4] {
+        let [red, green, blue, alpha] = packed.to_be_bytes();
+        [red, green, blue, alpha]
+    }
+}
+
+///Alpha in the most significant byte and blue in the least significant byte, or ARGB8888.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Argb;
+
+impl ComponentOrder for Argb {
+    #[inline]
+    fn pack([red, green, blue, alpha]: [u8; 4]) -> u32 {
+        u32::from_be_bytes([alpha, red, green, blue])
+    }
+
+    #[inline]
+    fn unpack(packed: u32) -> [u8; 4] {
+        let [alpha, red, green, blue] = packed.to_be_bytes();
+        [red, green, blue, alpha]
+    }
+}
+
+///Alpha in the most significant byte and red in the least significant byte, or ABGR8888.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Abgr;
+
+impl ComponentOrder for Abgr {
+    #[inline]
+    fn pack([red, green, blue, alpha]: [u8; 4]) -> u32 {
+        u32::from_be_bytes([alpha, blue, green, red])
+    }
+
+    #[inline]
+    fn unpack(packed: u32) -> [u8; 4] {
+        let [alpha, blue, green, red] = packed.to_be_bytes();
+        [red, green, blue, alpha]
+    }
+}
+
+///Blue in the most significant byte and alpha in the least significant byte, or BGRA8888.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bgra;
+
+impl ComponentOrder for Bgra {
+    #[inline]
+    fn pack([red, green, blue, alpha]: [u8; 4]) -> u32 {
+        u32::from_be_bytes([blue, green, red, alpha])
+    }
+
+    #[inline]
+    fn unpack(packed: u32) -> [u8; 4] {
+        let [blue, green, red, alpha] = packed.to_be_bytes();
+        [red, green, blue, alpha]
+    }
+}
+
+///An 8 bit RGBA color, packed into a `u32` in the channel order `O`.
+///
+///Colors without transparency are packed with an alpha of `255`, and the
+///alpha is dropped when unpacking into them.
+#[derive(Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Packed<O> {
+    ///The packed color.
+    pub color: u32,
+
+    order: PhantomData<O>,
+}
+
+impl<O> Packed<O> {
+    ///Wrap a packed color.
+    #[inline]
+    pub fn new(color: u32) -> Packed<O> {
+        Packed {
+            color: color,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O> Copy for Packed<O> {}
+
+impl<O> Clone for Packed<O> {
+    #[inline]
+    fn clone(&self) -> Packed<O> {
+        *self
+    }
+}
+
+impl<O> From<u32> for Packed<O> {
+    #[inline]
+    fn from(color: u32) -> Packed<O> {
+        Packed::new(color)
+    }
+}
+
+impl<O> From<Packed<O>> for u32 {
+    #[inline]
+    fn from(packed: Packed<O>) -> u32 {
+        packed.color
+    }
+}
+
+impl<S: RgbStandard, O: ComponentOrder> From<Alpha<Rgb<S, u8>, u8>> for Packed<O> {
+    #[inline]
+    fn from(color: Alpha<Rgb<S, u8>, u8>) -> Packed<O> {
+        Packed::new(O::pack([color.red, color.green, color.blue, color.alpha]))
+    }
+}
+
+impl<S: RgbStandard, O: ComponentOrder> From<Rgb<S, u8>> for Packed<O> {
+    #[inline]
+    fn from(color: Rgb<S, u8>) -> Packed<O> {
+        Packed::new(O::pack([color.red, color.green, color.blue, 255]))
+    }
+}
+
+impl<S: RgbStandard, O: ComponentOrder> From<Packed<O>> for Alpha<Rgb<S, u8>, u8> {
+    #[inline]
+    fn from(packed: Packed<O>) -> Alpha<Rgb<S, u8>, u8> {
+        let [red, green, blue, alpha] = O::unpack(packed.color);
+        Alpha {
+            color: Rgb::new(red, green, blue),
+            alpha: alpha,
+        }
+    }
+}
+
+impl<S: RgbStandard, O: ComponentOrder> From<Packed<O>> for Rgb<S, u8> {
+    #[inline]
+    fn from(packed: Packed<O>) -> Rgb<S, u8> {
+        let [red, green, blue, _] = O::unpack(packed.color);
+        Rgb::new(red, green, blue)
+    }
+}
+
+///Pack an 8 bit RGB or RGBA color into a `u32`, in the channel order `O`.
+#[inline]
+pub fn into_u32<O, C>(color: C) -> u32
+where
+    C: Into<Packed<O>>,
+{
+    color.into().color
+}
+
+///Unpack an 8 bit RGB or RGBA color from a `u32`, in the channel order `O`.
+#[inline]
+pub fn from_u32<O, C>(packed: u32) -> C
+where
+    Packed<O>: Into<C>,
+{
+    Packed::new(packed).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        from_array, from_tuple, from_u32, into_array, into_tuple, into_u32, Abgr, Argb, Bgra,
+        Packed, Rgba,
+    };
+    use luma::{Luma, Lumaa};
+    use {Hsla, Lab, Srgb, Srgba};
+
+    #[test]
+    fn arrays() {
+        let array: [f32; 3] = into_array(Lab::new(50.0, 10.0, -20.0));
+        assert_eq!(array, [50.0, 10.0, -20.0]);
+
+        let color: Hsla = from_array([90.0, 0.5, 0.25, 0.75]);
+        assert_eq!(color, Hsla::new(90.0, 0.5, 0.25, 0.75));
+
+        let luma: Luma<::encoding::Srgb, u8> = from_array([7u8]);
+        assert_eq!(luma.luma, 7);
+    }
+
+    #[test]
+    fn tuples() {
+        let tuple: (u8, u8) = into_tuple(Lumaa::<::encoding::Srgb, u8>::new(1, 2));
+        assert_eq!(tuple, (1, 2));
+
+        let color: Srgba<u8> = from_tuple((1u8, 2, 3, 4));
+        assert_eq!(color, Srgba::new(1, 2, 3, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_length() {
+        let _: [f32; 4] = into_array(Srgb::new(0.1, 0.2, 0.3));
+    }
+
+    #[test]
+    fn packed() {
+        let color = Srgba::new(0x11u8, 0x22, 0x33, 0x44);
+
+        assert_eq!(into_u32::<Rgba, _>(color), 0x1122_3344);
+        assert_eq!(into_u32::<Argb, _>(color), 0x4411_2233);
+        assert_eq!(into_u32::<Abgr, _>(color), 0x4433_2211);
+        assert_eq!(into_u32::<Bgra, _>(color), 0x3322_1144);
+
+        assert_eq!(from_u32::<Rgba, Srgba<u8>>(0x1122_3344), color);
+        assert_eq!(from_u32::<Argb, Srgba<u8>>(0x4411_2233), color);
+        assert_eq!(from_u32::<Abgr, Srgba<u8>>(0x4433_2211), color);
+        assert_eq!(from_u32::<Bgra, Srgba<u8>>(0x3322_1144), color);
+    }
+
+    #[test]
+    fn packed_without_alpha() {
+        let color = Srgb::new(0x11u8, 0x22, 0x33);
+        let packed: Packed<Argb> = color.into();
+
+        assert_eq!(u32::from(packed), 0xff11_2233);
+        assert_eq!(Srgb::from(packed), color);
+        assert_eq!(from_u32::<Argb, Srgb<u8>>(0x0011_2233), color);
+    }
+}
@@ -0,0 +1,152 @@
+use float::Float;
+
+use luma::{Luma, LumaStandard};
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {Alpha, Component, Hsl, Hsv, Hwb, Lab, Lch, Pixel, Xyz, Yxy};
+
+///Access to the components of a color by index or by name.
+///
+///The channels are numbered in the same order as the fields of the color,
+///and named after them. Their number is known at compile time, as
+///`Pixel::CHANNELS`, which makes it possible to write per-channel operations,
+///such as curves and histograms, once for all color types. Hues are accessed
+///as their raw number of degrees, without normalization.
+///
+///```
+///use palette::{Channels, Hsv, Pixel, Srgb};
+///
+///fn invert_channel<C: Channels<f32>>(color: &mut C, index: usize) {
+///    if let Some(channel) = color.channel_mut(index) {
+///        *channel = 1.0 - *channel;
+///    }
+///}
+///
+///let mut rgb = Srgb::new(0.2, 0.4, 0.6);
+///invert_channel(&mut rgb, 1);
+///assert_eq!(rgb.green, 0.6);
+///
+///let hsv = Hsv::new(120.0, 0.5, 0.25);
+///assert_eq!(hsv.channel_by_name("value"), Some(&0.25));
+///assert_eq!(<Hsv as Pixel<f32>>::CHANNELS, 3);
+///```
+pub trait Channels<T>: Pixel<T> {
+    ///Get the name of the channel with the index `index`, if it exists.
+    fn channel_name(index: usize) -> Option<&'static str>;
+
+    ///Get the index of the channel with the name `name`, if it exists.
+    fn channel_index(name: &str) -> Option<usize> {
+        (0..Self::CHANNELS).find(|&index| Self::channel_name(index) == Some(name))
+    }
+
+    ///Get a reference to the channel with the index `index`, if it exists.
+    #[inline]
+    fn channel(&self, index: usize) -> Option<&T> {
+        self.as_raw::<[T]>().get(index)
+    }
+
+    ///Get a mutable reference to the channel with the index `index`, if it
+    ///exists.
+    #[inline]
+    fn channel_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_raw_mut::<[T]>().get_mut(index)
+    }
+
+    ///Get a reference to the channel with the name `name`, if it exists.
+    #[inline]
+    fn channel_by_name(&self, name: &str) -> Option<&T> {
+        Self::channel_index(name).and_then(|index| self.channel(index))
+    }
+
+    ///Get a mutable reference to the channel with the name `name`, if it
+    ///exists.
+    #[inline]
+    fn channel_by_name_mut(&mut self, name: &str) -> Option<&mut T> {
+        Self::channel_index(name).and_then(move |index| self.channel_mut(index))
+    }
+}
+
+macro_rules! impl_channels {
+    (
+        $ty: ident <$param: ident : $bound: ident, $component: ident : $($component_bound: ident)+>,
+        $($name: expr),+
+    ) => {
+        impl<$param, $component> Channels<$component> for $ty<$param, $component>
+        where
+            $param: $bound,
+            $component: $($component_bound +)+,
+        {
+            fn channel_name(index: usize) -> Option<&'static str> {
+                [$($name),+].get(index).cloned()
+            }
+        }
+    };
+}
+
+impl_channels!(Rgb<S: RgbStandard, T: Component>, "red", "green", "blue");
+impl_channels!(Luma<S: LumaStandard, T: Component>, "luma");
+impl_channels!(Hsl<S: RgbSpace, T: Component Float>, "hue", "saturation", "lightness");
+impl_channels!(Hsv<S: RgbSpace, T: Component Float>, "hue", "saturation", "value");
+impl_channels!(Hwb<S: RgbSpace, T: Component Float>, "hue", "whiteness", "blackness");
+impl_channels!(Lab<Wp: WhitePoint, T: Component Float>, "l", "a", "b");
+impl_channels!(Lch<Wp: WhitePoint, T: Component Float>, "l", "chroma", "hue");
+impl_channels!(Xyz<Wp: WhitePoint, T: Component Float>, "x", "y", "z");
+impl_channels!(Yxy<Wp: WhitePoint, T: Component Float>, "x", "y", "luma");
+
+impl<C: Channels<T>, T> Channels<T> for Alpha<C, T> {
+    fn channel_name(index: usize) -> Option<&'static str> {
+        if index == C::CHANNELS {
+            Some("alpha")
+        } else {
+            C::channel_name(index)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Channels;
+    use rgb::Rgb;
+    use white_point::D65;
+    use {Hsva, Lab, Lch, Pixel, Srgb, Srgba};
+
+    #[test]
+    fn names() {
+        assert_eq!(Srgb::<f32>::channel_name(2), Some("blue"));
+        assert_eq!(Srgb::<f32>::channel_name(3), None);
+        assert_eq!(Srgba::<f32>::channel_name(3), Some("alpha"));
+        assert_eq!(Srgba::<f32>::channel_name(4), None);
+        assert_eq!(Lch::<D65, f32>::channel_index("hue"), Some(2));
+        assert_eq!(Lab::<D65, f32>::channel_index("hue"), None);
+    }
+
+    #[test]
+    fn access() {
+        let mut color = Hsva::new(40.0, 0.5, 0.25, 0.75);
+
+        assert_eq!(color.channel(0), Some(&40.0));
+        assert_eq!(color.channel_by_name("alpha"), Some(&0.75));
+        assert_eq!(color.channel(4), None);
+
+        *color.channel_by_name_mut("hue").unwrap() = 400.0;
+        *color.channel_mut(2).unwrap() = 1.0;
+        assert_eq!(color, Hsva::new(40.0, 0.5, 1.0, 0.75));
+    }
+
+    #[test]
+    fn generic() {
+        fn histogram<C: Channels<u8>>(colors: &[C], channel: usize) -> [usize; 4] {
+            let mut bins = [0; 4];
+            for color in colors {
+                bins[*color.channel(channel).unwrap() as usize / 64] += 1;
+            }
+            bins
+        }
+
+        let colors: &[Rgb<::encoding::Srgb, u8>] =
+            Pixel::from_raw_slice(&[0, 70, 255, 10, 130, 255, 200, 20, 0]);
+
+        assert_eq!(histogram(colors, 0), [2, 0, 0, 1]);
+        assert_eq!(histogram(colors, 1), [1, 1, 1, 0]);
+    }
+}
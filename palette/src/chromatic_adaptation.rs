@@ -24,9 +24,9 @@
 //!```
 use float::Float;
 
-use {cast, Component, FromColor, IntoColor, Xyz};
-use white_point::WhitePoint;
-use matrix::{multiply_xyz, Mat3, multiply_3x3};
+use {cast, clamp, Component, FromColorUnclamped, IntoColorUnclamped, Xyz};
+use white_point::{WhitePoint, D65};
+use matrix::{adaptation_matrix, multiply_xyz, Mat3};
 
 ///Chromatic adaptation methods implemented in the library
 pub enum Method {
@@ -62,25 +62,7 @@ where
     fn generate_transform_matrix(&self) -> Mat3<T> {
         let s_wp: Xyz<Swp, T> = Swp::get_xyz();
         let t_wp: Xyz<Dwp, T> = Dwp::get_xyz();
-        let adapt = self.get_cone_response();
-
-        let resp_src: Xyz<Swp, _> = multiply_xyz(&adapt.ma, &s_wp);
-        let resp_dst: Xyz<Dwp, _> = multiply_xyz(&adapt.ma, &t_wp);
-        let z = T::zero();
-        let resp = [
-            resp_dst.x / resp_src.x,
-            z,
-            z,
-            z,
-            resp_dst.y / resp_src.y,
-            z,
-            z,
-            z,
-            resp_dst.z / resp_src.z,
-        ];
-
-        let tmp = multiply_3x3(&resp, &adapt.ma);
-        multiply_3x3(&adapt.inv_ma, &tmp)
+        adaptation_matrix(self.get_cone_response(), &s_wp, &t_wp)
     }
 }
 
@@ -158,8 +140,8 @@ where
     T: Component + Float,
     Swp: WhitePoint,
     Dwp: WhitePoint,
-    S: IntoColor<Swp, T>,
-    D: FromColor<Dwp, T>,
+    S: IntoColorUnclamped<Swp, T>,
+    D: FromColorUnclamped<Dwp, T>,
 {
     fn adapt_from_using<M: TransformMatrix<Swp, Dwp, T>>(color: S, method: M) -> D {
         let src_xyz: Xyz<Swp, T> = color.into_xyz();
@@ -201,12 +183,153 @@ where
     }
 }
 
+///Trait for shifting the color temperature of a color, for quick warming and
+///cooling filters.
+///
+///The color is chromatically adapted from its white point to a new white point,
+///that's moved along the blackbody (Planckian) locus by `kelvin`. The current
+///temperature of the white point is approximated from its chromaticity, so a
+///shift of `0.0` leaves the color unchanged. A positive shift raises the color
+///temperature, making the color cooler and more blue, while a negative shift
+///lowers it, making the color warmer and more yellow. The resulting
+///temperature is limited to the range `[1667.0, 25000.0]` K.
+///
+///It's implemented for every color that can be converted to and from `Xyz`,
+///and uses the Bradford method by default.
+///
+///```
+///use palette::{LinSrgb, Srgb};
+///use palette::chromatic_adaptation::ShiftTemperature;
+///
+///let white = LinSrgb::new(1.0, 1.0, 1.0);
+///
+///let warm = white.warm(1500.0);
+///assert!(warm.red > warm.blue);
+///
+///let cool = white.cool(1500.0);
+///assert!(cool.red < cool.blue);
+///
+///let mut pixels = vec![Srgb::new(0.5, 0.4, 0.3).into_linear(); 4];
+///palette::chromatic_adaptation::shift_temperature_slice(&mut pixels, -1000.0);
+///```
+pub trait ShiftTemperature<Wp = D65, T = f32>: Sized
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+{
+    ///Shift the color temperature by `kelvin`, using the bradford method.
+    fn shift_temperature(&self, kelvin: T) -> Self {
+        self.shift_temperature_using(kelvin, Method::Bradford)
+    }
+
+    ///Shift the color temperature by `kelvin`, using the specified method.
+    fn shift_temperature_using<M: TransformMatrix<Wp, Wp, T>>(&self, kelvin: T, method: M) -> Self;
+
+    ///Make the color warmer by lowering its color temperature by `kelvin`.
+    fn warm(&self, kelvin: T) -> Self {
+        self.shift_temperature(-kelvin)
+    }
+
+    ///Make the color cooler by raising its color temperature by `kelvin`.
+    fn cool(&self, kelvin: T) -> Self {
+        self.shift_temperature(kelvin)
+    }
+}
+
+impl<C, Wp, T> ShiftTemperature<Wp, T> for C
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+{
+    fn shift_temperature_using<M: TransformMatrix<Wp, Wp, T>>(&self, kelvin: T, method: M) -> C {
+        let transform_matrix = temperature_matrix(kelvin, &method);
+        let xyz: Xyz<Wp, T> = self.clone().into_xyz();
+        C::from_xyz(multiply_xyz(&transform_matrix, &xyz))
+    }
+}
+
+///Shift the color temperature of each color in `colors` by `kelvin`, using the
+///bradford method. See [`ShiftTemperature`](trait.ShiftTemperature.html) for
+///details.
+///
+///The adaptation matrix is only calculated once, which makes this faster than
+///shifting each color individually.
+pub fn shift_temperature_slice<C, Wp, T>(colors: &mut [C], kelvin: T)
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+{
+    let transform_matrix = temperature_matrix::<Wp, T, _>(kelvin, &Method::Bradford);
+
+    for color in colors {
+        let xyz: Xyz<Wp, T> = color.clone().into_xyz();
+        *color = C::from_xyz(multiply_xyz(&transform_matrix, &xyz));
+    }
+}
+
+fn temperature_matrix<Wp, T, M>(kelvin: T, method: &M) -> Mat3<T>
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+    M: TransformMatrix<Wp, Wp, T>,
+{
+    let s_wp: Xyz<Wp, T> = Wp::get_xyz();
+    let sum = s_wp.x + s_wp.y + s_wp.z;
+    let (x, y) = (s_wp.x / sum, s_wp.y / sum);
+
+    //McCamy's approximation of the correlated color temperature
+    let n = (x - cast(0.3320)) / (cast::<T, _>(0.1858) - y);
+    let temperature = n * n * n * cast(449.0) + n * n * cast(3525.0) + n * cast(6823.3)
+        + cast(5520.33);
+
+    //Move the white point as much as the locus moves, to keep a shift of 0
+    //exact for white points that are not on the locus
+    let (from_x, from_y) = planckian_chromaticity(temperature);
+    let (to_x, to_y) = planckian_chromaticity(temperature + kelvin);
+    let x = x + to_x - from_x;
+    let y = y + to_y - from_y;
+
+    let t_wp: Xyz<Wp, T> = Xyz::with_wp(x / y, T::one(), (T::one() - x - y) / y);
+    adaptation_matrix(method.get_cone_response(), &s_wp, &t_wp)
+}
+
+///The chromaticity of a blackbody radiator, using the cubic spline
+///approximation by Kim et al.
+fn planckian_chromaticity<T: Float>(temperature: T) -> (T, T) {
+    let t = clamp(temperature, cast(1667.0), cast(25000.0));
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let x: T = if t <= cast(4000.0) {
+        cast::<T, _>(-0.2661239e9) / t3 - cast::<T, _>(0.2343589e6) / t2
+            + cast::<T, _>(0.8776956e3) / t + cast(0.179910)
+    } else {
+        cast::<T, _>(-3.0258469e9) / t3 + cast::<T, _>(2.1070379e6) / t2
+            + cast::<T, _>(0.2226347e3) / t + cast(0.240390)
+    };
+
+    let x2 = x * x;
+    let x3 = x2 * x;
+
+    let y: T = if t <= cast(2222.0) {
+        x3 * cast(-1.1063814) - x2 * cast(1.34811020) + x * cast(2.18555832) - cast(0.20219683)
+    } else if t <= cast(4000.0) {
+        x3 * cast(-0.9549476) - x2 * cast(1.37418593) + x * cast(2.09137015) - cast(0.16748867)
+    } else {
+        x3 * cast(3.0817580) - x2 * cast(5.87338670) + x * cast(3.75112997) - cast(0.37001483)
+    };
+
+    (x, y)
+}
+
 #[cfg(test)]
 mod test {
 
     use Xyz;
     use white_point::{D50, D65, A, C};
-    use super::{AdaptFrom, AdaptInto, Method, TransformMatrix};
+    use super::{AdaptFrom, AdaptInto, Method, ShiftTemperature, TransformMatrix};
 
     #[test]
     fn d65_to_d50_matrix_xyz_scaling() {
@@ -280,4 +403,25 @@ mod test {
         let computed_xyz_scaling: Xyz<C, _> = input_a.adapt_into_using(Method::XyzScaling);
         assert_relative_eq!(expected_xyz_scaling, computed_xyz_scaling, epsilon = 0.0001);
     }
+
+    #[test]
+    fn shift_temperature() {
+        use {LinSrgb, Yxy};
+
+        let color = LinSrgb::new(0.6, 0.5, 0.4);
+        assert_relative_eq!(color.shift_temperature(0.0), color, epsilon = 0.00001);
+
+        //Shifting D65 down by about 1500 K should land close to D50
+        let white = Xyz::<D65, f64>::with_wp(0.95047, 1.0, 1.08883);
+        let warm: Yxy<D65, f64> = white.warm(1500.0).into();
+        assert_relative_eq!(warm.x, 0.3457, epsilon = 0.005);
+        assert_relative_eq!(warm.y, 0.3585, epsilon = 0.005);
+
+        let cool = color.cool(2000.0);
+        assert!(cool.blue / cool.red > color.blue / color.red);
+
+        let mut colors = [color, color];
+        super::shift_temperature_slice(&mut colors, -500.0);
+        assert_relative_eq!(colors[1], color.warm(500.0));
+    }
 }
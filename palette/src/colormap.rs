@@ -0,0 +1,261 @@
+//!Perceptually uniform colormaps for scientific visualization. Can be toggled
+//!with the `"colormaps"` Cargo feature.
+//!
+//!The colormaps are looked up in their original, non-linear sRGB encoding and
+//!can be converted to any other component type or color space from there:
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::colormap::Colormap;
+//!
+//!let color: Srgb<u8> = Colormap::Viridis.get(0.5).into_format();
+//!
+//!let linear = Colormap::Magma.get(0.25f64).into_linear();
+//!```
+//!
+//!Viridis, magma, inferno and plasma are interpolated linearly between the
+//!256 colors of their matplotlib lookup tables. Cividis and turbo are
+//!interpolated between ten reference colors, sampled from their lookup tables.
+
+use float::Float;
+
+use rgb::Srgb;
+#[cfg(feature = "alloc")]
+use {Gradient, LinSrgb};
+use {cast, clamp, Component};
+
+///A scientific colormap.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Colormap {
+    ///The default colormap in matplotlib. Goes from dark blue, through green,
+    ///to yellow.
+    Viridis,
+    ///Goes from black, through purple and orange, to light yellow.
+    Magma,
+    ///Goes from black, through purple and orange, to bright yellow.
+    Inferno,
+    ///Goes from dark blue, through purple and orange, to yellow.
+    Plasma,
+    ///A colormap for viewers with color vision deficiencies. Goes from dark
+    ///blue, through gray, to yellow.
+    Cividis,
+    ///A rainbow colormap with smooth transitions and a monotonic lightness
+    ///within each half. Goes from dark blue, through green, to dark red.
+    Turbo,
+}
+
+impl Colormap {
+    ///Get the color at `t`, which is clamped to the range `[0.0, 1.0]`.
+    pub fn get<T: Component + Float>(self, t: T) -> Srgb<T> {
+        let t = clamp(t, T::zero(), T::one());
+
+        let (red, green, blue) = match self {
+            Colormap::Viridis => lookup(&VIRIDIS, t),
+            Colormap::Magma => lookup(&MAGMA, t),
+            Colormap::Inferno => lookup(&INFERNO, t),
+            Colormap::Plasma => lookup(&PLASMA, t),
+            Colormap::Cividis => table(&CIVIDIS, t),
+            Colormap::Turbo => table(&TURBO, t),
+        };
+
+        Srgb::new(
+            clamp(red, T::zero(), T::one()),
+            clamp(green, T::zero(), T::one()),
+            clamp(blue, T::zero(), T::one()),
+        )
+    }
+
+    ///Sample `n` evenly spaced colors from the colormap and make a linear
+    ///gradient out of them. There must be at least one color.
+    #[cfg(feature = "alloc")]
+    pub fn gradient<T: Component + Float>(self, n: usize) -> Gradient<LinSrgb<T>> {
+        assert!(n > 0);
+        let step = T::one() / cast(::core::cmp::max(n - 1, 1));
+
+        Gradient::new((0..n).map(|i| self.get(step * cast(i)).into_linear()))
+    }
+}
+
+///Interpolate linearly between the evenly spaced colors of a lookup table.
+fn lookup<T: Float>(colors: &[[f64; 3]], t: T) -> (T, T, T) {
+    let last = colors.len() - 1;
+    let position = t * cast(last);
+    let index = ::core::cmp::min(cast::<usize, _>(position.floor()), last - 1);
+    let t = position - cast(index);
+
+    let channel = |channel: usize| -> T {
+        let from: T = cast(colors[index][channel]);
+        let to: T = cast(colors[index + 1][channel]);
+        from + (to - from) * t
+    };
+
+    (channel(0), channel(1), channel(2))
+}
+
+///Interpolate between evenly spaced colors, using a Catmull-Rom spline.
+fn table<T: Float>(colors: &[[u8; 3]], t: T) -> (T, T, T) {
+    let last = colors.len() - 1;
+    let position = t * cast(last);
+    let index = ::core::cmp::min(cast::<usize, _>(position.floor()), last - 1);
+    let t = position - cast(index);
+
+    let component = |i: usize, channel: usize| -> T {
+        cast::<T, _>(colors[i][channel]) / cast(255u8)
+    };
+    let channel = |channel: usize| -> T {
+        let p1 = component(index, channel);
+        let p2 = component(index + 1, channel);
+        let p0 = if index > 0 {
+            component(index - 1, channel)
+        } else {
+            p1 + p1 - p2
+        };
+        let p3 = if index + 1 < last {
+            component(index + 2, channel)
+        } else {
+            p2 + p2 - p1
+        };
+
+        let a = p1 + p1;
+        let b = p2 - p0;
+        let c = p0 + p0 - p1 * cast(5.0) + p2 * cast(4.0) - p3;
+        let d = (p1 - p2) * cast(3.0) + p3 - p0;
+
+        (a + t * (b + t * (c + t * d))) / cast(2.0)
+    };
+
+    (channel(0), channel(1), channel(2))
+}
+
+//The matplotlib tables, generated from build/colormaps.txt
+include!(concat!(env!("OUT_DIR"), "/colormaps.rs"));
+
+const CIVIDIS: [[u8; 3]; 10] = [
+    [0x00, 0x20, 0x4d],
+    [0x00, 0x33, 0x6f],
+    [0x39, 0x48, 0x6b],
+    [0x57, 0x5c, 0x6d],
+    [0x70, 0x71, 0x73],
+    [0x8a, 0x87, 0x79],
+    [0xa6, 0x9d, 0x75],
+    [0xc4, 0xb5, 0x6c],
+    [0xe4, 0xcf, 0x5b],
+    [0xff, 0xea, 0x46],
+];
+
+const TURBO: [[u8; 3]; 10] = [
+    [0x30, 0x12, 0x3b],
+    [0x46, 0x62, 0xd7],
+    [0x36, 0xaa, 0xf9],
+    [0x1a, 0xe4, 0xb6],
+    [0x72, 0xfe, 0x5e],
+    [0xc7, 0xef, 0x34],
+    [0xfa, 0xba, 0x39],
+    [0xf6, 0x6b, 0x19],
+    [0xcb, 0x2a, 0x04],
+    [0x7a, 0x04, 0x03],
+];
+
+#[cfg(test)]
+mod test {
+    use super::Colormap;
+    use Srgb;
+
+    //Reference colors at 0, 0.25, 0.5, 0.75 and 1
+    const REFERENCE: [(Colormap, [u32; 5]); 6] = [
+        (Colormap::Viridis, [0x440154, 0x3b528b, 0x21908c, 0x5dc863, 0xfde725]),
+        (Colormap::Magma, [0x000004, 0x51127c, 0xb63679, 0xfb8861, 0xfcfdbf]),
+        (Colormap::Inferno, [0x000004, 0x57106e, 0xbb3754, 0xf98c0a, 0xfcffa4]),
+        (Colormap::Plasma, [0x0d0887, 0x7e03a8, 0xcc4678, 0xf89441, 0xf0f921]),
+        (Colormap::Cividis, [0x00204d, 0x414d6b, 0x7c7b78, 0xbcaf6f, 0xffea46]),
+        (Colormap::Turbo, [0x30123b, 0x28bbec, 0xa2fc3c, 0xfb8022, 0x7a0403]),
+    ];
+
+    fn rgb(hex: u32) -> Srgb<f64> {
+        Srgb::new(
+            (hex >> 16) as f64 / 255.0,
+            ((hex >> 8) & 0xff) as f64 / 255.0,
+            (hex & 0xff) as f64 / 255.0,
+        )
+    }
+
+    #[test]
+    fn reference_colors() {
+        for &(colormap, ref colors) in &REFERENCE {
+            for (i, &hex) in colors.iter().enumerate() {
+                let color = colormap.get(i as f64 / 4.0);
+                assert_relative_eq!(color, rgb(hex), epsilon = 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_table_entries() {
+        let entry = |red, green, blue| Srgb::new(red, green, blue);
+
+        assert_relative_eq!(
+            Colormap::Viridis.get(0.0),
+            entry(0.267004, 0.004874, 0.329415)
+        );
+        assert_relative_eq!(
+            Colormap::Viridis.get(128.0 / 255.0),
+            entry(0.127568, 0.566949, 0.550556),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            Colormap::Viridis.get(1.0),
+            entry(0.993248, 0.906157, 0.143936)
+        );
+        assert_relative_eq!(
+            Colormap::Magma.get(1.0),
+            entry(0.987053, 0.991438, 0.749504)
+        );
+        assert_relative_eq!(
+            Colormap::Inferno.get(128.0 / 255.0),
+            entry(0.735683, 0.215906, 0.330245),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            Colormap::Plasma.get(0.0),
+            entry(0.050383, 0.029803, 0.527975)
+        );
+    }
+
+    #[test]
+    fn lookup_between_entries() {
+        //Halfway between the first two entries of viridis
+        assert_relative_eq!(
+            Colormap::Viridis.get(0.5 / 255.0),
+            Srgb::new(
+                (0.267004 + 0.268510) / 2.0,
+                (0.004874 + 0.009605) / 2.0,
+                (0.329415 + 0.335427) / 2.0
+            ),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn table_hits_reference_colors() {
+        assert_relative_eq!(Colormap::Cividis.get(0.0), rgb(0x00204d));
+        assert_relative_eq!(Colormap::Turbo.get(5.0 / 9.0), rgb(0xc7ef34));
+        assert_relative_eq!(Colormap::Turbo.get(1.0), rgb(0x7a0403));
+    }
+
+    #[test]
+    fn clamped_input() {
+        assert_eq!(Colormap::Plasma.get(-1.0), Colormap::Plasma.get(0.0));
+        assert_eq!(Colormap::Plasma.get(2.0), Colormap::Plasma.get(1.0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn gradient() {
+        let gradient = Colormap::Inferno.gradient::<f64>(64);
+        assert_relative_eq!(
+            gradient.get(0.5),
+            Colormap::Inferno.get(0.5).into_linear(),
+            epsilon = 0.01
+        );
+    }
+}
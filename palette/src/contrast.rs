@@ -0,0 +1,344 @@
+//!Contrast between colors, for picking readable text colors.
+//!
+//!Two ways of measuring contrast are supported. The contrast ratio of
+//![WCAG 2](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) is the ratio
+//!between the luminances of the colors, from `1.0` to `21.0`, and is what most
+//!accessibility guidelines refer to. It requires at least `4.5` for normal
+//!text and `3.0` for large text. The
+//![APCA](https://github.com/Myndex/SAPC-APCA) lightness contrast, Lc, is
+//!proposed for WCAG 3. It's made to match the perceived contrast of text
+//!better, especially for dark colors, and depends on which color is the text
+//!and which is the background. It goes from about `-108` to `106`, where
+//!negative values mean light text on a dark background, and an absolute value
+//!of `60` is about the same as a contrast ratio of `4.5`.
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::contrast::{adjust_for_contrast, best_foreground, ContrastMethod};
+//!
+//!let background = Srgb::new(0.1f32, 0.4, 0.7);
+//!
+//!//Black or white text?
+//!let text = best_foreground(background, ContrastMethod::Wcag2);
+//!assert_eq!(text, Srgb::new(1.0, 1.0, 1.0));
+//!
+//!//A light blue that is still readable
+//!let link = Srgb::new(0.4, 0.7, 1.0);
+//!let readable = adjust_for_contrast(link, background, ContrastMethod::Wcag2, 4.5).unwrap();
+//!assert!(ContrastMethod::Wcag2.contrast(readable, background) >= 4.5);
+//!```
+
+use float::Float;
+
+use matrix::rgb_to_xyz_matrix;
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use {cast, clamp, Component, FromColorUnclamped, IntoColorUnclamped, Lch, Limited};
+
+///A way of measuring the contrast between two colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContrastMethod {
+    ///The contrast ratio of WCAG 2, from `1.0` to `21.0`.
+    Wcag2,
+    ///The absolute APCA lightness contrast, from `0.0` to about `108.0`.
+    Apca,
+}
+
+impl ContrastMethod {
+    ///Measure the contrast of `text` on `background`. Higher values mean more
+    ///contrast for both methods.
+    pub fn contrast<S, T>(self, text: Rgb<S, T>, background: Rgb<S, T>) -> T
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        match self {
+            ContrastMethod::Wcag2 => contrast_ratio(text, background),
+            ContrastMethod::Apca => apca_contrast(text, background).abs(),
+        }
+    }
+}
+
+///The WCAG 2 contrast ratio between two colors, from `1.0` to `21.0`. The
+///order of the colors doesn't matter.
+pub fn contrast_ratio<S, T>(a: Rgb<S, T>, b: Rgb<S, T>) -> T
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let offset: T = cast(0.05);
+    let a = relative_luminance(a) + offset;
+    let b = relative_luminance(b) + offset;
+
+    if a > b {
+        a / b
+    } else {
+        b / a
+    }
+}
+
+///The APCA lightness contrast, Lc, of `text` on `background`.
+///
+///The result is positive for dark text on a light background and negative for
+///light text on a dark background. The luminance is estimated with the simple
+///2.4 exponent of APCA, instead of the transfer function of `S`, since it's
+///what the APCA constants are fitted for.
+pub fn apca_contrast<S, T>(text: Rgb<S, T>, background: Rgb<S, T>) -> T
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let text = clamp_black(screen_luminance(text));
+    let background = clamp_black(screen_luminance(background));
+
+    if (background - text).abs() < cast(0.0005) {
+        return T::zero();
+    }
+
+    let scale: T = cast(1.14);
+    let offset: T = cast(0.027);
+    let low_clip: T = cast(0.1);
+
+    let contrast = if background > text {
+        let contrast = (background.powf(cast(0.56)) - text.powf(cast(0.57))) * scale;
+        if contrast < low_clip {
+            T::zero()
+        } else {
+            contrast - offset
+        }
+    } else {
+        let contrast = (background.powf(cast(0.65)) - text.powf(cast(0.62))) * scale;
+        if contrast > -low_clip {
+            T::zero()
+        } else {
+            contrast + offset
+        }
+    };
+
+    contrast * cast(100.0)
+}
+
+///Pick black or white, whichever has the most contrast against `background`.
+pub fn best_foreground<S, T>(background: Rgb<S, T>, method: ContrastMethod) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let black = Rgb::new(T::zero(), T::zero(), T::zero());
+    let white = Rgb::new(T::one(), T::one(), T::one());
+
+    best_foreground_of(background, &[black, white], method)
+        .expect("there are always two candidates")
+}
+
+///Pick the candidate with the most contrast against `background`. The first
+///of them is picked if several have the same contrast, and `None` is returned
+///if there are no candidates.
+pub fn best_foreground_of<S, T>(
+    background: Rgb<S, T>,
+    candidates: &[Rgb<S, T>],
+    method: ContrastMethod,
+) -> Option<Rgb<S, T>>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let mut best: Option<(Rgb<S, T>, T)> = None;
+
+    for &candidate in candidates {
+        let contrast = method.contrast(candidate, background);
+        match best {
+            Some((_, best_contrast)) if best_contrast >= contrast => {}
+            _ => best = Some((candidate, contrast)),
+        }
+    }
+
+    best.map(|(color, _)| color)
+}
+
+///Change the lightness of `foreground` until it has at least the contrast
+///`target` against `background`.
+///
+///The lightness is changed as little as possible, in CIE L\*C\*h°, towards
+///black or white, depending on which of them has the most contrast against
+///the background. The hue is kept, while the chroma may be reduced to stay in
+///the RGB gamut. The foreground is returned as it is if it already has enough
+///contrast, and `None` is returned if the target can't be reached.
+pub fn adjust_for_contrast<S, T>(
+    foreground: Rgb<S, T>,
+    background: Rgb<S, T>,
+    method: ContrastMethod,
+    target: T,
+) -> Option<Rgb<S, T>>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    if method.contrast(foreground, background) >= target {
+        return Some(foreground);
+    }
+
+    let extreme = best_foreground(background, method);
+    if method.contrast(extreme, background) < target {
+        return None;
+    }
+
+    let lch: Lch<<S::Space as RgbSpace>::WhitePoint, T> = foreground.into_linear().into_lch();
+    let with_lightness = |l| {
+        let linear = Rgb::<_, T>::from_lch(Lch { l: l, ..lch });
+        Rgb::<S, T>::from_linear(linear).clamp()
+    };
+
+    let mut low = lch.l;
+    let mut high = if extreme.red > T::zero() {
+        cast(100.0)
+    } else {
+        T::zero()
+    };
+    for _ in 0..24 {
+        let middle = (low + high) / (T::one() + T::one());
+        if method.contrast(with_lightness(middle), background) >= target {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+
+    //Clamping to the gamut may keep the lightest or darkest color from
+    //reaching the target, while black or white always does
+    let adjusted = with_lightness(high);
+    if method.contrast(adjusted, background) >= target {
+        Some(adjusted)
+    } else {
+        Some(extreme)
+    }
+}
+
+///The relative luminance of a color, as defined by WCAG.
+fn relative_luminance<S, T>(color: Rgb<S, T>) -> T
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let color = color.into_linear();
+    let weights = rgb_to_xyz_matrix::<S::Space, T>();
+
+    weights[3] * color.red + weights[4] * color.green + weights[5] * color.blue
+}
+
+///The estimated screen luminance of a color, as defined by APCA.
+fn screen_luminance<S, T>(color: Rgb<S, T>) -> T
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let weights = rgb_to_xyz_matrix::<S::Space, T>();
+    let decode = |value: T| clamp(value, T::zero(), T::one()).powf(cast(2.4));
+
+    weights[3] * decode(color.red)
+        + weights[4] * decode(color.green)
+        + weights[5] * decode(color.blue)
+}
+
+///Soften the luminance of very dark colors, as APCA does for flare.
+fn clamp_black<T: Float>(luminance: T) -> T {
+    let threshold: T = cast(0.022);
+    if luminance > threshold {
+        luminance
+    } else {
+        luminance + (threshold - luminance).powf(cast(1.414))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        adjust_for_contrast, apca_contrast, best_foreground, best_foreground_of, contrast_ratio,
+        ContrastMethod,
+    };
+    use {Lch, Srgb};
+
+    #[test]
+    fn wcag_ratio() {
+        let black = Srgb::new(0.0f64, 0.0, 0.0);
+        let white = Srgb::new(1.0f64, 1.0, 1.0);
+        assert_relative_eq!(contrast_ratio(black, white), 21.0, epsilon = 1e-9);
+        assert_relative_eq!(contrast_ratio(white, black), 21.0, epsilon = 1e-9);
+        assert_relative_eq!(contrast_ratio(white, white), 1.0);
+
+        //#777 on white is a well known borderline case
+        let gray = Srgb::new(
+            0x77 as f64 / 255.0,
+            0x77 as f64 / 255.0,
+            0x77 as f64 / 255.0,
+        );
+        assert_relative_eq!(contrast_ratio(gray, white), 4.48, epsilon = 0.01);
+    }
+
+    #[test]
+    fn apca() {
+        let black = Srgb::new(0.0f64, 0.0, 0.0);
+        let white = Srgb::new(1.0f64, 1.0, 1.0);
+        assert_relative_eq!(apca_contrast(black, white), 106.04, epsilon = 0.01);
+        assert_relative_eq!(apca_contrast(white, black), -107.88, epsilon = 0.01);
+        assert_relative_eq!(apca_contrast(white, white), 0.0);
+
+        //Reference values from the APCA test suite
+        let text = Srgb::new(
+            0x88 as f64 / 255.0,
+            0x88 as f64 / 255.0,
+            0x88 as f64 / 255.0,
+        );
+        assert_relative_eq!(apca_contrast(text, white), 63.06, epsilon = 0.01);
+        assert_relative_eq!(apca_contrast(white, text), -68.54, epsilon = 0.01);
+    }
+
+    #[test]
+    fn best() {
+        let dark = Srgb::new(0.2f32, 0.1, 0.3);
+        let light = Srgb::new(0.9f32, 0.8, 0.6);
+        let white = Srgb::new(1.0, 1.0, 1.0);
+        let black = Srgb::new(0.0, 0.0, 0.0);
+
+        for &method in &[ContrastMethod::Wcag2, ContrastMethod::Apca] {
+            assert_eq!(best_foreground(dark, method), white);
+            assert_eq!(best_foreground(light, method), black);
+        }
+
+        let candidates = [Srgb::new(0.5, 0.5, 0.5), Srgb::new(0.3, 0.3, 0.8), black];
+        assert_eq!(
+            best_foreground_of(light, &candidates[..2], ContrastMethod::Wcag2),
+            Some(candidates[1])
+        );
+        assert_eq!(best_foreground_of(light, &[], ContrastMethod::Wcag2), None);
+    }
+
+    #[test]
+    fn adjust() {
+        let background = Srgb::new(0.95f64, 0.95, 0.9);
+        let foreground = Srgb::new(0.9, 0.5, 0.3);
+
+        for &(method, target) in &[(ContrastMethod::Wcag2, 4.5), (ContrastMethod::Apca, 75.0)] {
+            let adjusted = adjust_for_contrast(foreground, background, method, target).unwrap();
+            let contrast = method.contrast(adjusted, background);
+            assert!(contrast >= target);
+            assert!(contrast < target * 1.01);
+
+            let original = Lch::from(foreground.into_linear());
+            let adjusted = Lch::from(adjusted.into_linear());
+            assert!(adjusted.l < original.l);
+            assert_relative_eq!(adjusted.hue, original.hue, epsilon = 1.0);
+        }
+
+        let readable = Srgb::new(0.1, 0.1, 0.1);
+        assert_eq!(
+            adjust_for_contrast(readable, background, ContrastMethod::Wcag2, 4.5),
+            Some(readable)
+        );
+
+        let gray = Srgb::new(0.45, 0.45, 0.45);
+        assert_eq!(
+            adjust_for_contrast(background, gray, ContrastMethod::Wcag2, 7.0),
+            None
+        );
+    }
+}
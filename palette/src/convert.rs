@@ -1,13 +1,13 @@
 use float::Float;
 
 use core::fmt::{self, Display, Formatter};
-use {Component, Limited, Hsl, Hsv, Hwb, Lab, Lch, Xyz, Yxy};
+use {Component, Limited, Pixel, Hsl, Hsv, Hwb, Lab, Lch, Xyz, Yxy};
 use white_point::{D65, WhitePoint};
 use rgb::{Rgb, RgbSpace};
 use luma::Luma;
 use encoding::Linear;
 
-/// FromColor provides conversion from the colors.
+/// FromColorUnclamped provides conversion from the colors, without clamping the result.
 ///
 /// It requires from_xyz, when implemented manually, and derives conversion to other colors as a
 /// default from this. These defaults must be overridden when direct conversion exists between
@@ -18,10 +18,10 @@ use encoding::Linear;
 ///
 /// # Deriving
 ///
-/// `FromColor` can be derived in a mostly automatic way. The strength of deriving it is that it
-/// will also derive `From` implementations for all of the `palette` color types. The minimum
-/// requirement is to implement `From<Xyz>`, but it can also be customized to make use of generics
-/// and have other manual implementations.
+/// `FromColorUnclamped` can be derived in a mostly automatic way. The strength of deriving it is
+/// that it will also derive `From` implementations for all of the `palette` color types. The
+/// minimum requirement is to implement `From<Xyz>`, but it can also be customized to make use of
+/// generics and have other manual implementations.
 ///
 /// ## Item Attributes
 ///
@@ -57,7 +57,7 @@ use encoding::Linear;
 /// use palette::{Srgb, Xyz};
 ///
 /// /// A custom version of Xyz that stores integer values from 0 to 100.
-/// #[derive(PartialEq, Debug, FromColor)]
+/// #[derive(PartialEq, Debug, FromColorUnclamped)]
 /// struct Xyz100 {
 ///     x: u8,
 ///     y: u8,
@@ -104,14 +104,14 @@ use encoding::Linear;
 /// #[macro_use]
 /// extern crate approx;
 ///
-/// use palette::{Component, FromColor, Hsv, Pixel, Srgb};
+/// use palette::{Component, FromColorUnclamped, Hsv, Pixel, Srgb};
 /// use palette::rgb::{Rgb, RgbSpace};
 /// use palette::encoding::Linear;
 /// use palette::white_point::D65;
 /// use palette::float::Float;
 ///
 /// /// sRGB, but with a reversed memory layout.
-/// #[derive(PartialEq, Debug, FromColor, Pixel)]
+/// #[derive(PartialEq, Debug, FromColorUnclamped, Pixel)]
 /// #[palette_manual_from(Rgb = "from_rgb_internal")]
 /// #[palette_component = "T"]
 /// #[repr(C)] // Makes sure the memory layout is as we want it.
@@ -162,13 +162,13 @@ use encoding::Linear;
 /// #[macro_use]
 /// extern crate palette;
 ///
-/// use palette::{FromColor, LinSrgba, Srgb};
+/// use palette::{FromColorUnclamped, LinSrgba, Srgb};
 /// use palette::rgb::{Rgb, RgbSpace};
 /// use palette::encoding::Linear;
 /// use palette::white_point::D65;
 ///
 /// /// CSS style sRGB.
-/// #[derive(PartialEq, Debug, FromColor)]
+/// #[derive(PartialEq, Debug, FromColorUnclamped)]
 /// #[palette_manual_from(Rgb = "from_rgb_internal")]
 /// struct CssRgb {
 ///     red: u8,
@@ -212,7 +212,7 @@ use encoding::Linear;
 ///     );
 /// }
 /// ```
-pub trait FromColor<Wp = D65, T = f32>: Sized
+pub trait FromColorUnclamped<Wp = D65, T = f32>: Sized
 where
     T: Component + Float,
     Wp: WhitePoint,
@@ -261,7 +261,7 @@ where
     }
 }
 
-/// IntoColor provides conversion to the colors.
+/// IntoColorUnclamped provides conversion to the colors, without clamping the result.
 ///
 /// It requires into_xyz, when implemented manually, and derives conversion to other colors as a
 /// default from this. These defaults must be overridden when direct conversion exists between
@@ -269,10 +269,10 @@ where
 ///
 /// # Deriving
 ///
-/// `IntoColor` can be derived in a mostly automatic way. The strength of deriving it is that it
-/// will also derive `Into` implementations for all of the `palette` color types. The minimum
-/// requirement is to implement `Into<Xyz>`, but it can also be customized to make use of generics
-/// and have other manual implementations.
+/// `IntoColorUnclamped` can be derived in a mostly automatic way. The strength of deriving it is
+/// that it will also derive `Into` implementations for all of the `palette` color types. The
+/// minimum requirement is to implement `Into<Xyz>`, but it can also be customized to make use of
+/// generics and have other manual implementations.
 ///
 /// ## Item Attributes
 ///
@@ -308,7 +308,7 @@ where
 /// use palette::{Srgb, Xyz};
 ///
 /// /// A custom version of Xyz that stores integer values from 0 to 100.
-/// #[derive(PartialEq, Debug, IntoColor)]
+/// #[derive(PartialEq, Debug, IntoColorUnclamped)]
 /// struct Xyz100 {
 ///     x: u8,
 ///     y: u8,
@@ -350,7 +350,7 @@ where
 /// #[macro_use]
 /// extern crate approx;
 ///
-/// use palette::{Component, Hsv, IntoColor, Pixel, Srgb};
+/// use palette::{Component, Hsv, IntoColorUnclamped, Pixel, Srgb};
 /// use palette::rgb::{Rgb, RgbSpace};
 /// use palette::encoding::{Linear, self};
 /// use palette::white_point::D65;
@@ -359,7 +359,7 @@ where
 /// type Hsv64 = Hsv<encoding::Srgb, f64>;
 ///
 /// /// sRGB, but with a reversed memory layout.
-/// #[derive(Copy, Clone, IntoColor, Pixel)]
+/// #[derive(Copy, Clone, IntoColorUnclamped, Pixel)]
 /// #[palette_manual_into(Rgb = "into_rgb_internal")]
 /// #[palette_component = "T"]
 /// #[repr(C)] // Makes sure the memory layout is as we want it.
@@ -407,13 +407,13 @@ where
 /// #[macro_use]
 /// extern crate approx;
 ///
-/// use palette::{IntoColor, LinSrgba, Srgb};
+/// use palette::{IntoColorUnclamped, LinSrgba, Srgb};
 /// use palette::rgb::{Rgb, RgbSpace};
 /// use palette::encoding::Linear;
 /// use palette::white_point::D65;
 ///
 /// /// CSS style sRGB.
-/// #[derive(PartialEq, Debug, IntoColor)]
+/// #[derive(PartialEq, Debug, IntoColorUnclamped)]
 /// #[palette_manual_into(Rgb = "into_rgb_internal")]
 /// struct CssRgb {
 ///     red: u8,
@@ -448,7 +448,7 @@ where
 ///     assert_relative_eq!(color, LinSrgba::new(0.496933, 0.0, 1.0, 0.3));
 /// }
 /// ```
-pub trait IntoColor<Wp = D65, T = f32>: Sized
+pub trait IntoColorUnclamped<Wp = D65, T = f32>: Sized
 where
     T: Component + Float,
     Wp: WhitePoint,
@@ -500,6 +500,66 @@ where
     }
 }
 
+///A trait for converting one color from another, with the result clamped to its color space.
+///
+///This is implemented for every color that can be converted with `From` and that has limits,
+///which includes all of the `palette` color types. The conversion itself is done without clamping,
+///as in [`FromColorUnclamped`](trait.FromColorUnclamped.html), and only the result is clamped.
+///Use `FromColorUnclamped` or `From` to keep out-of-gamut values, for example to map them into
+///the gamut in some other way.
+pub trait FromColor<C>: Sized {
+    ///Convert from `C` and clamp the result to the bounds of the color space.
+    ///
+    ///```
+    ///use palette::{FromColor, Lch, Limited, Srgb};
+    ///
+    ///let rgb = Srgb::from_color(Lch::new(50.0, 100.0, -175.0));
+    ///assert!(rgb.is_valid());
+    ///
+    ///let unclamped = Srgb::from(Lch::new(50.0, 100.0, -175.0));
+    ///assert!(!unclamped.is_valid());
+    ///```
+    fn from_color(color: C) -> Self;
+}
+
+impl<C, U> FromColor<C> for U
+where
+    U: From<C> + Limited,
+{
+    #[inline]
+    fn from_color(color: C) -> U {
+        let mut color = U::from(color);
+        color.clamp_self();
+        color
+    }
+}
+
+///A trait for converting a color into another, with the result clamped to its color space.
+///
+///This is the reciprocal of [`FromColor`](trait.FromColor.html), and is implemented for all
+///colors that can be converted with it.
+pub trait IntoColor<U>: Sized {
+    ///Convert into `U` and clamp the result to the bounds of the color space.
+    ///
+    ///```
+    ///use palette::{IntoColor, Lch, Limited, Srgb};
+    ///
+    ///let rgb: Srgb = Lch::new(50.0, 100.0, -175.0).into_color();
+    ///assert!(rgb.is_valid());
+    ///```
+    fn into_color(self) -> U;
+}
+
+impl<C, U> IntoColor<U> for C
+where
+    U: FromColor<C>,
+{
+    #[inline]
+    fn into_color(self) -> U {
+        U::from_color(self)
+    }
+}
+
 ///The error type for a color conversion that converted a color into a color with invalid values.
 #[derive(Debug)]
 pub struct OutOfBounds<T> {
@@ -665,9 +725,138 @@ impl<T, U> ConvertInto<U> for T where U: ConvertFrom<T> {
     }
 }
 
+///The error type for a fallible color conversion that produced a color outside the gamut of its
+///color space.
+///
+///It keeps the unclamped color and which of its components were out of bounds. The components
+///are numbered in the same order as their fields, which is also the order of their
+///[`Pixel`](trait.Pixel.html) representation.
+#[derive(Debug)]
+pub struct OutOfGamut<T> {
+    color: T,
+    components: u32,
+}
+
+impl<T> OutOfGamut<T> {
+    ///Consume this error and return the unclamped color.
+    #[inline]
+    pub fn color(self) -> T {
+        self.color
+    }
+
+    ///Check if the component with the index `component` was out of bounds.
+    ///
+    ///```
+    ///use palette::{Hsl, Srgb, TryFromColor};
+    ///
+    ///let error = Srgb::try_from_color(Hsl::new(150.0, 1.0, 1.1)).unwrap_err();
+    ///assert!(error.is_out_of_gamut(0));
+    ///assert!(!error.is_out_of_gamut(1));
+    ///assert!(error.is_out_of_gamut(2));
+    ///```
+    #[inline]
+    pub fn is_out_of_gamut(&self, component: usize) -> bool {
+        component < 32 && self.components & (1 << component) != 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ::std::fmt::Debug> ::std::error::Error for OutOfGamut<T> {
+    fn description(&self) -> &str {
+        "Color conversion is out of gamut"
+    }
+}
+
+impl<T> Display for OutOfGamut<T> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "Color conversion is out of gamut in component")?;
+
+        let mut separator = " ";
+        for component in (0..32).filter(|&component| self.is_out_of_gamut(component)) {
+            write!(fmt, "{}{}", separator, component)?;
+            separator = ", ";
+        }
+
+        Ok(())
+    }
+}
+
+///A trait for fallible conversion of one color from another.
+///
+///The conversion fails if the converted color is outside the gamut of its color space, instead of
+///clamping it, and the error tells which components were out of bounds. `T` is the component type
+///of the converted color, and is inferred from it.
+pub trait TryFromColor<C, T = f32>: Sized {
+    ///Convert from `C`, returning ok if the color is inside of its defined range, otherwise an
+    ///`OutOfGamut` error is returned which contains the unclamped color.
+    ///
+    ///```
+    ///use palette::{Hsl, Srgb, TryFromColor};
+    ///
+    ///assert!(Srgb::try_from_color(Hsl::new(0.0, 1.0, 0.5)).is_ok());
+    ///
+    ///match Srgb::try_from_color(Hsl::new(150.0, 1.0, 1.1)) {
+    ///    Ok(color) => println!("{:?} is in gamut", color),
+    ///    Err(err) => println!("{}", err),
+    ///}
+    ///```
+    fn try_from_color(color: C) -> Result<Self, OutOfGamut<Self>>;
+}
+
+impl<C, U, T> TryFromColor<C, T> for U
+where
+    U: From<C> + Limited + Pixel<T>,
+    T: PartialEq,
+{
+    fn try_from_color(color: C) -> Result<U, OutOfGamut<U>> {
+        let color = U::from(color);
+        if color.is_valid() {
+            return Ok(color);
+        }
+
+        let clamped = color.clamp();
+        let components = color
+            .as_raw::<[T]>()
+            .iter()
+            .zip(clamped.as_raw::<[T]>())
+            .enumerate()
+            .filter(|&(_, (unclamped, clamped))| unclamped != clamped)
+            .fold(0, |components, (index, _)| components | 1 << index);
+
+        Err(OutOfGamut { color, components })
+    }
+}
+
+///A trait for fallible conversion of a color into another.
+///
+///This is the reciprocal of [`TryFromColor`](trait.TryFromColor.html), and is implemented for all
+///colors that can be converted with it.
+pub trait TryIntoColor<U, T = f32>: Sized {
+    ///Convert into `U`, returning ok if the color is inside of its defined range, otherwise an
+    ///`OutOfGamut` error is returned which contains the unclamped color.
+    ///
+    ///```
+    ///use palette::{Hsl, Srgb, TryIntoColor};
+    ///
+    ///let result: Result<Srgb, _> = Hsl::new(150.0, 1.0, 1.1).try_into_color();
+    ///assert!(result.is_err());
+    ///```
+    fn try_into_color(self) -> Result<U, OutOfGamut<U>>;
+}
+
+impl<C, U, T> TryIntoColor<U, T> for C
+where
+    U: TryFromColor<C, T>,
+{
+    #[inline]
+    fn try_into_color(self) -> Result<U, OutOfGamut<U>> {
+        U::try_from_color(self)
+    }
+}
+
 macro_rules! impl_into_color {
     ($self_ty: ident, $from_fn: ident) => {
-        impl<Wp, T> IntoColor<Wp, T> for $self_ty<Wp, T>
+        impl<Wp, T> IntoColorUnclamped<Wp, T> for $self_ty<Wp, T>
         where
             T: Component + Float,
             Wp: WhitePoint,
@@ -709,7 +898,7 @@ macro_rules! impl_into_color {
 
 macro_rules! impl_into_color_rgb {
     ($self_ty: ident, $from_fn: ident) => {
-        impl<S, Wp, T> IntoColor<Wp, T> for $self_ty<S, T>
+        impl<S, Wp, T> IntoColorUnclamped<Wp, T> for $self_ty<S, T>
         where
             T: Component + Float,
             Wp: WhitePoint,
@@ -768,7 +957,7 @@ mod tests {
     use luma::Luma;
     use {Hsl, Hsv, Hwb, Lab, Lch, Xyz, Yxy};
 
-    #[derive(Copy, Clone, FromColor, IntoColor)]
+    #[derive(Copy, Clone, FromColorUnclamped, IntoColorUnclamped)]
     #[palette_manual_from(Xyz, Luma = "from_luma_internal")]
     #[palette_manual_into(Xyz, Luma = "into_luma_internal")]
     #[palette_white_point = "S::WhitePoint"]
@@ -799,7 +988,7 @@ mod tests {
         }
     }
 
-    #[derive(Copy, Clone, FromColor, IntoColor)]
+    #[derive(Copy, Clone, FromColorUnclamped, IntoColorUnclamped)]
     #[palette_manual_from(Lch, Luma = "from_luma_internal")]
     #[palette_manual_into(Lch, Luma = "into_luma_internal")]
     #[palette_white_point = "::white_point::E"]
@@ -919,4 +1108,51 @@ mod tests {
         let _hwb: Hwb<_, f64> = color.into();
         let _luma: Luma<Linear<::white_point::E>, f64> = color.into();
     }
+
+    #[test]
+    fn from_color_clamps() {
+        use {FromColor, IntoColor, Limited, Srgb};
+
+        let unclamped = Srgb::from(Lab::new(50.0, 0.0, -100.0));
+        assert!(!unclamped.is_valid());
+
+        let clamped = Srgb::from_color(Lab::new(50.0, 0.0, -100.0));
+        assert_relative_eq!(clamped, unclamped.clamp());
+
+        let hsv: Hsv = Srgb::new(1.5, 0.0, 0.0).into_color();
+        assert!(hsv.is_valid());
+    }
+
+    #[test]
+    fn try_from_color() {
+        use {Srgb, TryFromColor, TryIntoColor};
+
+        let rgb = Srgb::try_from_color(Hsl::new(0.0, 1.0, 0.5)).unwrap();
+        assert_relative_eq!(rgb, Srgb::new(1.0, 0.0, 0.0));
+
+        let error = Srgb::try_from_color(Lab::new(50.0, 0.0, -100.0)).unwrap_err();
+        assert!(error.is_out_of_gamut(0));
+        assert!(!error.is_out_of_gamut(1));
+        assert!(error.is_out_of_gamut(2));
+        assert!(!error.is_out_of_gamut(100));
+
+        let hsv: Result<Hsv, _> = Srgb::new(1.5, 0.0, 0.0).try_into_color();
+        let error = hsv.unwrap_err();
+        assert!(!error.is_out_of_gamut(0));
+        assert!(!error.is_out_of_gamut(1));
+        assert!(error.is_out_of_gamut(2));
+        assert!(error.color().value > 1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn out_of_gamut_display() {
+        use {Srgb, TryFromColor};
+
+        let error = Srgb::try_from_color(Hsl::new(150.0, 1.0, 1.1)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Color conversion is out of gamut in component 0, 2"
+        );
+    }
 }
@@ -0,0 +1,413 @@
+//!Precomputed conversions between color spaces.
+//!
+//!Converting a color with `From` or `into_color` derives the conversion
+//!matrices from the primaries and white points every time. A
+//![`Converter`](struct.Converter.html) does that once, when it's created,
+//!and fuses the matrix of the source space, the chromatic adaptation between
+//!the white points and the inverse matrix of the destination space into a
+//!single matrix. Converting a color is then only a matter of decoding it,
+//!multiplying it with the fused matrix and encoding it again. The matrix is
+//!skipped entirely when the spaces have the same primaries and white point,
+//!such as when converting between sRGB and linear sRGB. Conversions between
+//!RGB spaces, or between RGB and YUV, are done with that single matrix and
+//!never produce intermediate XYZ values.
+//!
+//!```
+//!use palette::{Srgb, Xyz};
+//!use palette::converter::Converter;
+//!use palette::white_point::D50;
+//!
+//!let converter = Converter::<Srgb, Xyz<D50>>::new();
+//!
+//!let colors = vec![Srgb::new(0.8, 0.2, 0.1); 100];
+//!let mut xyz = vec![Xyz::default(); 100];
+//!converter.convert_slice(&colors, &mut xyz);
+//!```
+//!
+//!The spaces don't have to be known at compile time. The `"icc"` feature
+//!makes it possible to convert from and to the raw `[T; 3]` components of an
+//![`RgbProfile`](../icc/struct.RgbProfile.html), with
+//![`Converter::with_spaces`](struct.Converter.html#method.with_spaces).
+
+use float::Float;
+
+use chromatic_adaptation::{Method, TransformMatrix};
+use encoding::TransferFn;
+use luma::{Luma, LumaStandard};
+use matrix::{adaptation_matrix, matrix_inverse, multiply_3x3};
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::{WhitePoint, D50};
+use {cast, Component, Mat3, Xyz};
+
+///A color type that a [`Converter`](struct.Converter.html) can convert from
+///and to.
+///
+///The color is described as linear components and a matrix that converts
+///them to XYZ, relative to the white point of the space.
+pub trait Endpoint: Sized {
+    ///The component type of the color.
+    type Scalar: Component + Float;
+
+    ///The parameters of the space, for spaces that are only known at runtime.
+    ///It's `()` for spaces that are fully described by their type.
+    type Space;
+
+    ///The matrix that converts the linear components to XYZ.
+    fn to_xyz_matrix(space: &Self::Space) -> Mat3<f64>;
+
+    ///The XYZ values of the white point of the space.
+    fn white_point(space: &Self::Space) -> [f64; 3];
+
+    ///Decode the color into linear components.
+    fn into_linear(self, space: &Self::Space) -> [Self::Scalar; 3];
+
+    ///Encode linear components as a color.
+    fn from_linear(linear: [Self::Scalar; 3], space: &Self::Space) -> Self;
+}
+
+impl<S, T> Endpoint for Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    type Scalar = T;
+    type Space = ();
+
+    fn to_xyz_matrix(_space: &()) -> Mat3<f64> {
+        ::matrix::rgb_to_xyz_matrix::<S::Space, f64>()
+    }
+
+    fn white_point(_space: &()) -> [f64; 3] {
+        white_point::<<S::Space as RgbSpace>::WhitePoint>()
+    }
+
+    fn into_linear(self, _space: &()) -> [T; 3] {
+        [
+            S::TransferFn::into_linear(self.red),
+            S::TransferFn::into_linear(self.green),
+            S::TransferFn::into_linear(self.blue),
+        ]
+    }
+
+    fn from_linear([red, green, blue]: [T; 3], _space: &()) -> Self {
+        Rgb::new(
+            S::TransferFn::from_linear(red),
+            S::TransferFn::from_linear(green),
+            S::TransferFn::from_linear(blue),
+        )
+    }
+}
+
+impl<Wp, T> Endpoint for Xyz<Wp, T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    type Scalar = T;
+    type Space = ();
+
+    fn to_xyz_matrix(_space: &()) -> Mat3<f64> {
+        IDENTITY
+    }
+
+    fn white_point(_space: &()) -> [f64; 3] {
+        white_point::<Wp>()
+    }
+
+    fn into_linear(self, _space: &()) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn from_linear([x, y, z]: [T; 3], _space: &()) -> Self {
+        Xyz::with_wp(x, y, z)
+    }
+}
+
+///Luma is decoded as three equal components, that are scaled to the white
+///point by the matrix.
+impl<S, T> Endpoint for Luma<S, T>
+where
+    S: LumaStandard,
+    T: Component + Float,
+{
+    type Scalar = T;
+    type Space = ();
+
+    fn to_xyz_matrix(_space: &()) -> Mat3<f64> {
+        let [x, y, z] = white_point::<S::WhitePoint>();
+        [x, 0.0, 0.0, 0.0, y, 0.0, 0.0, 0.0, z]
+    }
+
+    fn white_point(_space: &()) -> [f64; 3] {
+        white_point::<S::WhitePoint>()
+    }
+
+    fn into_linear(self, _space: &()) -> [T; 3] {
+        let luma = S::TransferFn::into_linear(self.luma);
+        [luma, luma, luma]
+    }
+
+    fn from_linear([_, luma, _]: [T; 3], _space: &()) -> Self {
+        Luma::new(S::TransferFn::from_linear(luma))
+    }
+}
+
+///Encoded RGB components of an ICC profile, relative to the D50 profile
+///connection space.
+///
+///```
+///use palette::Srgb;
+///use palette::converter::Converter;
+///use palette::icc::RgbProfile;
+///
+///let profile = RgbProfile::from_standard::<palette::encoding::Srgb>();
+///let converter = Converter::<[f32; 3], Srgb>::with_spaces(profile, ());
+///
+///let color = converter.convert([0.8, 0.4, 0.2]);
+///assert!((color.red - 0.8).abs() < 1e-3);
+///```
+#[cfg(feature = "icc")]
+impl<T> Endpoint for [T; 3]
+where
+    T: Component + Float,
+{
+    type Scalar = T;
+    type Space = ::icc::RgbProfile;
+
+    fn to_xyz_matrix(space: &::icc::RgbProfile) -> Mat3<f64> {
+        space.matrix()
+    }
+
+    fn white_point(_space: &::icc::RgbProfile) -> [f64; 3] {
+        white_point::<D50>()
+    }
+
+    fn into_linear(self, space: &::icc::RgbProfile) -> [T; 3] {
+        [
+            space.red_curve.into_linear(self[0]),
+            space.green_curve.into_linear(self[1]),
+            space.blue_curve.into_linear(self[2]),
+        ]
+    }
+
+    fn from_linear(linear: [T; 3], space: &::icc::RgbProfile) -> Self {
+        [
+            space.red_curve.from_linear(linear[0]),
+            space.green_curve.from_linear(linear[1]),
+            space.blue_curve.from_linear(linear[2]),
+        ]
+    }
+}
+
+///A precomputed conversion from `Src` to `Dst`.
+///
+///The white points are adapted with the Bradford method when they are
+///different.
+pub struct Converter<Src: Endpoint, Dst: Endpoint<Scalar = Src::Scalar>> {
+    source: Src::Space,
+    destination: Dst::Space,
+    matrix: Option<Mat3<Src::Scalar>>,
+}
+
+impl<Src, Dst> Converter<Src, Dst>
+where
+    Src: Endpoint<Space = ()>,
+    Dst: Endpoint<Space = (), Scalar = Src::Scalar>,
+{
+    ///Create a converter between two spaces that are known at compile time.
+    pub fn new() -> Self {
+        Converter::with_spaces((), ())
+    }
+}
+
+impl<Src, Dst> Default for Converter<Src, Dst>
+where
+    Src: Endpoint<Space = ()>,
+    Dst: Endpoint<Space = (), Scalar = Src::Scalar>,
+{
+    fn default() -> Self {
+        Converter::new()
+    }
+}
+
+impl<Src, Dst> Converter<Src, Dst>
+where
+    Src: Endpoint,
+    Dst: Endpoint<Scalar = Src::Scalar>,
+{
+    ///Create a converter between two spaces with runtime parameters.
+    pub fn with_spaces(source: Src::Space, destination: Dst::Space) -> Self {
+        let source_white = Src::white_point(&source);
+        let destination_white = Dst::white_point(&destination);
+
+        let mut matrix = Src::to_xyz_matrix(&source);
+
+        if !approximately_equal(&source_white, &destination_white) {
+            let bradford = TransformMatrix::<D50, D50, f64>::get_cone_response(&Method::Bradford);
+            let adaptation = adaptation_matrix(
+                bradford,
+                &Xyz::<D50, f64>::with_wp(source_white[0], source_white[1], source_white[2]),
+                &Xyz::<D50, f64>::with_wp(
+                    destination_white[0],
+                    destination_white[1],
+                    destination_white[2],
+                ),
+            );
+            matrix = multiply_3x3(&adaptation, &matrix);
+        }
+
+        matrix = multiply_3x3(&matrix_inverse(&Dst::to_xyz_matrix(&destination)), &matrix);
+
+        let matrix = if approximately_equal(&matrix, &IDENTITY) {
+            None
+        } else {
+            let mut result: Mat3<Src::Scalar> = [cast(0.0); 9];
+            for (result, &value) in result.iter_mut().zip(matrix.iter()) {
+                *result = cast(value);
+            }
+            Some(result)
+        };
+
+        Converter {
+            source: source,
+            destination: destination,
+            matrix: matrix,
+        }
+    }
+
+    ///Convert a color.
+    pub fn convert(&self, color: Src) -> Dst {
+        let linear = color.into_linear(&self.source);
+
+        let linear = match self.matrix {
+            Some(ref m) => {
+                let [a, b, c] = linear;
+                [
+                    m[0] * a + m[1] * b + m[2] * c,
+                    m[3] * a + m[4] * b + m[5] * c,
+                    m[6] * a + m[7] * b + m[8] * c,
+                ]
+            }
+            None => linear,
+        };
+
+        Dst::from_linear(linear, &self.destination)
+    }
+
+    ///Convert a slice of colors and write them to `output`.
+    ///
+    ///Panics if the slices don't have the same length.
+    pub fn convert_slice(&self, input: &[Src], output: &mut [Dst])
+    where
+        Src: Copy,
+    {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "the slices must have the same length"
+        );
+
+        for (&color, result) in input.iter().zip(output) {
+            *result = self.convert(color);
+        }
+    }
+}
+
+const IDENTITY: Mat3<f64> = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+
+fn white_point<Wp: WhitePoint>() -> [f64; 3] {
+    let white: Xyz<Wp, f64> = Wp::get_xyz();
+    [white.x, white.y, white.z]
+}
+
+fn approximately_equal(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Converter;
+    use chromatic_adaptation::AdaptInto;
+    use convert::IntoColorUnclamped;
+    use encoding::itu::BT709;
+    use luma::Luma;
+    use rgb::Rgb;
+    use white_point::{D50, D65};
+    use {LinSrgb, Srgb, SrgbLuma, Xyz};
+
+    #[test]
+    fn matches_from() {
+        let colors = [
+            Srgb::new(0.8, 0.2, 0.1),
+            Srgb::new(0.0, 0.5, 1.0),
+            Srgb::new(1.0, 1.0, 1.0),
+        ];
+
+        let to_xyz = Converter::<Srgb, Xyz<D65>>::new();
+        let to_bt709 = Converter::<Srgb, Rgb<BT709>>::new();
+        let to_luma = Converter::<Srgb, SrgbLuma>::new();
+
+        for &color in &colors {
+            let xyz: Xyz<D65> = color.into_linear().into();
+            assert_relative_eq!(to_xyz.convert(color), xyz, epsilon = 1e-6);
+            let bt709 = Rgb::<BT709>::from_linear(color.into_linear().into_xyz().into_rgb());
+            assert_relative_eq!(to_bt709.convert(color), bt709, epsilon = 1e-6);
+            assert_relative_eq!(to_luma.convert(color), Luma::from(color), epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn adapts_white_point() {
+        let converter = Converter::<Srgb, Xyz<D50>>::new();
+        let color = Srgb::new(0.8, 0.2, 0.1);
+        let xyz: Xyz<D65> = color.into_linear().into();
+
+        assert_relative_eq!(converter.convert(color), xyz.adapt_into(), epsilon = 1e-5);
+        assert_relative_eq!(
+            converter.convert(Srgb::new(1.0, 1.0, 1.0)),
+            Xyz::with_wp(0.96422, 1.0, 0.82521),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn skips_identity_matrix() {
+        let converter = Converter::<Srgb, LinSrgb>::new();
+        assert!(converter.matrix.is_none());
+        assert_eq!(
+            converter.convert(Srgb::new(0.8, 0.2, 0.1)),
+            Srgb::new(0.8, 0.2, 0.1).into_linear()
+        );
+
+        assert!(Converter::<Srgb, Xyz>::new().matrix.is_some());
+    }
+
+    #[test]
+    fn convert_slice() {
+        let converter = Converter::<Srgb, Xyz>::new();
+        let colors = [Srgb::new(0.8, 0.2, 0.1), Srgb::new(0.0, 0.5, 1.0)];
+        let mut xyz = [Xyz::default(); 2];
+        converter.convert_slice(&colors, &mut xyz);
+
+        assert_eq!(xyz[1], converter.convert(colors[1]));
+    }
+
+    #[cfg(feature = "icc")]
+    #[test]
+    fn icc_profile() {
+        use icc::RgbProfile;
+
+        let profile = RgbProfile::from_standard::<::encoding::Srgb>();
+        let from_profile = Converter::<[f64; 3], Srgb<f64>>::with_spaces(profile.clone(), ());
+        let to_profile = Converter::<Srgb<f64>, [f64; 3]>::with_spaces((), profile.clone());
+
+        let rgb = [0.8, 0.4, 0.2];
+        let color = from_profile.convert(rgb);
+        assert_relative_eq!(color, profile.into_srgb(rgb), epsilon = 1e-6);
+
+        let back = to_profile.convert(color);
+        for i in 0..3 {
+            assert_relative_eq!(back[i], rgb[i], epsilon = 1e-4);
+        }
+    }
+}
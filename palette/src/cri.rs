@@ -0,0 +1,236 @@
+//!The CIE color rendering index (CRI) of light sources.
+//!
+//!The color rendering index describes how faithfully a light source renders
+//!the colors of objects, compared to a reference illuminant with the same
+//!correlated color temperature. The reference is a black body radiator below
+//!5000 K, and CIE daylight above. Each test color sample gets a special index
+//!`Ri`, which is 100 for a perfect match, and the general index `Ra` is the
+//!mean of the special indices of the first eight CIE test color samples. The
+//!calculation follows CIE 13.3, with the von Kries adaptation and the CIE
+//!1964 U\*V\*W\* color space.
+//!
+//!The test color samples are given as reflectance spectra. The reflectances
+//!of the fourteen CIE test color samples are not included, and can be taken
+//!from the tables in CIE 13.3.
+//!
+//!```
+//!use palette::cri::ColorRendering;
+//!use palette::spectral::Planckian;
+//!
+//!//A pale red and a pale blue surface
+//!let red: &[(f64, f64)] = &[(380.0, 0.2), (580.0, 0.2), (780.0, 0.6)];
+//!let blue: &[(f64, f64)] = &[(380.0, 0.6), (480.0, 0.4), (580.0, 0.2), (780.0, 0.2)];
+//!
+//!let halogen = Planckian::new(3000.0f64);
+//!let rendering = ColorRendering::new(&halogen);
+//!assert!((rendering.cct().temperature - 3000.0).abs() < 1.0);
+//!assert!(rendering.general_index(&[red, blue]) > 99.9);
+//!```
+
+use float::Float;
+
+use spectral::{spectrum_to_xyz, Daylight, Observer, Planckian, Reflection, Spectrum};
+use temperature::{cct, Cct};
+use white_point::E;
+use {cast, Component, Xyz, Yxy};
+
+///The color rendering of a light source, compared to its reference
+///illuminant.
+pub struct ColorRendering<'a, T, S: 'a + ?Sized> {
+    test: &'a S,
+    reference: Reference<T>,
+    cct: Cct<T>,
+    test_white: Chromaticity,
+    reference_white: Chromaticity,
+}
+
+impl<'a, T, S> ColorRendering<'a, T, S>
+where
+    T: Component + Float,
+    S: Spectrum<T> + ?Sized,
+{
+    ///Prepare the color rendering calculations for the light source with the
+    ///spectrum `test`.
+    pub fn new(test: &'a S) -> ColorRendering<'a, T, S> {
+        let test_xyz = spectrum_xyz(test);
+        let cct = cct(Yxy::from(test_xyz));
+        let reference = if cct.temperature < cast(5000.0) {
+            Reference::Planckian(Planckian::new(cct.temperature))
+        } else {
+            Reference::Daylight(Daylight::new(cct.temperature))
+        };
+
+        let reference_xyz = spectrum_xyz(&reference);
+
+        ColorRendering {
+            test: test,
+            reference: reference,
+            cct: cct,
+            test_white: Chromaticity::new(test_xyz, cast(test_xyz.y)),
+            reference_white: Chromaticity::new(reference_xyz, cast(reference_xyz.y)),
+        }
+    }
+
+    ///Get the correlated color temperature and Duv of the light source. The
+    ///color rendering index is only meaningful if the Duv is less than about
+    ///0.0054.
+    pub fn cct(&self) -> Cct<T> {
+        self.cct
+    }
+
+    ///Calculate the special color rendering index `Ri` of the test color
+    ///sample with the reflectance spectrum `sample`.
+    pub fn special_index<R: Spectrum<T> + ?Sized>(&self, sample: &R) -> T {
+        let reference = Chromaticity::new(
+            spectrum_xyz(&Reflection::new(sample, &self.reference)),
+            self.reference_white.luminance,
+        );
+        let test = Chromaticity::new(
+            spectrum_xyz(&Reflection::new(sample, self.test)),
+            self.test_white.luminance,
+        );
+
+        //Von Kries adaptation of the sample from the test light source to the
+        //reference illuminant
+        let (c, d) = test.c_d();
+        let (test_c, test_d) = self.test_white.c_d();
+        let (reference_c, reference_d) = self.reference_white.c_d();
+        let c = c * reference_c / test_c;
+        let d = d * reference_d / test_d;
+        let denominator = 16.518 + 1.481 * c - d;
+        let adapted = Chromaticity {
+            y: test.y,
+            u: (10.872 + 0.404 * c - 4.0 * d) / denominator,
+            v: 5.52 / denominator,
+            luminance: test.luminance,
+        };
+
+        let (u1, v1, w1) = reference.uvw(&self.reference_white);
+        let (u2, v2, w2) = adapted.uvw(&self.reference_white);
+        let difference = ((u1 - u2).powi(2) + (v1 - v2).powi(2) + (w1 - w2).powi(2)).sqrt();
+
+        cast(100.0 - 4.6 * difference)
+    }
+
+    ///Calculate the mean of the special color rendering indices of
+    ///`samples`. This is the general color rendering index `Ra` if the
+    ///samples are the first eight CIE test color samples.
+    ///
+    ///# Panics
+    ///
+    ///Panics if `samples` is empty.
+    pub fn general_index<R: Spectrum<T>>(&self, samples: &[R]) -> T {
+        assert!(!samples.is_empty(), "there must be at least one sample");
+
+        let sum = samples
+            .iter()
+            .fold(T::zero(), |sum, sample| sum + self.special_index(sample));
+        sum / cast(samples.len())
+    }
+}
+
+///The chromaticity of a color in the CIE 1960 UCS, with its luminance
+///relative to a light source with a luminance of 100.
+#[derive(Copy, Clone)]
+struct Chromaticity {
+    y: f64,
+    u: f64,
+    v: f64,
+    luminance: f64,
+}
+
+impl Chromaticity {
+    fn new<T: Component + Float>(xyz: Xyz<E, T>, luminance: f64) -> Chromaticity {
+        let (x, y, z): (f64, f64, f64) = (cast(xyz.x), cast(xyz.y), cast(xyz.z));
+        let denominator = x + 15.0 * y + 3.0 * z;
+
+        Chromaticity {
+            y: y * 100.0 / luminance,
+            u: 4.0 * x / denominator,
+            v: 6.0 * y / denominator,
+            luminance: luminance,
+        }
+    }
+
+    fn c_d(&self) -> (f64, f64) {
+        (
+            (4.0 - self.u - 10.0 * self.v) / self.v,
+            (1.708 * self.v + 0.404 - 1.481 * self.u) / self.v,
+        )
+    }
+
+    ///The CIE 1964 U\*V\*W\* coordinates, relative to `white`.
+    fn uvw(&self, white: &Chromaticity) -> (f64, f64, f64) {
+        let w = 25.0 * self.y.cbrt() - 17.0;
+        (
+            13.0 * w * (self.u - white.u),
+            13.0 * w * (self.v - white.v),
+            w,
+        )
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Reference<T> {
+    Planckian(Planckian<T>),
+    Daylight(Daylight<T>),
+}
+
+impl<T: Float> Spectrum<T> for Reference<T> {
+    fn power(&self, wavelength: T) -> T {
+        match *self {
+            Reference::Planckian(ref spectrum) => spectrum.power(wavelength),
+            Reference::Daylight(ref spectrum) => spectrum.power(wavelength),
+        }
+    }
+}
+
+fn spectrum_xyz<T, S>(spectrum: &S) -> Xyz<E, T>
+where
+    T: Component + Float,
+    S: Spectrum<T> + ?Sized,
+{
+    spectrum_to_xyz(spectrum, Observer::Cie1931)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColorRendering;
+    use spectral::fixtures::{Peaks, SAMPLES};
+    use spectral::{Daylight, Illuminant, Planckian};
+
+    #[test]
+    fn reference_illuminants() {
+        let planckian = Planckian::new(2700.0f64);
+        let rendering = ColorRendering::new(&planckian);
+        assert!((rendering.cct().temperature - 2700.0).abs() < 1.0);
+        assert!(rendering.cct().duv.abs() < 1e-3);
+        for sample in &SAMPLES {
+            assert!(rendering.special_index(sample) > 99.9);
+        }
+
+        let daylight = Daylight::new(6500.0);
+        assert!(ColorRendering::new(&daylight).general_index(&SAMPLES) > 99.9);
+        assert!(ColorRendering::new(&Illuminant::D65).general_index(&SAMPLES) > 99.9);
+    }
+
+    #[test]
+    fn narrow_band() {
+        let rendering = ColorRendering::new(&Peaks);
+        let general = rendering.general_index(&SAMPLES);
+        assert!(general < 95.0, "{}", general);
+
+        let worst = SAMPLES
+            .iter()
+            .map(|sample| rendering.special_index(sample))
+            .fold(100.0, f64::min);
+        assert!(worst < general);
+    }
+
+    #[test]
+    #[should_panic(expected = "there must be at least one sample")]
+    fn no_samples() {
+        let samples: [&[(f64, f64)]; 0] = [];
+        ColorRendering::new(&Planckian::new(2700.0f64)).general_index(&samples);
+    }
+}
@@ -0,0 +1,1099 @@
+//!Parsing of CSS color values.
+//!
+//!```
+//!use palette::Srgba;
+//!use palette::css;
+//!
+//!let color: Srgba = css::parse_color("rgb(255 128 0 / 50%)").unwrap();
+//!assert_eq!(color, Srgba::new(1.0, 128.0 / 255.0, 0.0, 0.5));
+//!```
+//!
+//!Colors in other color spaces can be parsed into a [`Color`](enum.Color.html),
+//!using [`parse`](fn.parse.html), or converted to sRGB by `parse_color`. A
+//!`Color` can also be created from the corresponding color types and
+//!formatted as CSS again, using its `Display` implementation.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use float::Float;
+
+#[cfg(feature = "alloc")]
+use Gradient;
+#[cfg(feature = "alloc")]
+use gradient::Easing;
+use chromatic_adaptation::AdaptInto;
+use encoding;
+use rgb::{LinSrgb, LinSrgba, Srgb, Srgba};
+use white_point::{D50, D65};
+use {cast, clamp, Alpha, Component, FromColorUnclamped, Hsl, Hsla, Hwb, Hwba, Lab, Laba, Lch, Lcha, Xyz, Xyza};
+
+///An error from parsing a CSS value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    ///A hexadecimal color didn't have 3, 4, 6 or 8 hexadecimal digits.
+    InvalidHex,
+    ///A number or percentage couldn't be parsed, or was of the wrong type.
+    InvalidNumber,
+    ///The color keyword or function isn't known.
+    UnknownColor,
+    ///The value isn't structured as expected, for example by having the wrong
+    ///number of arguments.
+    InvalidSyntax,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ParseError::InvalidHex => "invalid hexadecimal color",
+            ParseError::InvalidNumber => "invalid number or percentage",
+            ParseError::UnknownColor => "unknown color",
+            ParseError::InvalidSyntax => "invalid syntax",
+        };
+
+        fmt.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ParseError {
+    fn description(&self) -> &str {
+        "could not parse CSS value"
+    }
+}
+
+///A parsed CSS color, in the color space of the syntax it was written in.
+///
+///The CIE L\*a\*b\* based colors use the D50 white point, as they do in CSS.
+///Palette doesn't have the Oklab color space, so `oklab()` and `oklch()` are
+///converted to linear sRGB. They are not clamped to the sRGB gamut.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color<T: Component + Float = f32> {
+    ///Hexadecimal colors, `rgb()`, `rgba()`, `color(srgb ...)`, named colors
+    ///and `transparent`.
+    Srgb(Srgba<T>),
+    ///`color(srgb-linear ...)`, `oklab()` and `oklch()`.
+    LinearSrgb(LinSrgba<T>),
+    ///`hsl()` and `hsla()`.
+    ///
+    ///The components are relative to non-linear sRGB in CSS, while `Hsl` is
+    ///relative to linear RGB, so the color should be converted with
+    ///[`into_srgba`](#method.into_srgba) to get the intended result.
+    Hsl(Hsla<encoding::Srgb, T>),
+    ///`hwb()`, relative to non-linear sRGB in the same way as `Hsl`.
+    Hwb(Hwba<encoding::Srgb, T>),
+    ///`lab()`.
+    Lab(Laba<D50, T>),
+    ///`lch()`.
+    Lch(Lcha<D50, T>),
+    ///`color(xyz ...)` and `color(xyz-d65 ...)`.
+    Xyz(Xyza<D65, T>),
+    ///`color(xyz-d50 ...)`.
+    XyzD50(Xyza<D50, T>),
+}
+
+impl<T: Component + Float> Color<T> {
+    ///Convert the color to non-linear sRGB with an alpha component. The D50
+    ///colors are adapted to D65 with the Bradford method. Colors outside of
+    ///the sRGB gamut are not clamped.
+    pub fn into_srgba(self) -> Srgba<T> {
+        let (linear, alpha): (LinSrgb<T>, T) = match self {
+            Color::Srgb(color) => return color,
+            Color::LinearSrgb(color) => return Srgba::from_linear(color),
+            Color::Hsl(color) => {
+                let rgb = LinSrgb::from_hsl(color.color);
+                let rgb = Srgb::new(rgb.red, rgb.green, rgb.blue);
+                return Alpha {
+                    color: rgb,
+                    alpha: color.alpha,
+                };
+            }
+            Color::Hwb(color) => {
+                let rgb = LinSrgb::from_hwb(color.color);
+                let rgb = Srgb::new(rgb.red, rgb.green, rgb.blue);
+                return Alpha {
+                    color: rgb,
+                    alpha: color.alpha,
+                };
+            }
+            Color::Lab(color) => (color.color.adapt_into(), color.alpha),
+            Color::Lch(color) => (color.color.adapt_into(), color.alpha),
+            Color::Xyz(color) => (color.color.into(), color.alpha),
+            Color::XyzD50(color) => (color.color.adapt_into(), color.alpha),
+        };
+
+        Alpha {
+            color: Srgb::from_linear(linear),
+            alpha: alpha,
+        }
+    }
+}
+
+///Formats the color as CSS Color Level 4 syntax, that can be parsed with
+///[`parse`](fn.parse.html). Non-linear sRGB becomes `rgb()`, unless it's
+///outside the sRGB gamut, and the other color spaces use their own functions,
+///or `color()`. The alpha component is only included if it's less than one.
+///
+///```
+///use palette::{Hsla, Srgb};
+///use palette::css::Color;
+///
+///assert_eq!(Color::from(Srgb::new(1.0, 0.5, 0.0)).to_string(), "rgb(255 127.5 0)");
+///assert_eq!(
+///    Color::from(Hsla::new(120.0, 1.0, 0.25, 0.5)).to_string(),
+///    "hsl(120 100% 25% / 0.5)"
+///);
+///```
+impl<T: Component + Float + fmt::Display> fmt::Display for Color<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let percent = |value: T| value * cast(100.0);
+
+        let alpha = match *self {
+            Color::Srgb(color) => {
+                let in_gamut = [color.red, color.green, color.blue]
+                    .iter()
+                    .all(|&c| c >= T::zero() && c <= T::one());
+
+                if in_gamut {
+                    let to_number = |value: T| value * cast(255.0);
+                    write!(
+                        fmt,
+                        "rgb({} {} {}",
+                        to_number(color.red),
+                        to_number(color.green),
+                        to_number(color.blue)
+                    )?;
+                } else {
+                    write!(fmt, "color(srgb {} {} {}", color.red, color.green, color.blue)?;
+                }
+
+                color.alpha
+            }
+            Color::LinearSrgb(color) => {
+                write!(
+                    fmt,
+                    "color(srgb-linear {} {} {}",
+                    color.red, color.green, color.blue
+                )?;
+                color.alpha
+            }
+            Color::Hsl(color) => {
+                write!(
+                    fmt,
+                    "hsl({} {}% {}%",
+                    color.hue.to_positive_degrees(),
+                    percent(color.saturation),
+                    percent(color.lightness)
+                )?;
+                color.alpha
+            }
+            Color::Hwb(color) => {
+                write!(
+                    fmt,
+                    "hwb({} {}% {}%",
+                    color.hue.to_positive_degrees(),
+                    percent(color.whiteness),
+                    percent(color.blackness)
+                )?;
+                color.alpha
+            }
+            Color::Lab(color) => {
+                write!(fmt, "lab({} {} {}", color.l, color.a, color.b)?;
+                color.alpha
+            }
+            Color::Lch(color) => {
+                write!(
+                    fmt,
+                    "lch({} {} {}",
+                    color.l,
+                    color.chroma,
+                    color.hue.to_positive_degrees()
+                )?;
+                color.alpha
+            }
+            Color::Xyz(color) => {
+                write!(fmt, "color(xyz-d65 {} {} {}", color.x, color.y, color.z)?;
+                color.alpha
+            }
+            Color::XyzD50(color) => {
+                write!(fmt, "color(xyz-d50 {} {} {}", color.x, color.y, color.z)?;
+                color.alpha
+            }
+        };
+
+        if alpha < T::one() {
+            write!(fmt, " / {}", alpha)?;
+        }
+
+        fmt.write_str(")")
+    }
+}
+
+macro_rules! impl_from_color {
+    ($($variant:ident: $color:ty),+) => {
+        $(
+            impl<T: Component + Float> From<$color> for Color<T> {
+                fn from(color: $color) -> Color<T> {
+                    Color::$variant(color.into())
+                }
+            }
+
+            impl<T: Component + Float> From<Alpha<$color, T>> for Color<T> {
+                fn from(color: Alpha<$color, T>) -> Color<T> {
+                    Color::$variant(color)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_color!(
+    Srgb: Srgb<T>,
+    LinearSrgb: LinSrgb<T>,
+    Hsl: Hsl<encoding::Srgb, T>,
+    Hwb: Hwb<encoding::Srgb, T>,
+    Lab: Lab<D50, T>,
+    Lch: Lch<D50, T>,
+    Xyz: Xyz<D65, T>,
+    XyzD50: Xyz<D50, T>
+);
+
+///Parse a CSS color value as sRGB with an alpha component.
+///
+///This accepts the same syntax as [`parse`](fn.parse.html) and converts the
+///result with [`Color::into_srgba`](enum.Color.html#method.into_srgba).
+pub fn parse_color<T: Component + Float>(input: &str) -> Result<Srgba<T>, ParseError> {
+    parse(input).map(Color::into_srgba)
+}
+
+///Parse a CSS color value into the color space of its syntax.
+///
+///Hexadecimal colors, `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`,
+///`lab()`, `lch()`, `oklab()`, `oklch()`, `color()` and the `transparent`
+///keyword are supported. The `color()` function accepts the `srgb`,
+///`srgb-linear`, `xyz`, `xyz-d50` and `xyz-d65` color spaces. The named
+///colors are supported with the `"named_from_str"` feature.
+///
+///```
+///use palette::Laba;
+///use palette::css::{self, Color};
+///
+///let color = css::parse::<f32>("lab(50% 40 -20 / 0.5)").unwrap();
+///assert_eq!(color, Color::Lab(Laba::with_wp(50.0, 40.0, -20.0, 0.5)));
+///```
+pub fn parse<T: Component + Float>(input: &str) -> Result<Color<T>, ParseError> {
+    let input = input.trim();
+
+    if let Some(digits) = input.strip_prefix('#') {
+        return parse_hex(digits).map(Color::Srgb);
+    }
+
+    if let Some(open) = input.find('(') {
+        if !input.ends_with(')') {
+            return Err(ParseError::InvalidSyntax);
+        }
+
+        let name = input[..open].trim();
+        let arguments = &input[open + 1..input.len() - 1];
+        let is = |function: &str| name.eq_ignore_ascii_case(function);
+
+        return if is("rgb") || is("rgba") {
+            parse_rgb(arguments).map(Color::Srgb)
+        } else if is("hsl") || is("hsla") {
+            parse_hsl(arguments)
+        } else if is("hwb") {
+            parse_hwb(arguments)
+        } else if is("lab") {
+            parse_lab(arguments)
+        } else if is("lch") {
+            parse_lch(arguments)
+        } else if is("oklab") {
+            parse_oklab(arguments, false)
+        } else if is("oklch") {
+            parse_oklab(arguments, true)
+        } else if is("color") {
+            parse_color_function(arguments)
+        } else {
+            Err(ParseError::UnknownColor)
+        };
+    }
+
+    if input.eq_ignore_ascii_case("transparent") {
+        return Ok(Color::Srgb(Srgba::new(T::zero(), T::zero(), T::zero(), T::zero())));
+    }
+
+    parse_named(input).map(Color::Srgb)
+}
+
+#[cfg(feature = "named_from_str")]
+fn parse_named<T: Component + Float>(input: &str) -> Result<Srgba<T>, ParseError> {
+    match ::named::from_str(&input.to_ascii_lowercase()) {
+        Some(color) => Ok(Srgba::from(color.into_format::<T>())),
+        None => Err(ParseError::UnknownColor),
+    }
+}
+
+#[cfg(not(feature = "named_from_str"))]
+fn parse_named<T: Component + Float>(_input: &str) -> Result<Srgba<T>, ParseError> {
+    Err(ParseError::UnknownColor)
+}
+
+///Parse the digits of a hexadecimal color, without the leading `#`.
+fn parse_hex<T: Component + Float>(digits: &str) -> Result<Srgba<T>, ParseError> {
+    let mut values = [0u8; 8];
+    let mut len = 0;
+
+    for digit in digits.chars() {
+        if len == values.len() {
+            return Err(ParseError::InvalidHex);
+        }
+
+        values[len] = digit.to_digit(16).ok_or(ParseError::InvalidHex)? as u8;
+        len += 1;
+    }
+
+    let (red, green, blue, alpha) = match len {
+        3 | 4 => {
+            let alpha = if len == 4 { values[3] * 17 } else { 255 };
+            (values[0] * 17, values[1] * 17, values[2] * 17, alpha)
+        }
+        6 | 8 => {
+            let alpha = if len == 8 {
+                values[6] * 16 + values[7]
+            } else {
+                255
+            };
+            (
+                values[0] * 16 + values[1],
+                values[2] * 16 + values[3],
+                values[4] * 16 + values[5],
+                alpha,
+            )
+        }
+        _ => return Err(ParseError::InvalidHex),
+    };
+
+    Ok(Srgba::new(red, green, blue, alpha).into_format())
+}
+
+///Parse the arguments of `rgb()` or `rgba()`, in either the legacy comma
+///separated syntax or the space separated syntax.
+fn parse_rgb<T: Component + Float>(arguments: &str) -> Result<Srgba<T>, ParseError> {
+    let ([red, green, blue], alpha) = split_components(arguments, true)?;
+
+    Ok(Srgba::new(
+        parse_rgb_component(red)?,
+        parse_rgb_component(green)?,
+        parse_rgb_component(blue)?,
+        alpha,
+    ))
+}
+
+///Parse the arguments of `hsl()` or `hsla()`.
+fn parse_hsl<T: Component + Float>(arguments: &str) -> Result<Color<T>, ParseError> {
+    let ([hue, saturation, lightness], alpha) = split_components(arguments, true)?;
+
+    Ok(Color::Hsl(Hsla::new(
+        cast::<T, _>(parse_hue(hue)?),
+        parse_unit_component(saturation)?,
+        parse_unit_component(lightness)?,
+        alpha,
+    )))
+}
+
+///Parse the arguments of `hwb()`. The whiteness and blackness are
+///normalized if their sum is greater than one, as in CSS.
+fn parse_hwb<T: Component + Float>(arguments: &str) -> Result<Color<T>, ParseError> {
+    let ([hue, whiteness, blackness], alpha) = split_components(arguments, false)?;
+    let mut whiteness: T = parse_unit_component(whiteness)?;
+    let mut blackness: T = parse_unit_component(blackness)?;
+
+    let sum = whiteness + blackness;
+    if sum > T::one() {
+        whiteness = whiteness / sum;
+        blackness = blackness / sum;
+    }
+
+    Ok(Color::Hwb(Hwba::new(
+        cast::<T, _>(parse_hue(hue)?),
+        whiteness,
+        blackness,
+        alpha,
+    )))
+}
+
+///Parse the arguments of `lab()`.
+fn parse_lab<T: Component + Float>(arguments: &str) -> Result<Color<T>, ParseError> {
+    let ([l, a, b], alpha) = split_components(arguments, false)?;
+    let l = clamp(parse_value(l, 100.0)?, 0.0, 100.0);
+
+    Ok(Color::Lab(Laba::with_wp(
+        cast(l),
+        cast(parse_value(a, 125.0)?),
+        cast(parse_value(b, 125.0)?),
+        alpha,
+    )))
+}
+
+///Parse the arguments of `lch()`.
+fn parse_lch<T: Component + Float>(arguments: &str) -> Result<Color<T>, ParseError> {
+    let ([l, chroma, hue], alpha) = split_components(arguments, false)?;
+    let l = clamp(parse_value(l, 100.0)?, 0.0, 100.0);
+    let chroma = parse_value(chroma, 150.0)?.max(0.0);
+
+    Ok(Color::Lch(Lcha::with_wp(
+        cast(l),
+        cast(chroma),
+        cast::<T, _>(parse_hue(hue)?),
+        alpha,
+    )))
+}
+
+///Parse the arguments of `oklab()`, or `oklch()` if `polar` is `true`, and
+///convert the color to linear sRGB.
+fn parse_oklab<T: Component + Float>(arguments: &str, polar: bool) -> Result<Color<T>, ParseError> {
+    let ([l, first, second], alpha) = split_components(arguments, false)?;
+    let l = clamp(parse_value(l, 1.0)?, 0.0, 1.0);
+
+    let (a, b) = if polar {
+        let chroma = parse_value(first, 0.4)?.max(0.0);
+        let hue = parse_hue(second)?.to_radians();
+        (chroma * hue.cos(), chroma * hue.sin())
+    } else {
+        (parse_value(first, 0.4)?, parse_value(second, 0.4)?)
+    };
+
+    //The inverse Oklab transform, from Björn Ottosson's definition
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    Ok(Color::LinearSrgb(LinSrgba::new(
+        cast(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+        cast(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+        cast(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+        alpha,
+    )))
+}
+
+///Parse the arguments of `color()`, starting with the name of the color
+///space.
+fn parse_color_function<T: Component + Float>(arguments: &str) -> Result<Color<T>, ParseError> {
+    let arguments = arguments.trim_start();
+    let end = arguments
+        .find(char::is_whitespace)
+        .ok_or(ParseError::InvalidSyntax)?;
+    let (space, arguments) = arguments.split_at(end);
+
+    let ([first, second, third], alpha) = split_components(arguments, false)?;
+    let first = cast(parse_value(first, 1.0)?);
+    let second = cast(parse_value(second, 1.0)?);
+    let third = cast(parse_value(third, 1.0)?);
+    let is = |name: &str| space.eq_ignore_ascii_case(name);
+
+    if is("srgb") {
+        Ok(Color::Srgb(Srgba::new(first, second, third, alpha)))
+    } else if is("srgb-linear") {
+        Ok(Color::LinearSrgb(LinSrgba::new(first, second, third, alpha)))
+    } else if is("xyz") || is("xyz-d65") {
+        Ok(Color::Xyz(Xyza::new(first, second, third, alpha)))
+    } else if is("xyz-d50") {
+        Ok(Color::XyzD50(Xyza::with_wp(first, second, third, alpha)))
+    } else {
+        Err(ParseError::UnknownColor)
+    }
+}
+
+///Split the arguments of a color function into three components and the
+///parsed alpha. The legacy comma separated syntax is only accepted if
+///`legacy` is `true`.
+fn split_components<T: Float>(
+    arguments: &str,
+    legacy: bool,
+) -> Result<([&str; 3], T), ParseError> {
+    let mut components = [""; 3];
+    let alpha;
+
+    if arguments.contains(',') {
+        if !legacy {
+            return Err(ParseError::InvalidSyntax);
+        }
+
+        let mut arguments = arguments.split(',');
+
+        for component in &mut components {
+            *component = arguments.next().ok_or(ParseError::InvalidSyntax)?;
+        }
+
+        alpha = arguments.next();
+
+        if arguments.next().is_some() {
+            return Err(ParseError::InvalidSyntax);
+        }
+    } else {
+        let mut parts = arguments.splitn(2, '/');
+        let mut arguments = parts.next().unwrap_or("").split_whitespace();
+
+        for component in &mut components {
+            *component = arguments.next().ok_or(ParseError::InvalidSyntax)?;
+        }
+
+        if arguments.next().is_some() {
+            return Err(ParseError::InvalidSyntax);
+        }
+
+        alpha = parts.next();
+    }
+
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => T::one(),
+    };
+
+    Ok((components, alpha))
+}
+
+fn parse_rgb_component<T: Float>(argument: &str) -> Result<T, ParseError> {
+    let value = parse_value(argument, 255.0)? / 255.0;
+    Ok(clamp(cast(value), T::zero(), T::one()))
+}
+
+///Parse a percentage, or a number between 0 and 100, as a value between 0
+///and 1.
+fn parse_unit_component<T: Float>(argument: &str) -> Result<T, ParseError> {
+    let value = parse_value(argument, 100.0)? / 100.0;
+    Ok(clamp(cast(value), T::zero(), T::one()))
+}
+
+fn parse_alpha<T: Float>(argument: &str) -> Result<T, ParseError> {
+    Ok(clamp(cast(parse_value(argument, 1.0)?), T::zero(), T::one()))
+}
+
+///Parse a hue as degrees, from a number or an angle.
+fn parse_hue(argument: &str) -> Result<f64, ParseError> {
+    let argument = argument.trim();
+
+    if argument.eq_ignore_ascii_case("none") {
+        return Ok(0.0);
+    }
+
+    //"grad" has to be checked before "rad"
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / ::core::f64::consts::PI),
+        ("turn", 360.0),
+    ];
+
+    for &(unit, degrees) in &units {
+        let split = argument.len().saturating_sub(unit.len());
+        if let (Some(number), Some(suffix)) = (argument.get(..split), argument.get(split..)) {
+            if suffix.eq_ignore_ascii_case(unit) {
+                return Ok(parse_number(number)? * degrees);
+            }
+        }
+    }
+
+    parse_number(argument)
+}
+
+///Parse a number, or a percentage where `100%` is `percent`. The `none`
+///keyword is parsed as zero.
+fn parse_value(argument: &str, percent: f64) -> Result<f64, ParseError> {
+    let argument = argument.trim();
+
+    if argument.eq_ignore_ascii_case("none") {
+        Ok(0.0)
+    } else if let Some(percentage) = argument.strip_suffix('%') {
+        Ok(parse_number(percentage)? / 100.0 * percent)
+    } else {
+        parse_number(argument)
+    }
+}
+
+fn parse_number(input: &str) -> Result<f64, ParseError> {
+    let value: f64 = input.trim().parse().map_err(|_| ParseError::InvalidNumber)?;
+
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ParseError::InvalidNumber)
+    }
+}
+
+///Parse a CSS `linear-gradient()` into a gradient with the domain `[0.0,
+///1.0]`.
+///
+///The color stops may have zero, one or two percentage positions and there
+///may be color hints between them, which become
+///[`Easing::Midpoint`](../gradient/enum.Easing.html) easing functions. Missing
+///positions are filled in the same way as in CSS. The gradient direction is
+///accepted, but ignored, since a `Gradient` is one dimensional.
+///
+///Note that the colors are interpolated in linear sRGB, while browsers mix
+///them in non-linear sRGB.
+///
+///```
+///use palette::LinSrgba;
+///use palette::css;
+///
+///let gradient = css::parse_linear_gradient::<f32>(
+///    "linear-gradient(to right, #f00, 30%, #00f 80%)"
+///).unwrap();
+///
+///assert_eq!(gradient.get(0.0), LinSrgba::new(1.0, 0.0, 0.0, 1.0));
+///assert_eq!(gradient.get(0.9), LinSrgba::new(0.0, 0.0, 1.0, 1.0));
+///```
+#[cfg(feature = "alloc")]
+pub fn parse_linear_gradient<T: Component + Float>(
+    input: &str,
+) -> Result<Gradient<LinSrgba<T>>, ParseError> {
+    let input = input.trim();
+    let open = input.find('(').ok_or(ParseError::InvalidSyntax)?;
+
+    if !input[..open].trim().eq_ignore_ascii_case("linear-gradient") || !input.ends_with(')') {
+        return Err(ParseError::InvalidSyntax);
+    }
+
+    let mut arguments = split_arguments(&input[open + 1..input.len() - 1])?;
+    if arguments.first().map(|first| is_direction(first)) == Some(true) {
+        arguments.remove(0);
+    }
+
+    //Collect the stops and hints, with their positions as `Option`s
+    let mut stops: Vec<(LinSrgba<T>, Option<T>)> = Vec::new();
+    let mut hints: Vec<(usize, T)> = Vec::new();
+
+    for argument in arguments {
+        if let Ok(position) = parse_percentage(argument) {
+            let index = stops.len();
+            if index == 0 || hints.last().map(|&(i, _)| i) == Some(index) {
+                return Err(ParseError::InvalidSyntax);
+            }
+
+            hints.push((index, position));
+            continue;
+        }
+
+        let (color, positions) = split_color(argument)?;
+        let color = parse_color::<T>(color)?.into_linear();
+        let mut positions = positions.split_whitespace();
+
+        match (positions.next(), positions.next(), positions.next()) {
+            (None, _, _) => stops.push((color, None)),
+            (Some(position), None, _) => stops.push((color, Some(parse_percentage(position)?))),
+            (Some(first), Some(second), None) => {
+                stops.push((color, Some(parse_percentage(first)?)));
+                stops.push((color, Some(parse_percentage(second)?)));
+            }
+            _ => return Err(ParseError::InvalidSyntax),
+        }
+    }
+
+    if stops.len() < 2 || hints.last().map(|&(i, _)| i) == Some(stops.len()) {
+        return Err(ParseError::InvalidSyntax);
+    }
+
+    let positions = resolve_positions(&stops);
+    let points: Vec<_> = positions
+        .iter()
+        .zip(stops)
+        .map(|(&position, (color, _))| (position, color))
+        .collect();
+
+    let mut gradient = Gradient::with_domain(points);
+
+    for (index, hint) in hints {
+        let from = positions[index - 1];
+        let to = positions[index];
+        let hint = clamp(hint, from, to);
+
+        let midpoint = if to > from {
+            (hint - from) / (to - from)
+        } else {
+            cast(0.5)
+        };
+
+        gradient = gradient.with_easing(index - 1, Easing::Midpoint(midpoint));
+    }
+
+    Ok(gradient)
+}
+
+///Fill in missing positions and make them increasing, as described in CSS
+///Images Module Level 3.
+#[cfg(feature = "alloc")]
+fn resolve_positions<C, T: Float>(stops: &[(C, Option<T>)]) -> Vec<T> {
+    let last = stops.len() - 1;
+    let mut positions: Vec<Option<T>> = stops.iter().map(|&(_, position)| position).collect();
+
+    if positions[0].is_none() {
+        positions[0] = Some(T::zero());
+    }
+
+    if positions[last].is_none() {
+        positions[last] = Some(T::one());
+    }
+
+    let mut max = T::zero();
+    for position in &mut positions {
+        if let Some(ref mut position) = *position {
+            if *position < max {
+                *position = max;
+            }
+            max = *position;
+        }
+    }
+
+    let mut previous = 0;
+    for index in 1..positions.len() {
+        if let Some(to) = positions[index] {
+            let from = positions[previous].unwrap_or(to);
+            let steps: T = cast(index - previous);
+
+            for (step, position) in positions[previous + 1..index].iter_mut().enumerate() {
+                *position = Some(from + (to - from) * cast(step + 1) / steps);
+            }
+
+            previous = index;
+        }
+    }
+
+    positions.into_iter().map(|p| p.unwrap_or(T::zero())).collect()
+}
+
+///Split function arguments on the commas that aren't within parentheses.
+#[cfg(feature = "alloc")]
+fn split_arguments(input: &str) -> Result<Vec<&str>, ParseError> {
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or(ParseError::InvalidSyntax)?,
+            ',' if depth == 0 => {
+                arguments.push(input[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(ParseError::InvalidSyntax);
+    }
+
+    arguments.push(input[start..].trim());
+    Ok(arguments)
+}
+
+///Split a color stop into its color and the rest.
+#[cfg(feature = "alloc")]
+fn split_color(input: &str) -> Result<(&str, &str), ParseError> {
+    let end = match input.find(|c: char| c == '(' || c.is_whitespace()) {
+        Some(index) if input[index..].starts_with('(') => {
+            input[index..].find(')').ok_or(ParseError::InvalidSyntax)? + index + 1
+        }
+        Some(index) => index,
+        None => input.len(),
+    };
+
+    Ok((&input[..end], &input[end..]))
+}
+
+#[cfg(feature = "alloc")]
+fn is_direction(argument: &str) -> bool {
+    let lower = argument.to_ascii_lowercase();
+
+    let is_angle = ["deg", "grad", "rad", "turn"].iter().any(|unit| {
+        lower.ends_with(unit) && parse_number(&lower[..lower.len() - unit.len()]).is_ok()
+    });
+
+    lower.starts_with("to ") || is_angle
+}
+
+#[cfg(feature = "alloc")]
+fn parse_percentage<T: Float>(input: &str) -> Result<T, ParseError> {
+    let input = input.trim();
+
+    if let Some(percentage) = input.strip_suffix('%') {
+        Ok(cast(parse_number(percentage)? / 100.0))
+    } else {
+        Err(ParseError::InvalidNumber)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, parse_color, Color, ParseError};
+    use {Hsla, Hwba, Laba, Lcha, LinSrgba, Srgba, Xyza};
+
+    fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Result<Srgba, ParseError> {
+        Ok(Srgba::new(red, green, blue, alpha).into_format())
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(parse_color("#f80"), rgba(255, 136, 0, 255));
+        assert_eq!(parse_color("#f808"), rgba(255, 136, 0, 136));
+        assert_eq!(parse_color("#FF8800"), rgba(255, 136, 0, 255));
+        assert_eq!(parse_color("#ff880080"), rgba(255, 136, 0, 128));
+        assert_eq!(parse_color("#ff88"), rgba(255, 255, 136, 136));
+
+        assert_eq!(parse_color::<f32>("#ff88001"), Err(ParseError::InvalidHex));
+        assert_eq!(parse_color::<f32>("#ff880g"), Err(ParseError::InvalidHex));
+        assert_eq!(parse_color::<f32>("#ff8800ff0"), Err(ParseError::InvalidHex));
+    }
+
+    #[test]
+    fn rgb() {
+        let expected = Srgba::new(1.0, 0.5, 0.0, 1.0);
+        assert_eq!(parse_color("rgb(255, 127.5, 0)"), Ok(expected));
+        assert_eq!(parse_color("rgb(100% 50% 0%)"), Ok(expected));
+        assert_eq!(parse_color("RGB( 255 127.5 0 )"), Ok(expected));
+
+        let expected = Srgba::new(1.0, 0.5, 0.0, 0.25);
+        assert_eq!(parse_color("rgba(255, 127.5, 0, 0.25)"), Ok(expected));
+        assert_eq!(parse_color("rgb(255 127.5 0 / 25%)"), Ok(expected));
+
+        assert_eq!(parse_color("rgb(300, -10, 0)"), Ok(Srgba::new(1.0, 0.0, 0.0, 1.0)));
+
+        assert_eq!(parse_color::<f32>("rgb(255, 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse_color::<f32>("rgb(255 0 0 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse_color::<f32>("rgb(255, 0, zero)"), Err(ParseError::InvalidNumber));
+        assert_eq!(parse_color::<f32>("rgb(255, 0, 0"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse_color::<f32>("foo(255, 0, 0)"), Err(ParseError::UnknownColor));
+    }
+
+    #[test]
+    fn hsl() {
+        let expected = Ok(Color::Hsl(Hsla::new(120.0, 1.0, 0.25, 0.5)));
+        assert_eq!(parse("hsl(120 100% 25% / 0.5)"), expected);
+        assert_eq!(parse("hsla(120deg, 100%, 25%, 0.5)"), expected);
+        assert_eq!(parse("hsl(0.3333333333333333turn 100 25 / 50%)"), expected);
+
+        assert_relative_eq!(
+            parse_color::<f64>("hsl(180 100% 50%)").unwrap(),
+            Srgba::new(0.0, 1.0, 1.0, 1.0)
+        );
+        assert_relative_eq!(
+            parse_color::<f64>("hsl(0 0% 50%)").unwrap(),
+            Srgba::new(0.5, 0.5, 0.5, 1.0)
+        );
+    }
+
+    #[test]
+    fn hwb() {
+        assert_eq!(parse("hwb(90 10% 20%)"), Ok(Color::Hwb(Hwba::new(90.0, 0.1, 0.2, 1.0))));
+        assert_eq!(parse("hwb(0 60% 60%)"), Ok(Color::Hwb(Hwba::new(0.0, 0.5, 0.5, 1.0))));
+        assert_relative_eq!(
+            parse_color::<f64>("hwb(0 0% 0%)").unwrap(),
+            Srgba::new(1.0, 0.0, 0.0, 1.0)
+        );
+        assert_eq!(parse::<f32>("hwb(0, 0%, 0%)"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    fn lab_and_lch() {
+        assert_eq!(
+            parse("lab(50% 40 -20 / 0.5)"),
+            Ok(Color::Lab(Laba::with_wp(50.0, 40.0, -20.0, 0.5)))
+        );
+        assert_eq!(
+            parse("lab(120 100% none)"),
+            Ok(Color::Lab(Laba::with_wp(100.0, 125.0, 0.0, 1.0)))
+        );
+        assert_eq!(
+            parse("lch(50 30 0.5turn)"),
+            Ok(Color::Lch(Lcha::with_wp(50.0, 30.0, 180.0, 1.0)))
+        );
+        assert_eq!(
+            parse("lch(50 -10 200grad)"),
+            Ok(Color::Lch(Lcha::with_wp(50.0, 0.0, 180.0, 1.0)))
+        );
+
+        assert_relative_eq!(
+            parse_color::<f64>("lab(100 0 0)").unwrap(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            parse_color::<f64>("lch(54.29 106.84 40.86)").unwrap(),
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            epsilon = 1e-3
+        );
+        assert_eq!(parse::<f32>("lab(50, 0, 0)"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[test]
+    fn oklab_and_oklch() {
+        match parse::<f64>("oklab(100% 0 0 / 0.5)") {
+            Ok(Color::LinearSrgb(color)) => {
+                assert_relative_eq!(color, LinSrgba::new(1.0, 1.0, 1.0, 0.5), epsilon = 1e-6)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_relative_eq!(
+            parse_color::<f64>("oklch(0.62796 0.25768 29.234)").unwrap(),
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn color_function() {
+        assert_eq!(
+            parse("color(srgb 1 50% 0)"),
+            Ok(Color::Srgb(Srgba::new(1.0, 0.5, 0.0, 1.0)))
+        );
+        assert_eq!(
+            parse("color(srgb-linear 1.5 0 0 / 50%)"),
+            Ok(Color::LinearSrgb(LinSrgba::new(1.5, 0.0, 0.0, 0.5)))
+        );
+        assert_eq!(
+            parse("color(xyz 0.25 0.5 0.75)"),
+            Ok(Color::Xyz(Xyza::new(0.25, 0.5, 0.75, 1.0)))
+        );
+        assert_eq!(
+            parse("COLOR(XYZ-D50 0.25 0.5 0.75)"),
+            Ok(Color::XyzD50(Xyza::with_wp(0.25, 0.5, 0.75, 1.0)))
+        );
+
+        assert_relative_eq!(
+            parse_color::<f64>("color(xyz-d50 0.9642 1 0.8251)").unwrap(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+            epsilon = 1e-3
+        );
+
+        assert_eq!(parse::<f32>("color(display-p3 1 0 0)"), Err(ParseError::UnknownColor));
+        assert_eq!(parse::<f32>("color(srgb 1 0)"), Err(ParseError::InvalidSyntax));
+        assert_eq!(parse::<f32>("color()"), Err(ParseError::InvalidSyntax));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let colors = [
+            Color::Srgb(Srgba::new(1.0, 0.5, 0.0, 1.0)),
+            Color::Srgb(Srgba::new(1.25, 0.5, -0.5, 0.25)),
+            Color::LinearSrgb(LinSrgba::new(0.25, 0.5, 0.75, 1.0)),
+            Color::Hsl(Hsla::new(-90.0, 0.5, 0.25, 0.5)),
+            Color::Hwb(Hwba::new(45.0, 0.1, 0.2, 1.0)),
+            Color::Lab(Laba::with_wp(50.0, 40.0, -20.0, 1.0)),
+            Color::Lch(Lcha::with_wp(50.0, 30.0, 180.0, 0.75)),
+            Color::Xyz(Xyza::new(0.25, 0.5, 0.75, 1.0)),
+            Color::XyzD50(Xyza::with_wp(0.25, 0.5, 0.75, 0.5)),
+        ];
+
+        let expected = [
+            "rgb(255 127.5 0)",
+            "color(srgb 1.25 0.5 -0.5 / 0.25)",
+            "color(srgb-linear 0.25 0.5 0.75)",
+            "hsl(270 50% 25% / 0.5)",
+            "hwb(45 10% 20%)",
+            "lab(50 40 -20)",
+            "lch(50 30 180 / 0.75)",
+            "color(xyz-d65 0.25 0.5 0.75)",
+            "color(xyz-d50 0.25 0.5 0.75 / 0.5)",
+        ];
+
+        for (color, expected) in colors.iter().zip(&expected) {
+            assert_eq!(color.to_string(), *expected);
+            assert_eq!(parse::<f32>(expected).unwrap().to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(parse_color("transparent"), Ok(Srgba::new(0.0, 0.0, 0.0, 0.0)));
+        assert_eq!(parse_color::<f32>("not-a-color"), Err(ParseError::UnknownColor));
+    }
+
+    #[cfg(feature = "named_from_str")]
+    #[test]
+    fn named() {
+        assert_eq!(parse_color("Red"), Ok(Srgba::new(1.0, 0.0, 0.0, 1.0)));
+        assert_eq!(parse_color(" blue "), Ok(Srgba::new(0.0, 0.0, 1.0, 1.0)));
+    }
+
+    #[cfg(feature = "alloc")]
+    mod gradient {
+        use super::super::{parse_linear_gradient, resolve_positions, ParseError};
+        use LinSrgba;
+
+        #[test]
+        fn positions() {
+            let stops = [
+                ((), None),
+                ((), Some(0.2)),
+                ((), None),
+                ((), Some(0.1)),
+                ((), Some(0.6)),
+                ((), Some(0.8)),
+                ((), None),
+            ];
+
+            assert_eq!(
+                resolve_positions(&stops),
+                vec![0.0, 0.2, 0.2, 0.2, 0.6, 0.8, 1.0]
+            );
+        }
+
+        #[test]
+        fn evenly_spaced() {
+            let stops = [((), None), ((), None), ((), None), ((), Some(0.9))];
+            assert_relative_eq!(&resolve_positions(&stops)[..], &[0.0, 0.3, 0.6, 0.9][..]);
+        }
+
+        #[test]
+        fn parse() {
+            let gradient = parse_linear_gradient::<f64>(
+                "linear-gradient(45deg, rgb(0 0 0) 50%, transparent 50% 75%, #fff)",
+            ).unwrap();
+
+            assert_relative_eq!(gradient.get(0.4), LinSrgba::new(0.0, 0.0, 0.0, 1.0));
+            assert_relative_eq!(gradient.get(0.6), LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+            assert_relative_eq!(gradient.get(0.875), LinSrgba::new(0.5, 0.5, 0.5, 0.5));
+        }
+
+        #[test]
+        fn hints() {
+            let gradient =
+                parse_linear_gradient::<f64>("linear-gradient(to left, #000, 25%, #fff 50%)")
+                    .unwrap();
+
+            assert_relative_eq!(gradient.get(0.25), LinSrgba::new(0.5, 0.5, 0.5, 1.0));
+            assert_relative_eq!(gradient.get(0.75), LinSrgba::new(1.0, 1.0, 1.0, 1.0));
+        }
+
+        #[test]
+        fn invalid() {
+            let invalid = [
+                "linear-gradient(#000)",
+                "linear-gradient(#000, 50%)",
+                "linear-gradient(50%, #000, #fff)",
+                "linear-gradient(#000, 20%, 40%, #fff)",
+                "linear-gradient(#000 10px, #fff)",
+                "radial-gradient(#000, #fff)",
+                "linear-gradient(#000, #fff",
+            ];
+
+            for input in &invalid {
+                assert!(parse_linear_gradient::<f64>(input).is_err(), "{}", input);
+            }
+
+            assert_eq!(
+                parse_linear_gradient::<f64>("linear-gradient(#000, #ggg)").unwrap_err(),
+                ParseError::InvalidHex
+            );
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//!Color vision deficiency simulation.
+//!
+//!The simulation shows how colors may look to someone with a color vision
+//!deficiency, which is useful when checking if a design is accessible. It
+//!uses the matrices by Machado, Oliveira and Fernandes (2009), which are
+//!applied to linear sRGB. A severity of `1.0` simulates dichromacy
+//!(protanopia, deuteranopia or tritanopia), while lower values simulate the
+//!milder anomalous trichromacy (protanomaly, deuteranomaly or tritanomaly).
+//!Severities below `1.0` are approximated by interpolating between the
+//!identity matrix and the dichromacy matrix.
+//!
+//!```
+//!use palette::{LinSrgb, Srgb};
+//!use palette::cvd::Deficiency;
+//!
+//!let red = Srgb::new(0.9, 0.1, 0.1).into_linear();
+//!let green = Srgb::new(0.3, 0.6, 0.1).into_linear();
+//!
+//!//Red and green become much harder to tell apart
+//!let red_seen: LinSrgb = Deficiency::Deutan.simulate(red, 1.0);
+//!let green_seen: LinSrgb = Deficiency::Deutan.simulate(green, 1.0);
+//!assert!((red_seen.red - red_seen.green).abs() < (red.red - red.green).abs());
+//!
+//!let mut pixels = vec![red, green];
+//!Deficiency::Protan.simulate_slice(&mut pixels, 0.6);
+//!```
+//!
+//!## Daltonization
+//!
+//!Daltonization compensates for a deficiency by taking the difference between
+//!the original and the simulated color, which is the information that's lost,
+//!and redistributing it into channels that are still visible. This follows
+//!the method by Fidaner, Lin and Ozguven (2005). The corrected colors may end
+//!up outside the sRGB gamut and may need to be clamped afterwards.
+//!
+//!```
+//!use palette::{LinSrgb, Srgb};
+//!use palette::cvd::Deficiency;
+//!
+//!let mut image = vec![Srgb::new(0.9, 0.1, 0.1).into_linear(); 16];
+//!Deficiency::Deutan.daltonize_slice(&mut image, 1.0);
+//!
+//!let corrected: LinSrgb = Deficiency::Deutan.daltonize(LinSrgb::new(0.2, 0.7, 0.1), 0.5);
+//!```
+
+use float::Float;
+
+use convert::{FromColorUnclamped, IntoColorUnclamped};
+use encoding::Srgb;
+use matrix::{multiply_3x3, Mat3};
+use white_point::D65;
+use {cast, clamp, Component, LinSrgb};
+
+///A type of color vision deficiency.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Deficiency {
+    ///Missing or anomalous long wavelength (red) cones.
+    Protan,
+    ///Missing or anomalous medium wavelength (green) cones.
+    Deutan,
+    ///Missing or anomalous short wavelength (blue) cones.
+    Tritan,
+}
+
+impl Deficiency {
+    ///Get the simulation matrix for linear sRGB, for a `severity` between
+    ///`0.0` (normal vision) and `1.0` (dichromacy). The severity is clamped to
+    ///that range.
+    pub fn matrix<T: Float>(self, severity: T) -> Mat3<T> {
+        let dichromacy = match self {
+            Deficiency::Protan => &PROTANOPIA,
+            Deficiency::Deutan => &DEUTERANOPIA,
+            Deficiency::Tritan => &TRITANOPIA,
+        };
+
+        let severity = clamp(severity, T::zero(), T::one());
+        let mut matrix = [T::zero(); 9];
+
+        for (index, (out, &value)) in matrix.iter_mut().zip(dichromacy).enumerate() {
+            let identity = if index % 4 == 0 { T::one() } else { T::zero() };
+            *out = identity + (cast::<T, _>(value) - identity) * severity;
+        }
+
+        matrix
+    }
+
+    ///Simulate how `color` is seen with this deficiency, at the given
+    ///`severity`.
+    pub fn simulate<C, T>(self, color: C, severity: T) -> C
+    where
+        C: IntoColorUnclamped<D65, T> + FromColorUnclamped<D65, T>,
+        T: Component + Float,
+    {
+        apply(&self.matrix(severity), color)
+    }
+
+    ///Simulate how each color in `colors` is seen with this deficiency, at
+    ///the given `severity`, and store the results in `colors`.
+    pub fn simulate_slice<C, T>(self, colors: &mut [C], severity: T)
+    where
+        C: IntoColorUnclamped<D65, T> + FromColorUnclamped<D65, T> + Clone,
+        T: Component + Float,
+    {
+        let matrix = self.matrix(severity);
+
+        for color in colors {
+            *color = apply(&matrix, color.clone());
+        }
+    }
+
+    ///Get the daltonization matrix for linear sRGB, for a `severity` between
+    ///`0.0` (normal vision) and `1.0` (dichromacy). The severity is clamped to
+    ///that range.
+    pub fn daltonize_matrix<T: Float>(self, severity: T) -> Mat3<T> {
+        let simulation = self.matrix(severity);
+
+        //The lost information, (I - S) * color
+        let mut error = [T::zero(); 9];
+        for (index, (out, &value)) in error.iter_mut().zip(&simulation).enumerate() {
+            let identity = if index % 4 == 0 { T::one() } else { T::zero() };
+            *out = identity - value;
+        }
+
+        let shift = match self {
+            Deficiency::Protan | Deficiency::Deutan => &RED_GREEN_SHIFT,
+            Deficiency::Tritan => &BLUE_YELLOW_SHIFT,
+        };
+        let mut shift_matrix = [T::zero(); 9];
+        for (out, &value) in shift_matrix.iter_mut().zip(shift) {
+            *out = cast(value);
+        }
+
+        //I + shift * (I - S)
+        let mut matrix = multiply_3x3(&shift_matrix, &error);
+        matrix[0] = matrix[0] + T::one();
+        matrix[4] = matrix[4] + T::one();
+        matrix[8] = matrix[8] + T::one();
+
+        matrix
+    }
+
+    ///Daltonize `color`, to make it easier to distinguish with this
+    ///deficiency, at the given `severity`.
+    pub fn daltonize<C, T>(self, color: C, severity: T) -> C
+    where
+        C: IntoColorUnclamped<D65, T> + FromColorUnclamped<D65, T>,
+        T: Component + Float,
+    {
+        apply(&self.daltonize_matrix(severity), color)
+    }
+
+    ///Daltonize each color in `colors`, to make them easier to distinguish
+    ///with this deficiency, at the given `severity`, and store the results in
+    ///`colors`.
+    pub fn daltonize_slice<C, T>(self, colors: &mut [C], severity: T)
+    where
+        C: IntoColorUnclamped<D65, T> + FromColorUnclamped<D65, T> + Clone,
+        T: Component + Float,
+    {
+        let matrix = self.daltonize_matrix(severity);
+
+        for color in colors {
+            *color = apply(&matrix, color.clone());
+        }
+    }
+}
+
+fn apply<C, T>(m: &Mat3<T>, color: C) -> C
+where
+    C: IntoColorUnclamped<D65, T> + FromColorUnclamped<D65, T>,
+    T: Component + Float,
+{
+    let rgb: LinSrgb<T> = color.into_rgb::<Srgb>();
+
+    C::from_rgb(LinSrgb::new(
+        m[0] * rgb.red + m[1] * rgb.green + m[2] * rgb.blue,
+        m[3] * rgb.red + m[4] * rgb.green + m[5] * rgb.blue,
+        m[6] * rgb.red + m[7] * rgb.green + m[8] * rgb.blue,
+    ))
+}
+
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const PROTANOPIA: [f64; 9] = [
+    0.152286, 1.052583, -0.204868,
+    0.114503, 0.786281, 0.099216,
+    -0.003882, -0.048116, 1.051998,
+];
+
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const DEUTERANOPIA: [f64; 9] = [
+    0.367322, 0.860646, -0.227968,
+    0.280085, 0.672501, 0.047413,
+    -0.011820, 0.042940, 0.968881,
+];
+
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const TRITANOPIA: [f64; 9] = [
+    1.255528, -0.076749, -0.178779,
+    -0.078411, 0.930809, 0.147602,
+    0.004733, 0.691367, 0.303900,
+];
+
+//Moves the lost red-green information into green and blue
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const RED_GREEN_SHIFT: [f64; 9] = [
+    0.0, 0.0, 0.0,
+    0.7, 1.0, 0.0,
+    0.7, 0.0, 1.0,
+];
+
+//Moves the lost blue-yellow information into red and green
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const BLUE_YELLOW_SHIFT: [f64; 9] = [
+    1.0, 0.0, 0.7,
+    0.0, 1.0, 0.7,
+    0.0, 0.0, 0.0,
+];
+
+#[cfg(test)]
+mod test {
+    use super::Deficiency;
+    use LinSrgb;
+
+    #[test]
+    fn no_severity() {
+        let color = LinSrgb::new(0.8f64, 0.3, 0.1);
+
+        for &deficiency in &[Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+            assert_relative_eq!(deficiency.simulate(color, 0.0), color);
+        }
+    }
+
+    #[test]
+    fn gray_is_unchanged() {
+        let gray = LinSrgb::new(0.5f64, 0.5, 0.5);
+
+        for &deficiency in &[Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+            for &severity in &[0.3, 0.7, 1.0] {
+                assert_relative_eq!(deficiency.simulate(gray, severity), gray, epsilon = 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn protanopia_red() {
+        let red = LinSrgb::new(1.0f64, 0.0, 0.0);
+
+        assert_relative_eq!(
+            Deficiency::Protan.simulate(red, 1.0),
+            LinSrgb::new(0.152286, 0.114503, -0.003882)
+        );
+        assert_relative_eq!(
+            Deficiency::Protan.simulate(red, 0.5),
+            LinSrgb::new(0.576143, 0.0572515, -0.001941)
+        );
+
+        let mut colors = [red, red];
+        Deficiency::Protan.simulate_slice(&mut colors, 2.0);
+        assert_relative_eq!(colors[1], LinSrgb::new(0.152286, 0.114503, -0.003882));
+    }
+
+    #[test]
+    fn daltonize() {
+        let gray = LinSrgb::new(0.4f64, 0.4, 0.4);
+        let color = LinSrgb::new(0.8f64, 0.3, 0.1);
+
+        for &deficiency in &[Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+            assert_relative_eq!(deficiency.daltonize(color, 0.0), color);
+            assert_relative_eq!(deficiency.daltonize(gray, 1.0), gray, epsilon = 0.001);
+        }
+
+        //The red that a protanope can't see is moved into green and blue
+        let red = LinSrgb::new(1.0f64, 0.0, 0.0);
+        let corrected = Deficiency::Protan.daltonize(red, 1.0);
+        assert_relative_eq!(corrected.red, 1.0);
+        assert_relative_eq!(corrected.green, 0.7 * 0.847714 - 0.114503);
+        assert_relative_eq!(corrected.blue, 0.7 * 0.847714 + 0.003882);
+
+        let mut colors = [red];
+        Deficiency::Protan.daltonize_slice(&mut colors, 1.0);
+        assert_relative_eq!(colors[0], corrected);
+    }
+}
@@ -0,0 +1,306 @@
+//!Components with bit depths that don't have their own integer type.
+//!
+//![`U10`](struct.U10.html) and [`U12`](struct.U12.html) are stored in a
+//!`u16`, but their maximum intensity is the maximum value of 10 and 12 bits.
+//!This puts the bit depth in the type of the color, and makes the `Component`
+//!conversions scale them correctly. The 8 and 16 bit depths are `u8` and
+//!`u16`.
+//!
+//!Values with a bit depth that is only known at runtime can be converted with
+//![`convert_depth`](fn.convert_depth.html) and
+//![`convert_depth_slice`](fn.convert_depth_slice.html).
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::depth::U10;
+//!
+//!let color: Srgb<U10> = Srgb::new(1.0, 0.5, 0.0).into_format();
+//!assert_eq!(color.red, U10::MAX);
+//!
+//!let wide: Srgb<u16> = color.into_format();
+//!assert_eq!(wide.red, 65535);
+//!```
+
+use core::ops::Add;
+
+use num_traits::{NumCast, ToPrimitive, Zero};
+
+use {cast, clamp, Component, ConstComponent};
+
+macro_rules! impl_depth {
+    ($name: ident, $bits: expr, $doc: expr) => {
+        #[doc = $doc]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $name(u16);
+
+        impl $name {
+            ///The highest value of the bit depth.
+            pub const MAX: $name = $name((1 << $bits) - 1);
+
+            ///Create a component, if `value` fits in the bit depth.
+            pub fn new(value: u16) -> Option<$name> {
+                if value <= Self::MAX.0 {
+                    Some($name(value))
+                } else {
+                    None
+                }
+            }
+
+            ///Get the value of the component.
+            pub fn get(self) -> u16 {
+                self.0
+            }
+        }
+
+        impl From<$name> for u16 {
+            fn from(component: $name) -> u16 {
+                component.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name::new(self.0 + other.0).expect("attempt to add with overflow")
+            }
+        }
+
+        impl Zero for $name {
+            fn zero() -> $name {
+                $name(0)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl ToPrimitive for $name {
+            fn to_i64(&self) -> Option<i64> {
+                Some(self.0.into())
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                Some(self.0.into())
+            }
+        }
+
+        impl NumCast for $name {
+            fn from<T: ToPrimitive>(n: T) -> Option<$name> {
+                n.to_u16().and_then($name::new)
+            }
+        }
+
+        impl Component for $name {
+            const LIMITED: bool = true;
+
+            fn max_intensity() -> Self {
+                Self::MAX
+            }
+
+            fn convert<T: Component>(&self) -> T {
+                let scaled = cast::<f64, _>(T::max_intensity())
+                    * (cast::<f64, _>(*self) / cast::<f64, _>(Self::max_intensity()));
+
+                if T::LIMITED {
+                    cast(clamp(scaled, 0.0, cast(T::max_intensity())))
+                } else {
+                    cast(scaled)
+                }
+            }
+        }
+
+        impl ConstComponent for $name {
+            const ZERO: Self = $name(0);
+            const MAX_INTENSITY: Self = Self::MAX;
+        }
+    };
+}
+
+impl_depth!(U10, 10, "A 10 bit component, stored in a `u16`.");
+impl_depth!(U12, 12, "A 12 bit component, stored in a `u16`.");
+
+///How values are converted from one bit depth to another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Scaling {
+    ///Map the highest value of one depth to the highest value of the other,
+    ///like the `Component` conversions. 8 bit `255` becomes 10 bit `1023`.
+    Rescale,
+
+    ///Multiply or divide by a power of two, like video formats do. This
+    ///keeps limited range values in their range, so 8 bit `235` becomes 10
+    ///bit `940`, but 8 bit `255` becomes 10 bit `1020`. Values that would
+    ///round past the highest value of the new depth are clamped.
+    Shift,
+}
+
+///Convert `value` from `from` bits to `to` bits, rounded to the nearest
+///value.
+///
+///```
+///use palette::depth::{convert_depth, Scaling};
+///
+///assert_eq!(convert_depth(255, 8, 10, Scaling::Rescale), 1023);
+///assert_eq!(convert_depth(255, 8, 10, Scaling::Shift), 1020);
+///assert_eq!(convert_depth(940, 10, 8, Scaling::Shift), 235);
+///assert_eq!(convert_depth(512, 10, 8, Scaling::Rescale), 128);
+///```
+///
+///# Panics
+///
+///Panics if either depth is not between 1 and 16 bits, or if `value` doesn't
+///fit in `from` bits.
+pub fn convert_depth(value: u16, from: u32, to: u32, scaling: Scaling) -> u16 {
+    assert_depths(from, to);
+    assert!(
+        (value as u32) < 1 << from,
+        "the value must fit in the bit depth"
+    );
+
+    convert_unchecked(value, from, to, scaling)
+}
+
+///Convert the values in `input` from `from` bits to `to` bits, rounded to
+///the nearest value, and write them to `output`.
+///
+///# Panics
+///
+///Panics if the slices don't have the same length, if either depth is not
+///between 1 and 16 bits, or if a value doesn't fit in `from` bits.
+pub fn convert_depth_slice(
+    input: &[u16],
+    output: &mut [u16],
+    from: u32,
+    to: u32,
+    scaling: Scaling,
+) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the slices must have the same length"
+    );
+    assert_depths(from, to);
+
+    for (&value, output) in input.iter().zip(output) {
+        assert!(
+            (value as u32) < 1 << from,
+            "the value must fit in the bit depth"
+        );
+        *output = convert_unchecked(value, from, to, scaling);
+    }
+}
+
+fn assert_depths(from: u32, to: u32) {
+    assert!(
+        (1..=16).contains(&from) && (1..=16).contains(&to),
+        "the bit depth must be between 1 and 16 bits"
+    );
+}
+
+fn convert_unchecked(value: u16, from: u32, to: u32, scaling: Scaling) -> u16 {
+    let value = value as u64;
+    let from_max = (1u64 << from) - 1;
+    let to_max = (1u64 << to) - 1;
+
+    let converted = match scaling {
+        Scaling::Rescale => (value * to_max * 2 + from_max) / (from_max * 2),
+        Scaling::Shift if to >= from => value << (to - from),
+        Scaling::Shift => {
+            let shift = from - to;
+            ::core::cmp::min((value + (1 << (shift - 1))) >> shift, to_max)
+        }
+    };
+
+    converted as u16
+}
+
+#[cfg(test)]
+mod test {
+    use super::{convert_depth, convert_depth_slice, Scaling, U10, U12};
+    use num_traits::NumCast;
+    use rgb::Rgb;
+    use {Component, Pixel, Srgb};
+
+    #[test]
+    fn new() {
+        assert_eq!(U10::new(1023), Some(U10::MAX));
+        assert_eq!(U10::new(1024), None);
+        assert_eq!(U12::new(4095).map(U12::get), Some(4095));
+        assert_eq!(<U10 as NumCast>::from(2000.0), None);
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(U10::MAX.convert::<f32>(), 1.0);
+        assert_eq!(U12::MAX.convert::<u8>(), 255);
+        assert_eq!(U12::MAX.convert::<U10>(), U10::MAX);
+        assert_eq!(255u8.convert::<U12>(), U12::MAX);
+        assert_eq!(0.5f32.convert::<U10>(), U10::new(511).unwrap());
+        assert_eq!(2.0f32.convert::<U10>(), U10::MAX);
+    }
+
+    #[test]
+    fn colors() {
+        let color: Srgb<U12> = Srgb::new(1.0, 0.0, 0.25).into_format();
+        assert_eq!(color.blue, U12::new(1023).unwrap());
+
+        let raw = [U10::MAX, U10::new(0).unwrap(), U10::new(512).unwrap()];
+        let color: &Rgb<::encoding::Srgb, U10> = Pixel::from_raw(&raw);
+        assert_eq!(color.into_format(), Srgb::new(1.0, 0.0, 512.0 / 1023.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_overflow() {
+        let _ = U10::MAX + U10::new(1).unwrap();
+    }
+
+    #[test]
+    fn rescale() {
+        for &(from, to) in &[(8, 16), (16, 8), (10, 8), (8, 10), (12, 10), (1, 8)] {
+            let from_max = ((1u32 << from) - 1) as f64;
+            let to_max = ((1u32 << to) - 1) as f64;
+
+            for value in 0..(1u32 << from) {
+                assert_eq!(
+                    convert_depth(value as u16, from, to, Scaling::Rescale) as f64,
+                    (value as f64 * to_max / from_max).round()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shift() {
+        assert_eq!(convert_depth(16, 8, 10, Scaling::Shift), 64);
+        assert_eq!(convert_depth(235, 8, 12, Scaling::Shift), 3760);
+        assert_eq!(convert_depth(64, 10, 8, Scaling::Shift), 16);
+        assert_eq!(convert_depth(1, 10, 8, Scaling::Shift), 0);
+        assert_eq!(convert_depth(2, 10, 8, Scaling::Shift), 1);
+        assert_eq!(convert_depth(1023, 10, 8, Scaling::Shift), 255);
+        assert_eq!(convert_depth(4095, 12, 12, Scaling::Shift), 4095);
+
+        for value in 0..256 {
+            let wide = convert_depth(value, 8, 16, Scaling::Shift);
+            assert_eq!(convert_depth(wide, 16, 8, Scaling::Shift), value);
+        }
+    }
+
+    #[test]
+    fn slices() {
+        let mut output = [0; 3];
+        convert_depth_slice(&[0, 512, 1023], &mut output, 10, 8, Scaling::Rescale);
+        assert_eq!(output, [0, 128, 255]);
+
+        convert_depth_slice(&[0, 512, 1023], &mut output, 10, 12, Scaling::Shift);
+        assert_eq!(output, [0, 2048, 4092]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value must fit in the bit depth")]
+    fn depth_overflow() {
+        convert_depth(1024, 10, 16, Scaling::Rescale);
+    }
+}
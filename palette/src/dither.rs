@@ -0,0 +1,485 @@
+//!Dithering, for hiding banding when colors are quantized to fewer bits.
+//!
+//!Smooth, dark color ramps tend to show visible steps when they are stored
+//!with 8 bits per component. Dithering adds a small, position dependent
+//!offset before rounding, which turns the steps into a fine pattern that's
+//!much less noticeable.
+//!
+//!The patterns can also reduce colors to fewer bits with
+//![`quantize_depth`](enum.Dither.html#method.quantize_depth), or map them to
+//!the colors of a palette with
+//![`palette_index`](enum.Dither.html#method.palette_index). Each pixel only
+//!depends on its own position, so the results are deterministic and the
+//!ordered patterns tile seamlessly.
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::dither::Dither;
+//!
+//!let color = Srgb::new(0.1015, 0.1015, 0.1015);
+//!
+//!let row: Vec<Srgb<u8>> = (0..8).map(|x| Dither::Ordered.quantize(color, x, 0)).collect();
+//!assert!(row.iter().any(|c| c.red == 25) && row.iter().any(|c| c.red == 26));
+//!```
+
+use float::Float;
+
+use rgb::{Rgb, RgbStandard};
+use {cast, clamp, Component, Pixel};
+
+///A dithering pattern.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dither {
+    ///An 8x8 Bayer matrix. It's deterministic and tiles seamlessly.
+    Ordered,
+    ///A 2x2 Bayer matrix, with only four levels and a coarse pattern.
+    Ordered2x2,
+    ///A 4x4 Bayer matrix, with sixteen levels.
+    Ordered4x4,
+    ///Interleaved gradient noise, which approximates blue noise. It has less
+    ///of a visible structure than the ordered pattern.
+    BlueNoise,
+    ///A 32x32 blue noise texture, made with the void-and-cluster method. It
+    ///tiles seamlessly like the ordered patterns, without their visible
+    ///structure.
+    ///
+    ///The texture is only included with the `"blue-noise"` feature. Without
+    ///it, this is the same as `BlueNoise`.
+    BlueNoiseTexture,
+}
+
+impl Dither {
+    ///Get the threshold for the pixel at `(x, y)`, in the range `[0.0, 1.0)`.
+    pub fn threshold<T: Float>(self, x: usize, y: usize) -> T {
+        match self {
+            Dither::Ordered => level(BAYER_8X8[y % 8][x % 8], 64),
+            Dither::Ordered2x2 => level(BAYER_2X2[y % 2][x % 2], 4),
+            Dither::Ordered4x4 => level(BAYER_4X4[y % 4][x % 4], 16),
+            Dither::BlueNoise => interleaved_gradient_noise(x, y),
+            #[cfg(feature = "blue-noise")]
+            Dither::BlueNoiseTexture => level(BLUE_NOISE_32X32[y % 32][x % 32], 1024),
+            #[cfg(not(feature = "blue-noise"))]
+            Dither::BlueNoiseTexture => interleaved_gradient_noise(x, y),
+        }
+    }
+
+    ///Quantize a component value in the range `[0.0, 1.0]` to 8 bits, with
+    ///the threshold for the pixel at `(x, y)`.
+    pub fn quantize_component<T: Float>(self, value: T, x: usize, y: usize) -> u8 {
+        self.quantize_depth(value, 8, x, y) as u8
+    }
+
+    ///Quantize a component value in the range `[0.0, 1.0]` to `bits` bits,
+    ///with the threshold for the pixel at `(x, y)`. This reduces the bit
+    ///depth without banding, for example from 16 or 10 bits to 8 bits, or
+    ///from 8 bits to the 5 or 6 bits of a `Rgb565` display.
+    ///
+    ///```
+    ///use palette::dither::Dither;
+    ///
+    ///let row: Vec<u16> = (0..4)
+    ///    .map(|x| Dither::Ordered2x2.quantize_depth(0.5f32, 1, x, 0))
+    ///    .collect();
+    ///assert_eq!(row, [0, 1, 0, 1]);
+    ///```
+    ///
+    ///Panics if `bits` is not between 1 and 16.
+    pub fn quantize_depth<T: Float>(self, value: T, bits: u32, x: usize, y: usize) -> u16 {
+        assert!(
+            (1..=16).contains(&bits),
+            "the bit depth must be between 1 and 16 bits"
+        );
+
+        let max: T = cast((1u32 << bits) - 1);
+        let scaled = clamp(value, T::zero(), T::one()) * max;
+        let quantized = (scaled + self.threshold(x, y)).floor();
+
+        cast(quantized.min(max))
+    }
+
+    ///Find the index of the color in `palette` that is closest to `color`,
+    ///after offsetting its components by the threshold for the pixel at
+    ///`(x, y)`.
+    ///
+    ///The threshold is scaled by `spread`, which should be about the
+    ///distance between neighboring colors of the palette, such as `1.0 /
+    ///(n - 1)` for a palette with `n` evenly spaced levels of each
+    ///component. The components are compared in the color's own space, so it
+    ///should be one where the components have similar scales, such as RGB.
+    ///Unlike error diffusion, each pixel is mapped on its own, so the result
+    ///is deterministic and tiles seamlessly.
+    ///
+    ///```
+    ///use palette::Srgb;
+    ///use palette::dither::Dither;
+    ///
+    ///let palette = [Srgb::new(0.0f32, 0.0, 0.0), Srgb::new(1.0, 1.0, 1.0)];
+    ///let gray = Srgb::new(0.5, 0.5, 0.5);
+    ///
+    ///let mut white = 0;
+    ///for y in 0..4 {
+    ///    for x in 0..4 {
+    ///        white += Dither::Ordered4x4.palette_index(&gray, &palette, 1.0, x, y);
+    ///    }
+    ///}
+    ///assert_eq!(white, 8);
+    ///```
+    ///
+    ///Panics if `palette` is empty.
+    pub fn palette_index<C, T>(
+        self,
+        color: &C,
+        palette: &[C],
+        spread: T,
+        x: usize,
+        y: usize,
+    ) -> usize
+    where
+        C: Pixel<T>,
+        T: Float,
+    {
+        assert!(!palette.is_empty(), "the palette must not be empty");
+
+        let offset = (self.threshold::<T>(x, y) - cast(0.5)) * spread;
+        let color = color.as_raw::<[T]>();
+
+        let mut closest = 0;
+        let mut closest_distance = T::infinity();
+        for (index, candidate) in palette.iter().enumerate() {
+            let distance = color.iter().zip(candidate.as_raw::<[T]>()).fold(
+                T::zero(),
+                |sum, (&value, &candidate)| {
+                    let difference = value + offset - candidate;
+                    sum + difference * difference
+                },
+            );
+            if distance < closest_distance {
+                closest = index;
+                closest_distance = distance;
+            }
+        }
+
+        closest
+    }
+
+    ///Quantize a color to 8 bits per component, with the threshold for the
+    ///pixel at `(x, y)`. The components are quantized in the color's own
+    ///encoding.
+    pub fn quantize<S, T>(self, color: Rgb<S, T>, x: usize, y: usize) -> Rgb<S, u8>
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        Rgb::new(
+            self.quantize_component(color.red, x, y),
+            self.quantize_component(color.green, x, y),
+            self.quantize_component(color.blue, x, y),
+        )
+    }
+}
+
+///The threshold in the middle of `level` out of `levels`.
+fn level<T: Float, L: Into<u32>>(level: L, levels: u32) -> T {
+    (cast::<T, _>(level.into()) + cast(0.5)) / cast(levels)
+}
+
+///Jorge Jimenez' interleaved gradient noise.
+fn interleaved_gradient_noise<T: Float>(x: usize, y: usize) -> T {
+    let x: T = cast(x % 4096);
+    let y: T = cast(y % 4096);
+    let value = x * cast(0.06711056) + y * cast(0.00583715);
+    (value.fract() * cast(52.9829189)).fract()
+}
+
+const BAYER_2X2: [[u8; 2]; 2] = [[0, 2], [3, 1]];
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+#[cfg(feature = "blue-noise")]
+const BLUE_NOISE_32X32: [[u16; 32]; 32] = [
+    [
+        229, 354, 101, 303, 909, 38, 513, 645, 415, 23, 858, 121, 990, 518, 724, 356, 811, 25, 534,
+        939, 253, 479, 41, 287, 623, 503, 4, 743, 966, 390, 279, 21,
+    ],
+    [
+        689, 851, 758, 570, 152, 772, 264, 971, 172, 786, 473, 669, 188, 593, 258, 102, 1001, 685,
+        184, 780, 113, 568, 751, 905, 138, 1023, 203, 434, 553, 148, 803, 492,
+    ],
+    [
+        420, 164, 497, 973, 382, 607, 870, 344, 715, 557, 305, 912, 380, 825, 936, 488, 615, 286,
+        463, 385, 637, 959, 208, 449, 359, 799, 596, 854, 319, 901, 612, 983,
+    ],
+    [
+        70, 906, 271, 6, 682, 218, 54, 501, 96, 997, 224, 80, 734, 8, 424, 169, 791, 60, 919, 838,
+        19, 314, 820, 546, 53, 709, 277, 95, 691, 36, 214, 332,
+    ],
+    [
+        644, 558, 742, 455, 832, 954, 435, 666, 816, 396, 628, 806, 533, 318, 700, 897, 375, 556,
+        693, 225, 514, 726, 127, 650, 975, 175, 478, 935, 393, 537, 765, 874,
+    ],
+    [
+        400, 204, 1018, 334, 118, 567, 302, 902, 244, 137, 934, 436, 196, 1017, 634, 116, 238, 962,
+        88, 348, 1012, 411, 888, 270, 397, 860, 575, 778, 237, 991, 467, 140,
+    ],
+    [
+        837, 672, 83, 526, 785, 178, 741, 69, 522, 763, 324, 576, 106, 844, 281, 505, 771, 432,
+        641, 853, 144, 598, 71, 490, 752, 15, 329, 135, 657, 59, 727, 294,
+    ],
+    [
+        27, 447, 879, 246, 949, 405, 599, 1006, 416, 652, 43, 885, 681, 454, 31, 595, 866, 176,
+        284, 532, 767, 242, 706, 957, 191, 640, 1011, 443, 890, 355, 597, 944,
+    ],
+    [
+        762, 578, 349, 714, 639, 16, 231, 339, 845, 193, 984, 261, 365, 789, 977, 336, 78, 717,
+        988, 9, 452, 928, 363, 560, 301, 819, 525, 212, 807, 115, 515, 195,
+    ],
+    [
+        262, 926, 97, 163, 461, 914, 793, 665, 77, 561, 481, 737, 150, 538, 228, 675, 921, 482,
+        369, 810, 664, 186, 46, 875, 123, 401, 64, 732, 290, 646, 980, 412,
+    ],
+    [
+        687, 499, 805, 1000, 268, 536, 133, 426, 950, 306, 809, 2, 638, 916, 99, 413, 569, 128,
+        226, 586, 298, 849, 422, 655, 754, 970, 602, 924, 475, 1, 855, 132,
+    ],
+    [
+        316, 34, 377, 616, 750, 330, 883, 711, 165, 619, 392, 963, 450, 312, 847, 755, 276, 831,
+        694, 938, 90, 540, 1003, 267, 480, 200, 338, 141, 695, 367, 566, 790,
+    ],
+    [
+        965, 549, 892, 202, 52, 587, 87, 487, 273, 899, 91, 235, 722, 173, 610, 24, 509, 1022, 55,
+        464, 357, 768, 143, 621, 17, 834, 552, 884, 250, 1007, 190, 459,
+    ],
+    [
+        241, 108, 696, 438, 967, 801, 386, 1014, 761, 541, 684, 857, 486, 996, 372, 889, 199, 402,
+        631, 177, 821, 234, 708, 387, 942, 728, 433, 45, 775, 624, 66, 725,
+    ],
+    [
+        881, 643, 828, 275, 517, 157, 659, 220, 20, 419, 155, 331, 48, 574, 130, 663, 787, 320,
+        738, 898, 494, 961, 73, 535, 291, 180, 658, 313, 520, 407, 932, 346,
+    ],
+    [
+        483, 171, 361, 7, 720, 925, 317, 852, 609, 945, 784, 524, 913, 748, 282, 472, 933, 110,
+        554, 5, 299, 604, 428, 759, 848, 109, 998, 894, 207, 826, 131, 588,
+    ],
+    [
+        56, 1021, 564, 896, 414, 93, 559, 466, 122, 360, 265, 674, 210, 406, 823, 35, 591, 263,
+        982, 409, 688, 158, 880, 219, 613, 489, 376, 590, 85, 677, 296, 764,
+    ],
+    [
+        248, 425, 777, 205, 617, 798, 254, 992, 654, 827, 37, 1005, 104, 620, 960, 187, 712, 440,
+        839, 227, 797, 1013, 40, 328, 951, 61, 782, 266, 465, 987, 542, 862,
+    ],
+    [
+        713, 635, 315, 125, 969, 352, 29, 735, 167, 410, 572, 477, 872, 353, 521, 310, 895, 134,
+        626, 75, 519, 364, 562, 730, 421, 679, 166, 868, 736, 10, 391, 146,
+    ],
+    [
+        943, 33, 836, 531, 697, 460, 886, 527, 325, 948, 757, 293, 153, 718, 50, 781, 543, 350,
+        955, 746, 272, 668, 114, 864, 257, 544, 972, 321, 627, 201, 900, 500,
+    ],
+    [
+        347, 457, 211, 911, 65, 233, 608, 112, 841, 216, 67, 636, 815, 439, 989, 230, 661, 26, 468,
+        183, 833, 437, 929, 189, 804, 30, 394, 100, 453, 788, 280, 606,
+    ],
+    [
+        119, 994, 649, 323, 779, 398, 979, 673, 441, 710, 507, 915, 236, 582, 89, 399, 813, 908,
+        601, 322, 995, 12, 583, 307, 485, 642, 893, 571, 703, 1008, 49, 817,
+    ],
+    [
+        245, 744, 498, 151, 563, 867, 181, 309, 14, 1015, 384, 117, 341, 941, 701, 495, 142, 252,
+        723, 84, 504, 776, 383, 707, 981, 160, 773, 240, 124, 362, 545, 671,
+    ],
+    [
+        887, 379, 0, 937, 692, 82, 511, 812, 585, 259, 859, 774, 551, 182, 850, 300, 1019, 548,
+        373, 861, 632, 239, 129, 877, 63, 445, 342, 947, 506, 876, 170, 430,
+    ],
+    [
+        92, 579, 842, 442, 274, 366, 731, 922, 470, 162, 611, 62, 686, 444, 42, 633, 756, 94, 930,
+        159, 427, 964, 660, 530, 283, 745, 630, 13, 808, 288, 733, 953,
+    ],
+    [
+        215, 311, 766, 168, 1002, 622, 206, 51, 343, 704, 952, 404, 278, 794, 958, 381, 223, 458,
+        676, 285, 818, 47, 345, 796, 185, 1016, 550, 222, 678, 474, 44, 625,
+    ],
+    [
+        523, 985, 667, 491, 98, 829, 423, 769, 978, 105, 508, 198, 907, 529, 145, 592, 878, 22,
+        770, 581, 516, 209, 920, 603, 388, 79, 835, 418, 139, 974, 374, 824,
+    ],
+    [
+        126, 358, 28, 249, 577, 903, 295, 539, 629, 251, 760, 856, 3, 351, 740, 260, 502, 976, 333,
+        120, 1004, 683, 451, 111, 882, 484, 699, 308, 869, 589, 247, 716,
+    ],
+    [
+        456, 873, 739, 931, 378, 690, 154, 18, 814, 371, 446, 647, 555, 1009, 103, 830, 662, 174,
+        417, 802, 269, 11, 753, 304, 653, 243, 940, 32, 749, 512, 68, 927,
+    ],
+    [
+        197, 600, 292, 528, 76, 783, 476, 1020, 194, 891, 72, 161, 297, 698, 471, 389, 57, 918,
+        614, 496, 904, 370, 840, 510, 986, 147, 573, 368, 192, 999, 326, 656,
+    ],
+    [
+        403, 58, 822, 179, 968, 232, 594, 327, 680, 493, 729, 946, 800, 221, 618, 956, 289, 747,
+        213, 81, 648, 149, 584, 217, 74, 792, 448, 670, 846, 469, 136, 795,
+    ],
+    [
+        547, 1010, 651, 462, 705, 395, 843, 107, 923, 255, 580, 340, 431, 39, 871, 156, 565, 429,
+        863, 337, 719, 993, 408, 865, 702, 335, 910, 256, 86, 605, 721, 917,
+    ],
+];
+
+#[cfg(test)]
+mod test {
+    use super::Dither;
+    use Srgb;
+
+    const DITHERS: &[Dither] = &[
+        Dither::Ordered,
+        Dither::Ordered2x2,
+        Dither::Ordered4x4,
+        Dither::BlueNoise,
+        Dither::BlueNoiseTexture,
+    ];
+
+    #[test]
+    fn thresholds_in_range() {
+        for &dither in DITHERS {
+            for y in 0..32 {
+                for x in 0..32 {
+                    let threshold: f64 = dither.threshold(x, y);
+                    assert!((0.0..1.0).contains(&threshold));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_average() {
+        //The average of a whole tile should be the unquantized value
+        for &(dither, size) in &[
+            (Dither::Ordered, 8),
+            (Dither::Ordered2x2, 2),
+            (Dither::Ordered4x4, 4),
+        ] {
+            for &value in &[0.1, 0.25, 0.5004, 0.9] {
+                let mut sum = 0.0;
+                for y in 0..size {
+                    for x in 0..size {
+                        sum += dither.quantize_component(value, x, y) as f64;
+                    }
+                }
+
+                let count = (size * size) as f64;
+                assert!((sum / count - value * 255.0).abs() <= 0.5 / count + 1e-9);
+            }
+        }
+    }
+
+    #[cfg(feature = "blue-noise")]
+    #[test]
+    fn blue_noise_texture() {
+        //Each level is used once
+        let mut used = [false; 1024];
+        for row in super::BLUE_NOISE_32X32.iter() {
+            for &level in row.iter() {
+                assert!(!used[level as usize]);
+                used[level as usize] = true;
+            }
+        }
+
+        //Any threshold is spread evenly over the texture
+        let mut quadrants = [0; 4];
+        for y in 0..32 {
+            for x in 0..32 {
+                if Dither::BlueNoiseTexture.quantize_depth(0.25f64, 1, x, y) == 1 {
+                    quadrants[(y / 16) * 2 + x / 16] += 1;
+                }
+            }
+        }
+        for &count in &quadrants {
+            assert!((56..=72).contains(&count));
+        }
+    }
+
+    #[cfg(not(feature = "blue-noise"))]
+    #[test]
+    fn blue_noise_texture_fallback() {
+        for y in 0..32 {
+            for x in 0..32 {
+                let texture: f64 = Dither::BlueNoiseTexture.threshold(x, y);
+                let noise: f64 = Dither::BlueNoise.threshold(x, y);
+                assert_eq!(texture, noise);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_values() {
+        let color = Srgb::new(0.0, 1.0, 128.0 / 255.0);
+
+        for &dither in DITHERS {
+            for x in 0..16 {
+                assert_eq!(dither.quantize(color, x, 3), Srgb::new(0, 255, 128));
+            }
+        }
+    }
+
+    #[test]
+    fn depth() {
+        for &dither in DITHERS {
+            for x in 0..16 {
+                assert_eq!(dither.quantize_depth(1.0f32, 5, x, 7), 31);
+                assert_eq!(dither.quantize_depth(0.0f32, 5, x, 7), 0);
+                assert_eq!(dither.quantize_depth(2.0 / 3.0f32, 2, x, 7), 2);
+                assert_eq!(dither.quantize_depth(1.0f32, 16, x, 7), 65535);
+            }
+        }
+
+        //The average of a tile is the unquantized value
+        let mut sum = 0;
+        for y in 0..4 {
+            for x in 0..4 {
+                sum += Dither::Ordered4x4.quantize_depth(0.3f64, 3, x, y);
+            }
+        }
+        assert_eq!(sum, 34);
+    }
+
+    #[test]
+    fn palette_index() {
+        let palette = [
+            Srgb::new(0.0, 0.0, 0.0),
+            Srgb::new(0.5, 0.5, 0.5),
+            Srgb::new(1.0, 1.0, 1.0),
+        ];
+
+        for &dither in DITHERS {
+            for y in 0..8 {
+                for x in 0..8 {
+                    for (index, color) in palette.iter().enumerate() {
+                        assert_eq!(dither.palette_index(color, &palette, 0.4f64, x, y), index);
+                    }
+                }
+            }
+        }
+
+        let color = Srgb::new(0.75, 0.75, 0.75);
+        let mut sum = 0;
+        for y in 0..8 {
+            for x in 0..8 {
+                sum += Dither::Ordered.palette_index(&color, &palette, 0.5, x, y);
+            }
+        }
+        assert_eq!(sum, 32 + 64);
+    }
+}
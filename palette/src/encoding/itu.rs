@@ -4,7 +4,7 @@ use float::Float;
 use encoding::TransferFn;
 use rgb::{Primaries, RgbSpace, RgbStandard};
 use luma::LumaStandard;
-use yuv::{DifferenceFn, YuvStandard};
+use yuv::{DifferenceFn, IntegerDifferenceFn, YuvStandard};
 use white_point::{D65, WhitePoint};
 use {cast, Component, Yxy};
 
@@ -32,10 +32,33 @@ pub struct BT601_625;
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct BT709;
 
-/// This transfer function is shared between `BT601` and `BT709`.
+/// The color space of ITU-R BT2020, for ultra high definition video.
+///
+/// See [ITU-R Rec.2020].
+///
+/// [ITU-R Rec.2020]: https://www.itu.int/rec/R-REC-BT.2020/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BT2020;
+
+/// The `BT2020` color space with the perceptual quantizer of ITU-R BT2100, as used for HDR10.
+///
+/// See [ITU-R Rec.2100].
+///
+/// [ITU-R Rec.2100]: https://www.itu.int/rec/R-REC-BT.2100/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BT2100Pq;
+
+/// This transfer function is shared between `BT601`, `BT709` and `BT2020`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Transfer601And709;
 
+/// The perceptual quantizer of SMPTE ST 2084 and ITU-R BT2100.
+///
+/// Unlike the other transfer functions, it encodes absolute luminance. The linear value `1.0` is
+/// `10000` cd/m², and a diffuse white is usually placed at `203` cd/m², or `0.0203`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TransferPq;
+
 /// The Yuv encoding difference functions for BT601.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct DifferenceFn601;
@@ -44,6 +67,10 @@ pub struct DifferenceFn601;
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct DifferenceFn709;
 
+/// The Yuv encoding difference functions for BT2020 and BT2100.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DifferenceFn2020;
+
 // See 2.5.1 (page 2). RGB primary luminances.
 const BT601_LUMINANCE: (f64, f64, f64) = (0.2990, 0.5870, 0.1140);
 // Divisor to renormalize the blue difference signal.
@@ -61,6 +88,20 @@ const BT709_BLUE_NORM: f64 = 1.8556;
 // Divisor to renormalize the red difference signal.
 const BT709_RED_NORM: f64 = 1.5748;
 
+// See table 4 (page 4). RGB primary luminances.
+const BT2020_LUMINANCE: (f64, f64, f64) = (0.2627, 0.6780, 0.0593);
+// Divisor to renormalize the blue difference signal.
+const BT2020_BLUE_NORM: f64 = 1.8814;
+// Divisor to renormalize the red difference signal.
+const BT2020_RED_NORM: f64 = 1.4746;
+
+// Constants of the perceptual quantizer. See table 4 of BT2100.
+const PQ_M1: f64 = 0.1593017578125;
+const PQ_M2: f64 = 78.84375;
+const PQ_C1: f64 = 0.8359375;
+const PQ_C2: f64 = 18.8515625;
+const PQ_C3: f64 = 18.6875;
+
 impl Primaries for BT601_525 {
     fn red<Wp: WhitePoint, T: Component + Float>() -> Yxy<Wp, T> {
         Yxy::with_wp(cast(0.6300), cast(0.3400), cast(BT601_LUMINANCE.0))
@@ -97,6 +138,18 @@ impl Primaries for BT709 {
     }
 }
 
+impl Primaries for BT2020 {
+    fn red<Wp: WhitePoint, T: Component + Float>() -> Yxy<Wp, T> {
+        Yxy::with_wp(cast(0.7080), cast(0.2920), cast(BT2020_LUMINANCE.0))
+    }
+    fn green<Wp: WhitePoint, T: Component + Float>() -> Yxy<Wp, T> {
+        Yxy::with_wp(cast(0.1700), cast(0.7970), cast(BT2020_LUMINANCE.1))
+    }
+    fn blue<Wp: WhitePoint, T: Component + Float>() -> Yxy<Wp, T> {
+        Yxy::with_wp(cast(0.1310), cast(0.0460), cast(BT2020_LUMINANCE.2))
+    }
+}
+
 impl RgbSpace for BT601_525 {
     type Primaries = BT601_525;
     type WhitePoint = D65;
@@ -112,6 +165,11 @@ impl RgbSpace for BT709 {
     type WhitePoint = D65;
 }
 
+impl RgbSpace for BT2020 {
+    type Primaries = BT2020;
+    type WhitePoint = D65;
+}
+
 impl RgbStandard for BT601_525 {
     type Space = BT601_525;
     type TransferFn = Transfer601And709;
@@ -127,6 +185,16 @@ impl RgbStandard for BT709 {
     type TransferFn = Transfer601And709;
 }
 
+impl RgbStandard for BT2020 {
+    type Space = BT2020;
+    type TransferFn = Transfer601And709;
+}
+
+impl RgbStandard for BT2100Pq {
+    type Space = BT2020;
+    type TransferFn = TransferPq;
+}
+
 impl LumaStandard for BT601_525 {
     type WhitePoint = D65;
     type TransferFn = Transfer601And709;
@@ -142,6 +210,16 @@ impl LumaStandard for BT709 {
     type TransferFn = Transfer601And709;
 }
 
+impl LumaStandard for BT2020 {
+    type WhitePoint = D65;
+    type TransferFn = Transfer601And709;
+}
+
+impl LumaStandard for BT2100Pq {
+    type WhitePoint = D65;
+    type TransferFn = TransferPq;
+}
+
 impl YuvStandard for BT601_525 {
     type RgbSpace = Self;
     type TransferFn = Transfer601And709;
@@ -160,6 +238,18 @@ impl YuvStandard for BT709 {
     type DifferenceFn = DifferenceFn709;
 }
 
+impl YuvStandard for BT2020 {
+    type RgbSpace = BT2020;
+    type TransferFn = Transfer601And709;
+    type DifferenceFn = DifferenceFn2020;
+}
+
+impl YuvStandard for BT2100Pq {
+    type RgbSpace = BT2020;
+    type TransferFn = TransferPq;
+    type DifferenceFn = DifferenceFn2020;
+}
+
 impl TransferFn for Transfer601And709 {
     fn into_linear<T: Float>(x: T) -> T {
         if x <= cast(0.0091) {
@@ -178,6 +268,23 @@ impl TransferFn for Transfer601And709 {
     }
 }
 
+impl TransferFn for TransferPq {
+    fn into_linear<T: Float>(x: T) -> T {
+        let x = x.max(T::zero()).powf(T::one() / cast(PQ_M2));
+        let numerator = (x - cast(PQ_C1)).max(T::zero());
+        let denominator = cast::<T, _>(PQ_C2) - cast::<T, _>(PQ_C3) * x;
+        (numerator / denominator).powf(T::one() / cast(PQ_M1))
+    }
+
+    fn from_linear<T: Float>(x: T) -> T {
+        // Negative luminance can't be encoded.
+        let x = x.max(T::zero()).powf(cast(PQ_M1));
+        let numerator = cast::<T, _>(PQ_C1) + cast::<T, _>(PQ_C2) * x;
+        let denominator = T::one() + cast::<T, _>(PQ_C3) * x;
+        (numerator / denominator).powf(cast(PQ_M2))
+    }
+}
+
 impl DifferenceFn for DifferenceFn601 {
     fn luminance<T: Float>() -> [T; 3] {
         // Full intensity matches whitepoint, these are exactly the Y component of primares.
@@ -226,6 +333,86 @@ impl DifferenceFn for DifferenceFn709 {
     }
 }
 
+impl DifferenceFn for DifferenceFn2020 {
+    fn luminance<T: Float>() -> [T; 3] {
+        // Full intensity matches whitepoint, these are exactly the Y component of primares.
+        let (r, g, b) = BT2020_LUMINANCE;
+        [cast(r), cast(g), cast(b)]
+    }
+
+    fn normalize_blue<T: Float>(denorm: T) -> T {
+        denorm / cast(BT2020_BLUE_NORM)
+    }
+
+    fn denormalize_blue<T: Float>(norm: T) -> T {
+        norm * cast(BT2020_BLUE_NORM)
+    }
+
+    fn normalize_red<T: Float>(denorm: T) -> T {
+        denorm / cast(BT2020_RED_NORM)
+    }
+
+    fn denormalize_red<T: Float>(norm: T) -> T {
+        norm * cast(BT2020_RED_NORM)
+    }
+}
+
+// The weights of `DifferenceFn601`, rounded to 16 fractional bits.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+impl IntegerDifferenceFn for DifferenceFn601 {
+    const INTO_FULL: [i32; 9] = [
+        19595, 38470, 7471,
+        -11058, -21710, 32768,
+        32768, -27439, -5329,
+    ];
+
+    const INTO_LIMITED: [i32; 9] = [
+        16829, 33039, 6416,
+        -9714, -19071, 28784,
+        28784, -24103, -4681,
+    ];
+
+    const FROM_FULL: [i32; 9] = [
+        65536, 0, 91881,
+        65536, -22553, -46802,
+        65536, 116130, 0,
+    ];
+
+    const FROM_LIMITED: [i32; 9] = [
+        76309, 0, 104597,
+        76309, -25675, -53279,
+        76309, 132201, 0,
+    ];
+}
+
+// The weights of `DifferenceFn709`, rounded to 16 fractional bits.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+impl IntegerDifferenceFn for DifferenceFn709 {
+    const INTO_FULL: [i32; 9] = [
+        13933, 46871, 4726,
+        -7509, -25259, 32771,
+        32768, -29763, -3001,
+    ];
+
+    const INTO_LIMITED: [i32; 9] = [
+        11966, 40254, 4059,
+        -6596, -22189, 28787,
+        28784, -26145, -2636,
+    ];
+
+    const FROM_FULL: [i32; 9] = [
+        65536, 0, 103206,
+        65543, -12263, -30679,
+        65536, 121609, 0,
+    ];
+
+    const FROM_LIMITED: [i32; 9] = [
+        76309, 0, 117489,
+        76318, -13960, -34925,
+        76309, 138438, 0,
+    ];
+}
+
 /// Forwards to the `DifferenceFn` of the yuv standard.
 impl DifferenceFn for BT601_525 {
     fn luminance<T: Float>() -> [T; 3] {
@@ -294,3 +481,26 @@ impl DifferenceFn for BT709 {
         DifferenceFn709::denormalize_red(norm)
     }
 }
+
+/// Forwards to the `DifferenceFn` of the yuv standard.
+impl DifferenceFn for BT2020 {
+    fn luminance<T: Float>() -> [T; 3] {
+        DifferenceFn2020::luminance()
+    }
+
+    fn normalize_blue<T: Float>(denorm: T) -> T {
+        DifferenceFn2020::normalize_blue(denorm)
+    }
+
+    fn denormalize_blue<T: Float>(norm: T) -> T {
+        DifferenceFn2020::denormalize_blue(norm)
+    }
+
+    fn normalize_red<T: Float>(denorm: T) -> T {
+        DifferenceFn2020::normalize_red(denorm)
+    }
+
+    fn denormalize_red<T: Float>(norm: T) -> T {
+        DifferenceFn2020::denormalize_red(norm)
+    }
+}
@@ -1,5 +1,10 @@
 //!Pixel encodings and pixel format conversion.
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 pub use self::raw::*;
 mod raw;
 
@@ -236,4 +241,55 @@ pub unsafe trait Pixel<T>: Sized {
         let new_length = slice.len() * Self::CHANNELS;
         unsafe { ::core::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut T, new_length) }
     }
+
+    /// Convert a vector of raw color components to a vector of colors,
+    /// without copying the components. The length of `vec` has to be a
+    /// multiple of the number of channels. Any excess capacity is released
+    /// first, so the allocation matches the new element type.
+    ///
+    /// ```rust
+    /// use palette::{Pixel, Srgb};
+    ///
+    /// let raw = vec![255u8, 128, 64, 10, 20, 30];
+    /// let colors = Srgb::from_raw_vec(raw);
+    ///
+    /// assert_eq!(colors, vec![Srgb::new(255u8, 128, 64), Srgb::new(10, 20, 30)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_raw_vec(vec: Vec<T>) -> Vec<Self> {
+        assert_eq!(vec.len() % Self::CHANNELS, 0);
+        let new_length = vec.len() / Self::CHANNELS;
+
+        // SAFETY: `Self` has the same layout as `[T; Self::CHANNELS]`, and the
+        // length is a multiple of it. A boxed slice has no excess capacity, so
+        // it's deallocated with the same size and alignment no matter which
+        // type it's viewed as.
+        let raw = Box::into_raw(vec.into_boxed_slice()).cast::<Self>();
+        unsafe { Box::from_raw(::core::ptr::slice_from_raw_parts_mut(raw, new_length)).into_vec() }
+    }
+
+    /// Convert a vector of colors to a vector of raw color components,
+    /// without copying the components. Any excess capacity is released first,
+    /// so the allocation matches the new element type.
+    ///
+    /// ```rust
+    /// use palette::{Pixel, Srgb};
+    ///
+    /// let colors = vec![Srgb::new(255u8, 128, 64), Srgb::new(10, 20, 30)];
+    /// let raw = Srgb::into_raw_vec(colors);
+    ///
+    /// assert_eq!(raw, vec![255u8, 128, 64, 10, 20, 30]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn into_raw_vec(vec: Vec<Self>) -> Vec<T> {
+        let new_length = vec.len() * Self::CHANNELS;
+
+        // SAFETY: `Self` has the same layout as `[T; Self::CHANNELS]`. A boxed
+        // slice has no excess capacity, so it's deallocated with the same size
+        // and alignment no matter which type it's viewed as.
+        let raw = Box::into_raw(vec.into_boxed_slice()).cast::<T>();
+        unsafe { Box::from_raw(::core::ptr::slice_from_raw_parts_mut(raw, new_length)).into_vec() }
+    }
 }
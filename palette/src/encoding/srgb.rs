@@ -56,3 +56,59 @@ impl TransferFn for Srgb {
         }
     }
 }
+
+include!(concat!(env!("OUT_DIR"), "/srgb_tables.rs"));
+
+///Decode an 8 bit sRGB component into a linear `f32` component.
+///
+///The result is looked up in a precomputed table, instead of being calculated
+///with `powf`.
+///
+///```
+///use palette::encoding::srgb;
+///
+///assert_eq!(srgb::u8_into_linear(0), 0.0);
+///assert_eq!(srgb::u8_into_linear(255), 1.0);
+///```
+#[inline]
+pub fn u8_into_linear(value: u8) -> f32 {
+    U8_INTO_LINEAR[value as usize]
+}
+
+///Decode a 16 bit sRGB component into a linear `f32` component.
+///
+///The result is looked up in a precomputed table, instead of being calculated
+///with `powf`.
+///
+///```
+///use palette::encoding::srgb;
+///
+///assert_eq!(srgb::u16_into_linear(0), 0.0);
+///assert_eq!(srgb::u16_into_linear(65535), 1.0);
+///```
+#[inline]
+pub fn u16_into_linear(value: u16) -> f32 {
+    U16_INTO_LINEAR[value as usize]
+}
+
+#[cfg(test)]
+mod test {
+    use encoding::TransferFn;
+    use super::{u16_into_linear, u8_into_linear, Srgb};
+
+    #[test]
+    fn u8_table() {
+        for i in 0..256 {
+            let expected = Srgb::into_linear(i as f64 / 255.0);
+            assert_relative_eq!(u8_into_linear(i as u8), expected as f32);
+        }
+    }
+
+    #[test]
+    fn u16_table() {
+        for i in (0..65536).step_by(97).chain(Some(65535)) {
+            let expected = Srgb::into_linear(i as f64 / 65535.0);
+            assert_relative_eq!(u16_into_linear(i as u16), expected as f32);
+        }
+    }
+}
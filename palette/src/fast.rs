@@ -0,0 +1,262 @@
+//!Fast approximations of the nonlinear parts of the color conversions.
+//!
+//!The transfer functions and the Lab conversion spend most of their time in
+//!`powf` and `cbrt`. This module has versions of them that use polynomial
+//!approximations and a few Newton iterations instead, for real-time uses
+//!where the exact results aren't needed. They calculate in `f32`, and the
+//!maximum errors within `[0.0, 1.0]` are:
+//!
+//!| Function | Maximum error |
+//!|---|---|
+//![`powf`](fn.powf.html) | `2e-5`, relative |
+//![`cbrt`](fn.cbrt.html) | `5e-7`, relative |
+//![`Srgb`](struct.Srgb.html) and [`BT709`](struct.BT709.html) | `2e-5`, absolute |
+//![`xyz_to_lab`](fn.xyz_to_lab.html) | `1e-4` ΔE |
+//!
+//!The approximated encodings can be used instead of their exact
+//!counterparts:
+//!
+//!```
+//!use palette::{Lab, LinSrgb, Xyz};
+//!use palette::fast;
+//!use palette::rgb::Rgb;
+//!
+//!let color: Rgb<fast::Srgb> = Rgb::new(0.8, 0.2, 0.1);
+//!let linear: LinSrgb = color.into_linear();
+//!let lab: Lab = fast::xyz_to_lab(Xyz::from(linear));
+//!```
+//!
+//!This module is only available if the `"fast-approx"` feature is enabled.
+
+use float::Float;
+
+use encoding::itu;
+use encoding::TransferFn;
+use luma::LumaStandard;
+use rgb::RgbStandard;
+use white_point::{WhitePoint, D65};
+use {cast, Lab, Xyz};
+
+///The sRGB standard, with approximated transfer functions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Srgb;
+
+impl RgbStandard for Srgb {
+    type Space = ::encoding::Srgb;
+    type TransferFn = Srgb;
+}
+
+impl LumaStandard for Srgb {
+    type WhitePoint = D65;
+    type TransferFn = Srgb;
+}
+
+impl TransferFn for Srgb {
+    fn into_linear<T: Float>(x: T) -> T {
+        if x <= cast(0.04045) {
+            x / cast(12.92)
+        } else {
+            let x: f32 = cast(x);
+            cast(powf((x + 0.055) / 1.055, 2.4))
+        }
+    }
+
+    fn from_linear<T: Float>(x: T) -> T {
+        if x <= cast(0.0031308) {
+            x * cast(12.92)
+        } else {
+            let x: f32 = cast(x);
+            cast(powf(x, 1.0 / 2.4) * 1.055 - 0.055)
+        }
+    }
+}
+
+///The ITU-R BT709 standard, with approximated transfer functions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BT709;
+
+impl RgbStandard for BT709 {
+    type Space = itu::BT709;
+    type TransferFn = BT709;
+}
+
+impl LumaStandard for BT709 {
+    type WhitePoint = D65;
+    type TransferFn = BT709;
+}
+
+impl TransferFn for BT709 {
+    fn into_linear<T: Float>(x: T) -> T {
+        if x <= cast(0.0091) {
+            x / cast(4.5)
+        } else {
+            let x: f32 = cast(x);
+            cast(powf((x + 0.099) / 1.099, 1.0 / 0.45))
+        }
+    }
+
+    fn from_linear<T: Float>(x: T) -> T {
+        if x <= cast(0.0018) {
+            x * cast(4.5)
+        } else {
+            let x: f32 = cast(x);
+            cast(powf(x, 0.45) * 1.099 - 0.099)
+        }
+    }
+}
+
+///Convert XYZ to L\*a\*b\*, with an approximated cube root.
+pub fn xyz_to_lab<Wp: WhitePoint>(color: Xyz<Wp, f32>) -> Lab<Wp, f32> {
+    let white: Xyz<Wp, f32> = Wp::get_xyz();
+
+    fn convert(c: f32) -> f32 {
+        if c > 216.0 / 24389.0 {
+            cbrt(c)
+        } else {
+            c * (841.0 / 108.0) + 4.0 / 29.0
+        }
+    }
+
+    let x = convert(color.x / white.x);
+    let y = convert(color.y / white.y);
+    let z = convert(color.z / white.z);
+
+    Lab::with_wp(y * 116.0 - 16.0, (x - y) * 500.0, (y - z) * 200.0)
+}
+
+///Approximate `x` to the power of `y`, for non-negative `x`.
+pub fn powf(x: f32, y: f32) -> f32 {
+    if x <= 0.0 {
+        0.0
+    } else {
+        exp2(y * log2(x))
+    }
+}
+
+///Approximate the cube root of `x`.
+pub fn cbrt(x: f32) -> f32 {
+    if x == 0.0 {
+        return x;
+    }
+
+    let magnitude = if x < 0.0 { -x } else { x };
+
+    //Dividing the exponent by three gives a rough first guess
+    let mut root = f32::from_bits(magnitude.to_bits() / 3 + 0x2a51_37a0);
+    for _ in 0..3 {
+        root = (2.0 * root + magnitude / (root * root)) / 3.0;
+    }
+
+    if x < 0.0 {
+        -root
+    } else {
+        root
+    }
+}
+
+fn log2(x: f32) -> f32 {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let t = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000) - 1.0;
+
+    //log2(1 + t) / t, for t in [0, 1)
+    let q = 1.442_681_5
+        + t * (-0.720_358_8
+            + t * (0.468_658_9 + t * (-0.301_638 + t * (0.144_471_1 + t * -0.033_822_046))));
+
+    exponent as f32 + t * q
+}
+
+fn exp2(x: f32) -> f32 {
+    if x < -126.0 {
+        return 0.0;
+    }
+
+    let mut integer = x as i32;
+    if integer as f32 > x {
+        integer -= 1;
+    }
+    let t = x - integer as f32;
+
+    //2^t, for t in [0, 1)
+    let fraction = 0.999_999_9
+        + t * (0.693_154_5
+            + t * (0.240_141_82 + t * (0.055_860_337 + t * (0.008_949_59 + t * 0.001_893_754))));
+
+    fraction * f32::from_bits(((integer + 127) as u32) << 23)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cbrt, powf, xyz_to_lab, Srgb, BT709};
+    use encoding::{itu, TransferFn};
+    use {Lab, Xyz};
+
+    fn samples() -> impl Iterator<Item = f32> {
+        (0..=10_000).map(|i| i as f32 / 10_000.0)
+    }
+
+    #[test]
+    fn powf_error() {
+        for &y in &[2.4, 1.0 / 2.4, 1.0 / 0.45, 0.45, 3.0] {
+            for x in samples().skip(1) {
+                let exact = (x as f64).powf(y as f64);
+                let error = (powf(x, y) as f64 - exact).abs() / exact;
+                assert!(error < 2e-5, "{}^{} is off by {}", x, y, error);
+            }
+        }
+
+        assert_eq!(powf(0.0, 2.4), 0.0);
+    }
+
+    #[test]
+    fn cbrt_error() {
+        for x in samples()
+            .chain(samples().map(|x| x * 1000.0))
+            .filter(|&x| x > 0.0)
+        {
+            let exact = (x as f64).cbrt();
+            let error = (cbrt(x) as f64 - exact).abs() / exact;
+            assert!(error < 5e-7, "cbrt({}) is off by {}", x, error);
+            assert_eq!(cbrt(-x), -cbrt(x));
+        }
+
+        assert_eq!(cbrt(0.0), 0.0);
+    }
+
+    #[test]
+    fn transfer_error() {
+        for x in samples() {
+            let x = x as f64;
+            let errors = [
+                Srgb::into_linear(x) - ::encoding::Srgb::into_linear(x),
+                Srgb::from_linear(x) - ::encoding::Srgb::from_linear(x),
+                BT709::into_linear(x) - itu::Transfer601And709::into_linear(x),
+                BT709::from_linear(x) - itu::Transfer601And709::from_linear(x),
+            ];
+
+            for error in &errors {
+                assert!(error.abs() < 2e-5, "{} is off by {}", x, error);
+            }
+        }
+    }
+
+    #[test]
+    fn lab_error() {
+        for x in samples().step_by(250) {
+            for y in samples().step_by(250) {
+                for z in samples().step_by(250) {
+                    let xyz = Xyz::new(x, y, z);
+                    let exact = Lab::from(xyz);
+                    let approximated = xyz_to_lab(xyz);
+
+                    let delta_e = ((exact.l - approximated.l).powi(2)
+                        + (exact.a - approximated.a).powi(2)
+                        + (exact.b - approximated.b).powi(2))
+                    .sqrt();
+                    assert!(delta_e < 1e-4, "{:?} is off by {}", xyz, delta_e);
+                }
+            }
+        }
+    }
+}
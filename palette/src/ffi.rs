@@ -0,0 +1,156 @@
+//!Conversion functions with a stable C ABI, for calling Palette from C and
+//!other languages with a C foreign function interface.
+//!
+//!The color types have guaranteed layouts. Every color struct is
+//!`#[repr(C)]`, with its components in the same order as in its definition
+//!and no other data, the hue types are `#[repr(transparent)]` wrappers around
+//!their angle, and `Alpha` places the alpha component directly after the
+//!color. The `f32` colors that are passed to and from the functions in this
+//!module can therefore be declared like this in C:
+//!
+//!```c
+//!typedef struct { float red, green, blue; } palette_rgb;
+//!typedef struct { float red, green, blue, alpha; } palette_rgba;
+//!typedef struct { float hue, saturation, lightness; } palette_hsl;
+//!typedef struct { float hue, saturation, value; } palette_hsv;
+//!typedef struct { float x, y, z; } palette_xyz;
+//!typedef struct { float l, a, b; } palette_lab;
+//!typedef struct { float l, chroma, hue; } palette_lch;
+//!
+//!palette_lab palette_srgb_to_lab(palette_rgb color);
+//!palette_rgb palette_lab_to_srgb(palette_lab color);
+//!```
+//!
+//!sRGB colors are non-linear, with components between `0.0` and `1.0`, and
+//!hues are in degrees. The CIE colors use the D65 white point. The functions
+//!are only exported when the `"ffi"` feature is enabled, to not add any
+//!symbols to other builds.
+
+use {Hsl, Hsv, Lab, Lch, LinSrgb, LinSrgba, Srgb, Srgba, Xyz};
+
+///Convert a non-linear sRGB color to linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_linear(color: Srgb) -> LinSrgb {
+    color.into_linear()
+}
+
+///Convert a linear sRGB color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_linear_to_srgb(color: LinSrgb) -> Srgb {
+    Srgb::from_linear(color)
+}
+
+///Convert a non-linear sRGB color with transparency to linear sRGB. The alpha
+///component is left unchanged.
+#[no_mangle]
+pub extern "C" fn palette_srgba_to_linear(color: Srgba) -> LinSrgba {
+    color.into_linear()
+}
+
+///Convert a linear sRGB color with transparency to non-linear sRGB. The alpha
+///component is left unchanged.
+#[no_mangle]
+pub extern "C" fn palette_linear_to_srgba(color: LinSrgba) -> Srgba {
+    Srgba::from_linear(color)
+}
+
+///Convert a non-linear sRGB color to HSL.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_hsl(color: Srgb) -> Hsl {
+    color.into_linear().into()
+}
+
+///Convert an HSL color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_hsl_to_srgb(color: Hsl) -> Srgb {
+    Srgb::from_linear(color.into())
+}
+
+///Convert a non-linear sRGB color to HSV.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_hsv(color: Srgb) -> Hsv {
+    color.into_linear().into()
+}
+
+///Convert an HSV color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_hsv_to_srgb(color: Hsv) -> Srgb {
+    Srgb::from_linear(color.into())
+}
+
+///Convert a non-linear sRGB color to CIE XYZ.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_xyz(color: Srgb) -> Xyz {
+    color.into_linear().into()
+}
+
+///Convert a CIE XYZ color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_xyz_to_srgb(color: Xyz) -> Srgb {
+    Srgb::from_linear(color.into())
+}
+
+///Convert a non-linear sRGB color to CIE L\*a\*b\*.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_lab(color: Srgb) -> Lab {
+    color.into_linear().into()
+}
+
+///Convert a CIE L\*a\*b\* color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_lab_to_srgb(color: Lab) -> Srgb {
+    Srgb::from_linear(color.into())
+}
+
+///Convert a non-linear sRGB color to CIE L\*C\*h°.
+#[no_mangle]
+pub extern "C" fn palette_srgb_to_lch(color: Srgb) -> Lch {
+    color.into_linear().into()
+}
+
+///Convert a CIE L\*C\*h° color to non-linear sRGB.
+#[no_mangle]
+pub extern "C" fn palette_lch_to_srgb(color: Lch) -> Srgb {
+    Srgb::from_linear(color.into())
+}
+
+#[cfg(test)]
+mod test {
+    use core::mem::{align_of, size_of};
+
+    use super::*;
+    use white_point::D65;
+    use {Hsla, Lcha, Pixel, RgbHue};
+
+    #[test]
+    fn layouts() {
+        assert_eq!(size_of::<Srgb>(), 3 * size_of::<f32>());
+        assert_eq!(size_of::<Srgba>(), 4 * size_of::<f32>());
+        assert_eq!(size_of::<Hsla>(), 4 * size_of::<f32>());
+        assert_eq!(size_of::<Lcha<D65>>(), 4 * size_of::<f32>());
+        assert_eq!(size_of::<Srgba<u8>>(), 4);
+        assert_eq!(size_of::<RgbHue>(), size_of::<f32>());
+        assert_eq!(align_of::<Lab>(), align_of::<f32>());
+        assert_eq!(align_of::<Srgba<u8>>(), 1);
+
+        let color = Lcha::<D65>::new(50.0, 30.0, 120.0, 0.5);
+        assert_eq!(color.as_raw::<[f32]>(), &[50.0, 30.0, 120.0, 0.5]);
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Srgb::new(0.8, 0.3, 0.1);
+
+        assert_relative_eq!(palette_linear_to_srgb(palette_srgb_to_linear(color)), color, epsilon = 1e-5);
+        assert_relative_eq!(palette_hsl_to_srgb(palette_srgb_to_hsl(color)), color, epsilon = 1e-5);
+        assert_relative_eq!(palette_hsv_to_srgb(palette_srgb_to_hsv(color)), color, epsilon = 1e-5);
+        assert_relative_eq!(palette_xyz_to_srgb(palette_srgb_to_xyz(color)), color, epsilon = 1e-5);
+        assert_relative_eq!(palette_lab_to_srgb(palette_srgb_to_lab(color)), color, epsilon = 1e-4);
+        assert_relative_eq!(palette_lch_to_srgb(palette_srgb_to_lch(color)), color, epsilon = 1e-4);
+
+        let transparent = Srgba::new(0.8, 0.3, 0.1, 0.5);
+        let linear = palette_srgba_to_linear(transparent);
+        assert_eq!(linear.alpha, 0.5);
+        assert_relative_eq!(palette_linear_to_srgba(linear), transparent, epsilon = 1e-5);
+    }
+}
@@ -50,6 +50,8 @@ mod no_std_float_trait {
         fn cbrt(self) -> Self;
         /// `x.powf(y)` computes `x` to the power of `y`.
         fn powf(self, other: Self) -> Self;
+        /// `x.ln()` computes the natural logarithm of `x`.
+        fn ln(self) -> Self;
         /// `x.sin()` computes the sine of `x` radians.
         fn sin(self) -> Self;
         /// `x.cos()` computes the cosine of `x` radians.
@@ -61,14 +63,17 @@ mod no_std_float_trait {
 
     impl Float for f32 {
         fn sqrt(self) -> f32 {
-            F32Ext::cbrt(self)
+            F32Ext::sqrt(self)
         }
         fn cbrt(self) -> f32 {
-            F32Ext::sqrt(self)
+            F32Ext::cbrt(self)
         }
         fn powf(self, other: f32) -> f32 {
             F32Ext::powf(self, other)
         }
+        fn ln(self) -> f32 {
+            F32Ext::ln(self)
+        }
         fn sin(self) -> f32 {
             F32Ext::sin(self)
         }
@@ -90,6 +95,9 @@ mod no_std_float_trait {
         fn powf(self, other: f64) -> f64 {
             F64Ext::powf(self, other)
         }
+        fn ln(self) -> f64 {
+            F64Ext::ln(self)
+        }
         fn sin(self) -> f64 {
             F64Ext::sin(self)
         }
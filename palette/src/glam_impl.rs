@@ -0,0 +1,76 @@
+//Conversions between colors and `glam` vectors.
+//
+//The components are placed in the same order as in the color types, so the
+//color is the `xyz` part of the vector and the alpha component is `w`.
+
+use glam::{DVec3, DVec4, Vec3, Vec4};
+
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {Alpha, Hsl, Hsv, Hwb, Lab, Lch, Pixel, Xyz, Yxy};
+
+macro_rules! impl_glam {
+    ($ty:ident<$param:ident: $bound:ident>) => {
+        impl_glam!(@vector $ty<$param: $bound>, f32, Vec3, Vec4);
+        impl_glam!(@vector $ty<$param: $bound>, f64, DVec3, DVec4);
+    };
+
+    (@vector $ty:ident<$param:ident: $bound:ident>, $float:ty, $vec3:ident, $vec4:ident) => {
+        impl<$param: $bound> From<$ty<$param, $float>> for $vec3 {
+            fn from(color: $ty<$param, $float>) -> $vec3 {
+                $vec3::from_array(color.into_raw())
+            }
+        }
+
+        impl<$param: $bound> From<$vec3> for $ty<$param, $float> {
+            fn from(vector: $vec3) -> $ty<$param, $float> {
+                *$ty::from_raw(&vector.to_array())
+            }
+        }
+
+        impl<$param: $bound> From<Alpha<$ty<$param, $float>, $float>> for $vec4 {
+            fn from(color: Alpha<$ty<$param, $float>, $float>) -> $vec4 {
+                $vec4::from_array(color.into_raw())
+            }
+        }
+
+        impl<$param: $bound> From<$vec4> for Alpha<$ty<$param, $float>, $float> {
+            fn from(vector: $vec4) -> Alpha<$ty<$param, $float>, $float> {
+                *Alpha::from_raw(&vector.to_array())
+            }
+        }
+    };
+}
+
+impl_glam!(Rgb<S: RgbStandard>);
+impl_glam!(Hsl<S: RgbSpace>);
+impl_glam!(Hsv<S: RgbSpace>);
+impl_glam!(Hwb<S: RgbSpace>);
+impl_glam!(Lab<Wp: WhitePoint>);
+impl_glam!(Lch<Wp: WhitePoint>);
+impl_glam!(Xyz<Wp: WhitePoint>);
+impl_glam!(Yxy<Wp: WhitePoint>);
+
+#[cfg(test)]
+mod test {
+    use glam::{DVec3, Vec3, Vec4};
+
+    use {Hsv, Lab, LinSrgb, LinSrgba};
+
+    #[test]
+    fn round_trips() {
+        let vector: Vec3 = LinSrgb::new(0.1, 0.2, 0.3).into();
+        assert_eq!(vector, Vec3::new(0.1, 0.2, 0.3));
+        assert_eq!(LinSrgb::from(vector), LinSrgb::new(0.1, 0.2, 0.3));
+
+        let vector: Vec4 = LinSrgba::new(0.1, 0.2, 0.3, 0.4).into();
+        assert_eq!(vector, Vec4::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(LinSrgba::from(vector), LinSrgba::new(0.1, 0.2, 0.3, 0.4));
+
+        let vector: DVec3 = Lab::new(50.0f64, 10.0, -20.0).into();
+        assert_eq!(vector, DVec3::new(50.0, 10.0, -20.0));
+
+        let hsv: Hsv = Vec3::new(120.0, 0.5, 0.25).into();
+        assert_eq!(hsv, Hsv::new(120.0, 0.5, 0.25));
+    }
+}
@@ -0,0 +1,207 @@
+//!GPU buffer layouts, using [`encase`](https://crates.io/crates/encase).
+//!
+//!The colors implement `encase::ShaderType` as vectors, so they can be
+//!written to uniform and storage buffers, for example for `wgpu`, and used
+//!as fields in structs that derive `ShaderType`. Colors with three
+//!components become `vec3<f32>`, colors with an alpha component become
+//!`vec4<f32>` and luma with an alpha component becomes `vec2<f32>`.
+//!
+//!A `vec3` has the same alignment as a `vec4` in WGSL, so `encase` inserts
+//!padding after three component colors in structs and arrays. That padding
+//!is left out when the colors are cast directly to bytes, so
+//![`Padded`](struct.Padded.html) can be used to give a color the layout of a
+//!`vec4<f32>` on the CPU side too.
+//!
+//!```rust,edition2018
+//!extern crate encase;
+//!extern crate palette;
+//!
+//!use encase::{ShaderType, UniformBuffer};
+//!use palette::{LinSrgb, LinSrgba};
+//!
+//!#[derive(ShaderType)]
+//!struct Material {
+//!    albedo: LinSrgb,
+//!    roughness: f32,
+//!    emission: LinSrgba,
+//!}
+//!
+//!fn main() {
+//!    let material = Material {
+//!        albedo: LinSrgb::new(0.8, 0.2, 0.1),
+//!        roughness: 0.5,
+//!        emission: LinSrgba::new(0.0, 0.0, 0.0, 1.0),
+//!    };
+//!
+//!    let mut buffer = UniformBuffer::new(Vec::<u8>::new());
+//!    buffer.write(&material).unwrap();
+//!    assert_eq!(buffer.into_inner().len(), 32);
+//!}
+//!```
+
+use encase::private::{AsMutVectorParts, AsRefVectorParts, FromVectorParts, VectorScalar};
+
+use float::Float;
+
+use luma::{Luma, LumaStandard};
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {Alpha, Component, Hsl, Hsv, Hwb, Lab, Lch, LinSrgb, Pixel, Srgb, Xyz, Yxy};
+
+///A three component color, padded to have the same size and alignment as a
+///`vec4<f32>`.
+///
+///The padding is an ordinary component, so a padded three component color
+///has no uninitialized bytes. It's written and read as the fourth component
+///of the vector, but has no meaning for the color.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[repr(C, align(16))]
+pub struct Padded<C, T = f32> {
+    ///The color.
+    pub color: C,
+
+    ///The padding after the color.
+    pub padding: T,
+}
+
+///Linear sRGB, padded to the layout of a `vec4<f32>`.
+pub type PaddedLinSrgb = Padded<LinSrgb<f32>>;
+
+///Non-linear sRGB, padded to the layout of a `vec4<f32>`.
+pub type PaddedSrgb = Padded<Srgb<f32>>;
+
+impl<C, T: Component> From<C> for Padded<C, T> {
+    fn from(color: C) -> Padded<C, T> {
+        Padded {
+            color: color,
+            padding: T::zero(),
+        }
+    }
+}
+
+macro_rules! impl_vector_parts {
+    ($n:expr, $self_ty:ty, [$($generics:tt)*]) => {
+        impl<$($generics)*> AsRefVectorParts<T, $n> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn as_ref_parts(&self) -> &[T; $n] {
+                self.as_raw()
+            }
+        }
+
+        impl<$($generics)*> AsMutVectorParts<T, $n> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn as_mut_parts(&mut self) -> &mut [T; $n] {
+                self.as_raw_mut()
+            }
+        }
+
+        impl<$($generics)*> FromVectorParts<T, $n> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn from_parts(parts: [T; $n]) -> Self {
+                *Self::from_raw(&parts)
+            }
+        }
+
+        impl_vector!($n, $self_ty; ($($generics)*));
+    };
+}
+
+//`Padded` can't implement `Pixel`, since it's more aligned than its
+//components, so it's converted via references to the color.
+macro_rules! impl_padded_vector_parts {
+    ($self_ty:ty, [$($generics:tt)*]) => {
+        impl<$($generics)*> AsRefVectorParts<T, 4> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn as_ref_parts(&self) -> &[T; 4] {
+                unsafe { &*(self as *const Self as *const [T; 4]) }
+            }
+        }
+
+        impl<$($generics)*> AsMutVectorParts<T, 4> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn as_mut_parts(&mut self) -> &mut [T; 4] {
+                unsafe { &mut *(self as *mut Self as *mut [T; 4]) }
+            }
+        }
+
+        impl<$($generics)*> FromVectorParts<T, 4> for $self_ty
+        where
+            T: VectorScalar,
+        {
+            fn from_parts(parts: [T; 4]) -> Self {
+                Padded {
+                    color: *Pixel::from_raw(&parts[..3]),
+                    padding: parts[3],
+                }
+            }
+        }
+
+        impl_vector!(4, $self_ty; ($($generics)*));
+    };
+}
+
+macro_rules! impl_shader_type {
+    ($ty:ident<$param:ident: $bound:ident>, [$($extra_bounds:tt)*]) => {
+        impl_vector_parts!(3, $ty<$param, T>, [$param: $bound, T: Component $($extra_bounds)*]);
+        impl_vector_parts!(4, Alpha<$ty<$param, T>, T>, [$param: $bound, T: Component $($extra_bounds)*]);
+        impl_padded_vector_parts!(Padded<$ty<$param, T>, T>, [$param: $bound, T: Component $($extra_bounds)*]);
+    };
+}
+
+impl_shader_type!(Rgb<S: RgbStandard>, []);
+impl_shader_type!(Hsl<S: RgbSpace>, [+ Float]);
+impl_shader_type!(Hsv<S: RgbSpace>, [+ Float]);
+impl_shader_type!(Hwb<S: RgbSpace>, [+ Float]);
+impl_shader_type!(Lab<Wp: WhitePoint>, [+ Float]);
+impl_shader_type!(Lch<Wp: WhitePoint>, [+ Float]);
+impl_shader_type!(Xyz<Wp: WhitePoint>, [+ Float]);
+impl_shader_type!(Yxy<Wp: WhitePoint>, [+ Float]);
+impl_vector_parts!(2, Alpha<Luma<S, T>, T>, [S: LumaStandard, T: Component]);
+
+#[cfg(test)]
+mod test {
+    use encase::{ShaderType, StorageBuffer};
+
+    use super::{Padded, PaddedLinSrgb};
+    use {Hsv, Lab, LinSrgb, LinSrgba, SrgbLumaa};
+
+    #[test]
+    fn sizes() {
+        assert_eq!(LinSrgb::<f32>::min_size().get(), 12);
+        assert_eq!(LinSrgba::<f32>::min_size().get(), 16);
+        assert_eq!(SrgbLumaa::<f32>::min_size().get(), 8);
+        assert_eq!(PaddedLinSrgb::min_size().get(), 16);
+        assert_eq!(::core::mem::size_of::<PaddedLinSrgb>(), 16);
+        assert_eq!(::core::mem::align_of::<PaddedLinSrgb>(), 16);
+    }
+
+    #[test]
+    fn write_and_read() {
+        let colors = vec![
+            Padded::from(Lab::new(50.0, 10.0, -20.0)),
+            Padded::from(Lab::new(80.0, -5.0, 5.0)),
+        ];
+
+        let mut buffer = StorageBuffer::new(Vec::<u8>::new());
+        buffer.write(&colors).unwrap();
+        assert_eq!(buffer.as_ref().len(), 32);
+
+        let read: Vec<Padded<Lab>> = buffer.create().unwrap();
+        assert_eq!(read, colors);
+
+        let mut buffer = StorageBuffer::new(Vec::<u8>::new());
+        buffer.write(&Hsv::new(120.0, 0.5, 0.25)).unwrap();
+        let read: Hsv = buffer.create().unwrap();
+        assert_eq!(read, Hsv::new(120.0, 0.5, 0.25));
+    }
+}
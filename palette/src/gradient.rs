@@ -1,16 +1,21 @@
 //!Types for interpolation between multiple colors.
 //!
-//!This module is only available if the `std` feature is enabled (this is the
-//!default).
+//!This module is only available if the `alloc` feature is enabled, which is
+//!implied by the default `std` feature.
 
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::max;
+use core::fmt::Write;
 use num_traits::{One, Zero};
 use float::Float;
-use std::cmp::max;
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 
-use cast;
+use {cast, clamp};
 
-use Mix;
+use dither::Dither;
+use rgb::{LinSrgb, LinSrgba, Srgb, Srgba};
+use {Component, ComponentWise, HueInterpolation, Mix};
 
 ///A linear interpolation between colors.
 ///
@@ -20,8 +25,57 @@ use Mix;
 ///number of evenly spaced points using the `take` method. Any point outside
 ///the domain of the gradient will have the same color as the closest control
 ///point.
+///
+///Colors with a hue take the shortest way around the color wheel between two
+///control points by default. This can be changed with
+///[`with_hue_interpolation`](#method.with_hue_interpolation).
+///
+///The colors are evenly distributed within each segment between two control
+///points, unless an [`Easing`](enum.Easing.html) is attached to it, using
+///[`with_easing`](#method.with_easing).
+///
+///Gradients can be serialized and deserialized with the `"serializing"`
+///feature. The hue interpolation and the easing functions are optional when
+///deserializing.
 #[derive(Clone, Debug)]
-pub struct Gradient<C: Mix + Clone>(Vec<(C::Scalar, C)>);
+#[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serializing",
+    serde(bound(
+        serialize = "C: ::serde::Serialize, C::Scalar: ::serde::Serialize",
+        deserialize = "C: ::serde::Deserialize<'de>, C::Scalar: ::serde::Deserialize<'de>"
+    ))
+)]
+pub struct Gradient<C: Mix + Clone> {
+    #[cfg_attr(feature = "serializing", serde(deserialize_with = "deserialize_points"))]
+    points: Vec<(C::Scalar, C)>,
+    #[cfg_attr(feature = "serializing", serde(default))]
+    hue_interpolation: HueInterpolation,
+    #[cfg_attr(
+        feature = "serializing",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    easing: Vec<Easing<C::Scalar>>,
+}
+
+///Deserialize the control points of a gradient, which can't be empty.
+#[cfg(feature = "serializing")]
+fn deserialize_points<'de, D, T, C>(deserializer: D) -> Result<Vec<(T, C)>, D::Error>
+where
+    D: ::serde::Deserializer<'de>,
+    T: ::serde::Deserialize<'de>,
+    C: ::serde::Deserialize<'de>,
+{
+    use serde::de::{Deserialize, Error};
+
+    let points = Vec::<(T, C)>::deserialize(deserializer)?;
+
+    if points.is_empty() {
+        Err(D::Error::invalid_length(0, &"at least one color"))
+    } else {
+        Ok(points)
+    }
+}
 
 impl<C: Mix + Clone> Gradient<C> {
     ///Create a gradient of evenly spaced colors with the domain [0.0, 1.0].
@@ -35,7 +89,11 @@ impl<C: Mix + Clone> Gradient<C> {
             *p = cast::<C::Scalar, _>(i) * step_size;
         }
 
-        Gradient(points)
+        Gradient {
+            points,
+            hue_interpolation: HueInterpolation::Shorter,
+            easing: Vec::new(),
+        }
     }
 
     ///Create a gradient of colors with custom spacing and domain. There must be
@@ -45,51 +103,109 @@ impl<C: Mix + Clone> Gradient<C> {
         assert!(colors.len() > 0);
 
         //Maybe sort the colors?
-        Gradient(colors)
+        Gradient {
+            points: colors,
+            hue_interpolation: HueInterpolation::Shorter,
+            easing: Vec::new(),
+        }
+    }
+
+    ///Set how the hue should be interpolated between the control points. This
+    ///has no effect on colors without a hue.
+    ///
+    ///```
+    ///use palette::{Gradient, Hsv, HueInterpolation};
+    ///
+    ///let gradient = Gradient::new(vec![Hsv::new(30.0, 1.0, 1.0), Hsv::new(330.0, 1.0, 1.0)])
+    ///    .with_hue_interpolation(HueInterpolation::Increasing);
+    ///
+    ///assert_eq!(gradient.get(0.5), Hsv::new(180.0, 1.0, 1.0));
+    ///```
+    pub fn with_hue_interpolation(mut self, hue_interpolation: HueInterpolation) -> Gradient<C> {
+        self.hue_interpolation = hue_interpolation;
+        self
+    }
+
+    ///Attach an easing function to the segment between the control points
+    ///`segment` and `segment + 1`. The segments are linear by default.
+    ///
+    ///```
+    ///use palette::LinSrgb;
+    ///use palette::gradient::{Easing, Gradient};
+    ///
+    ///let gradient = Gradient::new(vec![LinSrgb::new(0.0, 0.0, 0.0), LinSrgb::new(1.0, 1.0, 1.0)])
+    ///    .with_easing(0, Easing::Midpoint(0.25));
+    ///
+    ///assert_eq!(gradient.get(0.25), LinSrgb::new(0.5, 0.5, 0.5));
+    ///```
+    ///
+    ///It panics if `segment` is not between two control points.
+    pub fn with_easing(mut self, segment: usize, easing: Easing<C::Scalar>) -> Gradient<C> {
+        assert!(
+            segment + 1 < self.points.len(),
+            "the gradient has no segment {}",
+            segment
+        );
+
+        if self.easing.len() <= segment {
+            self.easing.resize(segment + 1, Easing::Linear);
+        }
+
+        self.easing[segment] = easing;
+        self
     }
 
     ///Get a color from the gradient. The color of the closest control point
     ///will be returned if `i` is outside the domain.
     pub fn get(&self, i: C::Scalar) -> C {
-        let &(mut min, ref min_color) = self.0
+        match self.search(i) {
+            Position::Point(index) => self.points[index].1.clone(),
+            Position::Between(index, factor) => {
+                let (_, ref min_color) = self.points[index];
+                let (_, ref max_color) = self.points[index + 1];
+                min_color.mix_with_hue(max_color, factor, self.hue_interpolation)
+            }
+        }
+    }
+
+    ///Find the control points around `i`.
+    fn search(&self, i: C::Scalar) -> Position<C::Scalar> {
+        let &(mut min, _) = self.points
             .get(0)
             .expect("a Gradient must contain at least one color");
-        let mut min_color = min_color;
         let mut min_index = 0;
 
         if i <= min {
-            return min_color.clone();
+            return Position::Point(min_index);
         }
 
-        let &(mut max, ref max_color) = self.0
+        let &(mut max, _) = self.points
             .last()
             .expect("a Gradient must contain at least one color");
-        let mut max_color = max_color;
-        let mut max_index = self.0.len() - 1;
+        let mut max_index = self.points.len() - 1;
 
         if i >= max {
-            return max_color.clone();
+            return Position::Point(max_index);
         }
 
         while min_index < max_index - 1 {
             let index = min_index + (max_index - min_index) / 2;
 
-            let (p, ref color) = self.0[index];
+            let (p, _) = self.points[index];
 
             if i <= p {
                 max = p;
-                max_color = color;
                 max_index = index;
             } else {
                 min = p;
-                min_color = color;
                 min_index = index;
             }
         }
 
         let factor = (i - min) / (max - min);
+        let easing = self.easing.get(min_index).unwrap_or(&Easing::Linear);
 
-        min_color.mix(max_color, factor)
+        Position::Between(min_index, easing.apply(factor))
     }
 
     ///Take `n` evenly spaced colors from the gradient, as an iterator.
@@ -115,16 +231,425 @@ impl<C: Mix + Clone> Gradient<C> {
 
     ///Get the limits of this gradient's domain.
     pub fn domain(&self) -> (C::Scalar, C::Scalar) {
-        let &(min, _) = self.0
+        let &(min, _) = self.points
             .get(0)
             .expect("a Gradient must contain at least one color");
-        let &(max, _) = self.0
+        let &(max, _) = self.points
             .last()
             .expect("a Gradient must contain at least one color");
         (min, max)
     }
 }
 
+///A function that redistributes the colors within a gradient segment.
+///
+///The easing function maps the relative position within the segment, from
+///`0.0` to `1.0`, to the mixing factor between its two colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
+pub enum Easing<T: Float> {
+    ///Distribute the colors evenly.
+    #[default]
+    Linear,
+    ///Move the halfway point between the two colors to the relative position
+    ///within the segment, like a CSS gradient color hint.
+    Midpoint(T),
+    ///A cubic Bézier curve from `(0, 0)` to `(1, 1)`, with the control points
+    ///`(x1, y1)` and `(x2, y2)`, like the CSS `cubic-bezier` easing function.
+    ///The `x` coordinates are expected to be between `0.0` and `1.0`.
+    CubicBezier(T, T, T, T),
+}
+
+impl<T: Float> Easing<T> {
+    ///The CSS `ease` function.
+    pub fn ease() -> Easing<T> {
+        Easing::CubicBezier(cast(0.25), cast(0.1), cast(0.25), T::one())
+    }
+
+    ///The CSS `ease-in` function.
+    pub fn ease_in() -> Easing<T> {
+        Easing::CubicBezier(cast(0.42), T::zero(), T::one(), T::one())
+    }
+
+    ///The CSS `ease-out` function.
+    pub fn ease_out() -> Easing<T> {
+        Easing::CubicBezier(T::zero(), T::zero(), cast(0.58), T::one())
+    }
+
+    ///The CSS `ease-in-out` function.
+    pub fn ease_in_out() -> Easing<T> {
+        Easing::CubicBezier(cast(0.42), T::zero(), cast(0.58), T::one())
+    }
+
+    ///Apply the easing function to a relative position, `t`, between `0.0` and
+    ///`1.0`.
+    pub fn apply(&self, t: T) -> T {
+        match *self {
+            Easing::Linear => t,
+            Easing::Midpoint(midpoint) => {
+                if midpoint <= T::zero() {
+                    T::one()
+                } else if midpoint >= T::one() {
+                    T::zero()
+                } else {
+                    let half: T = cast(0.5);
+                    t.powf(half.ln() / midpoint.ln())
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                let x1 = clamp(x1, T::zero(), T::one());
+                let x2 = clamp(x2, T::zero(), T::one());
+                let s = solve_bezier(t, x1, x2);
+                bezier(s, y1, y2)
+            }
+        }
+    }
+}
+
+///A one dimensional cubic Bézier curve from 0 to 1, at `s`.
+fn bezier<T: Float>(s: T, p1: T, p2: T) -> T {
+    let three: T = cast(3.0);
+    let inv = T::one() - s;
+    three * inv * inv * s * p1 + three * inv * s * s * p2 + s * s * s
+}
+
+///Find `s` such that the curve is at `x`, when it's monotonic.
+fn solve_bezier<T: Float>(x: T, p1: T, p2: T) -> T {
+    let three: T = cast(3.0);
+    let six: T = cast(6.0);
+    let epsilon: T = cast(1e-7);
+
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(s, p1, p2) - x;
+        if error.abs() < epsilon {
+            return s;
+        }
+
+        let inv = T::one() - s;
+        let derivative =
+            three * inv * inv * p1 + six * inv * s * (p2 - p1) + three * s * s * (T::one() - p2);
+        if derivative.abs() < epsilon {
+            break;
+        }
+
+        s = s - error / derivative;
+    }
+
+    //Fall back to bisection if Newton's method didn't converge
+    let (mut min, mut max) = (T::zero(), T::one());
+    s = x;
+    for _ in 0..64 {
+        let value = bezier(s, p1, p2);
+        if (value - x).abs() < epsilon {
+            break;
+        }
+
+        if value < x {
+            min = s;
+        } else {
+            max = s;
+        }
+        s = (min + max) / cast(2.0);
+    }
+
+    s
+}
+
+impl<T: Component + Float> Gradient<LinSrgba<T>> {
+    ///Format the gradient as a CSS `linear-gradient()`, with the colors as
+    ///hexadecimal sRGB and their positions as percentages of the domain.
+    ///
+    ///Midpoint easing functions become color hints, while other easing
+    ///functions, the hue interpolation and the spline interpolation can't be
+    ///represented. The result can be parsed with
+    ///[`css::parse_linear_gradient`](../css/fn.parse_linear_gradient.html).
+    ///
+    ///```
+    ///use palette::LinSrgba;
+    ///use palette::gradient::Gradient;
+    ///
+    ///let gradient = Gradient::new(vec![
+    ///    LinSrgba::new(1.0, 0.0, 0.0, 1.0),
+    ///    LinSrgba::new(0.0, 0.0, 1.0, 0.5),
+    ///]);
+    ///
+    ///assert_eq!(gradient.to_css(), "linear-gradient(#ff0000 0%, #0000ff80 100%)");
+    ///```
+    pub fn to_css(&self) -> String {
+        let (min, max) = self.domain();
+        let to_percent = |position: T| -> f64 {
+            if max > min {
+                cast::<f64, _>((position - min) / (max - min)) * 100.0
+            } else {
+                0.0
+            }
+        };
+
+        let mut css = String::from("linear-gradient(");
+
+        for (index, &(position, color)) in self.points.iter().enumerate() {
+            if index > 0 {
+                css.push_str(", ");
+
+                if let Some(&Easing::Midpoint(midpoint)) = self.easing.get(index - 1) {
+                    let previous = self.points[index - 1].0;
+                    let hint = previous + (position - previous) * midpoint;
+                    write!(css, "{}%, ", to_percent(hint)).unwrap();
+                }
+            }
+
+            let (red, green, blue, alpha) = Srgba::from_linear(color).into_components();
+            let to_u8 = |x: T| cast::<u8, _>((clamp(x, T::zero(), T::one()) * cast(255.0)).round());
+
+            write!(
+                css,
+                "#{:02x}{:02x}{:02x}",
+                to_u8(red),
+                to_u8(green),
+                to_u8(blue)
+            ).unwrap();
+
+            if to_u8(alpha) < 255 {
+                write!(css, "{:02x}", to_u8(alpha)).unwrap();
+            }
+
+            write!(css, " {}%", to_percent(position)).unwrap();
+        }
+
+        css.push(')');
+        css
+    }
+}
+
+impl<T: Component + Float> Gradient<LinSrgb<T>> {
+    ///Take `n` evenly spaced colors from the gradient, as an iterator of 8
+    ///bit sRGB colors. The colors are dithered, with the sample index as the
+    ///`x` coordinate, to avoid visible banding in smooth or dark ramps.
+    ///
+    ///```
+    ///use palette::LinSrgb;
+    ///use palette::dither::Dither;
+    ///use palette::gradient::Gradient;
+    ///
+    ///let gradient = Gradient::new(vec![
+    ///    LinSrgb::new(0.0, 0.0, 0.0),
+    ///    LinSrgb::new(0.01, 0.01, 0.01),
+    ///]);
+    ///
+    ///let ramp: Vec<_> = gradient.take_dithered(256, Dither::Ordered).row(3).collect();
+    ///assert_eq!(ramp.len(), 256);
+    ///```
+    pub fn take_dithered(&self, n: usize, dither: Dither) -> TakeDithered<'_, T> {
+        TakeDithered {
+            take: self.take(n),
+            dither,
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+impl<C, T> Gradient<C>
+where
+    C: Mix<Scalar = T> + Clone + ComponentWise<Scalar = T>,
+    T: Float,
+{
+    ///Use a smooth spline between the control points, instead of straight
+    ///lines.
+    ///
+    ///```
+    ///use palette::LinSrgb;
+    ///use palette::gradient::{Gradient, Spline};
+    ///
+    ///let gradient = Gradient::new(vec![
+    ///    LinSrgb::new(0.0, 0.0, 0.5),
+    ///    LinSrgb::new(0.0, 0.8, 0.4),
+    ///    LinSrgb::new(1.0, 1.0, 0.0),
+    ///]);
+    ///let smooth = gradient.spline(Spline::CatmullRom);
+    ///
+    ///assert_eq!(smooth.get(0.5), LinSrgb::new(0.0, 0.8, 0.4));
+    ///let colors: Vec<_> = smooth.take(10).collect();
+    ///```
+    pub fn spline(&self, spline: Spline) -> SplineGradient<'_, C> {
+        SplineGradient {
+            gradient: self,
+            spline,
+        }
+    }
+}
+
+///A type of spline for smooth gradients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Spline {
+    ///A Catmull-Rom spline, which passes through every control point.
+    CatmullRom,
+    ///A cubic B-spline, which passes through the first and last control
+    ///points and is pulled towards the ones in between. It's smoother than
+    ///Catmull-Rom, but doesn't hit the intermediate colors exactly.
+    BSpline,
+}
+
+impl Spline {
+    ///The weights of the four points around a segment, at `t`. The spans are
+    ///the length of the segment, relative to the distances between the points
+    ///before and after each of its ends.
+    fn weights<T: Float>(self, t: T, (before, after): (T, T)) -> [T; 4] {
+        let one = T::one();
+        let two: T = cast(2.0);
+        let three: T = cast(3.0);
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        match self {
+            Spline::CatmullRom => {
+                //Cubic Hermite basis, with Catmull-Rom tangents
+                let h00 = two * t3 - three * t2 + one;
+                let h10 = t3 - two * t2 + t;
+                let h01 = three * t2 - two * t3;
+                let h11 = t3 - t2;
+
+                [
+                    -h10 * before,
+                    h00 - h11 * after,
+                    h01 + h10 * before,
+                    h11 * after,
+                ]
+            }
+            Spline::BSpline => {
+                let six: T = cast(6.0);
+                let inv = one - t;
+
+                [
+                    inv * inv * inv / six,
+                    (three * t3 - six * t2 + cast(4.0)) / six,
+                    (-three * t3 + three * t2 + three * t + one) / six,
+                    t3 / six,
+                ]
+            }
+        }
+    }
+}
+
+///A gradient with smooth spline interpolation between its control points.
+#[derive(Clone)]
+pub struct SplineGradient<'a, C: Mix + Clone + 'a> {
+    gradient: &'a Gradient<C>,
+    spline: Spline,
+}
+
+impl<'a, C, T> SplineGradient<'a, C>
+where
+    C: Mix<Scalar = T> + Clone + ComponentWise<Scalar = T>,
+    T: Float,
+{
+    ///Get a color from the gradient. The color of the closest control point
+    ///will be returned if `i` is outside the domain.
+    pub fn get(&self, i: T) -> C {
+        let points = &self.gradient.points;
+
+        let (index, t) = match self.gradient.search(i) {
+            Position::Point(index) => return points[index].1.clone(),
+            Position::Between(index, factor) => (index, factor),
+        };
+
+        let (p1, ref c1) = points[index];
+        let (p2, ref c2) = points[index + 1];
+
+        //Points outside the ends are reflected, to make the curve end exactly
+        //at the first and last colors.
+        let (p0, c0) = match index.checked_sub(1) {
+            Some(before) => (points[before].0, points[before].1.clone()),
+            None => (p1 - (p2 - p1), reflect(c1, c2)),
+        };
+        let (p3, c3) = match points.get(index + 2) {
+            Some(&(p, ref color)) => (p, color.clone()),
+            None => (p2 + (p2 - p1), reflect(c2, c1)),
+        };
+
+        let span = p2 - p1;
+        let w = self.spline.weights(t, (span / (p2 - p0), span / (p3 - p1)));
+
+        let a = c0.component_wise(c1, |a, b| a * w[0] + b * w[1]);
+        let b = c2.component_wise(&c3, |a, b| a * w[2] + b * w[3]);
+        a.component_wise(&b, |a, b| a + b)
+    }
+
+    ///Take `n` evenly spaced colors from the gradient, as an iterator.
+    pub fn take(&self, n: usize) -> SplineTake<'a, C> {
+        let (min, max) = self.gradient.domain();
+
+        SplineTake {
+            gradient: self.clone(),
+            from: min,
+            diff: max - min,
+            len: n,
+            current: 0,
+        }
+    }
+
+    ///Get the limits of this gradient's domain.
+    pub fn domain(&self) -> (T, T) {
+        self.gradient.domain()
+    }
+}
+
+///An iterator over colors from a spline gradient.
+#[derive(Clone)]
+pub struct SplineTake<'a, C: Mix + Clone + 'a> {
+    gradient: SplineGradient<'a, C>,
+    from: C::Scalar,
+    diff: C::Scalar,
+    len: usize,
+    current: usize,
+}
+
+impl<'a, C, T> Iterator for SplineTake<'a, C>
+where
+    C: Mix<Scalar = T> + Clone + ComponentWise<Scalar = T>,
+    T: Float,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.current < self.len {
+            let i = self.from + (self.diff / cast(self.len)) * cast(self.current);
+            self.current += 1;
+            Some(self.gradient.get(i))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.current, Some(self.len - self.current))
+    }
+}
+
+impl<'a, C, T> ExactSizeIterator for SplineTake<'a, C>
+where
+    C: Mix<Scalar = T> + Clone + ComponentWise<Scalar = T>,
+    T: Float,
+{
+}
+
+///Mirror `color` around `center`.
+fn reflect<C: ComponentWise>(center: &C, color: &C) -> C
+where
+    C::Scalar: Float,
+{
+    center.component_wise(color, |c, x| c + c - x)
+}
+
+///The position of a point within a gradient's domain.
+enum Position<T> {
+    ///On, or outside, the control point with the index.
+    Point(usize),
+    ///Between the control point with the index and the next one.
+    Between(usize, T),
+}
+
 ///An iterator over interpolated colors.
 #[derive(Clone)]
 pub struct Take<'a, C: Mix + Clone + 'a> {
@@ -155,6 +680,42 @@ impl<'a, C: Mix + Clone> Iterator for Take<'a, C> {
 
 impl<'a, C: Mix + Clone> ExactSizeIterator for Take<'a, C> {}
 
+///An iterator over interpolated and dithered 8 bit sRGB colors.
+#[derive(Clone)]
+pub struct TakeDithered<'a, T: Component + Float + 'a> {
+    take: Take<'a, LinSrgb<T>>,
+    dither: Dither,
+    x: usize,
+    y: usize,
+}
+
+impl<'a, T: Component + Float> TakeDithered<'a, T> {
+    ///Set the row, or `y` coordinate, of the dithering pattern. Using a
+    ///different row for each line of an image avoids vertical streaks.
+    pub fn row(mut self, y: usize) -> Self {
+        self.y = y;
+        self
+    }
+}
+
+impl<'a, T: Component + Float> Iterator for TakeDithered<'a, T> {
+    type Item = Srgb<u8>;
+
+    fn next(&mut self) -> Option<Srgb<u8>> {
+        let color = self.take.next()?;
+        let x = self.x;
+        self.x += 1;
+
+        Some(self.dither.quantize(Srgb::from_linear(color), x, self.y))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.take.size_hint()
+    }
+}
+
+impl<'a, T: Component + Float> ExactSizeIterator for TakeDithered<'a, T> {}
+
 ///A slice of a Gradient that limits its domain.
 #[derive(Clone, Debug)]
 pub struct Slice<'a, C: Mix + Clone + 'a> {
@@ -255,8 +816,8 @@ impl<T: Float> Range<T> {
     }
 }
 
-impl<T: Float> From<::std::ops::Range<T>> for Range<T> {
-    fn from(range: ::std::ops::Range<T>) -> Range<T> {
+impl<T: Float> From<::core::ops::Range<T>> for Range<T> {
+    fn from(range: ::core::ops::Range<T>) -> Range<T> {
         Range {
             from: Some(range.start),
             to: Some(range.end),
@@ -264,8 +825,8 @@ impl<T: Float> From<::std::ops::Range<T>> for Range<T> {
     }
 }
 
-impl<T: Float> From<::std::ops::RangeFrom<T>> for Range<T> {
-    fn from(range: ::std::ops::RangeFrom<T>) -> Range<T> {
+impl<T: Float> From<::core::ops::RangeFrom<T>> for Range<T> {
+    fn from(range: ::core::ops::RangeFrom<T>) -> Range<T> {
         Range {
             from: Some(range.start),
             to: None,
@@ -273,8 +834,8 @@ impl<T: Float> From<::std::ops::RangeFrom<T>> for Range<T> {
     }
 }
 
-impl<T: Float> From<::std::ops::RangeTo<T>> for Range<T> {
-    fn from(range: ::std::ops::RangeTo<T>) -> Range<T> {
+impl<T: Float> From<::core::ops::RangeTo<T>> for Range<T> {
+    fn from(range: ::core::ops::RangeTo<T>) -> Range<T> {
         Range {
             from: None,
             to: Some(range.end),
@@ -282,8 +843,8 @@ impl<T: Float> From<::std::ops::RangeTo<T>> for Range<T> {
     }
 }
 
-impl<T: Float> From<::std::ops::RangeFull> for Range<T> {
-    fn from(_range: ::std::ops::RangeFull) -> Range<T> {
+impl<T: Float> From<::core::ops::RangeFull> for Range<T> {
+    fn from(_range: ::core::ops::RangeFull) -> Range<T> {
         Range {
             from: None,
             to: None,
@@ -393,8 +954,10 @@ impl<'a, C: Mix + Clone> MaybeSlice<'a, C> {
 
 #[cfg(test)]
 mod test {
-    use super::{Gradient, Range};
-    use LinSrgb;
+    use super::{Easing, Gradient, Range, Spline};
+    use css::parse_linear_gradient;
+    use dither::Dither;
+    use {Hsv, HueInterpolation, LinSrgb, LinSrgba};
 
     #[test]
     fn range_clamp() {
@@ -430,4 +993,203 @@ mod test {
             assert_relative_eq!(t1, t2);
         }
     }
+
+    #[test]
+    fn hue_interpolation() {
+        let colors = vec![Hsv::new(30.0, 1.0, 1.0), Hsv::new(330.0, 1.0, 1.0)];
+
+        let shorter = Gradient::new(colors.clone());
+        let longer = Gradient::new(colors).with_hue_interpolation(HueInterpolation::Longer);
+
+        assert_relative_eq!(shorter.get(0.5), Hsv::new(0.0, 1.0, 1.0));
+        assert_relative_eq!(longer.get(0.5), Hsv::new(180.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn catmull_rom_hits_control_points() {
+        let gradient = Gradient::with_domain(vec![
+            (0.0, LinSrgb::new(1.0, 0.0, 0.0)),
+            (0.2, LinSrgb::new(0.5, 0.5, 0.0)),
+            (0.7, LinSrgb::new(0.0, 0.2, 0.8)),
+            (1.0, LinSrgb::new(0.0, 0.0, 1.0)),
+        ]);
+        let smooth = gradient.spline(Spline::CatmullRom);
+
+        for &(p, color) in &gradient.points {
+            assert_relative_eq!(smooth.get(p), color);
+            assert_relative_eq!(smooth.get(p - 1e-9), color, epsilon = 1e-6);
+            assert_relative_eq!(smooth.get(p + 1e-9), color, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn spline_of_line_is_linear() {
+        let gradient = Gradient::new(vec![
+            LinSrgb::new(0.0, 0.0, 0.0),
+            LinSrgb::new(0.5, 0.5, 0.5),
+            LinSrgb::new(1.0, 1.0, 1.0),
+        ]);
+
+        for spline in &[Spline::CatmullRom, Spline::BSpline] {
+            for (i, color) in gradient.spline(*spline).take(11).enumerate() {
+                let expected = i as f32 / 11.0;
+                assert_relative_eq!(color, LinSrgb::new(expected, expected, expected));
+            }
+        }
+    }
+
+    #[test]
+    fn b_spline_ends() {
+        let gradient = Gradient::new(vec![
+            LinSrgb::new(1.0, 0.0, 0.0),
+            LinSrgb::new(0.0, 1.0, 0.0),
+            LinSrgb::new(0.0, 0.0, 1.0),
+        ]);
+        let smooth = gradient.spline(Spline::BSpline);
+
+        assert_relative_eq!(smooth.get(0.0), LinSrgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(smooth.get(0.49999), smooth.get(0.5), epsilon = 1e-4);
+        assert_relative_eq!(smooth.get(1.0), LinSrgb::new(0.0, 0.0, 1.0));
+        assert!(smooth.get(0.5).green < 1.0);
+    }
+
+    #[test]
+    fn easing_midpoint() {
+        let easing = Easing::Midpoint(0.8f64);
+        assert_relative_eq!(easing.apply(0.0), 0.0);
+        assert_relative_eq!(easing.apply(0.8), 0.5);
+        assert_relative_eq!(easing.apply(1.0), 1.0);
+
+        assert_relative_eq!(Easing::Midpoint(0.0f64).apply(0.3), 1.0);
+        assert_relative_eq!(Easing::Midpoint(1.0f64).apply(0.3), 0.0);
+    }
+
+    #[test]
+    fn easing_cubic_bezier() {
+        let linear = Easing::CubicBezier(0.25f64, 0.25, 0.75, 0.75);
+        let ease = Easing::ease();
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_relative_eq!(linear.apply(t), t, epsilon = 1e-6);
+        }
+
+        assert_relative_eq!(ease.apply(0.0), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(ease.apply(1.0), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(ease.apply(0.5), 0.8024033877399112, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn easing_per_segment() {
+        let gradient = Gradient::new(vec![
+            LinSrgb::new(0.0, 0.0, 0.0),
+            LinSrgb::new(0.5, 0.5, 0.5),
+            LinSrgb::new(1.0, 1.0, 1.0),
+        ]).with_easing(1, Easing::Midpoint(0.75));
+
+        assert_relative_eq!(gradient.get(0.25), LinSrgb::new(0.25, 0.25, 0.25));
+        assert_relative_eq!(gradient.get(0.875), LinSrgb::new(0.75, 0.75, 0.75));
+    }
+
+    #[test]
+    #[should_panic]
+    fn easing_outside_gradient() {
+        let gradient = Gradient::new(vec![LinSrgb::new(0.0, 0.0, 0.0), LinSrgb::new(1.0, 1.0, 1.0)]);
+        gradient.with_easing(1, Easing::Linear);
+    }
+
+    #[cfg(feature = "serializing")]
+    #[test]
+    fn serialize() {
+        let gradient = Gradient::new(vec![
+            LinSrgb::new(1.0, 0.0, 0.0),
+            LinSrgb::new(0.0, 0.0, 1.0),
+        ]).with_easing(0, Easing::Midpoint(0.3));
+        let serialized = ::serde_json::to_string(&gradient).unwrap();
+
+        assert_eq!(
+            serialized,
+            r#"{"points":[[0.0,{"red":1.0,"green":0.0,"blue":0.0}],[1.0,{"red":0.0,"green":0.0,"blue":1.0}]],"hue_interpolation":"Shorter","easing":[{"Midpoint":0.3}]}"#
+        );
+    }
+
+    #[cfg(feature = "serializing")]
+    #[test]
+    fn deserialize() {
+        let deserialized: Gradient<Hsv> = ::serde_json::from_str(
+            r#"{"points":[[0.0,{"hue":30.0,"saturation":1.0,"value":1.0}],[1.0,{"hue":330.0,"saturation":1.0,"value":1.0}]],"hue_interpolation":"Longer"}"#,
+        ).unwrap();
+
+        assert_relative_eq!(deserialized.get(0.0), Hsv::new(30.0, 1.0, 1.0));
+        assert_relative_eq!(deserialized.get(0.5), Hsv::new(180.0, 1.0, 1.0));
+        assert!(deserialized.easing.is_empty());
+    }
+
+    #[cfg(feature = "serializing")]
+    #[test]
+    fn deserialize_empty() {
+        let result: Result<Gradient<LinSrgb>, _> = ::serde_json::from_str(r#"{"points":[]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn css_round_trip() {
+        let css = "linear-gradient(#ff0000 0%, 25%, #00ff0080 50%, #0000ff 50%, #ffffff 100%)";
+        let gradient = parse_linear_gradient::<f64>(css).unwrap();
+
+        assert_eq!(gradient.to_css(), css);
+        assert_eq!(gradient.easing, vec![Easing::Midpoint(0.5)]);
+
+        let reparsed = parse_linear_gradient::<f64>(&gradient.to_css()).unwrap();
+        for (&(p1, c1), &(p2, c2)) in gradient.points.iter().zip(&reparsed.points) {
+            assert_relative_eq!(p1, p2);
+            assert_relative_eq!(c1, c2);
+        }
+    }
+
+    #[test]
+    fn to_css_custom_domain() {
+        let gradient = Gradient::with_domain(vec![
+            (2.0, LinSrgba::new(0.0, 0.0, 0.0, 1.0)),
+            (4.0, LinSrgba::new(1.0, 1.0, 1.0, 0.0)),
+        ]);
+
+        assert_eq!(gradient.to_css(), "linear-gradient(#000000 0%, #ffffff00 100%)");
+    }
+
+    #[test]
+    fn take_dithered() {
+        let gradient = Gradient::new(vec![
+            LinSrgb::new(0.0f64, 0.0, 0.0),
+            LinSrgb::new(0.02, 0.02, 0.02),
+        ]);
+
+        for &dither in &[Dither::Ordered, Dither::BlueNoise] {
+            let dithered: Vec<_> = gradient.take_dithered(256, dither).row(5).collect();
+            let plain: Vec<_> = gradient
+                .take(256)
+                .map(|c| ::Srgb::from_linear(c).into_format::<u8>())
+                .collect();
+            assert_eq!(dithered.len(), 256);
+
+            //The dithered ramp alternates between neighboring values instead
+            //of forming long bands.
+            let changes = |colors: &[::Srgb<u8>]| {
+                colors.windows(2).filter(|w| w[0] != w[1]).count()
+            };
+            assert!(changes(&dithered) > 2 * changes(&plain));
+
+            //Every 32 samples should average out to the exact value.
+            for (chunk, colors) in dithered.chunks(32).enumerate() {
+                let average = colors.iter().map(|c| c.red as f64).sum::<f64>() / 32.0;
+                let expected = gradient
+                    .take(256)
+                    .skip(chunk * 32)
+                    .take(32)
+                    .map(|c| ::Srgb::from_linear(c).red * 255.0)
+                    .sum::<f64>() / 32.0;
+                assert!((average - expected).abs() < 0.75);
+            }
+        }
+    }
 }
@@ -0,0 +1,158 @@
+//!Color harmonies, for picking sets of colors that go well together.
+//!
+//!A harmony is made by rotating the hue of a seed color by a number of fixed
+//!angles, while keeping its other components. The result depends on which
+//!color space the hue is rotated in. HSL is the traditional choice for color
+//!wheels, while CIE L\*C\*h° keeps the perceived lightness of the colors the
+//!same, which makes the harmony look more balanced.
+//!
+//!```
+//!use palette::{Hsl, Lch, Srgb};
+//!use palette::harmonies::Harmonies;
+//!
+//!let seed = Srgb::new(0.8, 0.3, 0.2).into_linear();
+//!
+//!let [_, complement] = Lch::from(seed).complementary();
+//!assert_eq!(complement.hue, Lch::from(seed).hue + 180.0);
+//!
+//!let triad: Vec<Srgb> = Hsl::from(seed)
+//!    .triadic()
+//!    .iter()
+//!    .map(|&color| Srgb::from_linear(color.into()))
+//!    .collect();
+//!```
+
+use float::Float;
+
+use rgb::RgbSpace;
+use white_point::WhitePoint;
+use {cast, Component, Hsl, Hsv, Hue, Hwb, Lch};
+
+///Generate color harmonies by rotating the hue of a color.
+///
+///The seed color is always the first color in the returned set, and the
+///other colors follow in order of increasing hue rotation.
+pub trait Harmonies: Hue + Sized {
+    ///The type of the hue angle, in degrees.
+    type Scalar: Float;
+
+    ///The color and the color on the opposite side of the color wheel.
+    fn complementary(&self) -> [Self; 2];
+
+    ///The color and the two colors next to its complement, at 150° and 210°.
+    fn split_complementary(&self) -> [Self; 3];
+
+    ///The color and two other colors, evenly spaced around the color wheel.
+    fn triadic(&self) -> [Self; 3];
+
+    ///The color and three other colors, forming a rectangle on the color
+    ///wheel, at 60°, 180° and 240°.
+    fn tetradic(&self) -> [Self; 4];
+
+    ///The color and three other colors, evenly spaced around the color wheel.
+    fn square(&self) -> [Self; 4];
+
+    ///The color and its two neighbors, `angle` degrees away in each direction.
+    ///The neighbors are ordered as `[self, self - angle, self + angle]`.
+    fn analogous(&self, angle: Self::Scalar) -> [Self; 3];
+}
+
+fn rotate<C, T>(color: &C, degrees: f64) -> C
+where
+    C: Hue,
+    C::Hue: From<T>,
+    T: Float,
+{
+    color.shift_hue(cast::<T, _>(degrees))
+}
+
+macro_rules! impl_harmonies {
+    ($ty:ident<$param:ident: $bound:ident>) => {
+        impl<$param, T> Harmonies for $ty<$param, T>
+        where
+            T: Component + Float,
+            $param: $bound,
+        {
+            type Scalar = T;
+
+            fn complementary(&self) -> [Self; 2] {
+                [*self, rotate::<_, T>(self, 180.0)]
+            }
+
+            fn split_complementary(&self) -> [Self; 3] {
+                [*self, rotate::<_, T>(self, 150.0), rotate::<_, T>(self, 210.0)]
+            }
+
+            fn triadic(&self) -> [Self; 3] {
+                [*self, rotate::<_, T>(self, 120.0), rotate::<_, T>(self, 240.0)]
+            }
+
+            fn tetradic(&self) -> [Self; 4] {
+                [
+                    *self,
+                    rotate::<_, T>(self, 60.0),
+                    rotate::<_, T>(self, 180.0),
+                    rotate::<_, T>(self, 240.0),
+                ]
+            }
+
+            fn square(&self) -> [Self; 4] {
+                [
+                    *self,
+                    rotate::<_, T>(self, 90.0),
+                    rotate::<_, T>(self, 180.0),
+                    rotate::<_, T>(self, 270.0),
+                ]
+            }
+
+            fn analogous(&self, angle: T) -> [Self; 3] {
+                [*self, self.shift_hue(-angle), self.shift_hue(angle)]
+            }
+        }
+    };
+}
+
+impl_harmonies!(Hsl<S: RgbSpace>);
+impl_harmonies!(Hsv<S: RgbSpace>);
+impl_harmonies!(Hwb<S: RgbSpace>);
+impl_harmonies!(Lch<Wp: WhitePoint>);
+
+#[cfg(test)]
+mod test {
+    use super::Harmonies;
+    use {Hsl, Lch};
+
+    #[test]
+    fn hsl_harmonies() {
+        let seed = Hsl::new(30.0, 0.7, 0.4);
+
+        let [_, complement] = seed.complementary();
+        assert_relative_eq!(complement, Hsl::new(210.0, 0.7, 0.4));
+
+        let triad = seed.triadic();
+        assert_relative_eq!(triad[1], Hsl::new(150.0, 0.7, 0.4));
+        assert_relative_eq!(triad[2], Hsl::new(270.0, 0.7, 0.4));
+
+        let square = seed.square();
+        assert_relative_eq!(square[3], Hsl::new(300.0, 0.7, 0.4));
+
+        let analogous = seed.analogous(40.0);
+        assert_relative_eq!(analogous[1], Hsl::new(-10.0, 0.7, 0.4));
+        assert_relative_eq!(analogous[2], Hsl::new(70.0, 0.7, 0.4));
+    }
+
+    #[test]
+    fn lch_harmonies() {
+        let seed = Lch::new(60.0f64, 40.0, 100.0);
+
+        let split = seed.split_complementary();
+        assert_relative_eq!(split[0], seed);
+        assert_relative_eq!(split[1], Lch::new(60.0, 40.0, 250.0));
+        assert_relative_eq!(split[2], Lch::new(60.0, 40.0, 310.0));
+
+        let tetrad = seed.tetradic();
+        assert_relative_eq!(tetrad[1], Lch::new(60.0, 40.0, 160.0));
+        assert_relative_eq!(tetrad[2], Lch::new(60.0, 40.0, 280.0));
+        assert_relative_eq!(tetrad[3], Lch::new(60.0, 40.0, 340.0));
+    }
+}
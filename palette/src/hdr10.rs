@@ -0,0 +1,271 @@
+//!Encoding of scene colors as HDR10 video, and back.
+//!
+//!HDR10 video stores colors as 10 bit, narrow range, Y'CbCr codes with the
+//![`BT2020`](../encoding/itu/struct.BT2020.html) primaries and the perceptual
+//!quantizer of [`BT2100Pq`](../encoding/itu/struct.BT2100Pq.html). Getting
+//!there from RGB takes a chain of steps that have to be done in the right
+//!order: decoding the transfer function, converting the primaries, scaling to
+//!absolute luminance, encoding with the perceptual quantizer, converting to
+//!Y'CbCr and quantizing the components.
+//![`Hdr10`](struct.Hdr10.html) does all of them in one call.
+//!
+//!The perceptual quantizer encodes absolute luminance, so the colors are
+//!placed at a reference white, which is `203` cd/m² by default, as
+//!recommended in ITU-R BT.2408. Lighter colors are kept as they are, up to
+//!the limit of `10000` cd/m².
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::hdr10::Hdr10;
+//!
+//!let hdr10 = Hdr10::<_, f64>::default();
+//!
+//!let codes = hdr10.encode(Srgb::new(1.0, 1.0, 1.0));
+//!assert_eq!(codes, [573, 512, 512]);
+//!
+//!let white: Srgb<f64> = hdr10.decode(codes);
+//!assert!((white.red - 1.0).abs() < 0.01);
+//!```
+
+use core::marker::PhantomData;
+
+use float::Float;
+
+use encoding::itu::{DifferenceFn2020, TransferPq, BT2020};
+use encoding::TransferFn;
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::D65;
+use yuv::sdi::clip_reserved;
+use yuv::DifferenceFn;
+use {cast, clamp, encoding, ColorMatrix, Component};
+
+///The luminance of the linear value `1.0` of the perceptual quantizer, in
+///cd/m².
+const PQ_PEAK: f64 = 10000.0;
+
+///Encodes colors in the RGB standard `S` as HDR10 codes, and decodes them
+///back.
+///
+///The codes are in the order `[Y', Cb, Cr]`. They avoid the words that are
+///reserved in SDI streams, so they can be passed to the
+///[`sdi`](../yuv/sdi/index.html) module as they are.
+#[derive(Debug, PartialEq)]
+pub struct Hdr10<S: RgbStandard = encoding::Srgb, T: Float = f32> {
+    ///The luminance of the RGB white, in cd/m².
+    pub reference_white: T,
+
+    into_bt2020: ColorMatrix<T>,
+    from_bt2020: ColorMatrix<T>,
+    standard: PhantomData<S>,
+}
+
+impl<S: RgbStandard, T: Float> Copy for Hdr10<S, T> {}
+
+impl<S: RgbStandard, T: Float> Clone for Hdr10<S, T> {
+    fn clone(&self) -> Hdr10<S, T> {
+        *self
+    }
+}
+
+impl<S, T> Hdr10<S, T>
+where
+    S: RgbStandard,
+    S::Space: RgbSpace<WhitePoint = D65>,
+    T: Component + Float,
+{
+    ///Create an encoder that places the RGB white at `reference_white`
+    ///cd/m².
+    pub fn new(reference_white: T) -> Hdr10<S, T> {
+        Hdr10 {
+            reference_white: reference_white,
+            into_bt2020: ColorMatrix::rgb_to_rgb::<S::Space, BT2020>(),
+            from_bt2020: ColorMatrix::rgb_to_rgb::<BT2020, S::Space>(),
+            standard: PhantomData,
+        }
+    }
+
+    ///Encode a color as 10 bit `[Y', Cb, Cr]` codes.
+    ///
+    ///Colors outside the BT.2020 gamut are clipped to it, and luminance
+    ///above `10000` cd/m² is clipped to the peak.
+    pub fn encode(&self, color: Rgb<S, T>) -> [u16; 3] {
+        let linear = color.into_linear();
+        let scale = self.reference_white / cast(PQ_PEAK);
+        let bt2020 = self
+            .into_bt2020
+            .transform([linear.red, linear.green, linear.blue]);
+
+        let encode = |value: T| {
+            let value = clamp(value * scale, T::zero(), T::one());
+            TransferPq::from_linear(value)
+        };
+        let red = encode(bt2020[0]);
+        let green = encode(bt2020[1]);
+        let blue = encode(bt2020[2]);
+
+        let weights = DifferenceFn2020::luminance::<T>();
+        let luma = weights[0] * red + weights[1] * green + weights[2] * blue;
+        let blue_diff = DifferenceFn2020::normalize_blue(blue - luma);
+        let red_diff = DifferenceFn2020::normalize_red(red - luma);
+
+        [
+            quantize(luma * cast(876.0) + cast(64.0)),
+            quantize(blue_diff * cast(896.0) + cast(512.0)),
+            quantize(red_diff * cast(896.0) + cast(512.0)),
+        ]
+    }
+
+    ///Decode 10 bit `[Y', Cb, Cr]` codes into a color.
+    ///
+    ///The result isn't clamped, since the BT.2020 gamut and the luminance
+    ///above the reference white may be outside the range of `S`.
+    pub fn decode(&self, codes: [u16; 3]) -> Rgb<S, T> {
+        let luma = (cast::<T, _>(codes[0]) - cast(64.0)) / cast(876.0);
+        let blue_diff = (cast::<T, _>(codes[1]) - cast(512.0)) / cast(896.0);
+        let red_diff = (cast::<T, _>(codes[2]) - cast(512.0)) / cast(896.0);
+
+        let weights = DifferenceFn2020::luminance::<T>();
+        let red = luma + DifferenceFn2020::denormalize_red(red_diff);
+        let blue = luma + DifferenceFn2020::denormalize_blue(blue_diff);
+        let green = (luma - weights[0] * red - weights[2] * blue) / weights[1];
+
+        let scale = cast::<T, _>(PQ_PEAK) / self.reference_white;
+        let decode = |value: T| {
+            let value = clamp(value, T::zero(), T::one());
+            TransferPq::into_linear(value) * scale
+        };
+        let bt2020 = [decode(red), decode(green), decode(blue)];
+
+        let [red, green, blue] = self.from_bt2020.transform(bt2020);
+        Rgb::from_linear(Rgb::new(red, green, blue))
+    }
+
+    ///Encode each color in `colors` into `codes`.
+    ///
+    ///# Panics
+    ///
+    ///Panics if `colors` and `codes` don't have the same length.
+    pub fn encode_slice(&self, colors: &[Rgb<S, T>], codes: &mut [[u16; 3]]) {
+        assert_eq!(
+            colors.len(),
+            codes.len(),
+            "there must be as many codes as colors"
+        );
+
+        for (&color, code) in colors.iter().zip(codes) {
+            *code = self.encode(color);
+        }
+    }
+
+    ///Decode each code in `codes` into `colors`.
+    ///
+    ///# Panics
+    ///
+    ///Panics if `codes` and `colors` don't have the same length.
+    pub fn decode_slice(&self, codes: &[[u16; 3]], colors: &mut [Rgb<S, T>]) {
+        assert_eq!(
+            codes.len(),
+            colors.len(),
+            "there must be as many colors as codes"
+        );
+
+        for (&code, color) in codes.iter().zip(colors) {
+            *color = self.decode(code);
+        }
+    }
+}
+
+impl<S, T> Default for Hdr10<S, T>
+where
+    S: RgbStandard,
+    S::Space: RgbSpace<WhitePoint = D65>,
+    T: Component + Float,
+{
+    ///Place the RGB white at `203` cd/m², as recommended in ITU-R BT.2408.
+    fn default() -> Hdr10<S, T> {
+        Hdr10::new(cast(203.0))
+    }
+}
+
+///Round a code and keep it out of the reserved words.
+fn quantize<T: Float>(value: T) -> u16 {
+    let value: T = clamp(value.round(), T::zero(), cast(1023.0));
+    clip_reserved(cast(value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Hdr10;
+    use encoding::itu::{TransferPq, BT2020};
+    use encoding::{Linear, TransferFn};
+    use rgb::Rgb;
+    use {LinSrgb, Srgb};
+
+    #[test]
+    fn pq_reference() {
+        assert_relative_eq!(TransferPq::from_linear(0.0f64), 7.3e-7, epsilon = 1e-7);
+        assert_relative_eq!(TransferPq::from_linear(1.0f64), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(TransferPq::from_linear(0.01f64), 0.508078, epsilon = 1e-6);
+        assert_relative_eq!(TransferPq::from_linear(0.0203f64), 0.580688, epsilon = 1e-6);
+        assert_eq!(
+            TransferPq::from_linear(-1.0f64),
+            TransferPq::from_linear(0.0)
+        );
+
+        for &value in &[0.0, 1e-4, 0.01, 0.5, 1.0] {
+            let encoded = TransferPq::from_linear(value);
+            assert_relative_eq!(TransferPq::into_linear(encoded), value, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn black_and_white() {
+        let hdr10 = Hdr10::<_, f64>::default();
+        assert_eq!(hdr10.encode(Srgb::new(0.0, 0.0, 0.0)), [64, 512, 512]);
+        assert_eq!(hdr10.encode(Srgb::new(1.0, 1.0, 1.0)), [573, 512, 512]);
+
+        let peak = Hdr10::<_, f64>::new(10000.0);
+        assert_eq!(peak.encode(Srgb::new(1.0, 1.0, 1.0)), [940, 512, 512]);
+        assert_eq!(peak.encode(Srgb::new(1.5, 1.5, 1.5)), [940, 512, 512]);
+    }
+
+    #[test]
+    fn primaries() {
+        let hdr10 = Hdr10::<BT2020, f64>::new(10000.0);
+        let red = hdr10.encode(Rgb::new(1.0, 0.0, 0.0));
+        assert_eq!(red, [294, 387, 960]);
+
+        //The sRGB primaries are inside the BT.2020 gamut, so the chroma isn't
+        //as strong
+        let hdr10 = Hdr10::<Linear<::encoding::Srgb>, f64>::new(10000.0);
+        let red = hdr10.encode(LinSrgb::new(1.0, 0.0, 0.0));
+        assert!(red[2] < 960);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let hdr10 = Hdr10::<_, f64>::default();
+        let colors = [
+            Srgb::new(0.2, 0.4, 0.8),
+            Srgb::new(0.9, 0.1, 0.1),
+            Srgb::new(0.5, 0.5, 0.5),
+            Srgb::new(0.05, 0.02, 0.0),
+        ];
+
+        let mut codes = [[0; 3]; 4];
+        hdr10.encode_slice(&colors, &mut codes);
+        let mut decoded = [Srgb::new(0.0, 0.0, 0.0); 4];
+        hdr10.decode_slice(&codes, &mut decoded);
+
+        for (color, decoded) in colors.iter().zip(&decoded) {
+            assert_relative_eq!(*color, *decoded, epsilon = 0.01);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "there must be as many codes as colors")]
+    fn slice_length() {
+        Hdr10::<::encoding::Srgb, f32>::default()
+            .encode_slice(&[Srgb::new(0.0, 0.0, 0.0)], &mut []);
+    }
+}
@@ -9,8 +9,8 @@ use encoding::pixel::RawPixel;
 use encoding::{Linear, Srgb};
 use rgb::{Rgb, RgbSpace};
 use {
-    cast, clamp, Alpha, Component, FromColor, GetHue, Hsv, Hue, IntoColor, Limited, Mix, Pixel,
-    RgbHue, Saturate, Shade, Xyz,
+    cast, clamp, Alpha, Complement, Component, FromColorUnclamped, GetHue, Hsv, Hue, HueInterpolation, IntoColorUnclamped, Limited,
+    Mix, Pixel, RgbHue, Saturate, SaturateFixed, Shade, Xyz,
 };
 
 /// Linear HSL with an alpha component. See the [`Hsla` implementation in
@@ -28,7 +28,7 @@ pub type Hsla<S = Srgb, T = f32> = Alpha<Hsl<S, T>, T>;
 ///
 ///See [HSV](struct.Hsv.html) for a very similar color space, with brightness
 /// instead of lightness.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_rgb_space = "S"]
@@ -68,6 +68,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Hsl<S, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    S: RgbSpace,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Hsl<S, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    S: RgbSpace + 'static,
+{
+}
+
 impl<S, T> Clone for Hsl<S, T>
 where
     T: Component + Float,
@@ -183,6 +199,30 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<S: RgbSpace> Hsl<S, $component> {
+            ///Black, with no lightness.
+            pub const BLACK: Hsl<S, $component> = Hsl {
+                hue: RgbHue::from_degrees(0.0),
+                saturation: 0.0,
+                lightness: 0.0,
+                space: PhantomData,
+            };
+
+            ///White, with full lightness and no saturation.
+            pub const WHITE: Hsl<S, $component> = Hsl {
+                hue: RgbHue::from_degrees(0.0),
+                saturation: 0.0,
+                lightness: 1.0,
+                space: PhantomData,
+            };
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Hsla"></span>[`Hsla`](type.Hsla.html) implementations.
 impl<T, A> Alpha<Hsl<Srgb, T>, A>
 where
@@ -332,11 +372,19 @@ where
     type Scalar = T;
 
     fn mix(&self, other: &Hsl<S, T>, factor: T) -> Hsl<S, T> {
+        self.mix_with_hue(other, factor, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(
+        &self,
+        other: &Hsl<S, T>,
+        factor: T,
+        hue_interpolation: HueInterpolation,
+    ) -> Hsl<S, T> {
         let factor = clamp(factor, T::zero(), T::one());
-        let hue_diff: T = (other.hue - self.hue).to_degrees();
 
         Hsl {
-            hue: self.hue + factor * hue_diff,
+            hue: self.hue.interpolate(other.hue, factor, hue_interpolation),
             saturation: self.saturation + factor * (other.saturation - self.saturation),
             lightness: self.lightness + factor * (other.lightness - self.lightness),
             space: PhantomData,
@@ -401,6 +449,16 @@ where
     }
 }
 
+impl<S, T> Complement for Hsl<S, T>
+where
+    T: Component + Float,
+    S: RgbSpace,
+{
+    fn complement(&self) -> Hsl<S, T> {
+        self.shift_hue(cast::<T, _>(180.0))
+    }
+}
+
 impl<S, T> Saturate for Hsl<S, T>
 where
     T: Component + Float,
@@ -411,7 +469,22 @@ where
     fn saturate(&self, factor: T) -> Hsl<S, T> {
         Hsl {
             hue: self.hue,
-            saturation: self.saturation * (T::one() + factor),
+            saturation: clamp(self.saturation * (T::one() + factor), T::zero(), T::one()),
+            lightness: self.lightness,
+            space: PhantomData,
+        }
+    }
+}
+
+impl<S, T> SaturateFixed for Hsl<S, T>
+where
+    T: Component + Float,
+    S: RgbSpace,
+{
+    fn saturate_fixed(&self, amount: T) -> Hsl<S, T> {
+        Hsl {
+            hue: self.hue,
+            saturation: clamp(self.saturation + amount, T::zero(), T::one()),
             lightness: self.lightness,
             space: PhantomData,
         }
@@ -626,6 +699,25 @@ where
     }
 }
 
+///Samples uniformly within the HSL cylinder. The saturation is the square
+///root of a uniform value, to not crowd the colors around the gray axis.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Hsl<S, T>> for ::rand::distributions::Standard
+where
+    S: RgbSpace,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Hsl<S, T> {
+        Hsl {
+            hue: rng.gen::<RgbHue<T>>(),
+            saturation: rng.gen::<T>().sqrt(),
+            lightness: rng.gen(),
+            space: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Hsl;
@@ -697,6 +789,19 @@ mod test {
         }
     }
 
+    #[test]
+    fn saturate_clamped() {
+        use {Saturate, SaturateFixed};
+
+        let color = Hsl::new(120.0, 0.6, 0.5);
+
+        assert_relative_eq!(color.saturate(0.5), Hsl::new(120.0, 0.9, 0.5));
+        assert_relative_eq!(color.saturate(1.0), Hsl::new(120.0, 1.0, 0.5));
+        assert_relative_eq!(color.desaturate(2.0), Hsl::new(120.0, 0.0, 0.5));
+        assert_relative_eq!(color.saturate_fixed(0.2), Hsl::new(120.0, 0.8, 0.5));
+        assert_relative_eq!(color.desaturate_fixed(0.8), Hsl::new(120.0, 0.0, 0.5));
+    }
+
     raw_pixel_conversion_tests!(Hsl<Srgb>: hue, saturation, lightness);
     raw_pixel_conversion_fail_tests!(Hsl<Srgb>: hue, saturation, lightness);
 
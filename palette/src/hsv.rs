@@ -10,7 +10,9 @@ use encoding::{Linear, Srgb};
 use rgb::{Rgb, RgbSpace};
 use {cast, clamp};
 use {Alpha, Hsl, Hwb, Xyz};
-use {Component, FromColor, GetHue, Hue, Limited, Mix, Pixel, RgbHue, Saturate, Shade};
+use {
+    Complement, Component, FromColorUnclamped, GetHue, Hue, HueInterpolation, Limited, Mix, Pixel, RgbHue, Saturate, SaturateFixed, Shade,
+};
 
 /// Linear HSV with an alpha component. See the [`Hsva` implementation in
 /// `Alpha`](struct.Alpha.html#Hsva).
@@ -24,7 +26,7 @@ pub type Hsva<S = Srgb, T = f32> = Alpha<Hsv<S, T>, T>;
 ///_lightness_. The difference is that, for example, red (100% R, 0% G, 0% B)
 ///and white (100% R, 100% G, 100% B) has the same brightness (or value), but
 ///not the same lightness.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "S::WhitePoint"]
@@ -65,6 +67,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Hsv<S, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    S: RgbSpace,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Hsv<S, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    S: RgbSpace + 'static,
+{
+}
+
 impl<S, T> Clone for Hsv<S, T>
 where
     T: Component + Float,
@@ -175,6 +193,30 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<S: RgbSpace> Hsv<S, $component> {
+            ///Black, with no value.
+            pub const BLACK: Hsv<S, $component> = Hsv {
+                hue: RgbHue::from_degrees(0.0),
+                saturation: 0.0,
+                value: 0.0,
+                space: PhantomData,
+            };
+
+            ///White, with full value and no saturation.
+            pub const WHITE: Hsv<S, $component> = Hsv {
+                hue: RgbHue::from_degrees(0.0),
+                saturation: 0.0,
+                value: 1.0,
+                space: PhantomData,
+            };
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Hsva"></span>[`Hsva`](type.Hsva.html) implementations.
 impl<T, A> Alpha<Hsv<Srgb, T>, A>
 where
@@ -343,11 +385,19 @@ where
     type Scalar = T;
 
     fn mix(&self, other: &Hsv<S, T>, factor: T) -> Hsv<S, T> {
+        self.mix_with_hue(other, factor, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(
+        &self,
+        other: &Hsv<S, T>,
+        factor: T,
+        hue_interpolation: HueInterpolation,
+    ) -> Hsv<S, T> {
         let factor = clamp(factor, T::zero(), T::one());
-        let hue_diff: T = (other.hue - self.hue).to_degrees();
 
         Hsv {
-            hue: self.hue + factor * hue_diff,
+            hue: self.hue.interpolate(other.hue, factor, hue_interpolation),
             saturation: self.saturation + factor * (other.saturation - self.saturation),
             value: self.value + factor * (other.value - self.value),
             space: PhantomData,
@@ -412,6 +462,16 @@ where
     }
 }
 
+impl<S, T> Complement for Hsv<S, T>
+where
+    T: Component + Float,
+    S: RgbSpace,
+{
+    fn complement(&self) -> Hsv<S, T> {
+        self.shift_hue(cast::<T, _>(180.0))
+    }
+}
+
 impl<S, T> Saturate for Hsv<S, T>
 where
     T: Component + Float,
@@ -422,7 +482,22 @@ where
     fn saturate(&self, factor: T) -> Hsv<S, T> {
         Hsv {
             hue: self.hue,
-            saturation: self.saturation * (T::one() + factor),
+            saturation: clamp(self.saturation * (T::one() + factor), T::zero(), T::one()),
+            value: self.value,
+            space: PhantomData,
+        }
+    }
+}
+
+impl<S, T> SaturateFixed for Hsv<S, T>
+where
+    T: Component + Float,
+    S: RgbSpace,
+{
+    fn saturate_fixed(&self, amount: T) -> Hsv<S, T> {
+        Hsv {
+            hue: self.hue,
+            saturation: clamp(self.saturation + amount, T::zero(), T::one()),
             value: self.value,
             space: PhantomData,
         }
@@ -637,6 +712,25 @@ where
     }
 }
 
+///Samples uniformly within the HSV cylinder. The saturation is the square
+///root of a uniform value, to not crowd the colors around the gray axis.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Hsv<S, T>> for ::rand::distributions::Standard
+where
+    S: RgbSpace,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Hsv<S, T> {
+        Hsv {
+            hue: rng.gen::<RgbHue<T>>(),
+            saturation: rng.gen::<T>().sqrt(),
+            value: rng.gen(),
+            space: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Hsv;
@@ -2,10 +2,63 @@ use float::Float;
 
 use core::f64::consts::PI;
 use core::cmp::PartialEq;
-use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 use cast;
 
+/// The direction to take around the color wheel when interpolating between two
+/// hues.
+///
+/// These are the same strategies as the hue interpolation methods in CSS Color
+/// Module Level 4.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
+pub enum HueInterpolation {
+    /// Take the shortest way around the color wheel. This is the default.
+    #[default]
+    Shorter,
+    /// Take the longest way around the color wheel.
+    Longer,
+    /// Always go in the direction of increasing angles.
+    Increasing,
+    /// Always go in the direction of decreasing angles.
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Get the signed distance, in degrees, from `from` to `to`.
+    fn distance<T: Float>(self, from: T, to: T) -> T {
+        let c360: T = cast(360.0);
+        let diff = to - from;
+
+        match self {
+            HueInterpolation::Shorter => normalize_angle(diff),
+            HueInterpolation::Longer => {
+                let diff = normalize_angle(diff);
+                let c180: T = cast(180.0);
+
+                if diff == c180 {
+                    diff
+                } else if diff > T::zero() {
+                    diff - c360
+                } else {
+                    diff + c360
+                }
+            }
+            HueInterpolation::Increasing => normalize_angle_positive(diff),
+            HueInterpolation::Decreasing => {
+                let diff = normalize_angle_positive(diff);
+
+                if diff > T::zero() {
+                    diff - c360
+                } else {
+                    diff
+                }
+            }
+        }
+    }
+}
+
 macro_rules! make_hues {
     ($($(#[$doc:meta])+ struct $name:ident;)+) => ($(
         $(#[$doc])+
@@ -17,13 +70,35 @@ macro_rules! make_hues {
         /// linear number.
         #[derive(Clone, Copy, Debug, Default)]
         #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
-        #[repr(C)]
+        #[repr(transparent)]
         pub struct $name<T: Float = f32>(T);
 
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: Float + ::bytemuck::Zeroable> ::bytemuck::Zeroable for $name<T> {}
+
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: Float + ::bytemuck::Pod> ::bytemuck::Pod for $name<T> {}
+
+        #[cfg(feature = "bytemuck")]
+        unsafe impl<T: Float> ::bytemuck::TransparentWrapper<T> for $name<T> {}
+
+        ///Samples the angle uniformly around the whole color wheel, in
+        ///`[0.0, 360.0)` degrees.
+        #[cfg(feature = "rand")]
+        impl<T> ::rand::distributions::Distribution<$name<T>> for ::rand::distributions::Standard
+        where
+            T: Float,
+            ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+        {
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $name<T> {
+                $name(rng.gen::<T>() * cast(360.0))
+            }
+        }
+
         impl<T: Float> $name<T> {
             /// Create a new hue from degrees.
             #[inline]
-            pub fn from_degrees(degrees: T) -> $name<T> {
+            pub const fn from_degrees(degrees: T) -> $name<T> {
                 $name(degrees)
             }
 
@@ -33,6 +108,13 @@ macro_rules! make_hues {
                 $name(radians * cast(180.0) / cast(PI))
             }
 
+            /// Create a new hue from turns, where `1` is a full turn around
+            /// the color wheel.
+            #[inline]
+            pub fn from_turns(turns: T) -> $name<T> {
+                $name(turns * cast(360.0))
+            }
+
             /// Get the hue as degrees, in the range `(-180, 180]`.
             #[inline]
             pub fn to_degrees(self) -> T {
@@ -57,6 +139,48 @@ macro_rules! make_hues {
                 normalize_angle_positive(self.0) * cast(PI) / cast(180.0)
             }
 
+            /// Convert the hue to turns, in the range `(-0.5, 0.5]`.
+            #[inline]
+            pub fn to_turns(self) -> T {
+                normalize_angle(self.0) / cast(360.0)
+            }
+
+            /// Convert the hue to positive turns, in the range `[0, 1)`.
+            #[inline]
+            pub fn to_positive_turns(self) -> T {
+                normalize_angle_positive(self.0) / cast(360.0)
+            }
+
+            /// Get the shortest signed distance, in degrees, from this hue
+            /// to `other`. It's in the range `(-180, 180]`, where a positive
+            /// distance goes in the direction of increasing angles.
+            ///
+            /// ```
+            /// use palette::RgbHue;
+            ///
+            /// let red = RgbHue::from_degrees(10.0);
+            /// let magenta = RgbHue::from_degrees(300.0);
+            ///
+            /// assert_eq!(red.hue_distance(magenta), -70.0);
+            /// assert_eq!(magenta.hue_distance(red), 70.0);
+            /// ```
+            #[inline]
+            pub fn hue_distance(self, other: $name<T>) -> T {
+                HueInterpolation::Shorter.distance(self.0, other.0)
+            }
+
+            /// Interpolate between this hue and `other`, by `factor`, going
+            /// around the color wheel as described by `method`.
+            #[inline]
+            pub fn interpolate(
+                self,
+                other: $name<T>,
+                factor: T,
+                method: HueInterpolation,
+            ) -> $name<T> {
+                $name(self.0 + factor * method.distance(self.0, other.0))
+            }
+
             /// Get the internal representation, without normalizing it.
             #[inline]
             pub fn to_raw_degrees(self) -> T {
@@ -241,6 +365,56 @@ macro_rules! make_hues {
                 *self -= other.0;
             }
         }
+
+        impl<T: Float> Mul<T> for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn mul(self, other: T) -> $name<T> {
+                $name(self.0 * other)
+            }
+        }
+
+        impl Mul<$name<f32>> for f32 {
+            type Output = $name<f32>;
+
+            #[inline]
+            fn mul(self, other: $name<f32>) -> $name<f32> {
+                $name(self * other.0)
+            }
+        }
+
+        impl Mul<$name<f64>> for f64 {
+            type Output = $name<f64>;
+
+            #[inline]
+            fn mul(self, other: $name<f64>) -> $name<f64> {
+                $name(self * other.0)
+            }
+        }
+
+        impl<T: Float + MulAssign> MulAssign<T> for $name<T> {
+            #[inline]
+            fn mul_assign(&mut self, other: T) {
+                self.0 *= other;
+            }
+        }
+
+        impl<T: Float> Div<T> for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn div(self, other: T) -> $name<T> {
+                $name(self.0 / other)
+            }
+        }
+
+        impl<T: Float + DivAssign> DivAssign<T> for $name<T> {
+            #[inline]
+            fn div_assign(&mut self, other: T) {
+                self.0 /= other;
+            }
+        }
     )+)
 }
 
@@ -275,7 +449,7 @@ fn normalize_angle_positive<T: Float>(deg: T) -> T {
 #[cfg(test)]
 mod test {
     use RgbHue;
-    use super::{normalize_angle, normalize_angle_positive};
+    use super::{normalize_angle, normalize_angle_positive, HueInterpolation};
 
     #[test]
     fn normalize_angle_0_360() {
@@ -364,7 +538,7 @@ mod test {
             assert!(degs > -180.0 && degs <= 180.0);
 
             let pos_degs = hue.to_positive_degrees();
-            assert!(pos_degs >= 0.0 && pos_degs < 360.0);
+            assert!((0.0..360.0).contains(&pos_degs));
 
             assert_eq!(RgbHue::from(degs), RgbHue::from(pos_degs));
         }
@@ -385,4 +559,77 @@ mod test {
 
         assert_eq!(deserialized, RgbHue::from_degrees(10.2));
     }
+
+    #[test]
+    fn interpolate() {
+        let a = RgbHue::from_degrees(30.0);
+        let b = RgbHue::from_degrees(-60.0);
+
+        let shorter = a.interpolate(b, 0.5, HueInterpolation::Shorter);
+        let longer = a.interpolate(b, 0.5, HueInterpolation::Longer);
+        let increasing = a.interpolate(b, 0.5, HueInterpolation::Increasing);
+        let decreasing = a.interpolate(b, 0.5, HueInterpolation::Decreasing);
+
+        assert_relative_eq!(shorter.to_positive_degrees(), 345.0);
+        assert_relative_eq!(longer.to_positive_degrees(), 165.0);
+        assert_relative_eq!(increasing.to_positive_degrees(), 165.0);
+        assert_relative_eq!(decreasing.to_positive_degrees(), 345.0);
+    }
+
+    #[test]
+    fn interpolate_same_hue() {
+        let a = RgbHue::from_degrees(90.0);
+
+        let shorter = a.interpolate(a, 0.5, HueInterpolation::Shorter);
+        let longer = a.interpolate(a, 0.5, HueInterpolation::Longer);
+        let increasing = a.interpolate(a, 0.5, HueInterpolation::Increasing);
+        let decreasing = a.interpolate(a, 0.5, HueInterpolation::Decreasing);
+
+        assert_relative_eq!(shorter.to_positive_degrees(), 90.0);
+        assert_relative_eq!(longer.to_positive_degrees(), 270.0);
+        assert_relative_eq!(increasing.to_positive_degrees(), 90.0);
+        assert_relative_eq!(decreasing.to_positive_degrees(), 90.0);
+    }
+
+    #[test]
+    fn turns() {
+        let hue = RgbHue::from_turns(0.75);
+
+        assert_relative_eq!(hue.to_degrees(), -90.0);
+        assert_relative_eq!(hue.to_turns(), -0.25);
+        assert_relative_eq!(hue.to_positive_turns(), 0.75);
+        assert_relative_eq!(RgbHue::from_turns(-1.25).to_positive_turns(), 0.75);
+    }
+
+    #[test]
+    fn scalar_arithmetic() {
+        let hue = RgbHue::from_degrees(100.0);
+
+        assert_relative_eq!((hue * 3.0).to_degrees(), -60.0);
+        assert_relative_eq!((2.0f32 * hue).to_degrees(), -160.0);
+        assert_relative_eq!((hue / 4.0).to_degrees(), 25.0);
+
+        let mut hue = hue;
+        hue *= 2.0;
+        hue /= 8.0;
+        assert_relative_eq!(hue.to_degrees(), 25.0);
+    }
+
+    #[test]
+    fn hue_distance() {
+        let a = RgbHue::from_degrees(350.0);
+        let b = RgbHue::from_degrees(-340.0);
+
+        assert_relative_eq!(a.hue_distance(b), 30.0);
+        assert_relative_eq!(b.hue_distance(a), -30.0);
+        assert_relative_eq!(a.hue_distance(a), 0.0);
+        assert_relative_eq!(
+            RgbHue::from_degrees(0.0).hue_distance(RgbHue::from_degrees(180.0)),
+            180.0
+        );
+        assert_relative_eq!(
+            RgbHue::from_degrees(180.0).hue_distance(RgbHue::from_degrees(0.0)),
+            180.0
+        );
+    }
 }
@@ -9,8 +9,8 @@ use encoding::pixel::RawPixel;
 use encoding::Srgb;
 use rgb::RgbSpace;
 use {
-    clamp, Alpha, Component, FromColor, GetHue, Hsv, Hue, IntoColor, Limited, Mix, Pixel, RgbHue,
-    Shade, Xyz,
+    cast, clamp, Alpha, Complement, Component, FromColorUnclamped, GetHue, Hsv, Hue, HueInterpolation, IntoColorUnclamped, Limited,
+    Mix, Pixel, RgbHue, Shade, Xyz,
 };
 
 /// Linear HWB with an alpha component. See the [`Hwba` implementation in
@@ -26,7 +26,7 @@ pub type Hwba<S = Srgb, T = f32> = Alpha<Hwb<S, T>, T>;
 ///
 ///It is very intuitive for humans to use and many color-pickers are based on
 /// the HWB color system
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_rgb_space = "S"]
@@ -71,6 +71,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Hwb<S, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    S: RgbSpace,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Hwb<S, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    S: RgbSpace + 'static,
+{
+}
+
 impl<S, T> Clone for Hwb<S, T>
 where
     T: Component + Float,
@@ -140,6 +156,30 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<S: RgbSpace> Hwb<S, $component> {
+            ///Black, with full blackness.
+            pub const BLACK: Hwb<S, $component> = Hwb {
+                hue: RgbHue::from_degrees(0.0),
+                whiteness: 0.0,
+                blackness: 1.0,
+                space: PhantomData,
+            };
+
+            ///White, with full whiteness.
+            pub const WHITE: Hwb<S, $component> = Hwb {
+                hue: RgbHue::from_degrees(0.0),
+                whiteness: 1.0,
+                blackness: 0.0,
+                space: PhantomData,
+            };
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Hwba"></span>[`Hwba`](type.Hwba.html) implementations.
 impl<T, A> Alpha<Hwb<Srgb, T>, A>
 where
@@ -277,11 +317,19 @@ where
     type Scalar = T;
 
     fn mix(&self, other: &Hwb<S, T>, factor: T) -> Hwb<S, T> {
+        self.mix_with_hue(other, factor, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(
+        &self,
+        other: &Hwb<S, T>,
+        factor: T,
+        hue_interpolation: HueInterpolation,
+    ) -> Hwb<S, T> {
         let factor = clamp(factor, T::zero(), T::one());
-        let hue_diff: T = (other.hue - self.hue).to_degrees();
 
         Hwb {
-            hue: self.hue + factor * hue_diff,
+            hue: self.hue.interpolate(other.hue, factor, hue_interpolation),
             whiteness: self.whiteness + factor * (other.whiteness - self.whiteness),
             blackness: self.blackness + factor * (other.blackness - self.blackness),
             space: PhantomData,
@@ -346,6 +394,16 @@ where
     }
 }
 
+impl<S, T> Complement for Hwb<S, T>
+where
+    T: Component + Float,
+    S: RgbSpace,
+{
+    fn complement(&self) -> Hwb<S, T> {
+        self.shift_hue(cast::<T, _>(180.0))
+    }
+}
+
 impl<S, T> Default for Hwb<S, T>
 where
     T: Component + Float,
@@ -592,6 +650,34 @@ where
     }
 }
 
+///Samples uniformly within the valid HWB colors, where the sum of whiteness
+///and blackness is at most `1.0`.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Hwb<S, T>> for ::rand::distributions::Standard
+where
+    S: RgbSpace,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Hwb<S, T> {
+        let mut whiteness: T = rng.gen();
+        let mut blackness: T = rng.gen();
+
+        //Fold the upper half of the square into the valid triangle
+        if whiteness + blackness > T::one() {
+            whiteness = T::one() - whiteness;
+            blackness = T::one() - blackness;
+        }
+
+        Hwb {
+            hue: rng.gen::<RgbHue<T>>(),
+            whiteness: whiteness,
+            blackness: blackness,
+            space: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Hwb;
@@ -0,0 +1,780 @@
+//!Minimal parsing of ICC color profiles.
+//!
+//!Only matrix/TRC based RGB display profiles, version 2 and 4, are
+//!supported. They describe an RGB space with the XYZ values of its primaries,
+//!a white point and a tone reproduction curve (TRC) for each component, which
+//!is the same information as an [`RgbStandard`](../rgb/trait.RgbStandard.html),
+//!but known at runtime. This is enough for the profiles that are usually
+//!embedded in images, such as sRGB, Display P3 and Adobe RGB. Profiles based
+//!on lookup tables are not supported.
+//!
+//!The XYZ values in a profile are relative to the D50 profile connection
+//!space, so colors are converted to and from `Xyz<D50>`, or to and from sRGB
+//!with Bradford chromatic adaptation.
+//!
+//!Profiles can also be created from an `RgbStandard` and written as version 4
+//!ICC data, for embedding in PNG, JPEG or TIFF files.
+//!
+//!```
+//!use palette::encoding::Srgb;
+//!use palette::icc::RgbProfile;
+//!
+//!let bytes = RgbProfile::from_standard::<Srgb>().to_bytes("sRGB");
+//!
+//!let profile = RgbProfile::parse(&bytes).unwrap();
+//!let color = profile.into_srgb([0.2f32, 0.6, 0.4]);
+//!assert!((color.green - 0.6).abs() < 1e-3);
+//!```
+//!
+//!```no_run
+//!use palette::icc::RgbProfile;
+//!
+//!let bytes = std::fs::read("display.icc").unwrap();
+//!let profile = RgbProfile::parse(&bytes).unwrap();
+//!
+//!let srgb = profile.into_srgb([0.2f32, 0.6, 0.4]);
+//!```
+
+use std::fmt;
+
+use float::Float;
+
+use chromatic_adaptation::{AdaptInto, Method, TransformMatrix};
+use encoding::TransferFn;
+use matrix::{adaptation_matrix, matrix_inverse, multiply_xyz, rgb_to_xyz_matrix};
+use rgb::{RgbSpace, RgbStandard};
+use white_point::{D50, D65, WhitePoint};
+use {cast, clamp, Component, LinSrgb, Mat3, Srgb, Xyz};
+
+///An error from parsing an ICC profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    ///The data ended before the end of the header, the tag table or a tag.
+    UnexpectedEnd,
+    ///The header doesn't have the `acsp` profile signature.
+    InvalidHeader,
+    ///The profile isn't an RGB profile with an XYZ profile connection space.
+    UnsupportedColorSpace,
+    ///A tag that is required for a matrix/TRC profile is missing.
+    MissingTag([u8; 4]),
+    ///A tag has an unsupported type or invalid content.
+    InvalidTag([u8; 4]),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::UnexpectedEnd => fmt.write_str("unexpected end of profile data"),
+            ParseError::InvalidHeader => fmt.write_str("invalid profile header"),
+            ParseError::UnsupportedColorSpace => {
+                fmt.write_str("not an RGB profile with an XYZ connection space")
+            }
+            ParseError::MissingTag(ref tag) => {
+                write!(fmt, "missing tag `{}`", String::from_utf8_lossy(tag))
+            }
+            ParseError::InvalidTag(ref tag) => {
+                write!(fmt, "invalid or unsupported tag `{}`", String::from_utf8_lossy(tag))
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for ParseError {
+    fn description(&self) -> &str {
+        "could not parse ICC profile"
+    }
+}
+
+///A tone reproduction curve, that converts an encoded color component to
+///linear light.
+///
+///The curves are defined for components between `0.0` and `1.0`, and inputs
+///outside of that range are clamped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ToneCurve {
+    ///A parametric curve, with the parameters of the most general ICC
+    ///parametric function: `(a * x + b)^g + e` if `x >= d`, otherwise `c * x +
+    ///f`. Simpler parametric functions and pure gamma curves are expressed
+    ///with this function too.
+    Parametric {
+        ///The exponent.
+        g: f64,
+        ///The scale before the exponent.
+        a: f64,
+        ///The offset before the exponent.
+        b: f64,
+        ///The slope of the linear segment.
+        c: f64,
+        ///The input where the exponential segment starts.
+        d: f64,
+        ///The offset after the exponent.
+        e: f64,
+        ///The offset of the linear segment.
+        f: f64,
+    },
+    ///Evenly spaced samples of the curve, from `0.0` to `1.0`, that are
+    ///linearly interpolated.
+    Table(Vec<f64>),
+}
+
+impl ToneCurve {
+    ///A pure gamma curve, `x^gamma`.
+    pub fn gamma(gamma: f64) -> ToneCurve {
+        ToneCurve::Parametric {
+            g: gamma,
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    ///Convert the component `x` into linear space.
+    pub fn into_linear<T: Float>(&self, x: T) -> T {
+        let x = clamp(cast::<f64, _>(x), 0.0, 1.0);
+
+        let y = match *self {
+            ToneCurve::Parametric { g, a, b, c, d, e, f } => {
+                if x >= d {
+                    (a * x + b).max(0.0).powf(g) + e
+                } else {
+                    c * x + f
+                }
+            }
+            ToneCurve::Table(ref table) => interpolate(table, x),
+        };
+
+        cast(y)
+    }
+
+    ///Convert the component `y` from linear space. The curve is assumed to be
+    ///increasing.
+    pub fn from_linear<T: Float>(&self, y: T) -> T {
+        let y: f64 = cast(y);
+
+        let x = match *self {
+            ToneCurve::Parametric { g, a, b, c, d, e, f } => {
+                let start = (a * d + b).max(0.0).powf(g) + e;
+
+                if y >= start && a != 0.0 && g != 0.0 {
+                    ((y - e).max(0.0).powf(1.0 / g) - b) / a
+                } else if c != 0.0 {
+                    (y - f) / c
+                } else {
+                    0.0
+                }
+            }
+            ToneCurve::Table(ref table) => interpolate_inverse(table, y),
+        };
+
+        cast(clamp(x, 0.0, 1.0))
+    }
+}
+
+fn interpolate(table: &[f64], x: f64) -> f64 {
+    match table.len() {
+        0 => x,
+        1 => table[0],
+        len => {
+            let position = x * (len - 1) as f64;
+            let index = (position.floor() as usize).min(len - 2);
+            let factor = position - index as f64;
+
+            table[index] + (table[index + 1] - table[index]) * factor
+        }
+    }
+}
+
+fn interpolate_inverse(table: &[f64], y: f64) -> f64 {
+    match table.len() {
+        0 => y,
+        1 => 0.0,
+        len => {
+            let index = match table.iter().position(|&value| value > y) {
+                Some(0) => return 0.0,
+                Some(index) => index - 1,
+                None => return 1.0,
+            };
+
+            let range = table[index + 1] - table[index];
+            let factor = if range > 0.0 {
+                (y - table[index]) / range
+            } else {
+                0.0
+            };
+
+            (index as f64 + factor) / (len - 1) as f64
+        }
+    }
+}
+
+///A matrix/TRC based RGB profile.
+///
+///The profile converts encoded RGB components to the D50 profile connection
+///space by linearizing them with the tone curves and multiplying them with
+///the XYZ values of the primaries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RgbProfile {
+    ///The XYZ values of the red primary, adapted to D50.
+    pub red: Xyz<D50, f64>,
+    ///The XYZ values of the green primary, adapted to D50.
+    pub green: Xyz<D50, f64>,
+    ///The XYZ values of the blue primary, adapted to D50.
+    pub blue: Xyz<D50, f64>,
+
+    ///The XYZ values of the white point of the RGB space, before it was
+    ///adapted to D50. Version 4 profiles store the white point as D50 and the
+    ///original white point is restored from their chromatic adaptation tag.
+    pub white_point: [f64; 3],
+
+    ///The tone curve of the red component.
+    pub red_curve: ToneCurve,
+    ///The tone curve of the green component.
+    pub green_curve: ToneCurve,
+    ///The tone curve of the blue component.
+    pub blue_curve: ToneCurve,
+}
+
+impl RgbProfile {
+    ///Create a profile for the RGB standard `S`. The primaries are adapted
+    ///from the white point of `S` to D50 with the Bradford method, and the
+    ///transfer function is sampled into tables with 1024 entries.
+    pub fn from_standard<S: RgbStandard>() -> RgbProfile {
+        let matrix = rgb_to_xyz_matrix::<S::Space, f64>();
+        let white: Xyz<<S::Space as RgbSpace>::WhitePoint, f64> =
+            <S::Space as RgbSpace>::WhitePoint::get_xyz();
+        let adaptation = to_d50_matrix([white.x, white.y, white.z]);
+
+        let primary = |index: usize| -> Xyz<D50, f64> {
+            let xyz = Xyz::<D50, f64>::with_wp(matrix[index], matrix[index + 3], matrix[index + 6]);
+            multiply_xyz(&adaptation, &xyz)
+        };
+
+        let curve = ToneCurve::Table(
+            (0..1024)
+                .map(|index| S::TransferFn::into_linear(index as f64 / 1023.0))
+                .collect(),
+        );
+
+        RgbProfile {
+            red: primary(0),
+            green: primary(1),
+            blue: primary(2),
+            white_point: [white.x, white.y, white.z],
+            red_curve: curve.clone(),
+            green_curve: curve.clone(),
+            blue_curve: curve,
+        }
+    }
+
+    ///Parse a matrix/TRC based RGB profile from the bytes of an ICC file.
+    pub fn parse(bytes: &[u8]) -> Result<RgbProfile, ParseError> {
+        if bytes.len() < 132 {
+            return Err(ParseError::UnexpectedEnd);
+        }
+
+        if &bytes[36..40] != b"acsp" {
+            return Err(ParseError::InvalidHeader);
+        }
+
+        if &bytes[16..20] != b"RGB " || &bytes[20..24] != b"XYZ " {
+            return Err(ParseError::UnsupportedColorSpace);
+        }
+
+        let tags = Tags { bytes: bytes };
+
+        let media_white = tags.xyz(b"wtpt")?;
+        let white_point = match tags.find(b"chad")? {
+            Some(data) if is_d50(media_white) => {
+                let adaptation = parse_matrix(data).ok_or(ParseError::InvalidTag(*b"chad"))?;
+                let white: Xyz<D50, f64> = multiply_xyz(
+                    &matrix_inverse(&adaptation),
+                    &Xyz::<D50, f64>::with_wp(media_white[0], media_white[1], media_white[2]),
+                );
+                [white.x / white.y, 1.0, white.z / white.y]
+            }
+            _ => media_white,
+        };
+
+        let to_xyz = |tag| -> Result<Xyz<D50, f64>, ParseError> {
+            let [x, y, z] = tags.xyz(tag)?;
+            Ok(Xyz::with_wp(x, y, z))
+        };
+
+        Ok(RgbProfile {
+            red: to_xyz(b"rXYZ")?,
+            green: to_xyz(b"gXYZ")?,
+            blue: to_xyz(b"bXYZ")?,
+            white_point: white_point,
+            red_curve: tags.curve(b"rTRC")?,
+            green_curve: tags.curve(b"gTRC")?,
+            blue_curve: tags.curve(b"bTRC")?,
+        })
+    }
+
+    ///The matrix that converts linear RGB to XYZ in the D50 profile
+    ///connection space.
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn matrix(&self) -> Mat3<f64> {
+        [
+            self.red.x, self.green.x, self.blue.x,
+            self.red.y, self.green.y, self.blue.y,
+            self.red.z, self.green.z, self.blue.z,
+        ]
+    }
+
+    ///Convert encoded RGB components to XYZ in the D50 profile connection
+    ///space.
+    pub fn into_xyz<T: Component + Float>(&self, rgb: [T; 3]) -> Xyz<D50, T> {
+        let linear: Xyz<D50, f64> = Xyz::with_wp(
+            self.red_curve.into_linear(cast::<f64, _>(rgb[0])),
+            self.green_curve.into_linear(cast::<f64, _>(rgb[1])),
+            self.blue_curve.into_linear(cast::<f64, _>(rgb[2])),
+        );
+        let xyz: Xyz<D50, f64> = multiply_xyz(&self.matrix(), &linear);
+
+        Xyz::with_wp(cast(xyz.x), cast(xyz.y), cast(xyz.z))
+    }
+
+    ///Convert XYZ in the D50 profile connection space to encoded RGB
+    ///components. Colors outside of the RGB space are clamped by the tone
+    ///curves.
+    pub fn from_xyz<T: Component + Float>(&self, xyz: Xyz<D50, T>) -> [T; 3] {
+        let xyz: Xyz<D50, f64> = Xyz::with_wp(cast(xyz.x), cast(xyz.y), cast(xyz.z));
+        let linear: Xyz<D50, f64> = multiply_xyz(&matrix_inverse(&self.matrix()), &xyz);
+
+        [
+            self.red_curve.from_linear(cast::<T, _>(linear.x)),
+            self.green_curve.from_linear(cast::<T, _>(linear.y)),
+            self.blue_curve.from_linear(cast::<T, _>(linear.z)),
+        ]
+    }
+
+    ///Convert encoded RGB components to sRGB, with Bradford chromatic
+    ///adaptation from D50 to D65.
+    pub fn into_srgb<T: Component + Float>(&self, rgb: [T; 3]) -> Srgb<T> {
+        let linear: LinSrgb<T> = self.into_xyz(rgb).adapt_into();
+        Srgb::from_linear(linear)
+    }
+
+    ///Convert sRGB to encoded RGB components, with Bradford chromatic
+    ///adaptation from D65 to D50.
+    pub fn from_srgb<T: Component + Float>(&self, color: Srgb<T>) -> [T; 3] {
+        let xyz: Xyz<D65, T> = color.into_linear().into();
+        self.from_xyz(xyz.adapt_into())
+    }
+}
+
+impl RgbProfile {
+    ///Write the profile as a version 4.3 ICC display profile, with the
+    ///description `description`.
+    ///
+    ///The white point is written as D50, with a chromatic adaptation tag from
+    ///the white point of the profile, as required by version 4. Parametric
+    ///curves are written as the most general parametric function and tables
+    ///are quantized to 16 bits.
+    pub fn to_bytes(&self, description: &str) -> Vec<u8> {
+        let mut chad = b"sf32\0\0\0\0".to_vec();
+        for &value in &to_d50_matrix(self.white_point) {
+            push_s15_fixed16(&mut chad, value);
+        }
+
+        let xyz = |xyz: &Xyz<D50, f64>| xyz_tag([xyz.x, xyz.y, xyz.z]);
+
+        let tags: [(&[u8; 4], Vec<u8>); 10] = [
+            (b"desc", text_tag(description)),
+            (b"cprt", text_tag("No copyright, use freely")),
+            (b"wtpt", xyz_tag(ICC_D50)),
+            (b"chad", chad),
+            (b"rXYZ", xyz(&self.red)),
+            (b"gXYZ", xyz(&self.green)),
+            (b"bXYZ", xyz(&self.blue)),
+            (b"rTRC", curve_tag(&self.red_curve)),
+            (b"gTRC", curve_tag(&self.green_curve)),
+            (b"bTRC", curve_tag(&self.blue_curve)),
+        ];
+
+        let mut bytes = vec![0; 128];
+        bytes[8..12].copy_from_slice(&[4, 0x30, 0, 0]);
+        bytes[12..16].copy_from_slice(b"mntr");
+        bytes[16..20].copy_from_slice(b"RGB ");
+        bytes[20..24].copy_from_slice(b"XYZ ");
+        bytes[36..40].copy_from_slice(b"acsp");
+        for (index, &value) in ICC_D50.iter().enumerate() {
+            let mut encoded = Vec::with_capacity(4);
+            push_s15_fixed16(&mut encoded, value);
+            bytes[68 + index * 4..72 + index * 4].copy_from_slice(&encoded);
+        }
+
+        push_u32(&mut bytes, tags.len() as u32);
+
+        let mut offset = bytes.len() + tags.len() * 12;
+        for &(signature, ref data) in &tags {
+            bytes.extend(signature);
+            push_u32(&mut bytes, offset as u32);
+            push_u32(&mut bytes, data.len() as u32);
+            offset += padded_len(data.len());
+        }
+
+        for &(_, ref data) in &tags {
+            bytes.extend(data);
+            let padding = padded_len(data.len()) - data.len();
+            bytes.extend(&[0, 0, 0][..padding]);
+        }
+
+        let size = bytes.len() as u32;
+        bytes[..4].copy_from_slice(&[(size >> 24) as u8, (size >> 16) as u8, (size >> 8) as u8, size as u8]);
+
+        bytes
+    }
+}
+
+///The D50 illuminant of the profile connection space, as it's written in ICC
+///profiles.
+const ICC_D50: [f64; 3] = [0.9642, 1.0, 0.8249];
+
+///The Bradford chromatic adaptation matrix from `white_point` to D50.
+fn to_d50_matrix(white_point: [f64; 3]) -> Mat3<f64> {
+    let cone_response = TransformMatrix::<D50, D50, f64>::get_cone_response(&Method::Bradford);
+    let white = Xyz::<D50, f64>::with_wp(white_point[0], white_point[1], white_point[2]);
+    adaptation_matrix(cone_response, &white, &D50::get_xyz::<D50, f64>())
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 3) / 4 * 4
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend(&[(value >> 24) as u8, (value >> 16) as u8, (value >> 8) as u8, value as u8]);
+}
+
+fn push_s15_fixed16(bytes: &mut Vec<u8>, value: f64) {
+    push_u32(bytes, (value * 65536.0).round() as i32 as u32);
+}
+
+fn xyz_tag(xyz: [f64; 3]) -> Vec<u8> {
+    let mut tag = b"XYZ \0\0\0\0".to_vec();
+    for &value in &xyz {
+        push_s15_fixed16(&mut tag, value);
+    }
+    tag
+}
+
+///A `multiLocalizedUnicodeType` with a single English text.
+fn text_tag(text: &str) -> Vec<u8> {
+    let text: Vec<u16> = text.encode_utf16().collect();
+
+    let mut tag = b"mluc\0\0\0\0".to_vec();
+    push_u32(&mut tag, 1);
+    push_u32(&mut tag, 12);
+    tag.extend(b"enUS");
+    push_u32(&mut tag, text.len() as u32 * 2);
+    push_u32(&mut tag, 28);
+
+    for unit in text {
+        tag.extend(&[(unit >> 8) as u8, unit as u8]);
+    }
+
+    tag
+}
+
+fn curve_tag(curve: &ToneCurve) -> Vec<u8> {
+    match *curve {
+        ToneCurve::Parametric { g, a, b, c, d, e, f } => {
+            let mut tag = b"para\0\0\0\0\0\x04\0\0".to_vec();
+            for &parameter in &[g, a, b, c, d, e, f] {
+                push_s15_fixed16(&mut tag, parameter);
+            }
+            tag
+        }
+        ToneCurve::Table(ref table) => {
+            let mut tag = b"curv\0\0\0\0".to_vec();
+            push_u32(&mut tag, table.len() as u32);
+            for &value in table {
+                let value = (clamp(value, 0.0, 1.0) * 65535.0).round() as u16;
+                tag.extend(&[(value >> 8) as u8, value as u8]);
+            }
+            tag
+        }
+    }
+}
+
+fn is_d50(xyz: [f64; 3]) -> bool {
+    let d50: Xyz<D50, f64> = D50::get_xyz();
+    (xyz[0] - d50.x).abs() < 1e-3 && (xyz[1] - d50.y).abs() < 1e-3
+        && (xyz[2] - d50.z).abs() < 1e-3
+}
+
+///Lookup of tags in the tag table of a profile.
+struct Tags<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Tags<'a> {
+    fn find(&self, signature: &[u8; 4]) -> Result<Option<&'a [u8]>, ParseError> {
+        let count = read_u32(self.bytes, 128)? as usize;
+
+        for index in 0..count {
+            let entry = 132 + index * 12;
+            let tag = self.bytes
+                .get(entry..entry + 4)
+                .ok_or(ParseError::UnexpectedEnd)?;
+
+            if tag == signature {
+                let offset = read_u32(self.bytes, entry + 4)? as usize;
+                let size = read_u32(self.bytes, entry + 8)? as usize;
+
+                return self.bytes
+                    .get(offset..offset.saturating_add(size))
+                    .map(Some)
+                    .ok_or(ParseError::UnexpectedEnd);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn get(&self, signature: &[u8; 4]) -> Result<&'a [u8], ParseError> {
+        self.find(signature)?
+            .ok_or(ParseError::MissingTag(*signature))
+    }
+
+    fn xyz(&self, signature: &[u8; 4]) -> Result<[f64; 3], ParseError> {
+        let data = self.get(signature)?;
+        let invalid = ParseError::InvalidTag(*signature);
+
+        if data.get(..4) != Some(b"XYZ ") {
+            return Err(invalid);
+        }
+
+        Ok([
+            read_s15_fixed16(data, 8).map_err(|_| invalid)?,
+            read_s15_fixed16(data, 12).map_err(|_| invalid)?,
+            read_s15_fixed16(data, 16).map_err(|_| invalid)?,
+        ])
+    }
+
+    fn curve(&self, signature: &[u8; 4]) -> Result<ToneCurve, ParseError> {
+        let data = self.get(signature)?;
+        parse_curve(data).ok_or(ParseError::InvalidTag(*signature))
+    }
+}
+
+fn parse_curve(data: &[u8]) -> Option<ToneCurve> {
+    match data.get(..4)? {
+        b"curv" => {
+            let count = read_u32(data, 8).ok()? as usize;
+
+            match count {
+                0 => Some(ToneCurve::gamma(1.0)),
+                1 => Some(ToneCurve::gamma(read_u16(data, 12).ok()? as f64 / 256.0)),
+                _ => (0..count)
+                    .map(|index| read_u16(data, 12 + index * 2).ok().map(|value| value as f64 / 65535.0))
+                    .collect::<Option<Vec<_>>>()
+                    .map(ToneCurve::Table),
+            }
+        }
+        b"para" => {
+            let function = read_u16(data, 8).ok()?;
+            let count = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return None,
+            };
+
+            let mut parameters = [0.0; 7];
+            for (index, parameter) in parameters[..count].iter_mut().enumerate() {
+                *parameter = read_s15_fixed16(data, 12 + index * 4).ok()?;
+            }
+
+            let [g, a, b, c, d, e, f] = parameters;
+
+            Some(match function {
+                0 => ToneCurve::gamma(g),
+                1 => ToneCurve::Parametric { g: g, a: a, b: b, c: 0.0, d: -b / a, e: 0.0, f: 0.0 },
+                2 => ToneCurve::Parametric { g: g, a: a, b: b, c: 0.0, d: -b / a, e: c, f: c },
+                3 => ToneCurve::Parametric { g: g, a: a, b: b, c: c, d: d, e: 0.0, f: 0.0 },
+                _ => ToneCurve::Parametric { g: g, a: a, b: b, c: c, d: d, e: e, f: f },
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_matrix(data: &[u8]) -> Option<Mat3<f64>> {
+    if data.get(..4)? != b"sf32" {
+        return None;
+    }
+
+    let mut matrix = [0.0; 9];
+    for (index, value) in matrix.iter_mut().enumerate() {
+        *value = read_s15_fixed16(data, 8 + index * 4).ok()?;
+    }
+
+    Some(matrix)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, ParseError> {
+    let bytes = bytes
+        .get(offset..offset + 2)
+        .ok_or(ParseError::UnexpectedEnd)?;
+    Ok((bytes[0] as u16) << 8 | bytes[1] as u16)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, ParseError> {
+    Ok((read_u16(bytes, offset)? as u32) << 16 | read_u16(bytes, offset + 2)? as u32)
+}
+
+fn read_s15_fixed16(bytes: &[u8], offset: usize) -> Result<f64, ParseError> {
+    Ok(read_u32(bytes, offset)? as i32 as f64 / 65536.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{push_s15_fixed16, xyz_tag, ParseError, RgbProfile, ToneCurve};
+    use chromatic_adaptation::AdaptInto;
+    use encoding::{Srgb, TransferFn};
+    use white_point::D50;
+    use {LinSrgb, Xyz};
+
+    fn profile(tags: Vec<(&[u8; 4], Vec<u8>)>) -> Vec<u8> {
+        let mut bytes = vec![0; 128];
+        bytes[8] = 4;
+        bytes[12..16].copy_from_slice(b"mntr");
+        bytes[16..20].copy_from_slice(b"RGB ");
+        bytes[20..24].copy_from_slice(b"XYZ ");
+        bytes[36..40].copy_from_slice(b"acsp");
+
+        let count = tags.len();
+        bytes.extend(&[0, 0, 0, count as u8]);
+
+        let mut offset = 132 + count * 12;
+        for &(signature, ref data) in &tags {
+            bytes.extend(signature);
+            bytes.extend(&[0, 0, (offset >> 8) as u8, offset as u8]);
+            bytes.extend(&[0, 0, (data.len() >> 8) as u8, data.len() as u8]);
+            offset += data.len();
+        }
+
+        for (_, data) in tags {
+            bytes.extend(data);
+        }
+
+        bytes
+    }
+
+    //An sRGB profile in the version 4 style, with a D50 media white point,
+    //a chromatic adaptation tag and parametric curves.
+    fn srgb_profile() -> Vec<u8> {
+        let red: Xyz<D50, f64> = LinSrgb::new(1.0, 0.0, 0.0).adapt_into();
+        let green: Xyz<D50, f64> = LinSrgb::new(0.0, 1.0, 0.0).adapt_into();
+        let blue: Xyz<D50, f64> = LinSrgb::new(0.0, 0.0, 1.0).adapt_into();
+
+        let mut curve = b"para\0\0\0\0\0\x03\0\0".to_vec();
+        for &parameter in &[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045] {
+            push_s15_fixed16(&mut curve, parameter);
+        }
+
+        let mut chad = b"sf32\0\0\0\0".to_vec();
+        for &value in &[
+            1.0478, 0.0229, -0.0501, 0.0295, 0.9905, -0.0171, -0.0092, 0.0151, 0.7517,
+        ] {
+            push_s15_fixed16(&mut chad, value);
+        }
+
+        profile(vec![
+            (b"wtpt", xyz_tag([0.9642, 1.0, 0.8249])),
+            (b"chad", chad),
+            (b"rXYZ", xyz_tag([red.x, red.y, red.z])),
+            (b"gXYZ", xyz_tag([green.x, green.y, green.z])),
+            (b"bXYZ", xyz_tag([blue.x, blue.y, blue.z])),
+            (b"rTRC", curve.clone()),
+            (b"gTRC", curve.clone()),
+            (b"bTRC", curve),
+        ])
+    }
+
+    #[test]
+    fn parse_srgb() {
+        let profile = RgbProfile::parse(&srgb_profile()).unwrap();
+
+        assert_relative_eq!(profile.white_point[0], 0.9505, epsilon = 1e-3);
+        assert_relative_eq!(profile.white_point[2], 1.0891, epsilon = 1e-3);
+
+        for &x in &[0.0, 0.02, 0.3, 0.75, 1.0] {
+            assert_relative_eq!(profile.red_curve.into_linear(x), Srgb::into_linear(x), epsilon = 1e-4);
+            assert_relative_eq!(profile.red_curve.from_linear(Srgb::into_linear(x)), x, epsilon = 1e-4);
+        }
+
+        let srgb = profile.into_srgb([0.2, 0.6, 0.4]);
+        assert_relative_eq!(srgb, ::Srgb::new(0.2, 0.6, 0.4), epsilon = 1e-3);
+
+        let rgb = profile.from_srgb(::Srgb::new(0.9, 0.1, 0.5));
+        assert_relative_eq!(&rgb[..], &[0.9, 0.1, 0.5][..], epsilon = 1e-3);
+    }
+
+    #[test]
+    fn curves() {
+        let table = ToneCurve::Table(vec![0.0, 0.25, 1.0]);
+        assert_relative_eq!(table.into_linear(0.25), 0.125);
+        assert_relative_eq!(table.into_linear(0.75), 0.625);
+        assert_relative_eq!(table.from_linear(0.625), 0.75);
+        assert_relative_eq!(table.into_linear(2.0), 1.0);
+
+        let gamma = ToneCurve::gamma(2.2);
+        assert_relative_eq!(gamma.into_linear(0.5), 0.5f64.powf(2.2));
+        assert_relative_eq!(gamma.from_linear(0.5f64.powf(2.2)), 0.5);
+
+        assert_eq!(super::parse_curve(b"curv\0\0\0\0\0\0\0\x01\x02\x33"), Some(ToneCurve::gamma(2.19921875)));
+        assert_eq!(super::parse_curve(b"curv\0\0\0\0\0\0\0\0"), Some(ToneCurve::gamma(1.0)));
+        assert_eq!(super::parse_curve(b"curv\0\0\0\0\0\0\0\x02\0\0"), None);
+    }
+
+    #[test]
+    fn write_and_parse() {
+        let profile = RgbProfile::from_standard::<Srgb>();
+        let bytes = profile.to_bytes("Test sRGB");
+
+        assert_eq!(bytes.len() % 4, 0);
+        assert_eq!(bytes.len(), super::read_u32(&bytes, 0).unwrap() as usize);
+        assert_eq!(&bytes[36..40], b"acsp");
+
+        let parsed = RgbProfile::parse(&bytes).unwrap();
+        assert_relative_eq!(parsed.red, profile.red, epsilon = 1e-4);
+        assert_relative_eq!(parsed.blue, profile.blue, epsilon = 1e-4);
+        assert_relative_eq!(&parsed.white_point[..], &profile.white_point[..], epsilon = 1e-3);
+
+        for &x in &[0.0, 0.001, 0.02, 0.3, 0.75, 1.0] {
+            assert_relative_eq!(parsed.green_curve.into_linear(x), Srgb::into_linear(x), epsilon = 1e-4);
+        }
+
+        let srgb = parsed.into_srgb([0.2, 0.6, 0.4]);
+        assert_relative_eq!(srgb, ::Srgb::new(0.2, 0.6, 0.4), epsilon = 1e-3);
+
+        let mut gamma = profile.clone();
+        gamma.red_curve = ToneCurve::gamma(2.2);
+        let parsed = RgbProfile::parse(&gamma.to_bytes("Gamma")).unwrap();
+        assert_relative_eq!(parsed.red_curve.into_linear(0.5), 0.5f64.powf(2.2), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(RgbProfile::parse(&[0; 100]), Err(ParseError::UnexpectedEnd));
+        assert_eq!(RgbProfile::parse(&[0; 200]), Err(ParseError::InvalidHeader));
+
+        let mut bytes = srgb_profile();
+        bytes[16..20].copy_from_slice(b"GRAY");
+        assert_eq!(RgbProfile::parse(&bytes), Err(ParseError::UnsupportedColorSpace));
+
+        let bytes = profile(vec![(b"wtpt", xyz_tag([0.9642, 1.0, 0.8249]))]);
+        assert_eq!(RgbProfile::parse(&bytes), Err(ParseError::MissingTag(*b"rXYZ")));
+
+        let bytes = profile(vec![(b"wtpt", b"desc".to_vec())]);
+        assert_eq!(RgbProfile::parse(&bytes), Err(ParseError::InvalidTag(*b"wtpt")));
+    }
+}
@@ -0,0 +1,274 @@
+//!Integration with the [`image`](https://crates.io/crates/image) crate.
+//!
+//!RGB and luma colors, with or without transparency, implement
+//!`image::Pixel`, so they can be used directly as the pixel type of an
+//!`ImageBuffer`. They have the same memory layout as the corresponding pixel
+//!types from `image`, so a buffer can be cast between the two without
+//!copying, using [`cast`](fn.cast.html). Colors that don't have a counterpart
+//!in `image` can be produced with [`map`](fn.map.html).
+//!
+//!```
+//!extern crate image;
+//!extern crate palette;
+//!
+//!use image::{ImageBuffer, RgbImage};
+//!use palette::{Invert, Srgb};
+//!use palette::image_buffer::{cast, map};
+//!
+//!fn main() {
+//!    let image = RgbImage::from_pixel(4, 4, image::Rgb([255, 128, 0]));
+//!
+//!    //View the image as sRGB, without copying it
+//!    let image: ImageBuffer<Srgb<u8>, _> = cast(image);
+//!
+//!    //Invert each pixel
+//!    let inverted: ImageBuffer<Srgb<u8>, _> = map(&image, |color| color.invert());
+//!
+//!    let inverted: RgbImage = cast(inverted);
+//!    assert_eq!(inverted.get_pixel(0, 0).data, [0, 127, 255]);
+//!}
+//!```
+
+use core::ops::Deref;
+
+use image::{self, ColorType, ImageBuffer, Primitive};
+
+use luma::{Luma, LumaStandard};
+use rgb::{Rgb, RgbStandard};
+use {Alpha, Component, Pixel};
+
+///Cast an image buffer to another pixel type with the same components,
+///without copying it. This is meant for going between the pixel types from
+///`image` and the colors in Palette.
+///
+///It panics if the pixel types don't have the same number of channels.
+pub fn cast<P, Q, Container>(buffer: ImageBuffer<P, Container>) -> ImageBuffer<Q, Container>
+where
+    P: image::Pixel + 'static,
+    Q: image::Pixel<Subpixel = P::Subpixel> + 'static,
+    P::Subpixel: 'static,
+    Container: Deref<Target = [P::Subpixel]>,
+{
+    assert_eq!(
+        P::channel_count(),
+        Q::channel_count(),
+        "the pixel types have different numbers of channels"
+    );
+
+    let (width, height) = buffer.dimensions();
+    ImageBuffer::from_raw(width, height, buffer.into_raw())
+        .expect("the buffer should have the same size when the channels are the same")
+}
+
+///Convert each pixel in an image buffer, and collect the results in a new
+///buffer of the same size.
+pub fn map<P, Q, Container, F>(
+    buffer: &ImageBuffer<P, Container>,
+    mut convert: F,
+) -> ImageBuffer<Q, Vec<Q::Subpixel>>
+where
+    P: image::Pixel + 'static,
+    Q: image::Pixel + 'static,
+    P::Subpixel: 'static,
+    Q::Subpixel: 'static,
+    Container: Deref<Target = [P::Subpixel]>,
+    F: FnMut(P) -> Q,
+{
+    let (width, height) = buffer.dimensions();
+    ImageBuffer::from_fn(width, height, |x, y| convert(*buffer.get_pixel(x, y)))
+}
+
+macro_rules! impl_image_pixel {
+    ($self_ty:ty, $param:ident: $bound:ident, $image_ty:ident, $model:expr, $color_type:ident) => {
+        impl<$param, T> image::Pixel for $self_ty
+        where
+            $param: $bound + 'static,
+            T: Component + Primitive + 'static,
+        {
+            type Subpixel = T;
+
+            fn channel_count() -> u8 {
+                <Self as Pixel<T>>::CHANNELS as u8
+            }
+
+            fn channels(&self) -> &[T] {
+                self.as_raw()
+            }
+
+            fn channels_mut(&mut self) -> &mut [T] {
+                self.as_raw_mut()
+            }
+
+            fn color_model() -> &'static str {
+                $model
+            }
+
+            fn color_type() -> ColorType {
+                ColorType::$color_type(::core::mem::size_of::<T>() as u8 * 8)
+            }
+
+            fn channels4(&self) -> (T, T, T, T) {
+                as_image(self).channels4()
+            }
+
+            fn from_channels(a: T, b: T, c: T, d: T) -> Self {
+                *Self::from_raw(image::$image_ty::from_channels(a, b, c, d).channels())
+            }
+
+            fn from_slice(slice: &[T]) -> &Self {
+                assert_eq!(slice.len(), <Self as Pixel<T>>::CHANNELS);
+                Self::from_raw(slice)
+            }
+
+            fn from_slice_mut(slice: &mut [T]) -> &mut Self {
+                assert_eq!(slice.len(), <Self as Pixel<T>>::CHANNELS);
+                Self::from_raw_mut(slice)
+            }
+
+            fn to_rgb(&self) -> image::Rgb<T> {
+                as_image(self).to_rgb()
+            }
+
+            fn to_rgba(&self) -> image::Rgba<T> {
+                as_image(self).to_rgba()
+            }
+
+            fn to_luma(&self) -> image::Luma<T> {
+                as_image(self).to_luma()
+            }
+
+            fn to_luma_alpha(&self) -> image::LumaA<T> {
+                as_image(self).to_luma_alpha()
+            }
+
+            fn map<F>(&self, f: F) -> Self
+            where
+                F: FnMut(T) -> T,
+            {
+                *Self::from_raw(as_image(self).map(f).channels())
+            }
+
+            fn apply<F>(&mut self, f: F)
+            where
+                F: FnMut(T) -> T,
+            {
+                as_image_mut(self).apply(f)
+            }
+
+            fn map_with_alpha<F, G>(&self, f: F, g: G) -> Self
+            where
+                F: FnMut(T) -> T,
+                G: FnMut(T) -> T,
+            {
+                *Self::from_raw(as_image(self).map_with_alpha(f, g).channels())
+            }
+
+            fn apply_with_alpha<F, G>(&mut self, f: F, g: G)
+            where
+                F: FnMut(T) -> T,
+                G: FnMut(T) -> T,
+            {
+                as_image_mut(self).apply_with_alpha(f, g)
+            }
+
+            fn map2<F>(&self, other: &Self, f: F) -> Self
+            where
+                F: FnMut(T, T) -> T,
+            {
+                *Self::from_raw(as_image(self).map2(as_image(other), f).channels())
+            }
+
+            fn apply2<F>(&mut self, other: &Self, f: F)
+            where
+                F: FnMut(T, T) -> T,
+            {
+                as_image_mut(self).apply2(as_image(other), f)
+            }
+
+            fn invert(&mut self) {
+                as_image_mut(self).invert()
+            }
+
+            fn blend(&mut self, other: &Self) {
+                as_image_mut(self).blend(as_image(other))
+            }
+        }
+
+        impl<$param, T> AsImage<T> for $self_ty
+        where
+            $param: $bound + 'static,
+            T: Component + Primitive + 'static,
+        {
+            type Image = image::$image_ty<T>;
+        }
+    };
+}
+
+//The pixel types from `image` have the same layout as the colors, so the
+//operations can be delegated to them.
+trait AsImage<T>: Pixel<T> {
+    type Image: image::Pixel<Subpixel = T>;
+}
+
+fn as_image<C: AsImage<T>, T: 'static>(color: &C) -> &C::Image {
+    <C::Image as image::Pixel>::from_slice(color.as_raw())
+}
+
+fn as_image_mut<C: AsImage<T>, T: 'static>(color: &mut C) -> &mut C::Image {
+    <C::Image as image::Pixel>::from_slice_mut(color.as_raw_mut())
+}
+
+impl_image_pixel!(Rgb<S, T>, S: RgbStandard, Rgb, "RGB", RGB);
+impl_image_pixel!(Alpha<Rgb<S, T>, T>, S: RgbStandard, Rgba, "RGBA", RGBA);
+impl_image_pixel!(Luma<S, T>, S: LumaStandard, Luma, "Y", Gray);
+impl_image_pixel!(Alpha<Luma<S, T>, T>, S: LumaStandard, LumaA, "YA", GrayA);
+
+#[cfg(test)]
+mod test {
+    use image::{self, GrayImage, ImageBuffer, Pixel, RgbaImage};
+
+    use super::{cast, map};
+    use {LinSrgb, Srgb, SrgbLuma, Srgba};
+
+    #[test]
+    fn pixel_operations() {
+        let mut color = Srgba::new(10u8, 20, 30, 40);
+        assert_eq!(color.channels(), &[10, 20, 30, 40]);
+        assert_eq!(color.to_rgb(), image::Rgb([10, 20, 30]));
+        assert_eq!(color.map_with_alpha(|c| c * 2, |a| a + 1), Srgba::new(20, 40, 60, 41));
+
+        color.invert();
+        assert_eq!(color, Srgba::new(245, 235, 225, 40));
+
+        assert_eq!(SrgbLuma::<u8>::from_channels(7, 8, 9, 10), SrgbLuma::new(7));
+        assert_eq!(Srgb::<u8>::channel_count(), 3);
+    }
+
+    #[test]
+    fn cast_buffers() {
+        let image = RgbaImage::from_pixel(2, 3, image::Rgba([1, 2, 3, 4]));
+
+        let colors: ImageBuffer<Srgba<u8>, _> = cast(image);
+        assert_eq!(colors.dimensions(), (2, 3));
+        assert_eq!(*colors.get_pixel(1, 2), Srgba::new(1, 2, 3, 4));
+
+        let image: RgbaImage = cast(colors);
+        assert_eq!(image.get_pixel(1, 2).data, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different numbers of channels")]
+    fn cast_wrong_channels() {
+        let image = GrayImage::new(2, 2);
+        let _: ImageBuffer<Srgb<u8>, _> = cast(image);
+    }
+
+    #[test]
+    fn map_buffer() {
+        let image: ImageBuffer<Srgb<u8>, _> = ImageBuffer::from_pixel(3, 1, Srgb::new(255, 255, 255));
+        let linear: ImageBuffer<LinSrgb<f32>, _> = map(&image, |color| color.into_format().into_linear());
+
+        assert_eq!(linear.dimensions(), (3, 1));
+        assert_relative_eq!(*linear.get_pixel(2, 0), LinSrgb::new(1.0, 1.0, 1.0));
+    }
+}
@@ -24,7 +24,7 @@ pub type Laba<Wp, T = f32> = Alpha<Lab<Wp, T>, T>;
 ///
 ///The parameters of L\*a\*b\* are quite different, compared to many other
 /// color spaces, so manipulating them manually may be unintuitive.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "Wp"]
@@ -60,6 +60,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Zeroable for Lab<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    Wp: WhitePoint,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Pod for Lab<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    Wp: WhitePoint + 'static,
+{
+}
+
 impl<Wp, T> Clone for Lab<Wp, T>
 where
     T: Component + Float,
@@ -111,6 +127,48 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<Wp: WhitePoint> Lab<Wp, $component> {
+            ///Black, with no lightness.
+            pub const BLACK: Lab<Wp, $component> = Lab {
+                l: 0.0,
+                a: 0.0,
+                b: 0.0,
+                white_point: PhantomData,
+            };
+
+            ///The white point, with full lightness.
+            pub const WHITE: Lab<Wp, $component> = Lab {
+                l: 100.0,
+                a: 0.0,
+                b: 0.0,
+                white_point: PhantomData,
+            };
+
+            ///The lowest valid value of `l`.
+            pub const MIN_L: $component = 0.0;
+
+            ///The highest valid value of `l`.
+            pub const MAX_L: $component = 100.0;
+
+            ///The lowest valid value of `a`.
+            pub const MIN_A: $component = -128.0;
+
+            ///The highest valid value of `a`.
+            pub const MAX_A: $component = 127.0;
+
+            ///The lowest valid value of `b`.
+            pub const MIN_B: $component = -128.0;
+
+            ///The highest valid value of `b`.
+            pub const MAX_B: $component = 127.0;
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Laba"></span>[`Laba`](type.Laba.html) implementations.
 impl<T, A> Alpha<Lab<D65, T>, A>
 where
@@ -599,6 +657,25 @@ where
     }
 }
 
+///Samples uniformly within `0.0 <= l < 100.0`, `-128.0 <= a < 127.0` and
+///`-128.0 <= b < 127.0`.
+#[cfg(feature = "rand")]
+impl<Wp, T> ::rand::distributions::Distribution<Lab<Wp, T>> for ::rand::distributions::Standard
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Lab<Wp, T> {
+        Lab {
+            l: rng.gen::<T>() * cast(100.0),
+            a: rng.gen::<T>() * cast(255.0) - cast(128.0),
+            b: rng.gen::<T>() * cast(255.0) - cast(128.0),
+            white_point: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Lab;
@@ -658,4 +735,16 @@ mod test {
 
         assert_eq!(deserialized, Lab::new(0.3, 0.8, 0.1));
     }
+
+    #[test]
+    fn constants() {
+        use {Limited, Srgb};
+
+        assert_eq!(Lab::<D65, f32>::WHITE, Lab::from(Srgb::new(1.0, 1.0, 1.0).into_linear()));
+        assert_eq!(Lab::<D65, f64>::BLACK, Lab::new(0.0, 0.0, 0.0));
+
+        type Lab32 = Lab<D65, f32>;
+        assert!(Lab32::new(Lab32::MAX_L, Lab32::MIN_A, Lab32::MAX_B).is_valid());
+        assert!(!Lab32::new(Lab32::MIN_L, Lab32::MAX_A + 1.0, 0.0).is_valid());
+    }
 }
@@ -6,8 +6,8 @@ use core::ops::{Add, AddAssign, Sub, SubAssign};
 use encoding::pixel::RawPixel;
 use white_point::{D65, WhitePoint};
 use {cast, clamp};
-use {Alpha, Hue, Lab, LabHue, Xyz};
-use {Component, FromColor, GetHue, IntoColor, Limited, Mix, Pixel, Saturate, Shade};
+use {Alpha, Hue, HueInterpolation, Lab, LabHue, Xyz};
+use {Complement, Component, FromColorUnclamped, GetHue, IntoColorUnclamped, Limited, Mix, Pixel, Saturate, SaturateFixed, Shade};
 
 /// CIE L\*C\*h° with an alpha component. See the [`Lcha` implementation in
 /// `Alpha`](struct.Alpha.html#Lcha).
@@ -19,7 +19,7 @@ pub type Lcha<Wp, T = f32> = Alpha<Lch<Wp, T>, T>;
 /// it's a cylindrical color space, like [HSL](struct.Hsl.html) and
 ///[HSV](struct.Hsv.html). This gives it the same ability to directly change
 ///the hue and colorfulness of a color, while preserving other visual aspects.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "Wp"]
@@ -60,6 +60,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Zeroable for Lch<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    Wp: WhitePoint,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Pod for Lch<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    Wp: WhitePoint + 'static,
+{
+}
+
 impl<Wp, T> Clone for Lch<Wp, T>
 where
     T: Component + Float,
@@ -111,6 +127,39 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<Wp: WhitePoint> Lch<Wp, $component> {
+            ///Black, with no lightness.
+            pub const BLACK: Lch<Wp, $component> = Lch {
+                l: 0.0,
+                chroma: 0.0,
+                hue: LabHue::from_degrees(0.0),
+                white_point: PhantomData,
+            };
+
+            ///The white point, with full lightness.
+            pub const WHITE: Lch<Wp, $component> = Lch {
+                l: 100.0,
+                chroma: 0.0,
+                hue: LabHue::from_degrees(0.0),
+                white_point: PhantomData,
+            };
+
+            ///The lowest valid value of `l`.
+            pub const MIN_L: $component = 0.0;
+
+            ///The highest valid value of `l`.
+            pub const MAX_L: $component = 100.0;
+
+            ///The lowest valid value of `chroma`. It has no upper limit.
+            pub const MIN_CHROMA: $component = 0.0;
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Lcha"></span>[`Lcha`](type.Lcha.html) implementations.
 impl<T, A> Alpha<Lch<D65, T>, A>
 where
@@ -235,12 +284,20 @@ where
     type Scalar = T;
 
     fn mix(&self, other: &Lch<Wp, T>, factor: T) -> Lch<Wp, T> {
+        self.mix_with_hue(other, factor, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(
+        &self,
+        other: &Lch<Wp, T>,
+        factor: T,
+        hue_interpolation: HueInterpolation,
+    ) -> Lch<Wp, T> {
         let factor = clamp(factor, T::zero(), T::one());
-        let hue_diff: T = (other.hue - self.hue).to_degrees();
         Lch {
             l: self.l + factor * (other.l - self.l),
             chroma: self.chroma + factor * (other.chroma - self.chroma),
-            hue: self.hue + factor * hue_diff,
+            hue: self.hue.interpolate(other.hue, factor, hue_interpolation),
             white_point: PhantomData,
         }
     }
@@ -303,6 +360,16 @@ where
     }
 }
 
+impl<Wp, T> Complement for Lch<Wp, T>
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+{
+    fn complement(&self) -> Lch<Wp, T> {
+        self.shift_hue(cast::<T, _>(180.0))
+    }
+}
+
 impl<Wp, T> Saturate for Lch<Wp, T>
 where
     T: Component + Float,
@@ -313,7 +380,22 @@ where
     fn saturate(&self, factor: T) -> Lch<Wp, T> {
         Lch {
             l: self.l,
-            chroma: self.chroma * (T::one() + factor),
+            chroma: (self.chroma * (T::one() + factor)).max(T::zero()),
+            hue: self.hue,
+            white_point: PhantomData,
+        }
+    }
+}
+
+impl<Wp, T> SaturateFixed for Lch<Wp, T>
+where
+    T: Component + Float,
+    Wp: WhitePoint,
+{
+    fn saturate_fixed(&self, amount: T) -> Lch<Wp, T> {
+        Lch {
+            l: self.l,
+            chroma: (self.chroma + amount * cast(128.0)).max(T::zero()),
             hue: self.hue,
             white_point: PhantomData,
         }
@@ -468,6 +550,26 @@ where
     }
 }
 
+///Samples uniformly within a cylinder with `0.0 <= l < 100.0` and
+///`0.0 <= chroma < 128.0`. The chroma is the square root of a uniform value,
+///to not crowd the colors around the gray axis.
+#[cfg(feature = "rand")]
+impl<Wp, T> ::rand::distributions::Distribution<Lch<Wp, T>> for ::rand::distributions::Standard
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Lch<Wp, T> {
+        Lch {
+            l: rng.gen::<T>() * cast(100.0),
+            chroma: rng.gen::<T>().sqrt() * cast(128.0),
+            hue: rng.gen::<LabHue<T>>(),
+            white_point: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use white_point::D65;
@@ -489,6 +591,61 @@ mod test {
         }
     }
 
+    #[test]
+    fn perceptual_shade() {
+        use {Hsl, IntoColorUnclamped, PerceptualShade};
+
+        let color = Hsl::new(30.0, 0.6, 0.4);
+        let original = color.into_lch();
+
+        let lighter = color.lighten_perceptual(0.5).into_lch();
+        assert_relative_eq!(lighter.l, (original.l + 100.0) / 2.0, epsilon = 1e-3);
+        assert_relative_eq!(lighter.chroma, original.chroma, epsilon = 1e-3);
+        assert_relative_eq!(lighter.hue, original.hue, epsilon = 1e-3);
+
+        let darker = color.darken_perceptual(0.5).into_lch();
+        assert_relative_eq!(darker.l, original.l / 2.0, epsilon = 1e-3);
+        assert_relative_eq!(darker.hue, original.hue, epsilon = 1e-3);
+
+        let fixed = color.lighten_perceptual_fixed(0.1).into_lch();
+        assert_relative_eq!(fixed.l, original.l + 10.0, epsilon = 1e-3);
+
+        let black = Lch::new(5.0f64, 10.0, 60.0).darken_perceptual_fixed(0.2);
+        assert_relative_eq!(black, Lch::new(0.0, 10.0, 60.0));
+    }
+
+    #[test]
+    fn vibrance() {
+        use {vibrance_slice, Vibrance};
+
+        let gray_ish = Lch::new(50.0f64, 32.0, 200.0);
+        assert_relative_eq!(gray_ish.vibrance(0.5), Lch::new(50.0, 44.0, 200.0));
+        assert_relative_eq!(gray_ish.vibrance(-0.5), Lch::new(50.0, 20.0, 200.0));
+
+        let full = Lch::new(50.0f64, 130.0, 200.0);
+        assert_relative_eq!(full.vibrance(0.5), full);
+
+        //Halfway out of the skin tone range
+        let skin = Lch::new(50.0f64, 32.0, 65.0);
+        assert_relative_eq!(skin.vibrance(0.5), Lch::new(50.0, 38.0, 65.0));
+
+        let mut colors = [gray_ish, skin];
+        vibrance_slice(&mut colors, 0.5);
+        assert_relative_eq!(colors[0], Lch::new(50.0, 44.0, 200.0));
+    }
+
+    #[test]
+    fn saturate_clamped() {
+        use {Saturate, SaturateFixed};
+
+        let color = Lch::new(50.0f64, 40.0, 30.0);
+
+        assert_relative_eq!(color.saturate(0.5), Lch::new(50.0, 60.0, 30.0));
+        assert_relative_eq!(color.desaturate(2.0), Lch::new(50.0, 0.0, 30.0));
+        assert_relative_eq!(color.saturate_fixed(0.25), Lch::new(50.0, 72.0, 30.0));
+        assert_relative_eq!(color.desaturate_fixed(0.5), Lch::new(50.0, 0.0, 30.0));
+    }
+
     raw_pixel_conversion_tests!(Lch<D65>: l, chroma, hue);
     raw_pixel_conversion_fail_tests!(Lch<D65>: l, chroma, hue);
 
@@ -122,7 +122,9 @@
 //! processing. This includes things like blending, hue shifting, darkening and
 //! conversion to other formats. Just make sure that your non-linear RGB is
 //! made linear first (`my_srgb.into_linear()`), to make the operations
-//! available.
+//! available. Blending is the exception, since blending the encoded values is
+//! sometimes needed to match other software, but it's still more physically
+//! accurate to blend linear colors.
 //!
 //! Different color spaced have different capabilities, pros and cons. You may
 //! have to experiment a bit (or look at the example programs) to find out what
@@ -134,6 +136,31 @@
 //! into some image format. The same rules applies as for the decoding, but the
 //! process reversed.
 //!
+//! # Comparing Colors
+//!
+//! Floating point colors are rarely exactly equal after a conversion, so all
+//! color types with floating point components, including the hues and
+//! `Alpha`, implement `AbsDiffEq`, `RelativeEq` and `UlpsEq` from the
+//! [`approx`](https://crates.io/crates/approx) crate. They compare the colors
+//! component by component, and hues are compared by their shortest distance
+//! on the color wheel:
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate approx;
+//! extern crate palette;
+//!
+//! use palette::{Hsla, Lch, LinSrgb};
+//!
+//! fn main() {
+//!     let color = LinSrgb::new(0.2f64, 0.6, 0.4);
+//!     let round_trip = LinSrgb::from(Lch::from(color));
+//!     assert_relative_eq!(color, round_trip, epsilon = 1e-12);
+//!
+//!     assert_abs_diff_eq!(Hsla::new(-10.0, 0.5, 0.5, 1.0), Hsla::new(350.0, 0.5, 0.5, 1.0));
+//! }
+//! ```
+//!
 
 // Keep the standard library when running tests, too
 #![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
@@ -145,6 +172,9 @@
 #[cfg(any(feature = "std", test))]
 extern crate core;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg_attr(test, macro_use)]
 extern crate approx;
 
@@ -156,6 +186,31 @@ extern crate num_traits;
 #[cfg(feature = "phf")]
 extern crate phf;
 
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+
+#[cfg(feature = "encase")]
+#[macro_use]
+extern crate encase;
+
+#[cfg(feature = "glam")]
+extern crate glam;
+
+#[cfg(feature = "image")]
+extern crate image;
+
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "simd")]
+extern crate wide;
+
 #[cfg(feature = "serializing")]
 #[macro_use]
 extern crate serde;
@@ -166,13 +221,14 @@ use num_traits::{NumCast, ToPrimitive, Zero};
 use float::Float;
 
 use luma::Luma;
+use white_point::{WhitePoint, D65};
 
 #[doc(hidden)]
 pub use palette_derive::*;
 
-pub use alpha::Alpha;
+pub use alpha::{Alpha, WithAlpha};
 pub use blend::Blend;
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 pub use gradient::Gradient;
 
 pub use hsl::{Hsl, Hsla};
@@ -186,9 +242,12 @@ pub use xyz::{Xyz, Xyza};
 pub use yxy::{Yxy, Yxya};
 
 pub use convert::{ConvertFrom, ConvertInto, OutOfBounds, FromColor, IntoColor};
+pub use convert::{FromColorUnclamped, IntoColorUnclamped};
+pub use convert::{OutOfGamut, TryFromColor, TryIntoColor};
+pub use channels::Channels;
 pub use encoding::pixel::Pixel;
-pub use hues::{LabHue, RgbHue};
-pub use matrix::Mat3;
+pub use hues::{HueInterpolation, LabHue, RgbHue};
+pub use matrix::{ColorMatrix, Mat3};
 
 //Helper macro for checking ranges and clamping.
 #[cfg(test)]
@@ -345,12 +404,54 @@ macro_rules! assert_ranges {
 #[macro_use]
 mod macros;
 
+pub mod adjust;
+#[cfg(feature = "nalgebra")]
+pub mod algebra;
 pub mod blend;
-#[cfg(feature = "std")]
+pub mod bulk;
+pub mod cast;
+#[cfg(feature = "colormaps")]
+pub mod colormap;
+pub mod contrast;
+pub mod converter;
+pub mod cri;
+pub mod css;
+pub mod cvd;
+pub mod depth;
+pub mod dither;
+#[cfg(feature = "fast-approx")]
+pub mod fast;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "encase")]
+pub mod gpu;
+#[cfg(feature = "alloc")]
 pub mod gradient;
+pub mod harmonies;
+pub mod hdr10;
+#[cfg(feature = "icc")]
+pub mod icc;
+#[cfg(all(feature = "image", feature = "std"))]
+pub mod image_buffer;
+#[cfg(feature = "alloc")]
+pub mod lut;
+pub mod macadam;
+pub mod mastering;
+#[cfg(feature = "munsell")]
+pub mod munsell;
 
 #[cfg(feature = "named")]
 pub mod named;
+pub mod nearest;
+
+#[cfg(feature = "alloc")]
+pub mod quantize;
+
+pub mod scale;
+pub mod seeded;
+
+#[cfg(feature = "serializing")]
+pub mod serialize;
 
 mod alpha;
 mod hsl;
@@ -366,12 +467,24 @@ mod yxy;
 
 mod hues;
 
+mod channels;
+
 pub mod chromatic_adaptation;
 mod convert;
 pub mod encoding;
 mod equality;
+#[cfg(feature = "glam")]
+mod glam_impl;
 mod matrix;
+pub mod spectral;
+pub mod statistics;
+pub mod temperature;
+pub mod tm30;
+pub mod tonal;
+pub mod tonemap;
+pub mod white_balance;
 pub mod white_point;
+pub mod whiteness;
 
 pub mod float;
 
@@ -420,6 +533,100 @@ pub trait Mix {
     ///the same color as `self` and `1.0` will result in the same color as
     ///`other`.
     fn mix(&self, other: &Self, factor: Self::Scalar) -> Self;
+
+    ///Mix the color with an other color, by `factor`, using `hue_interpolation`
+    ///to decide which way around the color wheel the hue should go.
+    ///
+    ///Colors without a hue ignore `hue_interpolation`, and behave just like
+    ///[`mix`](#tymethod.mix).
+    ///
+    ///```
+    ///use palette::{Hsv, HueInterpolation, Mix};
+    ///
+    ///let a = Hsv::new(30.0, 1.0, 1.0);
+    ///let b = Hsv::new(330.0, 1.0, 1.0);
+    ///
+    ///assert_eq!(a.mix(&b, 0.5), Hsv::new(0.0, 1.0, 1.0));
+    ///assert_eq!(
+    ///    a.mix_with_hue(&b, 0.5, HueInterpolation::Increasing),
+    ///    Hsv::new(180.0, 1.0, 1.0)
+    ///);
+    ///```
+    fn mix_with_hue(
+        &self,
+        other: &Self,
+        factor: Self::Scalar,
+        hue_interpolation: HueInterpolation,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = hue_interpolation;
+        self.mix(other, factor)
+    }
+}
+
+/// A trait for linear color interpolation in place.
+///
+/// It's implemented for every color that implements [`Mix`](trait.Mix.html).
+///
+/// ```
+/// use palette::{LinSrgb, MixAssign};
+///
+/// let mut a = LinSrgb::new(0.0, 0.5, 1.0);
+/// a.mix_assign(&LinSrgb::new(1.0, 0.5, 0.0), 0.5);
+///
+/// assert_eq!(a, LinSrgb::new(0.5, 0.5, 0.5));
+/// ```
+pub trait MixAssign {
+    ///The type of the mixing factor.
+    type Scalar: Float;
+
+    ///Mix the color with an other color, by `factor`, and store the result in
+    ///`self`.
+    ///
+    ///`factor` sould be between `0.0` and `1.0`, where `0.0` will leave `self`
+    ///unchanged and `1.0` will make it the same color as `other`.
+    fn mix_assign(&mut self, other: &Self, factor: Self::Scalar);
+}
+
+impl<C: Mix> MixAssign for C {
+    type Scalar = C::Scalar;
+
+    fn mix_assign(&mut self, other: &C, factor: C::Scalar) {
+        *self = self.mix(other, factor);
+    }
+}
+
+/// Mix each color in `destination` with the corresponding color in `source`,
+/// by `factor`, and store the results in `destination`.
+///
+/// This is useful for cross-fading whole images or other large buffers of
+/// colors, without allocating a new buffer for the result.
+///
+/// ```
+/// use palette::{mix_slices, LinSrgb};
+///
+/// let mut frame = vec![LinSrgb::new(0.0, 0.0, 0.0); 4];
+/// let next_frame = vec![LinSrgb::new(1.0, 0.5, 0.0); 4];
+///
+/// mix_slices(&mut frame, &next_frame, 0.5);
+/// assert!(frame.iter().all(|&c| c == LinSrgb::new(0.5, 0.25, 0.0)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the slices don't have the same length.
+pub fn mix_slices<C: MixAssign>(destination: &mut [C], source: &[C], factor: C::Scalar) {
+    assert_eq!(
+        destination.len(),
+        source.len(),
+        "the slices must have the same length"
+    );
+
+    for (destination, source) in destination.iter_mut().zip(source) {
+        destination.mix_assign(source, factor);
+    }
 }
 
 /// The `Shade` trait allows a color to be lightened or darkened.
@@ -445,6 +652,219 @@ pub trait Shade: Sized {
     }
 }
 
+/// A trait for lightening and darkening colors by their perceived lightness.
+///
+/// Changing the lightness component of HSL or HSV, or scaling RGB, also
+/// changes how saturated the color looks, and may even shift its perceived
+/// hue. This trait instead converts the color to CIE L\*C\*h°, changes its
+/// lightness, `L*`, and converts it back, so the hue and chroma stay the same.
+///
+/// It's implemented for every color that can be converted to and from the
+/// CIE color spaces. The lightened or
+/// darkened color may end up outside the color's valid range, for example
+/// when lightening a saturated RGB color, so it may need to be clamped
+/// afterwards.
+///
+/// ```
+/// use palette::{Lch, PerceptualShade, Srgb};
+///
+/// let color = Srgb::new(0.8f64, 0.3, 0.2);
+///
+/// // Move the lightness 20% of the way towards white.
+/// let lighter = color.lighten_perceptual(0.2);
+///
+/// // Reduce the lightness by 10% of the full range.
+/// let darker = color.darken_perceptual_fixed(0.1);
+///
+/// let (original, darker): (Lch<_, f64>, Lch<_, f64>) = (color.into(), darker.into());
+/// assert!((original.l - darker.l - 10.0).abs() < 1e-6);
+/// ```
+pub trait PerceptualShade<Wp = D65, T = f32>: Sized
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    ///Change the perceived lightness with `change`, which is given the
+    ///current `L*` lightness, in the range `[0.0, 100.0]`, and returns the new
+    ///lightness.
+    fn shade_perceptual<F: FnOnce(T) -> T>(&self, change: F) -> Self;
+
+    ///Lighten the color by `factor`, relative to how far it is from white. A
+    ///factor of `0.0` leaves the color unchanged and `1.0` results in white.
+    fn lighten_perceptual(&self, factor: T) -> Self {
+        let max: T = cast(100.0);
+        self.shade_perceptual(|l| l + (max - l) * factor)
+    }
+
+    ///Darken the color by `factor`, relative to how far it is from black. A
+    ///factor of `0.0` leaves the color unchanged and `1.0` results in black.
+    fn darken_perceptual(&self, factor: T) -> Self {
+        self.shade_perceptual(|l| l - l * factor)
+    }
+
+    ///Lighten the color by a fixed `amount`, where `1.0` is the whole
+    ///lightness range, from black to white.
+    fn lighten_perceptual_fixed(&self, amount: T) -> Self {
+        self.shade_perceptual(|l| l + amount * cast(100.0))
+    }
+
+    ///Darken the color by a fixed `amount`, where `1.0` is the whole
+    ///lightness range, from black to white.
+    fn darken_perceptual_fixed(&self, amount: T) -> Self {
+        self.lighten_perceptual_fixed(-amount)
+    }
+}
+
+impl<C, Wp, T> PerceptualShade<Wp, T> for C
+where
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    fn shade_perceptual<F: FnOnce(T) -> T>(&self, change: F) -> Self {
+        let lch = self.clone().into_lch();
+        let l = clamp(change(lch.l), T::zero(), cast(100.0));
+
+        C::from_lch(Lch::with_wp(l, lch.chroma, lch.hue))
+    }
+}
+
+/// A trait for changing the exposure of colors, in linear light.
+///
+/// Both operations scale the amount of light in the color, which is what
+/// happens when the exposure of a camera is changed. The scaling is always
+/// done in linear light, even for encoded colors, such as sRGB, which are
+/// converted to linear and back again. This makes the change photometrically
+/// correct, and it keeps the chromaticity of the color unchanged.
+///
+/// It's implemented for every color that can be converted to and from `Xyz`.
+/// The result may need to be clamped afterwards.
+///
+/// ```
+/// use palette::{Exposure, LinSrgb, Srgb};
+///
+/// let color = LinSrgb::new(0.1f64, 0.2, 0.3);
+///
+/// // One stop doubles the amount of light.
+/// let brighter = color.expose(1.0);
+/// assert!((brighter.green - 0.4).abs() < 1e-9);
+///
+/// let darker = color.gain(0.5);
+/// assert!((darker.green - 0.1).abs() < 1e-9);
+///
+/// // Encoded colors are changed in linear light.
+/// let encoded = Srgb::new(0.5f64, 0.5, 0.5);
+/// let darker = encoded.expose(-1.0);
+/// assert!((darker.into_linear().red - encoded.into_linear().red / 2.0).abs() < 1e-9);
+/// ```
+pub trait Exposure<Wp = D65, T = f32>: Sized
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    ///Multiply the amount of light in the color by `factor`.
+    fn gain(&self, factor: T) -> Self;
+
+    ///Change the exposure by `stops`, where each stop doubles the amount of
+    ///light. Negative values darken the color.
+    fn expose(&self, stops: T) -> Self {
+        self.gain(cast::<T, _>(2.0).powf(stops))
+    }
+}
+
+impl<C, Wp, T> Exposure<Wp, T> for C
+where
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    fn gain(&self, factor: T) -> Self {
+        let xyz = self.clone().into_xyz();
+        C::from_xyz(Xyz::with_wp(xyz.x * factor, xyz.y * factor, xyz.z * factor))
+    }
+}
+
+/// A trait for adjusting the vibrance of colors.
+///
+/// Vibrance is a smarter form of saturation, which increases the chroma of
+/// muted colors more than the chroma of colors that are already saturated. It
+/// also holds back around the hues of skin tones, roughly 20° to 80° in CIE
+/// L\*C\*h°, so people don't end up looking sunburnt. A negative amount mutes
+/// the colors instead, in the same way.
+///
+/// The color is converted to CIE L\*C\*h° and back, so the lightness and hue
+/// stay the same. It's implemented for every color that can be converted to
+/// and from the CIE color spaces, and the result may need to be clamped
+/// afterwards.
+///
+/// ```
+/// use palette::{Lch, Vibrance};
+///
+/// let muted = Lch::new(60.0f64, 20.0, 250.0);
+/// let saturated = Lch::new(60.0f64, 100.0, 250.0);
+///
+/// // The muted color gets a larger boost than the saturated color.
+/// let muted_boost = muted.vibrance(0.5).chroma / muted.chroma;
+/// let saturated_boost = saturated.vibrance(0.5).chroma / saturated.chroma;
+/// assert!(muted_boost > saturated_boost);
+///
+/// // Skin tones are protected.
+/// let skin = Lch::new(70.0f64, 25.0, 50.0);
+/// assert_eq!(skin.vibrance(0.5), skin);
+/// ```
+pub trait Vibrance<Wp = D65, T = f32>: Sized
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    ///Change the vibrance by `amount`. An amount of `0.0` leaves the color
+    ///unchanged, and `1.0` at most doubles the chroma of a gray-ish color.
+    fn vibrance(&self, amount: T) -> Self;
+}
+
+impl<C, Wp, T> Vibrance<Wp, T> for C
+where
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    fn vibrance(&self, amount: T) -> Self {
+        let lch = self.clone().into_lch();
+
+        //Saturated colors get less of a boost
+        let saturation = clamp(lch.chroma / cast(128.0), T::zero(), T::one());
+
+        //Skin tones get less of a boost, fading out 30° from the center
+        let skin_distance = (lch.hue - cast::<T, _>(50.0)).to_degrees().abs();
+        let skin = clamp(T::one() - skin_distance / cast(30.0), T::zero(), T::one());
+
+        let boost = amount * (T::one() - saturation) * (T::one() - skin);
+        let chroma = (lch.chroma * (T::one() + boost)).max(T::zero());
+
+        C::from_lch(Lch::with_wp(lch.l, chroma, lch.hue))
+    }
+}
+
+/// Change the vibrance of each color in `colors` by `amount`, and store the
+/// results in `colors`. See [`Vibrance`](trait.Vibrance.html) for details.
+///
+/// ```
+/// use palette::{vibrance_slice, Srgb};
+///
+/// let mut image = vec![Srgb::new(0.5, 0.45, 0.4).into_linear(); 16];
+/// vibrance_slice(&mut image, 0.4);
+/// ```
+pub fn vibrance_slice<C, Wp, T>(colors: &mut [C], amount: T)
+where
+    C: Vibrance<Wp, T>,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    for color in colors {
+        *color = color.vibrance(amount);
+    }
+}
+
 /// A trait for colors where a hue may be calculated.
 ///
 /// ```
@@ -500,15 +920,84 @@ pub trait Saturate: Sized {
     ///The type of the (de)saturation factor.
     type Scalar: Float;
 
-    ///Increase the saturation by `factor`.
+    ///Increase the saturation by `factor`, relative to the current
+    ///saturation. A factor of `0.2` increases it by 20%.
     fn saturate(&self, factor: Self::Scalar) -> Self;
 
-    ///Decrease the saturation by `factor`.
+    ///Decrease the saturation by `factor`, relative to the current
+    ///saturation. A factor of `0.2` decreases it by 20%.
     fn desaturate(&self, factor: Self::Scalar) -> Self {
         self.saturate(-factor)
     }
 }
 
+/// A trait for colors where the saturation (or chroma) can be changed by a
+/// fixed amount, instead of relative to its current value.
+///
+/// The result is clamped to the valid saturation range of the color space, so
+/// it never becomes negative, and never goes above `1.0` for HSL and HSV.
+///
+/// ```
+/// use palette::{Hsv, SaturateFixed};
+///
+/// let a = Hsv::new(0.0, 0.25, 1.0);
+///
+/// // Increase the saturation by a quarter of the full range.
+/// assert_eq!(a.saturate_fixed(0.25), Hsv::new(0.0, 0.5, 1.0));
+/// assert_eq!(a.saturate_fixed(2.0), Hsv::new(0.0, 1.0, 1.0));
+/// ```
+pub trait SaturateFixed: Saturate {
+    ///Increase the saturation by a fixed `amount`, where `1.0` is the whole
+    ///saturation range. For CIE L\*C\*h°, the range is considered to be
+    ///`[0.0, 128.0]`.
+    fn saturate_fixed(&self, amount: Self::Scalar) -> Self;
+
+    ///Decrease the saturation by a fixed `amount`, where `1.0` is the whole
+    ///saturation range. For CIE L\*C\*h°, the range is considered to be
+    ///`[0.0, 128.0]`.
+    fn desaturate_fixed(&self, amount: Self::Scalar) -> Self {
+        self.saturate_fixed(-amount)
+    }
+}
+
+/// A trait for inverting each component of a color.
+///
+/// Each component is subtracted from its maximum intensity, which turns white
+/// into black and red into cyan, like a photographic negative. The inversion
+/// is done directly on the components, so the result depends on the encoding
+/// of the color. Inverting an sRGB encoded color gives the same result as the
+/// "invert" filter in most image editors.
+///
+/// ```
+/// use palette::{Invert, Srgb};
+///
+/// assert_eq!(Srgb::new(1.0, 0.25, 0.0).invert(), Srgb::new(0.0, 0.75, 1.0));
+/// assert_eq!(Srgb::<u8>::new(255, 64, 0).invert(), Srgb::new(0, 191, 255));
+/// ```
+pub trait Invert {
+    ///Return a new color with each component inverted.
+    fn invert(&self) -> Self;
+}
+
+/// A trait for getting the complement of a color, which is the color on the
+/// opposite side of the color wheel.
+///
+/// The complement is found by rotating the hue by 180°, which makes the result
+/// depend on the color space. Convert the color to the preferred cylindrical
+/// space, such as HSL or CIE L\*C\*h°, first to choose how the complement is
+/// calculated.
+///
+/// ```
+/// use palette::{Complement, Hsl, Lch};
+///
+/// assert_eq!(Hsl::new(30.0, 0.8, 0.5).complement(), Hsl::new(210.0, 0.8, 0.5));
+/// assert_eq!(Lch::new(50.0, 40.0, 300.0).complement(), Lch::new(50.0, 40.0, 120.0));
+/// ```
+pub trait Complement {
+    ///Return the color on the opposite side of the color wheel.
+    fn complement(&self) -> Self;
+}
+
 ///Perform a unary or binary operation on each component of a color.
 pub trait ComponentWise {
     ///The scalar type for color components.
@@ -540,6 +1029,17 @@ pub trait Component: Copy + Zero + PartialOrd + NumCast {
     fn convert<T: Component>(&self) -> T;
 }
 
+/// Color components with constant lowest and highest displayable values, for
+/// defining constant colors, such as `Srgb::WHITE`.
+pub trait ConstComponent: Component {
+    /// The lowest displayable value, which is zero.
+    const ZERO: Self;
+
+    /// The highest displayable value, which is the same as
+    /// [`max_intensity`](trait.Component.html#tymethod.max_intensity).
+    const MAX_INTENSITY: Self;
+}
+
 impl Component for f32 {
     const LIMITED: bool = false;
 
@@ -652,6 +1152,36 @@ impl Component for u64 {
     }
 }
 
+impl ConstComponent for f32 {
+    const ZERO: Self = 0.0;
+    const MAX_INTENSITY: Self = 1.0;
+}
+
+impl ConstComponent for f64 {
+    const ZERO: Self = 0.0;
+    const MAX_INTENSITY: Self = 1.0;
+}
+
+impl ConstComponent for u8 {
+    const ZERO: Self = 0;
+    const MAX_INTENSITY: Self = u8::MAX;
+}
+
+impl ConstComponent for u16 {
+    const ZERO: Self = 0;
+    const MAX_INTENSITY: Self = u16::MAX;
+}
+
+impl ConstComponent for u32 {
+    const ZERO: Self = 0;
+    const MAX_INTENSITY: Self = u32::MAX;
+}
+
+impl ConstComponent for u64 {
+    const ZERO: Self = 0;
+    const MAX_INTENSITY: Self = u64::MAX;
+}
+
 /// A convenience function to convert a constant number to Float Type
 #[inline]
 fn cast<T: NumCast, P: ToPrimitive>(prim: P) -> T {
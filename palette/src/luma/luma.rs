@@ -6,7 +6,7 @@ use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 
 use float::Float;
 
-use blend::PreAlpha;
+use blend::{BlendFunction, PreAlpha};
 use clamp;
 use encoding::linear::LinearFn;
 use encoding::pixel::RawPixel;
@@ -14,7 +14,7 @@ use encoding::{Linear, Srgb, TransferFn};
 use luma::LumaStandard;
 use white_point::WhitePoint;
 use {Alpha, Xyz, Yxy};
-use {Blend, Component, ComponentWise, FromColor, IntoColor, Limited, Mix, Pixel, Shade};
+use {Blend, Component, ComponentWise, ConstComponent, FromColorUnclamped, IntoColorUnclamped, Invert, Limited, Mix, Pixel, Shade};
 
 /// Luminance with an alpha component. See the [`Lumaa` implementation
 /// in `Alpha`](struct.Alpha.html#Lumaa).
@@ -27,7 +27,7 @@ pub type Lumaa<S = Srgb, T = f32> = Alpha<Luma<S, T>, T>;
 ///perceived to be. It's basically the `Y` component of [CIE
 ///XYZ](struct.Xyz.html). The lack of any form of hue representation limits
 ///the set of operations that can be performed on it.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "S::WhitePoint"]
@@ -55,6 +55,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Luma<S, T>
+where
+    T: Component + ::bytemuck::Zeroable,
+    S: LumaStandard,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Luma<S, T>
+where
+    T: Component + ::bytemuck::Pod,
+    S: LumaStandard + 'static,
+{
+}
+
 impl<S, T> Clone for Luma<S, T>
 where
     T: Component,
@@ -65,6 +81,24 @@ where
     }
 }
 
+impl<S, T> Luma<S, T>
+where
+    T: ConstComponent,
+    S: LumaStandard,
+{
+    /// Black, with no luminance.
+    pub const BLACK: Luma<S, T> = Luma {
+        luma: T::ZERO,
+        standard: PhantomData,
+    };
+
+    /// White, with the maximum intensity.
+    pub const WHITE: Luma<S, T> = Luma {
+        luma: T::MAX_INTENSITY,
+        standard: PhantomData,
+    };
+}
+
 impl<S, T> Luma<S, T>
 where
     T: Component,
@@ -132,6 +166,35 @@ where
             color.luma,
         )))
     }
+
+    /// Blend the color with `destination` in the `St` encoding, instead of
+    /// the color's own encoding. The colors are converted to `St` before
+    /// blending, and the result is converted back.
+    ///
+    /// ```
+    /// use palette::{Blend, SrgbLuma};
+    /// use palette::blend::BlendMode;
+    /// use palette::encoding::Linear;
+    ///
+    /// let a = SrgbLuma::new(0.5);
+    /// let b = SrgbLuma::new(0.25);
+    ///
+    /// let encoded = a.blend(b, BlendMode::Screen);
+    /// let linear = a.blend_in::<Linear<_>, _>(b, BlendMode::Screen);
+    ///
+    /// assert_eq!(encoded, SrgbLuma::new(0.625));
+    /// assert!(linear.luma < encoded.luma);
+    /// ```
+    pub fn blend_in<St, F>(self, destination: Self, blend_function: F) -> Self
+    where
+        St: LumaStandard<WhitePoint = S::WhitePoint>,
+        F: BlendFunction<Luma<St, T>>,
+    {
+        Self::from_encoding(
+            self.into_encoding::<St>()
+                .blend(destination.into_encoding(), blend_function),
+        )
+    }
 }
 
 ///<span id="Lumaa"></span>[`Lumaa`](type.Lumaa.html) implementations.
@@ -211,6 +274,28 @@ where
     }
 }
 
+///<span id="Lumaa"></span>[`Lumaa`](type.Lumaa.html) implementations.
+impl<S, T> Alpha<Luma<S, T>, T>
+where
+    T: Component + Float,
+    S: LumaStandard,
+{
+    /// Blend the color with `destination` in the `St` encoding, instead of
+    /// the color's own encoding. The colors are converted to `St` before
+    /// blending, and the result is converted back. See
+    /// [`Luma::blend_in`](struct.Luma.html#method.blend_in) for more details.
+    pub fn blend_in<St, F>(self, destination: Self, blend_function: F) -> Self
+    where
+        St: LumaStandard<WhitePoint = S::WhitePoint>,
+        F: BlendFunction<Luma<St, T>>,
+    {
+        Self::from_encoding(
+            self.into_encoding::<St>()
+                .blend(destination.into_encoding(), blend_function),
+        )
+    }
+}
+
 impl<S, T> From<Xyz<S::WhitePoint, T>> for Luma<S, T>
 where
     S: LumaStandard,
@@ -261,7 +346,7 @@ impl<S: LumaStandard, T: Component, A: Component> Into<(T, A)> for Alpha<Luma<S,
     }
 }
 
-impl<S, Wp, T> IntoColor<Wp, T> for Luma<S, T>
+impl<S, Wp, T> IntoColorUnclamped<Wp, T> for Luma<S, T>
 where
     S: LumaStandard<WhitePoint = Wp>,
     T: Component + Float,
@@ -335,7 +420,7 @@ where
 impl<S, T> Blend for Luma<S, T>
 where
     T: Component + Float,
-    S: LumaStandard<TransferFn = LinearFn>,
+    S: LumaStandard,
 {
     type Color = Luma<S, T>;
 
@@ -348,6 +433,19 @@ where
     }
 }
 
+impl<S, T> Invert for Luma<S, T>
+where
+    T: Component + Sub<Output = T>,
+    S: LumaStandard,
+{
+    fn invert(&self) -> Luma<S, T> {
+        Luma {
+            luma: T::max_intensity() - self.luma,
+            standard: PhantomData,
+        }
+    }
+}
+
 impl<S, T> ComponentWise for Luma<S, T>
 where
     T: Component,
@@ -691,6 +789,10 @@ where
     S: LumaStandard,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(f, "{:0width$x}", self.luma, width = size)
     }
@@ -702,16 +804,56 @@ where
     S: LumaStandard,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(f, "{:0width$X}", self.luma, width = size)
     }
 }
 
+///Samples the luma uniformly, in `[0.0, 1.0)` for floating point components
+///and over the full range for integer components.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Luma<S, T>> for ::rand::distributions::Standard
+where
+    S: LumaStandard,
+    T: Component,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Luma<S, T> {
+        Luma {
+            luma: rng.gen(),
+            standard: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use encoding::Srgb;
     use Luma;
 
+    #[test]
+    fn into_luma_with() {
+        use encoding::Linear;
+        use luma::{IntoLuma, LumaWeights};
+        use white_point::D65;
+        use LinSrgb;
+
+        let color = LinSrgb::new(0.2f64, 0.5, 0.8);
+
+        let equal: Luma<Linear<D65>, f64> = color.into_luma_with(LumaWeights::Equal);
+        assert_relative_eq!(equal.luma, 0.5);
+
+        let bt2020: Luma<Linear<D65>, f64> = color.into_luma_with(LumaWeights::Bt2020);
+        assert_relative_eq!(bt2020.luma, 0.2 * 0.2627 + 0.5 * 0.6780 + 0.8 * 0.0593);
+
+        let white: Luma<Srgb, f64> = ::Srgb::new(1.0, 1.0, 1.0).into_luma_with(LumaWeights::Bt601);
+        assert_relative_eq!(white.luma, 1.0);
+    }
+
     #[test]
     fn ranges() {
         assert_ranges!{
@@ -750,6 +892,12 @@ mod test {
         assert_eq!(format!("{:03x}", Luma::<Srgb, u64>::new(1)), "001");
     }
 
+    #[test]
+    fn hex_alternate() {
+        assert_eq!(format!("{:#x}", Luma::<Srgb, u8>::new(171)), "#ab");
+        assert_eq!(format!("{:#X}", Luma::<Srgb, u8>::new(171)), "#AB");
+    }
+
     #[test]
     fn upper_hex() {
         assert_eq!(format!("{:X}", Luma::<Srgb, u8>::new(161)), "A1");
@@ -2,8 +2,12 @@
 
 mod luma;
 
+use float::Float;
+
 use white_point::{D65, WhitePoint};
 use encoding::{Gamma, Linear, Srgb, TransferFn};
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use {cast, Component};
 
 pub use self::luma::{Luma, Lumaa};
 
@@ -35,3 +39,79 @@ impl<Wp: WhitePoint, T: TransferFn> LumaStandard for (Wp, T) {
     type WhitePoint = Wp;
     type TransferFn = T;
 }
+
+/// The weights for calculating luma from red, green and blue.
+///
+/// Which weights are the right ones depends on the RGB primaries and on the
+/// pipeline the luma is meant for. Video standards define their own weights,
+/// while equal weights give a simple average.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LumaWeights {
+    /// ITU-R BT.601, used for standard definition video and by many image
+    /// formats, such as JPEG.
+    Bt601,
+    /// ITU-R BT.709, used for high definition video. The weights match the
+    /// sRGB primaries.
+    Bt709,
+    /// ITU-R BT.2020, used for ultra high definition and HDR video.
+    Bt2020,
+    /// The same weight for each component.
+    Equal,
+}
+
+impl LumaWeights {
+    /// Get the `[red, green, blue]` weights. They add up to `1.0`.
+    pub fn weights<T: Float>(self) -> [T; 3] {
+        let (red, green, blue) = match self {
+            LumaWeights::Bt601 => (0.299, 0.587, 0.114),
+            LumaWeights::Bt709 => (0.2126, 0.7152, 0.0722),
+            LumaWeights::Bt2020 => (0.2627, 0.6780, 0.0593),
+            LumaWeights::Equal => {
+                let third = T::one() / cast(3.0);
+                return [third, third, third];
+            }
+        };
+
+        [cast(red), cast(green), cast(blue)]
+    }
+}
+
+/// Convert a color to gray scale luma, using a selectable weighting.
+///
+/// The weights are applied to the components as they are, which means that
+/// encoded RGB colors result in encoded luma, with the same transfer
+/// function. This is how video standards define luma. Convert the color to
+/// linear RGB first to get the relative luminance instead.
+///
+/// ```
+/// use palette::{LinLuma, LinSrgb, Srgb, SrgbLuma};
+/// use palette::luma::{IntoLuma, LumaWeights};
+///
+/// let color = Srgb::new(0.8, 0.4, 0.1);
+/// let luma: SrgbLuma = color.into_luma_with(LumaWeights::Bt601);
+/// assert!((luma.luma - 0.4854).abs() < 1e-6);
+///
+/// let linear = LinSrgb::new(0.0, 1.0, 0.0);
+/// let luminance: LinLuma = linear.into_luma_with(LumaWeights::Bt709);
+/// assert_eq!(luminance.luma, 0.7152);
+/// ```
+pub trait IntoLuma<St, T>
+where
+    St: LumaStandard,
+    T: Component + Float,
+{
+    /// Convert the color to luma, using `weights`.
+    fn into_luma_with(self, weights: LumaWeights) -> Luma<St, T>;
+}
+
+impl<S, St, T> IntoLuma<St, T> for Rgb<S, T>
+where
+    S: RgbStandard,
+    St: LumaStandard<WhitePoint = <S::Space as RgbSpace>::WhitePoint, TransferFn = S::TransferFn>,
+    T: Component + Float,
+{
+    fn into_luma_with(self, weights: LumaWeights) -> Luma<St, T> {
+        let [red, green, blue] = weights.weights::<T>();
+        Luma::new(self.red * red + self.green * green + self.blue * blue)
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use super::{Lut3d, ParseError};
+use rgb::{Rgb, RgbStandard};
+use {cast, Component};
+
+///The largest supported level of a Hald image, that gives a table with 256
+///samples along each side.
+const MAX_LEVEL: usize = 16;
+
+impl Lut3d {
+    ///Create a lookup table from the pixels of a Hald CLUT image.
+    ///
+    ///A Hald image of level `L` is a square image with `L^3` pixels along each
+    ///side, that holds a table with `L^2` samples along each side. The pixels
+    ///are read row by row, and the red component of the table changes
+    ///fastest, then green and then blue, so it's the same order as in
+    ///[`table`](#method.table). The level is detected from the number of
+    ///pixels, and may be between 2 and 16.
+    ///
+    ///The pixels are usually decoded from a PNG file with 8 or 16 bits per
+    ///component, and can be converted from a raw buffer with
+    ///[`Pixel::from_raw_slice`](../encoding/pixel/trait.Pixel.html#method.from_raw_slice).
+    ///
+    ///```
+    ///use palette::Srgb;
+    ///use palette::lut::{hald_identity, Lut3d};
+    ///
+    /////An identity image with a warming look applied to it
+    ///let image: Vec<Srgb<u8>> = hald_identity(4)
+    ///    .into_iter()
+    ///    .map(|color: Srgb<u8>| Srgb::new(color.red, color.green, (color.blue as f32 * 0.9) as u8))
+    ///    .collect();
+    ///
+    ///let lut = Lut3d::from_hald(&image).unwrap();
+    ///assert_eq!(lut.size(), 16);
+    ///
+    ///let color = lut.apply(Srgb::new(0.5f32, 0.5, 0.5));
+    ///assert!((color.blue - 0.45).abs() < 0.01);
+    ///```
+    pub fn from_hald<S, T>(pixels: &[Rgb<S, T>]) -> Result<Lut3d, ParseError>
+    where
+        S: RgbStandard,
+        T: Component,
+    {
+        let level = (2..=MAX_LEVEL)
+            .find(|&level| level.pow(6) == pixels.len())
+            .ok_or(ParseError::InvalidSize)?;
+
+        let table = pixels
+            .iter()
+            .map(|pixel| {
+                [
+                    pixel.red.convert(),
+                    pixel.green.convert(),
+                    pixel.blue.convert(),
+                ]
+            })
+            .collect();
+
+        Ok(Lut3d::new(level * level, table))
+    }
+}
+
+///Create the pixels of an identity Hald CLUT image of level `level`.
+///
+///The image is `level^3` pixels wide and high, and doesn't change the colors
+///when it's used as a lookup table. A look can be captured as a Hald image by
+///applying it to the identity image in an image editor.
+///
+///Panics if `level` is smaller than 2 or larger than 16.
+pub fn hald_identity<S, T>(level: usize) -> Vec<Rgb<S, T>>
+where
+    S: RgbStandard,
+    T: Component,
+{
+    assert!(
+        (2..=MAX_LEVEL).contains(&level),
+        "the level must be between 2 and 16"
+    );
+
+    let size = level * level;
+    let scale = cast::<f64, _>(T::max_intensity()) / (size - 1) as f64;
+    let component = |step: usize| {
+        let value = step as f64 * scale;
+        cast(if T::LIMITED { value.round() } else { value })
+    };
+
+    (0..size * size * size)
+        .map(|index| {
+            Rgb::new(
+                component(index % size),
+                component(index / size % size),
+                component(index / (size * size)),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::hald_identity;
+    use encoding::Srgb;
+    use lut::{Lut3d, ParseError};
+    use rgb::Rgb;
+
+    #[test]
+    fn identity() {
+        let image: Vec<Rgb<Srgb, u16>> = hald_identity(3);
+        assert_eq!(image.len(), 27 * 27);
+        assert_eq!(image[1], Rgb::new(8192, 0, 0));
+        assert_eq!(image[9], Rgb::new(0, 8192, 0));
+        assert_eq!(image[81], Rgb::new(0, 0, 8192));
+        assert_eq!(image[27 * 27 - 1], Rgb::new(65535, 65535, 65535));
+
+        let lut = Lut3d::from_hald(&image).unwrap();
+        let identity = Lut3d::identity(9);
+        assert_eq!(lut.size(), 9);
+        for (sample, expected) in lut.table().iter().zip(identity.table()) {
+            for channel in 0..3 {
+                assert_relative_eq!(sample[channel], expected[channel], epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn float_pixels() {
+        let image: Vec<Rgb<Srgb, f32>> = hald_identity(2)
+            .into_iter()
+            .map(|color: Rgb<Srgb, f32>| Rgb::new(color.green, color.blue, color.red))
+            .collect();
+
+        let lut = Lut3d::from_hald(&image).unwrap();
+        let result = lut.lookup([0.2, 0.4, 0.6]);
+        assert_relative_eq!(result[0], 0.4, epsilon = 1e-6);
+        assert_relative_eq!(result[1], 0.6, epsilon = 1e-6);
+        assert_relative_eq!(result[2], 0.2, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn invalid_size() {
+        let image = vec![Rgb::<Srgb, u8>::new(0, 0, 0); 100];
+        assert_eq!(Lut3d::from_hald(&image), Err(ParseError::InvalidSize));
+        assert_eq!(
+            Lut3d::from_hald::<Srgb, u8>(&[]),
+            Err(ParseError::InvalidSize)
+        );
+    }
+}
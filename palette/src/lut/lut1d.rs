@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+
+use float::Float;
+
+use super::{is_valid_domain, Cube, ParseError};
+use rgb::{Rgb, RgbStandard};
+use {cast, clamp, Component};
+
+///The largest supported number of samples in a 1D lookup table.
+pub const MAX_SIZE: usize = 65536;
+
+///A 1D lookup table, with a separate curve for each RGB component.
+///
+///The curves are made of evenly spaced samples that cover the domain of the
+///table, and are linearly interpolated between. Input values outside of the
+///domain are clamped to it. Each sample has the values of all three curves,
+///as in `.cube` files.
+///
+///```
+///use palette::lut::Lut1d;
+///
+///let gamma = Lut1d::from_fn(4096, |[r, g, b]| [r.powf(2.2), g.powf(2.2), b.powf(2.2)]);
+///let inverse = gamma.invert(4096).unwrap();
+///
+///let linear = gamma.lookup([0.5, 0.5, 0.5]);
+///assert!((inverse.lookup(linear)[0] - 0.5).abs() < 1e-3);
+///```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut1d {
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    table: Vec<[f32; 3]>,
+}
+
+impl Lut1d {
+    ///Create a lookup table from its samples, with a domain from `0.0` to
+    ///`1.0`.
+    ///
+    ///Panics if `table` has fewer than 2 or more than 65536 samples.
+    pub fn new(table: Vec<[f32; 3]>) -> Lut1d {
+        assert!(
+            (2..=MAX_SIZE).contains(&table.len()),
+            "the table must have between 2 and 65536 samples"
+        );
+
+        Lut1d {
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table: table,
+        }
+    }
+
+    ///Create a lookup table by sampling `function` at `size` points from
+    ///`0.0` to `1.0`. The components of each point are equal.
+    ///
+    ///Panics if `size` is smaller than 2 or larger than 65536.
+    pub fn from_fn<F>(size: usize, mut function: F) -> Lut1d
+    where
+        F: FnMut([f32; 3]) -> [f32; 3],
+    {
+        assert!(
+            (2..=MAX_SIZE).contains(&size),
+            "the table must have between 2 and 65536 samples"
+        );
+
+        let max = (size - 1) as f32;
+        Lut1d::new(
+            (0..size)
+                .map(|index| function([index as f32 / max; 3]))
+                .collect(),
+        )
+    }
+
+    ///Create a lookup table that doesn't change the colors.
+    pub fn identity(size: usize) -> Lut1d {
+        Lut1d::from_fn(size, |rgb| rgb)
+    }
+
+    ///Parse the content of a `.cube` file with a 1D table, in the format of
+    ///Adobe and DaVinci Resolve.
+    ///
+    ///The `LUT_1D_SIZE`, `DOMAIN_MIN`, `DOMAIN_MAX` and `LUT_1D_INPUT_RANGE`
+    ///keywords are used, while `TITLE` and other keywords are ignored. Lines
+    ///that start with `#` are comments. Files that also have a 3D table can
+    ///be parsed with [`Lut3d::parse_cube`](struct.Lut3d.html#method.parse_cube).
+    pub fn parse_cube(text: &str) -> Result<Lut1d, ParseError> {
+        let cube = Cube::parse(text)?;
+        let size = cube.size_1d.ok_or(ParseError::MissingSize)?;
+        if cube.samples.len() != size {
+            return Err(ParseError::WrongLength {
+                expected: size,
+                found: cube.samples.len(),
+            });
+        }
+
+        Ok(Lut1d {
+            domain_min: cube.domain_1d.0,
+            domain_max: cube.domain_1d.1,
+            table: cube.samples,
+        })
+    }
+
+    ///Set the range of input values that the table covers, for each
+    ///component.
+    ///
+    ///Panics if any minimum isn't smaller than its maximum.
+    pub fn with_domain(mut self, min: [f32; 3], max: [f32; 3]) -> Lut1d {
+        assert!(
+            is_valid_domain(&min, &max),
+            "the domain minimum must be smaller than the maximum"
+        );
+
+        self.domain_min = min;
+        self.domain_max = max;
+        self
+    }
+
+    ///The number of samples in the table.
+    pub fn size(&self) -> usize {
+        self.table.len()
+    }
+
+    ///The range of input values that the table covers, as `(min, max)`.
+    pub fn domain(&self) -> ([f32; 3], [f32; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+
+    ///The samples of the table.
+    pub fn table(&self) -> &[[f32; 3]] {
+        &self.table
+    }
+
+    ///Look up the components of an RGB color in the table.
+    pub fn lookup(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let last = self.table.len() - 1;
+        let mut result = [0.0; 3];
+        for channel in 0..3 {
+            let min = self.domain_min[channel];
+            let max = self.domain_max[channel];
+            let position = clamp((rgb[channel] - min) / (max - min), 0.0, 1.0) * last as f32;
+            let index = (position as usize).min(last - 1);
+            let fraction = position - index as f32;
+
+            let low = self.table[index][channel];
+            let high = self.table[index + 1][channel];
+            result[channel] = low + (high - low) * fraction;
+        }
+
+        result
+    }
+
+    ///Create the inverse of the table, with `size` samples.
+    ///
+    ///The domain of the inverse is the range of values in the table, and its
+    ///output is in the domain of the table. `None` is returned if any of the
+    ///curves isn't monotonic, or has the same value at both ends, since they
+    ///can't be inverted.
+    ///
+    ///Panics if `size` is smaller than 2 or larger than 65536.
+    pub fn invert(&self, size: usize) -> Option<Lut1d> {
+        assert!(
+            (2..=MAX_SIZE).contains(&size),
+            "the table must have between 2 and 65536 samples"
+        );
+
+        let last = (self.table.len() - 1) as f32;
+        let mut inverse = Lut1d::new((0..size).map(|_| [0.0; 3]).collect());
+        for channel in 0..3 {
+            let curve: Vec<f32> = self.table.iter().map(|sample| sample[channel]).collect();
+            let first = curve[0];
+            let end = curve[curve.len() - 1];
+            let increasing = end > first;
+            let monotonic = curve.windows(2).all(|pair| {
+                if increasing {
+                    pair[0] <= pair[1]
+                } else {
+                    pair[0] >= pair[1]
+                }
+            });
+            if first == end || !monotonic {
+                return None;
+            }
+
+            let (low, high) = if increasing {
+                (first, end)
+            } else {
+                (end, first)
+            };
+            inverse.domain_min[channel] = low;
+            inverse.domain_max[channel] = high;
+
+            let min = self.domain_min[channel];
+            let max = self.domain_max[channel];
+            for (index, sample) in inverse.table.iter_mut().enumerate() {
+                let value = low + (high - low) * index as f32 / (size - 1) as f32;
+                let position = find_position(&curve, value, increasing);
+                sample[channel] = min + (max - min) * position / last;
+            }
+        }
+
+        Some(inverse)
+    }
+
+    ///Apply the table to an RGB color.
+    pub fn apply<S, T>(&self, color: Rgb<S, T>) -> Rgb<S, T>
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        let [red, green, blue] =
+            self.lookup([cast(color.red), cast(color.green), cast(color.blue)]);
+        Rgb::new(cast(red), cast(green), cast(blue))
+    }
+
+    ///Apply the table to each color in a slice.
+    pub fn apply_slice<S, T>(&self, colors: &mut [Rgb<S, T>])
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        for color in colors {
+            *color = self.apply(*color);
+        }
+    }
+}
+
+///Find the fractional index where a monotonic curve has `value`.
+fn find_position(curve: &[f32], value: f32, increasing: bool) -> f32 {
+    let index = curve.partition_point(|&sample| {
+        if increasing {
+            sample < value
+        } else {
+            sample > value
+        }
+    });
+
+    if index == 0 {
+        0.0
+    } else if index == curve.len() {
+        (curve.len() - 1) as f32
+    } else {
+        let low = curve[index - 1];
+        let high = curve[index];
+        (index - 1) as f32 + clamp((value - low) / (high - low), 0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lut1d;
+    use lut::ParseError;
+    use Srgb;
+
+    #[test]
+    fn identity() {
+        let lut = Lut1d::identity(7);
+        for &value in &[0.0, 0.1, 0.5, 0.55, 0.999, 1.0] {
+            let result = lut.lookup([value, 1.0 - value, value * 0.5]);
+            assert_relative_eq!(result[0], value, epsilon = 1e-6);
+            assert_relative_eq!(result[1], 1.0 - value, epsilon = 1e-6);
+            assert_relative_eq!(result[2], value * 0.5, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let lut = Lut1d::new(vec![[0.0, 1.0, 0.0], [0.2, 0.5, 1.0], [1.0, 0.0, 1.0]])
+            .with_domain([0.0; 3], [1.0, 1.0, 2.0]);
+
+        assert_eq!(lut.lookup([0.25, 0.75, 0.5]), [0.1, 0.25, 0.5]);
+        assert_eq!(lut.lookup([-1.0, 2.0, 3.0]), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn invert() {
+        let lut = Lut1d::from_fn(1024, |[red, green, blue]| {
+            [red * red, 1.0 - green, 0.5 + blue.sqrt()]
+        })
+        .with_domain([0.0; 3], [1.0, 2.0, 1.0]);
+        let inverse = lut.invert(1024).unwrap();
+
+        assert_eq!(inverse.domain(), ([0.0, 0.0, 0.5], [1.0, 1.0, 1.5]));
+        for &value in &[0.1, 0.3, 0.5, 0.8, 1.0] {
+            let result = inverse.lookup(lut.lookup([value; 3]));
+            assert_relative_eq!(result[0], value, epsilon = 1e-3);
+            assert_relative_eq!(result[1], value, epsilon = 1e-3);
+            assert_relative_eq!(result[2], value, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn invert_flat_segment() {
+        let lut = Lut1d::new(vec![[0.0; 3], [0.5; 3], [0.5; 3], [1.0; 3]]);
+        let inverse = lut.invert(5).unwrap();
+
+        assert_eq!(inverse.lookup([0.25; 3]), [1.0 / 6.0; 3]);
+        assert_eq!(inverse.lookup([0.75; 3]), [5.0 / 6.0; 3]);
+    }
+
+    #[test]
+    fn invert_not_monotonic() {
+        let lut = Lut1d::from_fn(16, |[red, green, blue]| {
+            [red, green, (blue - 0.5) * (blue - 0.5)]
+        });
+        assert_eq!(lut.invert(16), None);
+
+        let lut = Lut1d::from_fn(16, |[red, green, _]| [red, green, 0.5]);
+        assert_eq!(lut.invert(16), None);
+    }
+
+    #[test]
+    fn apply() {
+        let lut = Lut1d::from_fn(2, |[red, green, blue]| [1.0 - red, green * 0.5, blue]);
+        let mut colors = [Srgb::new(0.2f32, 0.4, 0.6)];
+        lut.apply_slice(&mut colors);
+
+        assert_relative_eq!(colors[0], Srgb::new(0.8, 0.2, 0.6), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn parse_cube() {
+        let lut = Lut1d::parse_cube(
+            "TITLE \"Contrast\"
+            LUT_1D_SIZE 3
+            LUT_1D_INPUT_RANGE -1.0 1.0
+            0 0 0
+            0.4 0.5 0.6
+            1 1 1
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(lut.size(), 3);
+        assert_eq!(lut.domain(), ([-1.0; 3], [1.0; 3]));
+        assert_eq!(lut.lookup([0.0; 3]), [0.4, 0.5, 0.6]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Lut1d::parse_cube("TITLE \"Empty\""),
+            Err(ParseError::MissingSize)
+        );
+        assert_eq!(
+            Lut1d::parse_cube("LUT_1D_SIZE 100000"),
+            Err(ParseError::InvalidSize)
+        );
+        assert_eq!(
+            Lut1d::parse_cube("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n1 1 1"),
+            Err(ParseError::WrongLength {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Lut1d::parse_cube(
+                "LUT_3D_SIZE 2\n0 0 0\n0 0 1\n0 1 0\n0 1 1\n1 0 0\n1 0 1\n1 1 0\n1 1 1"
+            ),
+            Err(ParseError::MissingSize)
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use alloc::vec::Vec;
+
+use float::Float;
+
+use super::{is_valid_domain, Cube, Interpolation, Lut1d, ParseError};
+use rgb::{Rgb, RgbStandard};
+use {cast, clamp, Component};
+
+///The largest supported number of samples along each side of a 3D lookup
+///table.
+pub const MAX_SIZE: usize = 256;
+
+///A 3D lookup table, that maps RGB colors to other RGB colors.
+///
+///The table is a cube of `size * size * size` evenly spaced samples, that
+///covers the domain of the table. The samples are stored with the red
+///component changing fastest, then green and then blue, as in `.cube` files.
+///Colors between the samples are interpolated with the selected
+///[`Interpolation`](enum.Interpolation.html) method, and colors outside of
+///the domain are clamped to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut3d {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    table: Vec<[f32; 3]>,
+    shaper: Option<Lut1d>,
+
+    ///The method for interpolating between the samples.
+    pub interpolation: Interpolation,
+}
+
+impl Lut3d {
+    ///Create a lookup table from its samples, with red changing fastest, and
+    ///a domain from `0.0` to `1.0`.
+    ///
+    ///Panics if `size` is smaller than 2 or larger than 256, or if `table`
+    ///doesn't have `size * size * size` samples.
+    pub fn new(size: usize, table: Vec<[f32; 3]>) -> Lut3d {
+        assert!(
+            (2..=MAX_SIZE).contains(&size),
+            "the size must be between 2 and 256"
+        );
+        assert_eq!(
+            table.len(),
+            size * size * size,
+            "the table must have size * size * size samples"
+        );
+
+        Lut3d {
+            size: size,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table: table,
+            shaper: None,
+            interpolation: Interpolation::Trilinear,
+        }
+    }
+
+    ///Create a lookup table by sampling `function` at each of the
+    ///`size * size * size` points from `0.0` to `1.0`.
+    ///
+    ///```
+    ///use palette::lut::Lut3d;
+    ///
+    ///let lut = Lut3d::from_fn(17, |[red, green, blue]| [red, green, blue * 0.5]);
+    ///assert_eq!(lut.lookup([0.5, 0.5, 0.5]), [0.5, 0.5, 0.25]);
+    ///```
+    ///
+    ///Panics if `size` is smaller than 2 or larger than 256.
+    pub fn from_fn<F>(size: usize, mut function: F) -> Lut3d
+    where
+        F: FnMut([f32; 3]) -> [f32; 3],
+    {
+        assert!(
+            (2..=MAX_SIZE).contains(&size),
+            "the size must be between 2 and 256"
+        );
+
+        let max = (size - 1) as f32;
+        let mut table = Vec::with_capacity(size * size * size);
+        for blue in 0..size {
+            for green in 0..size {
+                for red in 0..size {
+                    table.push(function([
+                        red as f32 / max,
+                        green as f32 / max,
+                        blue as f32 / max,
+                    ]));
+                }
+            }
+        }
+
+        Lut3d::new(size, table)
+    }
+
+    ///Create a lookup table that doesn't change the colors.
+    pub fn identity(size: usize) -> Lut3d {
+        Lut3d::from_fn(size, |rgb| rgb)
+    }
+
+    ///Parse the content of a `.cube` file, in the format of Adobe and
+    ///DaVinci Resolve.
+    ///
+    ///The `LUT_3D_SIZE`, `DOMAIN_MIN`, `DOMAIN_MAX` and `LUT_3D_INPUT_RANGE`
+    ///keywords are used, while `TITLE` and other keywords are ignored. Lines
+    ///that start with `#` are comments. A 1D table before the 3D table, with
+    ///`LUT_1D_SIZE` and `LUT_1D_INPUT_RANGE`, becomes the shaper of the
+    ///table.
+    pub fn parse_cube(text: &str) -> Result<Lut3d, ParseError> {
+        let mut cube = Cube::parse(text)?;
+        let size = cube.size_3d.ok_or(ParseError::MissingSize)?;
+
+        let shaper = match cube.size_1d {
+            Some(shaper_size) => {
+                let table = cube.samples.drain(..shaper_size).collect();
+                let (min, max) = cube.domain_1d;
+                Some(Lut1d::new(table).with_domain(min, max))
+            }
+            None => None,
+        };
+
+        Ok(Lut3d {
+            size: size,
+            domain_min: cube.domain_3d.0,
+            domain_max: cube.domain_3d.1,
+            table: cube.samples,
+            shaper: shaper,
+            interpolation: Interpolation::Trilinear,
+        })
+    }
+
+    ///Set the range of input values that the table covers, for each
+    ///component.
+    ///
+    ///Panics if any minimum isn't smaller than its maximum.
+    pub fn with_domain(mut self, min: [f32; 3], max: [f32; 3]) -> Lut3d {
+        assert!(
+            is_valid_domain(&min, &max),
+            "the domain minimum must be smaller than the maximum"
+        );
+
+        self.domain_min = min;
+        self.domain_max = max;
+        self
+    }
+
+    ///Set the method for interpolating between the samples.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Lut3d {
+        self.interpolation = interpolation;
+        self
+    }
+
+    ///Set a 1D table that is applied to the colors before they are looked
+    ///up in the 3D table.
+    ///
+    ///The domain of the 3D table should cover the output of the shaper.
+    ///
+    ///```
+    ///use palette::lut::{Lut1d, Lut3d};
+    ///
+    /////Spread the samples over linear values up to 16.0, that are encoded
+    /////with a square root
+    ///let shaper = Lut1d::from_fn(1024, |[r, g, b]| [r.sqrt(), g.sqrt(), b.sqrt()])
+    ///    .with_domain([0.0; 3], [16.0; 3]);
+    ///let lut = Lut3d::identity(33).with_shaper(shaper);
+    ///
+    ///assert!((lut.lookup([4.0, 4.0, 4.0])[0] - 0.5).abs() < 1e-3);
+    ///```
+    pub fn with_shaper(mut self, shaper: Lut1d) -> Lut3d {
+        self.shaper = Some(shaper);
+        self
+    }
+
+    ///The 1D table that is applied before the 3D table, if any.
+    pub fn shaper(&self) -> Option<&Lut1d> {
+        self.shaper.as_ref()
+    }
+
+    ///The number of samples along each side of the table.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    ///The range of input values that the table covers, as `(min, max)`.
+    pub fn domain(&self) -> ([f32; 3], [f32; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+
+    ///The samples of the table, with red changing fastest.
+    pub fn table(&self) -> &[[f32; 3]] {
+        &self.table
+    }
+
+    ///Look up the components of an RGB color in the table.
+    pub fn lookup(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let rgb = match self.shaper {
+            Some(ref shaper) => shaper.lookup(rgb),
+            None => rgb,
+        };
+
+        let last = self.size - 1;
+        let mut index = [0; 3];
+        let mut fraction = [0.0f32; 3];
+        for channel in 0..3 {
+            let min = self.domain_min[channel];
+            let max = self.domain_max[channel];
+            let position = clamp((rgb[channel] - min) / (max - min), 0.0, 1.0) * last as f32;
+            index[channel] = (position as usize).min(last - 1);
+            fraction[channel] = position - index[channel] as f32;
+        }
+
+        let sample = |offset: [usize; 3]| {
+            self.table[(index[0] + offset[0])
+                + (index[1] + offset[1]) * self.size
+                + (index[2] + offset[2]) * self.size * self.size]
+        };
+
+        match self.interpolation {
+            Interpolation::Trilinear => {
+                let mut result = [0.0; 3];
+                for corner in 0..8 {
+                    let offset = [corner & 1, (corner >> 1) & 1, corner >> 2];
+                    let mut weight = 1.0;
+                    for channel in 0..3 {
+                        weight *= if offset[channel] == 1 {
+                            fraction[channel]
+                        } else {
+                            1.0 - fraction[channel]
+                        };
+                    }
+
+                    add_weighted(&mut result, sample(offset), weight);
+                }
+
+                result
+            }
+            Interpolation::Tetrahedral => {
+                //Walk from the first to the last corner of the cube, along
+                //the axes with the largest fraction first
+                let mut axes = [0, 1, 2];
+                axes.sort_unstable_by(|&a, &b| {
+                    fraction[b]
+                        .partial_cmp(&fraction[a])
+                        .unwrap_or(::core::cmp::Ordering::Equal)
+                });
+
+                let mut offset = [0; 3];
+                let mut result = [0.0; 3];
+                add_weighted(&mut result, sample(offset), 1.0 - fraction[axes[0]]);
+                for (step, &axis) in axes.iter().enumerate() {
+                    offset[axis] = 1;
+                    let next = axes.get(step + 1).map_or(0.0, |&next| fraction[next]);
+                    add_weighted(&mut result, sample(offset), fraction[axis] - next);
+                }
+
+                result
+            }
+        }
+    }
+
+    ///Apply the table to an RGB color.
+    pub fn apply<S, T>(&self, color: Rgb<S, T>) -> Rgb<S, T>
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        let [red, green, blue] =
+            self.lookup([cast(color.red), cast(color.green), cast(color.blue)]);
+        Rgb::new(cast(red), cast(green), cast(blue))
+    }
+
+    ///Apply the table to each color in a slice.
+    pub fn apply_slice<S, T>(&self, colors: &mut [Rgb<S, T>])
+    where
+        S: RgbStandard,
+        T: Component + Float,
+    {
+        for color in colors {
+            *color = self.apply(*color);
+        }
+    }
+}
+
+fn add_weighted(result: &mut [f32; 3], sample: [f32; 3], weight: f32) {
+    for (result, sample) in result.iter_mut().zip(&sample) {
+        *result += sample * weight;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lut3d;
+    use lut::{Interpolation, Lut1d, ParseError};
+    use Srgb;
+
+    const INTERPOLATIONS: [Interpolation; 2] =
+        [Interpolation::Trilinear, Interpolation::Tetrahedral];
+
+    fn points() -> Vec<[f32; 3]> {
+        let mut points = Vec::new();
+        for &red in &[0.0, 0.13, 0.5, 0.77, 1.0] {
+            for &green in &[0.0, 0.31, 0.62, 1.0] {
+                for &blue in &[0.0, 0.05, 0.49, 0.999, 1.0] {
+                    points.push([red, green, blue]);
+                }
+            }
+        }
+
+        points
+    }
+
+    #[test]
+    fn identity() {
+        for &interpolation in &INTERPOLATIONS {
+            let lut = Lut3d::identity(5).with_interpolation(interpolation);
+            for point in points() {
+                let result = lut.lookup(point);
+                for channel in 0..3 {
+                    assert_relative_eq!(result[channel], point[channel], epsilon = 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn affine() {
+        let function = |[red, green, blue]: [f32; 3]| {
+            [
+                0.5 * red + 0.25 * green + 0.1,
+                green - 0.3 * blue + 0.2,
+                0.2 * red + 0.2 * green + 0.6 * blue,
+            ]
+        };
+
+        //Both methods are exact for affine functions
+        for &interpolation in &INTERPOLATIONS {
+            let lut = Lut3d::from_fn(3, function).with_interpolation(interpolation);
+            for point in points() {
+                let result = lut.lookup(point);
+                let expected = function(point);
+                for channel in 0..3 {
+                    assert_relative_eq!(result[channel], expected[channel], epsilon = 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tetrahedral_neutral_axis() {
+        //Only the corners on the neutral axis are gray
+        let lut = Lut3d::from_fn(2, |[red, green, blue]| {
+            if red == green && green == blue {
+                [red, green, blue]
+            } else {
+                [red, 1.0 - green, blue]
+            }
+        })
+        .with_interpolation(Interpolation::Tetrahedral);
+
+        for &value in &[0.1, 0.4, 0.75] {
+            for &component in &lut.lookup([value; 3]) {
+                assert_relative_eq!(component, value, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn domain() {
+        let lut = Lut3d::identity(3).with_domain([0.0; 3], [2.0, 2.0, 4.0]);
+
+        let result = lut.lookup([1.0, 3.0, 1.0]);
+        assert_relative_eq!(result[0], 0.5);
+        assert_relative_eq!(result[1], 1.0);
+        assert_relative_eq!(result[2], 0.25);
+    }
+
+    #[test]
+    fn apply() {
+        let lut = Lut3d::from_fn(9, |[red, green, blue]| [blue, red, green]);
+        let mut colors = [Srgb::new(0.1f64, 0.5, 0.9), Srgb::new(1.0, 0.0, 0.25)];
+        lut.apply_slice(&mut colors);
+
+        assert_relative_eq!(colors[0], Srgb::new(0.9, 0.1, 0.5), epsilon = 1e-6);
+        assert_relative_eq!(colors[1], Srgb::new(0.25, 1.0, 0.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn parse_cube() {
+        let lut = Lut3d::parse_cube(
+            "# Created by hand
+            TITLE \"Half\"
+            LUT_3D_SIZE 2
+            DOMAIN_MIN 0.0 0.0 0.0
+            DOMAIN_MAX 1.0 2.0 1.0
+
+            0 0 0
+            0.5 0 0
+            0 0.5 0
+            0.5 0.5 0
+            0 0 0.5
+            0.5 0 0.5
+            0 0.5 0.5
+            0.5 0.5 0.5
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(lut.size(), 2);
+        assert_eq!(lut.domain(), ([0.0; 3], [1.0, 2.0, 1.0]));
+        assert_eq!(lut.table()[1], [0.5, 0.0, 0.0]);
+        assert_eq!(lut.lookup([1.0, 1.0, 0.5]), [0.5, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn parse_input_range() {
+        let mut text = String::from("LUT_3D_SIZE 2\nLUT_3D_INPUT_RANGE -0.5 1.5\n");
+        for _ in 0..8 {
+            text.push_str("1.0e-1 0.2 3E-1\n");
+        }
+
+        let lut = Lut3d::parse_cube(&text).unwrap();
+        assert_eq!(lut.domain(), ([-0.5; 3], [1.5; 3]));
+        assert_eq!(lut.table()[7], [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Lut3d::parse_cube("0 0 0"), Err(ParseError::MissingSize));
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE 1\n0 0 0"),
+            Err(ParseError::InvalidSize)
+        );
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE 1024"),
+            Err(ParseError::InvalidSize)
+        );
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE two"),
+            Err(ParseError::InvalidLine(1))
+        );
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n0 0"),
+            Err(ParseError::InvalidLine(3))
+        );
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n0 0 0 0"),
+            Err(ParseError::InvalidLine(3))
+        );
+        assert_eq!(
+            Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n0 0 0"),
+            Err(ParseError::WrongLength {
+                expected: 8,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Lut3d::parse_cube(&format!(
+                "LUT_3D_SIZE 2\nDOMAIN_MAX 1 0 1\n{}",
+                "0 0 0\n".repeat(8)
+            )),
+            Err(ParseError::InvalidDomain)
+        );
+    }
+
+    #[test]
+    fn shaper() {
+        let shaper = Lut1d::from_fn(256, |[red, green, blue]| [red * red, green, blue])
+            .with_domain([0.0; 3], [1.0, 1.0, 2.0]);
+        let lut = Lut3d::from_fn(2, |[red, green, blue]| [green, red, blue]).with_shaper(shaper);
+
+        let result = lut.lookup([0.5, 0.25, 1.0]);
+        assert_relative_eq!(result[0], 0.25, epsilon = 1e-5);
+        assert_relative_eq!(result[1], 0.25, epsilon = 1e-5);
+        assert_relative_eq!(result[2], 0.5, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn parse_shaper() {
+        let mut text = String::from(
+            "LUT_1D_SIZE 3
+            LUT_1D_INPUT_RANGE 0.0 4.0
+            LUT_3D_SIZE 2
+            0 0 0
+            0.25 0.25 0.25
+            1 1 1
+            ",
+        );
+        for blue in 0..2 {
+            for green in 0..2 {
+                for red in 0..2 {
+                    text.push_str(&format!("{} {} {}\n", red, green, blue));
+                }
+            }
+        }
+
+        let lut = Lut3d::parse_cube(&text).unwrap();
+        let shaper = lut.shaper().unwrap();
+        assert_eq!(shaper.size(), 3);
+        assert_eq!(shaper.domain(), ([0.0; 3], [4.0; 3]));
+        assert_eq!(lut.domain(), ([0.0; 3], [1.0; 3]));
+        assert_eq!(lut.lookup([2.0, 3.0, 4.0]), [0.25, 0.625, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the table must have size * size * size samples")]
+    fn wrong_length() {
+        Lut3d::new(2, vec![[0.0; 3]; 7]);
+    }
+}
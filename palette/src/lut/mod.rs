@@ -0,0 +1,255 @@
+//!Color lookup tables, for applying color grades and film looks.
+//!
+//!A [`Lut3d`](struct.Lut3d.html) maps RGB colors to other RGB colors, using a
+//!cube of evenly spaced samples that are interpolated between. The tables are
+//!usually made by color grading software and shared as `.cube` files, that
+//!can be parsed with [`Lut3d::parse_cube`](struct.Lut3d.html#method.parse_cube).
+//!They can also be loaded from Hald CLUT images, with
+//![`Lut3d::from_hald`](struct.Lut3d.html#method.from_hald), which is a common
+//!format for film emulation looks.
+//!
+//!A [`Lut1d`](struct.Lut1d.html) has a separate curve for each component,
+//!which is enough for changes of contrast, gamma or white balance. It can also
+//!be used as a shaper in front of a 3D table, to spread the samples of the 3D
+//!table more evenly over the input values, for example for linear or HDR
+//!colors.
+//!
+//!The table is applied to the component values as they are, so the colors have
+//!to be in the same RGB space and encoding as the table was made for. That's
+//!usually non-linear sRGB or a log encoding of a camera.
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::lut::Lut3d;
+//!
+//!//Inverts the colors
+//!let lut = Lut3d::parse_cube("
+//!TITLE \"Invert\"
+//!LUT_3D_SIZE 2
+//!1 1 1
+//!0 1 1
+//!1 0 1
+//!0 0 1
+//!1 1 0
+//!0 1 0
+//!1 0 0
+//!0 0 0
+//!").unwrap();
+//!
+//!let color = lut.apply(Srgb::new(0.2f32, 0.5, 0.9));
+//!assert!((color.red - 0.8).abs() < 1e-6);
+//!assert!((color.blue - 0.1).abs() < 1e-6);
+//!```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+pub use self::hald::hald_identity;
+pub use self::lut1d::Lut1d;
+pub use self::lut3d::Lut3d;
+
+mod hald;
+mod lut1d;
+mod lut3d;
+
+///A method for interpolating between the samples of a 3D lookup table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    ///Interpolates between the eight samples around the color. This is the
+    ///most common method.
+    Trilinear,
+    ///Interpolates between four of the samples around the color, by splitting
+    ///the cube between them into six tetrahedrons. It's a bit faster and
+    ///keeps the neutral axis of the table neutral.
+    Tetrahedral,
+}
+
+///An error from parsing a lookup table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    ///The size of the table is missing.
+    MissingSize,
+    ///The size of the table is smaller than 2 or too large.
+    InvalidSize,
+    ///A line, with this line number, couldn't be parsed.
+    InvalidLine(usize),
+    ///The domain has a minimum that isn't smaller than its maximum.
+    InvalidDomain,
+    ///The table doesn't have as many samples as its size requires.
+    WrongLength {
+        ///The number of samples the size requires.
+        expected: usize,
+        ///The number of samples in the table.
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::MissingSize => fmt.write_str("missing table size"),
+            ParseError::InvalidSize => fmt.write_str("invalid table size"),
+            ParseError::InvalidLine(line) => write!(fmt, "invalid content on line {}", line),
+            ParseError::InvalidDomain => fmt.write_str("invalid domain"),
+            ParseError::WrongLength { expected, found } => write!(
+                fmt,
+                "expected {} samples in the table, but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ParseError {
+    fn description(&self) -> &str {
+        "could not parse lookup table"
+    }
+}
+
+///The content of a `.cube` file, with an optional 1D table followed by an
+///optional 3D table.
+struct Cube {
+    size_1d: Option<usize>,
+    domain_1d: ([f32; 3], [f32; 3]),
+    size_3d: Option<usize>,
+    domain_3d: ([f32; 3], [f32; 3]),
+    samples: Vec<[f32; 3]>,
+}
+
+impl Cube {
+    ///Parse the content of a `.cube` file. `DOMAIN_MIN` and `DOMAIN_MAX` set
+    ///the domain of both tables, while `LUT_1D_INPUT_RANGE` and
+    ///`LUT_3D_INPUT_RANGE` set the domain of one of them.
+    fn parse(text: &str) -> Result<Cube, ParseError> {
+        let mut cube = Cube {
+            size_1d: None,
+            domain_1d: ([0.0; 3], [1.0; 3]),
+            size_3d: None,
+            domain_3d: ([0.0; 3], [1.0; 3]),
+            samples: Vec::new(),
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("LUT_1D_SIZE") => {
+                    let size = parse_size(words, number)?;
+                    if size > lut1d::MAX_SIZE {
+                        return Err(ParseError::InvalidSize);
+                    }
+
+                    cube.size_1d = Some(size);
+                }
+                Some("LUT_3D_SIZE") => {
+                    let size = parse_size(words, number)?;
+                    if size > lut3d::MAX_SIZE {
+                        return Err(ParseError::InvalidSize);
+                    }
+
+                    cube.size_3d = Some(size);
+                }
+                Some("DOMAIN_MIN") => {
+                    parse_numbers(words, &mut cube.domain_1d.0, number)?;
+                    cube.domain_3d.0 = cube.domain_1d.0;
+                }
+                Some("DOMAIN_MAX") => {
+                    parse_numbers(words, &mut cube.domain_1d.1, number)?;
+                    cube.domain_3d.1 = cube.domain_1d.1;
+                }
+                Some("LUT_1D_INPUT_RANGE") => {
+                    cube.domain_1d = parse_range(words, number)?;
+                }
+                Some("LUT_3D_INPUT_RANGE") => {
+                    cube.domain_3d = parse_range(words, number)?;
+                }
+                Some(keyword) if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => {
+                    let mut sample = [0.0; 3];
+                    parse_numbers(line.split_whitespace(), &mut sample, number)?;
+                    cube.samples.push(sample);
+                }
+            }
+        }
+
+        if cube.size_1d.is_none() && cube.size_3d.is_none() {
+            return Err(ParseError::MissingSize);
+        }
+
+        let expected =
+            cube.size_1d.unwrap_or(0) + cube.size_3d.map_or(0, |size| size * size * size);
+        if expected != cube.samples.len() {
+            return Err(ParseError::WrongLength {
+                expected: expected,
+                found: cube.samples.len(),
+            });
+        }
+
+        for (min, max) in &[cube.domain_1d, cube.domain_3d] {
+            if !is_valid_domain(min, max) {
+                return Err(ParseError::InvalidDomain);
+            }
+        }
+
+        Ok(cube)
+    }
+}
+
+///Check that each minimum of a domain is smaller than its maximum.
+fn is_valid_domain(min: &[f32; 3], max: &[f32; 3]) -> bool {
+    min.iter().zip(max).all(|(min, max)| min < max)
+}
+
+///Parse the numbers on a line of a `.cube` file into `values`, and require
+///that the line has exactly as many numbers.
+fn parse_numbers<'a, I>(numbers: I, values: &mut [f32], line: usize) -> Result<(), ParseError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut numbers = numbers.into_iter();
+    for value in values.iter_mut() {
+        *value = numbers
+            .next()
+            .and_then(|number| number.parse().ok())
+            .ok_or(ParseError::InvalidLine(line))?;
+    }
+
+    match numbers.next() {
+        Some(_) => Err(ParseError::InvalidLine(line)),
+        None => Ok(()),
+    }
+}
+
+///Parse an input range, that is the same for all components.
+fn parse_range<'a, I>(numbers: I, line: usize) -> Result<([f32; 3], [f32; 3]), ParseError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut range = [0.0; 2];
+    parse_numbers(numbers, &mut range, line)?;
+    Ok(([range[0]; 3], [range[1]; 3]))
+}
+
+///Parse a table size.
+fn parse_size<'a, I>(words: I, line: usize) -> Result<usize, ParseError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut words = words.into_iter();
+    let size: usize = match (words.next(), words.next()) {
+        (Some(size), None) => size.parse().map_err(|_| ParseError::InvalidLine(line))?,
+        _ => return Err(ParseError::InvalidLine(line)),
+    };
+
+    if size < 2 {
+        Err(ParseError::InvalidSize)
+    } else {
+        Ok(size)
+    }
+}
@@ -0,0 +1,268 @@
+//!Color discrimination ellipses on the chromaticity diagram.
+//!
+//!MacAdam measured how precisely observers can match the chromaticity of a
+//!color, around 25 colors on the CIE 1931 `(x, y)` chromaticity diagram. The
+//!standard deviations of the matches form ellipses, which are small in the
+//!blue and large in the green part of the diagram. That's why the same
+//!distance in `(x, y)` may be invisible for one color and obvious for another.
+//!
+//!The size of a difference is measured in MacAdam steps, where one step is
+//!the size of an ellipse. A difference of one step is about a third of a just
+//!noticeable difference, so the lighting industry usually considers colors
+//!within 3 steps to look the same. Between the 25 measured colors, the
+//!ellipses are interpolated.
+//!
+//!```
+//!use palette::Yxy;
+//!use palette::macadam::{is_distinguishable, macadam_steps};
+//!
+//!let target = Yxy::new(0.3127, 0.3290, 1.0);
+//!let measured = Yxy::new(0.3150, 0.3290, 1.0);
+//!
+//!assert!(macadam_steps(target, measured) < 3.0);
+//!assert!(!is_distinguishable(target, measured, 3.0));
+//!```
+
+use core::f64::consts::E;
+
+use float::Float;
+
+use white_point::WhitePoint;
+use {cast, Component, Yxy};
+
+///An ellipse on the CIE 1931 `(x, y)` chromaticity diagram.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse<T> {
+    ///The `(x, y)` chromaticity of the center.
+    pub center: (T, T),
+
+    ///The length of the semi-major axis.
+    pub semi_major: T,
+
+    ///The length of the semi-minor axis.
+    pub semi_minor: T,
+
+    ///The angle of the major axis, in degrees counterclockwise from the `x`
+    ///axis.
+    pub angle: T,
+}
+
+impl<T: Component + Float> Ellipse<T> {
+    ///The distance from the center to the chromaticity `(x, y)`, in units
+    ///of the ellipse. It's `1.0` on the ellipse.
+    pub fn distance(&self, (x, y): (T, T)) -> T {
+        let metric = Metric::from_ellipse(self);
+        metric.distance((x - self.center.0, y - self.center.1))
+    }
+
+    ///Check if the chromaticity `(x, y)` is inside or on the ellipse.
+    pub fn contains(&self, point: (T, T)) -> bool {
+        self.distance(point) <= T::one()
+    }
+
+    fn cast<U: Component + Float>(&self) -> Ellipse<U> {
+        Ellipse {
+            center: (cast(self.center.0), cast(self.center.1)),
+            semi_major: cast(self.semi_major),
+            semi_minor: cast(self.semi_minor),
+            angle: cast(self.angle),
+        }
+    }
+}
+
+///An ellipse from MacAdam's table, where the axes are given in units of
+///`10^-3`.
+macro_rules! macadam {
+    ($x:expr, $y:expr, $a:expr, $b:expr, $angle:expr) => {
+        Ellipse {
+            center: ($x, $y),
+            semi_major: $a / 1000.0,
+            semi_minor: $b / 1000.0,
+            angle: $angle,
+        }
+    };
+}
+
+///The 25 ellipses that MacAdam measured in 1942, for the CIE 1931 2°
+///observer.
+pub const MACADAM_1942: [Ellipse<f64>; 25] = [
+    macadam!(0.160, 0.057, 0.85, 0.35, 62.5),
+    macadam!(0.187, 0.118, 2.2, 0.55, 77.0),
+    macadam!(0.253, 0.125, 2.5, 0.5, 55.5),
+    macadam!(0.150, 0.680, 9.6, 2.3, 105.0),
+    macadam!(0.131, 0.521, 4.7, 2.0, 112.5),
+    macadam!(0.212, 0.550, 5.8, 2.3, 100.0),
+    macadam!(0.258, 0.450, 5.0, 2.0, 92.0),
+    macadam!(0.152, 0.365, 3.8, 1.9, 110.0),
+    macadam!(0.280, 0.385, 4.0, 1.5, 75.5),
+    macadam!(0.380, 0.498, 4.4, 1.2, 70.0),
+    macadam!(0.160, 0.200, 2.1, 0.95, 104.0),
+    macadam!(0.228, 0.250, 3.1, 0.9, 72.0),
+    macadam!(0.305, 0.323, 2.3, 0.9, 58.0),
+    macadam!(0.385, 0.393, 3.8, 1.6, 65.5),
+    macadam!(0.472, 0.399, 3.2, 1.4, 51.0),
+    macadam!(0.527, 0.350, 2.6, 1.3, 20.0),
+    macadam!(0.475, 0.300, 2.9, 1.1, 28.5),
+    macadam!(0.510, 0.236, 2.4, 1.2, 29.5),
+    macadam!(0.596, 0.283, 2.6, 1.3, 13.0),
+    macadam!(0.344, 0.284, 2.3, 0.9, 60.0),
+    macadam!(0.390, 0.237, 2.5, 1.0, 47.0),
+    macadam!(0.441, 0.198, 2.8, 0.95, 34.5),
+    macadam!(0.278, 0.223, 2.4, 0.55, 57.5),
+    macadam!(0.300, 0.163, 2.9, 0.6, 54.0),
+    macadam!(0.365, 0.153, 3.6, 0.95, 40.0),
+];
+
+///Get the discrimination ellipse around the chromaticity `(x, y)`.
+///
+///The ellipse is interpolated from the [`MACADAM_1942`](constant.MACADAM_1942.html)
+///ellipses, weighted by the inverse square of their distances from `(x, y)`.
+///The lengths of the axes are interpolated logarithmically, and the angle is
+///interpolated as an axis, where `0°` and `180°` are the same. It's one of the
+///measured ellipses if `(x, y)` is the center of it.
+pub fn ellipse_at<T: Component + Float>(x: T, y: T) -> Ellipse<T> {
+    let (x, y): (f64, f64) = (cast(x), cast(y));
+    let (mut major, mut minor, mut cos, mut sin, mut weights) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for ellipse in &MACADAM_1942 {
+        let distance_squared = (x - ellipse.center.0).powi(2) + (y - ellipse.center.1).powi(2);
+        if distance_squared == 0.0 {
+            return ellipse.cast();
+        }
+
+        let weight = 1.0 / distance_squared;
+        let angle = (ellipse.angle * 2.0).to_radians();
+        major += ellipse.semi_major.ln() * weight;
+        minor += ellipse.semi_minor.ln() * weight;
+        cos += angle.cos() * weight;
+        sin += angle.sin() * weight;
+        weights += weight;
+    }
+
+    let angle = (sin.atan2(cos).to_degrees() / 2.0 + 180.0) % 180.0;
+    Ellipse {
+        center: (x, y),
+        semi_major: E.powf(major / weights),
+        semi_minor: E.powf(minor / weights),
+        angle: angle,
+    }
+    .cast()
+}
+
+///The difference between the chromaticities of two colors, in MacAdam steps.
+///
+///The difference is measured with the ellipse in the middle of them, so it's
+///only accurate for small differences. The luminance is ignored.
+pub fn macadam_steps<Wp, T>(a: Yxy<Wp, T>, b: Yxy<Wp, T>) -> T
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    let two = T::one() + T::one();
+    let ellipse = ellipse_at((a.x + b.x) / two, (a.y + b.y) / two);
+    Metric::from_ellipse(&ellipse).distance((b.x - a.x, b.y - a.y))
+}
+
+///Check if the chromaticities of two colors are more than `steps` MacAdam
+///steps apart.
+pub fn is_distinguishable<Wp, T>(a: Yxy<Wp, T>, b: Yxy<Wp, T>, steps: T) -> bool
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    macadam_steps(a, b) > steps
+}
+
+///The metric tensor of an ellipse, where the distance of `(dx, dy)` is
+///`sqrt(g11 dx² + 2 g12 dx dy + g22 dy²)`.
+struct Metric<T> {
+    g11: T,
+    g12: T,
+    g22: T,
+}
+
+impl<T: Component + Float> Metric<T> {
+    fn from_ellipse(ellipse: &Ellipse<T>) -> Metric<T> {
+        let angle = ellipse.angle.to_radians();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let major = T::one() / (ellipse.semi_major * ellipse.semi_major);
+        let minor = T::one() / (ellipse.semi_minor * ellipse.semi_minor);
+
+        Metric {
+            g11: major * cos * cos + minor * sin * sin,
+            g12: (major - minor) * sin * cos,
+            g22: major * sin * sin + minor * cos * cos,
+        }
+    }
+
+    fn distance(&self, (dx, dy): (T, T)) -> T {
+        let two = T::one() + T::one();
+        (self.g11 * dx * dx + two * self.g12 * dx * dy + self.g22 * dy * dy).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ellipse_at, is_distinguishable, macadam_steps, Ellipse, MACADAM_1942};
+    use float::Float;
+    use Yxy;
+
+    #[test]
+    fn measured_ellipses() {
+        for ellipse in &MACADAM_1942 {
+            assert_eq!(ellipse_at(ellipse.center.0, ellipse.center.1), *ellipse);
+        }
+    }
+
+    #[test]
+    fn ellipse_distance() {
+        let ellipse = Ellipse {
+            center: (0.3, 0.3),
+            semi_major: 0.002,
+            semi_minor: 0.001,
+            angle: 90.0f64,
+        };
+
+        assert_relative_eq!(ellipse.distance((0.3, 0.302)), 1.0, epsilon = 1e-9);
+        assert_relative_eq!(ellipse.distance((0.301, 0.3)), 1.0, epsilon = 1e-9);
+        assert_relative_eq!(ellipse.distance((0.3005, 0.3)), 0.5, epsilon = 1e-9);
+        assert!(ellipse.contains((0.3, 0.3015)));
+        assert!(!ellipse.contains((0.3015, 0.3)));
+    }
+
+    #[test]
+    fn interpolation() {
+        let ellipse = ellipse_at(0.33, 0.35f64);
+        assert!(ellipse.semi_major > ellipse.semi_minor);
+        assert!(ellipse.semi_major > 0.002 && ellipse.semi_major < 0.005);
+
+        let near = ellipse_at(0.3051, 0.323f64);
+        let measured = MACADAM_1942[12];
+        assert_relative_eq!(near.semi_major, measured.semi_major, epsilon = 1e-4);
+        assert_relative_eq!(near.semi_minor, measured.semi_minor, epsilon = 1e-5);
+        assert_relative_eq!(near.angle, measured.angle, epsilon = 1.0);
+    }
+
+    #[test]
+    fn steps() {
+        let measured = MACADAM_1942[12];
+        let angle = measured.angle.to_radians();
+        let (sin, cos) = (Float::sin(angle), Float::cos(angle));
+        let center = Yxy::new(measured.center.0, measured.center.1, 1.0);
+        let along_major = Yxy::new(
+            center.x + measured.semi_major * cos,
+            center.y + measured.semi_major * sin,
+            0.5,
+        );
+
+        assert_relative_eq!(macadam_steps(center, along_major), 1.0, epsilon = 0.05);
+        assert!(is_distinguishable(center, along_major, 0.5));
+        assert!(!is_distinguishable(center, along_major, 2.0));
+
+        //Blue is much easier to tell apart than green
+        let blue = Yxy::new(0.17, 0.07, 1.0);
+        let green = Yxy::new(0.2, 0.6, 1.0);
+        let shift = |color: Yxy<_, f64>| Yxy::new(color.x + 0.003, color.y, 1.0);
+        assert!(macadam_steps(blue, shift(blue)) > 3.0 * macadam_steps(green, shift(green)));
+    }
+}
@@ -20,6 +20,12 @@ macro_rules! raw_pixel_conversion_tests {
         fn convert_from_f64_slice() {
             raw_pixel_conversion_tests!(@float_slice_test f64, $name<$($ty_param),+>: $($component),+);
         }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn convert_f32_vec() {
+            raw_pixel_conversion_tests!(@float_vec_test f32, $name<$($ty_param),+>: $($component),+);
+        }
     };
 
     (@float_array_test $float: ty, $name: ident <$($ty_param: ident),+> : $($component: ident),+) => {
@@ -82,6 +88,38 @@ macro_rules! raw_pixel_conversion_tests {
         assert_eq!(color_alpha, Alpha::<$name<$($ty_param,)+ $float>, $float>::new($($component,)+ alpha));
         assert_eq!(color_alpha_long, Alpha::<$name<$($ty_param,)+ $float>, $float>::new($($component,)+ alpha));
     };
+
+    (@float_vec_test $float: ty, $name: ident <$($ty_param: ident),+> : $($component: ident),+) => {
+        use ::Pixel;
+
+        let mut counter: $float = 0.0;
+        $(
+            counter += 0.1;
+            let $component = counter;
+        )+
+
+        let channels = <$name<$($ty_param,)+ $float> as Pixel<$float>>::CHANNELS;
+        let color = $name::new($($component),+);
+
+        //Excess capacity must not end up in the converted vector
+        let mut raw: Vec<$float> = Vec::with_capacity(2 * channels + 1);
+        raw.extend_from_slice(&[$($component),+]);
+        raw.extend_from_slice(&[$($component),+]);
+        assert!(raw.capacity() > raw.len());
+
+        let mut colors: Vec<$name<$($ty_param,)+ $float>> = $name::from_raw_vec(raw);
+        assert_eq!(colors, vec![color; 2]);
+        assert_eq!(colors.capacity(), colors.len());
+
+        //Growing reallocates with the layout of the color type
+        colors.reserve(2);
+        colors.push(color);
+        assert!(colors.capacity() > colors.len());
+
+        let raw = $name::into_raw_vec(colors);
+        assert_eq!(raw.capacity(), raw.len());
+        assert_eq!(raw, [[$($component),+]; 3].concat());
+    };
 }
 
 #[cfg(test)]
@@ -110,6 +148,14 @@ macro_rules! raw_pixel_conversion_fail_tests {
         fn convert_from_short_f64_slice() {
             raw_pixel_conversion_fail_tests!(@float_slice_test f64, $name<$($ty_param),+>);
         }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        #[should_panic]
+        fn convert_from_uneven_f32_vec() {
+            use ::Pixel;
+            let _: Vec<$name<$($ty_param,)+ f32>> = $name::from_raw_vec(vec![0.1; <$name<$($ty_param,)+ f32> as Pixel<f32>>::CHANNELS + 1]);
+        }
     };
 
     (@float_array_test $float: ty, $name: ident <$($ty_param: ident),+>) => {
@@ -0,0 +1,304 @@
+//!Mastering display metadata, as in SMPTE ST 2086.
+//!
+//!HDR video carries a description of the display it was graded on, with the
+//!chromaticities of its primaries and white point and the range of its
+//!luminance. A player can use it to fit the colors into the gamut and the
+//!luminance range of the display it's playing on, with
+//![`gamut_transform`](struct.MasteringDisplay.html#method.gamut_transform) and
+//![`relative_peak`](struct.MasteringDisplay.html#method.relative_peak).
+//!
+//!The metadata is stored in the mastering display colour volume SEI message
+//!of HEVC and other formats, as integer codes that can be converted with
+//![`MasteringDisplay::from_codes`](struct.MasteringDisplay.html#method.from_codes).
+//!
+//!```
+//!use palette::encoding::Srgb;
+//!use palette::mastering::{MasteringDisplay, MasteringDisplayCodes};
+//!use palette::tonemap::{ExtendedReinhard, ToneMap};
+//!
+//!//A 1000 nit display with P3 primaries and a D65 white point
+//!let source = MasteringDisplay::<f64>::from_codes(&MasteringDisplayCodes {
+//!    display_primaries: [[13250, 34500], [7500, 3000], [34000, 16000]],
+//!    white_point: [15635, 16450],
+//!    max_display_mastering_luminance: 10_000_000,
+//!    min_display_mastering_luminance: 50,
+//!});
+//!
+//!//A 400 nit sRGB display
+//!let display = MasteringDisplay::from_space::<Srgb>(400.0, 0.1);
+//!
+//!let gamut = source.gamut_transform(&display).unwrap();
+//!let tone_map = ExtendedReinhard::new(source.relative_peak(&display));
+//!
+//!//The source white, at 1000 nits, relative to the display peak
+//!let white = gamut.transform([2.5, 2.5, 2.5]);
+//!assert!((tone_map.map(white[0]) - 1.0).abs() < 1e-3);
+//!```
+
+use float::Float;
+
+use chromatic_adaptation::{Method, TransformMatrix};
+use matrix::adaptation_matrix;
+use rgb::{Primaries, RgbSpace};
+use white_point::{WhitePoint, D65};
+use {cast, ColorMatrix, Component, Xyz};
+
+///The unit of the chromaticity codes, in the CIE 1931 `x` and `y`.
+const CHROMATICITY_UNIT: f64 = 0.00002;
+
+///The unit of the luminance codes, in candela per square meter.
+const LUMINANCE_UNIT: f64 = 0.0001;
+
+///The color volume of a mastering display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MasteringDisplay<T: Float = f32> {
+    ///The `(x, y)` chromaticity of the red primary.
+    pub red: (T, T),
+
+    ///The `(x, y)` chromaticity of the green primary.
+    pub green: (T, T),
+
+    ///The `(x, y)` chromaticity of the blue primary.
+    pub blue: (T, T),
+
+    ///The `(x, y)` chromaticity of the white point.
+    pub white_point: (T, T),
+
+    ///The highest luminance of the display, in candela per square meter.
+    pub max_luminance: T,
+
+    ///The lowest luminance of the display, in candela per square meter.
+    pub min_luminance: T,
+}
+
+///The integer codes of the mastering display colour volume SEI message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MasteringDisplayCodes {
+    ///The `[x, y]` chromaticities of the primaries, in units of `0.00002`.
+    ///They are in the order green, blue and red, as in the SEI message.
+    pub display_primaries: [[u16; 2]; 3],
+
+    ///The `[x, y]` chromaticity of the white point, in units of `0.00002`.
+    pub white_point: [u16; 2],
+
+    ///The highest luminance, in units of `0.0001` candela per square meter.
+    pub max_display_mastering_luminance: u32,
+
+    ///The lowest luminance, in units of `0.0001` candela per square meter.
+    pub min_display_mastering_luminance: u32,
+}
+
+impl<T: Component + Float> MasteringDisplay<T> {
+    ///Describe a display with the primaries and white point of the RGB space
+    ///`S`.
+    pub fn from_space<S: RgbSpace>(max_luminance: T, min_luminance: T) -> MasteringDisplay<T> {
+        let chromaticity = |primary: ::Yxy<S::WhitePoint, T>| (primary.x, primary.y);
+        let white: Xyz<S::WhitePoint, T> = S::WhitePoint::get_xyz();
+        let sum = white.x + white.y + white.z;
+
+        MasteringDisplay {
+            red: chromaticity(S::Primaries::red()),
+            green: chromaticity(S::Primaries::green()),
+            blue: chromaticity(S::Primaries::blue()),
+            white_point: (white.x / sum, white.y / sum),
+            max_luminance: max_luminance,
+            min_luminance: min_luminance,
+        }
+    }
+
+    ///Decode the integer codes of an SEI message.
+    pub fn from_codes(codes: &MasteringDisplayCodes) -> MasteringDisplay<T> {
+        let chromaticity = |[x, y]: [u16; 2]| {
+            (
+                cast::<T, _>(f64::from(x) * CHROMATICITY_UNIT),
+                cast::<T, _>(f64::from(y) * CHROMATICITY_UNIT),
+            )
+        };
+        let luminance = |value: u32| cast::<T, _>(f64::from(value) * LUMINANCE_UNIT);
+
+        MasteringDisplay {
+            red: chromaticity(codes.display_primaries[2]),
+            green: chromaticity(codes.display_primaries[0]),
+            blue: chromaticity(codes.display_primaries[1]),
+            white_point: chromaticity(codes.white_point),
+            max_luminance: luminance(codes.max_display_mastering_luminance),
+            min_luminance: luminance(codes.min_display_mastering_luminance),
+        }
+    }
+
+    ///Encode the metadata as the integer codes of an SEI message, rounded to
+    ///the nearest codes. Values outside the range of the codes are clamped.
+    pub fn to_codes(&self) -> MasteringDisplayCodes {
+        let code = |value: T, unit: f64, max: f64| {
+            let value = (cast::<f64, _>(value) / unit).round();
+            value.max(0.0).min(max)
+        };
+        let chromaticity = |(x, y): (T, T)| {
+            [
+                code(x, CHROMATICITY_UNIT, 50_000.0) as u16,
+                code(y, CHROMATICITY_UNIT, 50_000.0) as u16,
+            ]
+        };
+        let luminance = |value: T| code(value, LUMINANCE_UNIT, f64::from(u32::MAX)) as u32;
+
+        MasteringDisplayCodes {
+            display_primaries: [
+                chromaticity(self.green),
+                chromaticity(self.blue),
+                chromaticity(self.red),
+            ],
+            white_point: chromaticity(self.white_point),
+            max_display_mastering_luminance: luminance(self.max_luminance),
+            min_display_mastering_luminance: luminance(self.min_luminance),
+        }
+    }
+
+    ///Check if the primaries and white point are the same as in the RGB space
+    ///`S`, within the precision of the integer codes.
+    pub fn has_space<S: RgbSpace>(&self) -> bool {
+        let space = MasteringDisplay::<T>::from_space::<S>(T::zero(), T::zero());
+        let tolerance: T = cast(CHROMATICITY_UNIT * 2.0);
+        let same = |(x1, y1): (T, T), (x2, y2): (T, T)| {
+            (x1 - x2).abs() <= tolerance && (y1 - y2).abs() <= tolerance
+        };
+
+        same(self.red, space.red)
+            && same(self.green, space.green)
+            && same(self.blue, space.blue)
+            && same(self.white_point, space.white_point)
+    }
+
+    ///The matrix that converts linear RGB, with the primaries of the display,
+    ///to XYZ. `None` is returned if the primaries don't span a color space.
+    pub fn rgb_to_xyz(&self) -> Option<ColorMatrix<T>> {
+        ColorMatrix::from_chromaticities(self.red, self.green, self.blue, self.white_point)
+    }
+
+    ///The matrix that converts linear RGB, with the primaries of this
+    ///display, to linear RGB with the primaries of `display`.
+    ///
+    ///The white point is adapted with the Bradford method if the displays
+    ///have different white points, so the white of this display becomes the
+    ///white of `display`. The result is not clamped, so colors outside the
+    ///gamut of `display` get negative components or components above the
+    ///white. `None` is returned if the primaries of either display don't
+    ///span a color space.
+    pub fn gamut_transform(&self, display: &MasteringDisplay<T>) -> Option<ColorMatrix<T>> {
+        let source = self.rgb_to_xyz()?;
+        let destination = display.rgb_to_xyz()?.inverse()?;
+
+        if self.white_point == display.white_point {
+            return Some(source.then(destination));
+        }
+
+        let white = |(x, y): (T, T)| Xyz::<D65, T>::new(x / y, T::one(), (T::one() - x - y) / y);
+        let cone_response = TransformMatrix::<D65, D65, T>::get_cone_response(&Method::Bradford);
+        let adaptation = ColorMatrix::new(adaptation_matrix(
+            cone_response,
+            &white(self.white_point),
+            &white(display.white_point),
+        ));
+
+        Some(source.then(adaptation).then(destination))
+    }
+
+    ///The peak luminance of this display, relative to the peak luminance of
+    ///`display`.
+    ///
+    ///This is the peak to use with the [`tonemap`](../tonemap/index.html)
+    ///operators, when the luminance is measured relative to the peak of
+    ///`display`. It's below `1.0` if `display` is brighter than this display,
+    ///and no tone mapping is needed then.
+    pub fn relative_peak(&self, display: &MasteringDisplay<T>) -> T {
+        self.max_luminance / display.max_luminance
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MasteringDisplay, MasteringDisplayCodes};
+    use encoding::itu::BT709;
+    use encoding::Srgb;
+    use ColorMatrix;
+
+    fn bt2020_codes() -> MasteringDisplayCodes {
+        MasteringDisplayCodes {
+            display_primaries: [[8500, 39850], [6550, 2300], [35400, 14600]],
+            white_point: [15635, 16450],
+            max_display_mastering_luminance: 10_000_000,
+            min_display_mastering_luminance: 50,
+        }
+    }
+
+    #[test]
+    fn codes() {
+        let display = MasteringDisplay::<f64>::from_codes(&bt2020_codes());
+        assert_relative_eq!(display.red.0, 0.708);
+        assert_relative_eq!(display.red.1, 0.292);
+        assert_relative_eq!(display.green.0, 0.170);
+        assert_relative_eq!(display.blue.1, 0.046);
+        assert_relative_eq!(display.white_point.0, 0.3127);
+        assert_relative_eq!(display.max_luminance, 1000.0);
+        assert_relative_eq!(display.min_luminance, 0.005);
+
+        assert_eq!(display.to_codes(), bt2020_codes());
+        assert_eq!(
+            MasteringDisplay::<f32>::from_codes(&bt2020_codes()).to_codes(),
+            bt2020_codes()
+        );
+    }
+
+    #[test]
+    fn spaces() {
+        let display = MasteringDisplay::<f64>::from_space::<Srgb>(100.0, 0.2);
+        assert_relative_eq!(display.red.0, 0.64);
+        assert_relative_eq!(display.green.1, 0.6);
+        assert_relative_eq!(display.white_point.0, 0.3127, epsilon = 1e-4);
+        assert!(display.has_space::<BT709>());
+
+        let codes = display.to_codes();
+        assert!(MasteringDisplay::<f64>::from_codes(&codes).has_space::<Srgb>());
+        assert!(!MasteringDisplay::<f64>::from_codes(&bt2020_codes()).has_space::<Srgb>());
+
+        let expected = ColorMatrix::<f64>::rgb_to_xyz::<Srgb>();
+        let matrix = display.rgb_to_xyz().unwrap();
+        for (&a, &b) in matrix.elements.iter().zip(&expected.elements) {
+            assert_relative_eq!(a, b, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn gamut_transform() {
+        let bt2020 = MasteringDisplay::<f64>::from_codes(&bt2020_codes());
+        let srgb = MasteringDisplay::<f64>::from_space::<Srgb>(400.0, 0.1);
+
+        let identity = srgb.gamut_transform(&srgb).unwrap();
+        for (&a, &b) in identity
+            .elements
+            .iter()
+            .zip(&ColorMatrix::<f64>::identity().elements)
+        {
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+
+        //BT.2020 green is far outside of sRGB
+        let green = bt2020
+            .gamut_transform(&srgb)
+            .unwrap()
+            .transform([0.0, 1.0, 0.0]);
+        assert!(green[0] < 0.0 && green[1] > 1.0 && green[2] < 0.0);
+
+        //White stays white, even with a different white point
+        let mut warm = srgb;
+        warm.white_point = (0.3457, 0.3585);
+        let white = warm
+            .gamut_transform(&srgb)
+            .unwrap()
+            .transform([1.0, 1.0, 1.0]);
+        for &component in &white {
+            assert_relative_eq!(component, 1.0, epsilon = 1e-3);
+        }
+
+        assert_relative_eq!(bt2020.relative_peak(&srgb), 2.5);
+    }
+}
@@ -4,16 +4,248 @@
 use float::Float;
 
 use core::marker::PhantomData;
+use core::ops::Mul;
 
 use {Component, Xyz};
 use white_point::WhitePoint;
 use rgb::{Primaries, Rgb, RgbSpace};
 use encoding::Linear;
-use convert::IntoColor;
+use convert::IntoColorUnclamped;
+use chromatic_adaptation::{ConeResponseMatrices, TransformMatrix};
 
 ///A 9 element array representing a 3x3 matrix
 pub type Mat3<T> = [T; 9];
 
+///A 3x3 matrix for linear color transforms, such as the conversions between
+///linear RGB and XYZ.
+///
+///The matrices of the conversions can be created from the RGB spaces and
+///white points, or from the chromaticities of primaries that are only known
+///at runtime. They can then be combined into a single transform, with
+///[`then`](#method.then) or multiplication, and be applied to the
+///components of colors, or be passed to a shader.
+///
+///```
+///use palette::ColorMatrix;
+///use palette::chromatic_adaptation::Method;
+///use palette::encoding::Srgb;
+///use palette::white_point::{D50, D65};
+///
+/////Linear sRGB to XYZ, adapted to D50, as used by ICC profiles
+///let matrix = ColorMatrix::<f64>::rgb_to_xyz::<Srgb>()
+///    .then(ColorMatrix::adaptation::<D65, D50, _>(Method::Bradford));
+///
+///let white = matrix.transform([1.0, 1.0, 1.0]);
+///assert!((white[0] - 0.9642).abs() < 1e-4);
+///
+/////A column major layout with padding, for a `mat3x3<f32>` in WGSL
+///let columns: [[f32; 4]; 3] = matrix.cast::<f32>().padded_columns();
+///```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorMatrix<T> {
+    ///The elements of the matrix, in row major order.
+    pub elements: Mat3<T>,
+}
+
+impl<T: Float> ColorMatrix<T> {
+    ///Create a matrix from its elements, in row major order.
+    pub fn new(elements: Mat3<T>) -> ColorMatrix<T> {
+        ColorMatrix { elements: elements }
+    }
+
+    ///Create a matrix from its rows.
+    pub fn from_rows(rows: [[T; 3]; 3]) -> ColorMatrix<T> {
+        ColorMatrix::new([
+            rows[0][0], rows[0][1], rows[0][2],
+            rows[1][0], rows[1][1], rows[1][2],
+            rows[2][0], rows[2][1], rows[2][2],
+        ])
+    }
+
+    ///The matrix that doesn't change the colors.
+    pub fn identity() -> ColorMatrix<T> {
+        ColorMatrix::diagonal([T::one(); 3])
+    }
+
+    ///A matrix that scales each component, such as the gains of a white
+    ///balance.
+    pub fn diagonal(values: [T; 3]) -> ColorMatrix<T> {
+        let z = T::zero();
+        ColorMatrix::new([values[0], z, z, z, values[1], z, z, z, values[2]])
+    }
+
+    ///Create the matrix that converts linear RGB to XYZ, from the `(x, y)`
+    ///chromaticities of the red, green and blue primaries and the white
+    ///point. The white point gets a luminance, `Y`, of `1.0`.
+    ///
+    ///This gives the same matrix as [`rgb_to_xyz`](#method.rgb_to_xyz), for
+    ///primaries that are only known at runtime. `None` is returned if the
+    ///primaries don't span a color space, such as when two of them are the
+    ///same.
+    pub fn from_chromaticities(
+        red: (T, T),
+        green: (T, T),
+        blue: (T, T),
+        white: (T, T),
+    ) -> Option<ColorMatrix<T>> {
+        let to_xyz = |(x, y): (T, T)| [x / y, T::one(), (T::one() - x - y) / y];
+        let (r, g, b) = (to_xyz(red), to_xyz(green), to_xyz(blue));
+        let primaries = ColorMatrix::new([
+            r[0], g[0], b[0],
+            r[1], g[1], b[1],
+            r[2], g[2], b[2],
+        ]);
+
+        primaries
+            .inverse()
+            .map(|inverse| primaries * ColorMatrix::diagonal(inverse.transform(to_xyz(white))))
+    }
+
+    ///The rows of the matrix.
+    pub fn rows(&self) -> [[T; 3]; 3] {
+        let e = &self.elements;
+        [[e[0], e[1], e[2]], [e[3], e[4], e[5]], [e[6], e[7], e[8]]]
+    }
+
+    ///The columns of the matrix, which is the column major layout of
+    ///libraries such as `glam` and `nalgebra`.
+    pub fn columns(&self) -> [[T; 3]; 3] {
+        self.transpose().rows()
+    }
+
+    ///The columns of the matrix, with each column padded to four
+    ///components. This is the layout of a `mat3x3` in WGSL and of a `mat3` in
+    ///GLSL uniform buffers.
+    pub fn padded_columns(&self) -> [[T; 4]; 3] {
+        let c = self.columns();
+        let z = T::zero();
+        [
+            [c[0][0], c[0][1], c[0][2], z],
+            [c[1][0], c[1][1], c[1][2], z],
+            [c[2][0], c[2][1], c[2][2], z],
+        ]
+    }
+
+    ///Swap the rows and columns of the matrix.
+    pub fn transpose(&self) -> ColorMatrix<T> {
+        let e = &self.elements;
+        ColorMatrix::new([e[0], e[3], e[6], e[1], e[4], e[7], e[2], e[5], e[8]])
+    }
+
+    ///The determinant of the matrix.
+    pub fn determinant(&self) -> T {
+        let a = &self.elements;
+        a[0] * (a[4] * a[8] - a[5] * a[7]) - a[1] * (a[3] * a[8] - a[5] * a[6])
+            + a[2] * (a[3] * a[7] - a[4] * a[6])
+    }
+
+    ///The inverse of the matrix, or `None` if it isn't invertible.
+    pub fn inverse(&self) -> Option<ColorMatrix<T>> {
+        if self.determinant().is_normal() {
+            Some(ColorMatrix::new(matrix_inverse(&self.elements)))
+        } else {
+            None
+        }
+    }
+
+    ///Combine this transform with `next`, which is applied after it.
+    ///
+    ///This is the same as `next * self`.
+    pub fn then(self, next: ColorMatrix<T>) -> ColorMatrix<T> {
+        next * self
+    }
+
+    ///Apply the matrix to the components of a color.
+    pub fn transform(&self, components: [T; 3]) -> [T; 3] {
+        let e = &self.elements;
+        let [a, b, c] = components;
+        [
+            e[0] * a + e[1] * b + e[2] * c,
+            e[3] * a + e[4] * b + e[5] * c,
+            e[6] * a + e[7] * b + e[8] * c,
+        ]
+    }
+
+    ///Convert the elements into another float type.
+    pub fn cast<U: Float>(&self) -> ColorMatrix<U> {
+        let mut elements = [U::zero(); 9];
+        for (element, &value) in elements.iter_mut().zip(&self.elements) {
+            *element = ::cast(value);
+        }
+
+        ColorMatrix::new(elements)
+    }
+}
+
+impl<T: Component + Float> ColorMatrix<T> {
+    ///The matrix that converts linear RGB in the space `S` to XYZ.
+    pub fn rgb_to_xyz<S: RgbSpace>() -> ColorMatrix<T> {
+        ColorMatrix::new(rgb_to_xyz_matrix::<S, T>())
+    }
+
+    ///The matrix that converts XYZ to linear RGB in the space `S`.
+    pub fn xyz_to_rgb<S: RgbSpace>() -> ColorMatrix<T> {
+        ColorMatrix::new(matrix_inverse(&rgb_to_xyz_matrix::<S, T>()))
+    }
+
+    ///The matrix that converts linear RGB in `Src` to linear RGB in `Dst`,
+    ///when they have the same white point.
+    pub fn rgb_to_rgb<Src, Dst>() -> ColorMatrix<T>
+    where
+        Src: RgbSpace,
+        Dst: RgbSpace<WhitePoint = Src::WhitePoint>,
+    {
+        ColorMatrix::new(rgb_to_rgb_matrix::<Src, Dst, T>())
+    }
+
+    ///The matrix that adapts XYZ colors from the white point `Swp` to
+    ///`Dwp`, using the chromatic adaptation `method`.
+    pub fn adaptation<Swp, Dwp, M>(method: M) -> ColorMatrix<T>
+    where
+        Swp: WhitePoint,
+        Dwp: WhitePoint,
+        M: TransformMatrix<Swp, Dwp, T>,
+    {
+        ColorMatrix::new(method.generate_transform_matrix())
+    }
+}
+
+impl<T: Float> Mul for ColorMatrix<T> {
+    type Output = ColorMatrix<T>;
+
+    ///Multiply the matrices, which gives a transform that applies `other`
+    ///first and `self` after it.
+    fn mul(self, other: ColorMatrix<T>) -> ColorMatrix<T> {
+        ColorMatrix::new(multiply_3x3(&self.elements, &other.elements))
+    }
+}
+
+impl<T: Float> Mul<[T; 3]> for ColorMatrix<T> {
+    type Output = [T; 3];
+
+    fn mul(self, components: [T; 3]) -> [T; 3] {
+        self.transform(components)
+    }
+}
+
+impl<T: Float> Default for ColorMatrix<T> {
+    fn default() -> ColorMatrix<T> {
+        ColorMatrix::identity()
+    }
+}
+
+impl<T> From<Mat3<T>> for ColorMatrix<T> {
+    fn from(elements: Mat3<T>) -> ColorMatrix<T> {
+        ColorMatrix { elements: elements }
+    }
+}
+
+impl<T> From<ColorMatrix<T>> for Mat3<T> {
+    fn from(matrix: ColorMatrix<T>) -> Mat3<T> {
+        matrix.elements
+    }
+}
+
 ///Multiply the 3x3 matrix with the XYZ color
 pub fn multiply_xyz<Swp: WhitePoint, Dwp: WhitePoint, T: Component + Float>(
     c: &Mat3<T>,
@@ -51,6 +283,19 @@ pub fn multiply_rgb_to_xyz<S: RgbSpace, T: Component + Float>(
     }
 }
 
+///Multiply the 3x3 matrix with the RGB color into another RGB space
+pub fn multiply_rgb<Src: RgbSpace, Dst: RgbSpace, T: Component + Float>(
+    c: &Mat3<T>,
+    f: &Rgb<Linear<Src>, T>,
+) -> Rgb<Linear<Dst>, T> {
+    Rgb {
+        red: (c[0] * f.red) + (c[1] * f.green) + (c[2] * f.blue),
+        green: (c[3] * f.red) + (c[4] * f.green) + (c[5] * f.blue),
+        blue: (c[6] * f.red) + (c[7] * f.green) + (c[8] * f.blue),
+        standard: PhantomData,
+    }
+}
+
 ///Multiply a 3x3 matrix with another 3x3 matrix
 pub fn multiply_3x3<T: Float>(c: &Mat3<T>, f: &Mat3<T>) -> Mat3<T> {
     let mut out = [T::zero(); 9];
@@ -98,6 +343,38 @@ pub fn matrix_inverse<T: Float>(a: &Mat3<T>) -> Mat3<T> {
     ]
 }
 
+///Generate a chromatic adaptation matrix from the source white point
+///`s_wp` to the destination white point `t_wp`, using the cone response
+///matrices `adapt`.
+pub fn adaptation_matrix<Swp, Dwp, T>(
+    adapt: ConeResponseMatrices<T>,
+    s_wp: &Xyz<Swp, T>,
+    t_wp: &Xyz<Dwp, T>,
+) -> Mat3<T>
+where
+    T: Component + Float,
+    Swp: WhitePoint,
+    Dwp: WhitePoint,
+{
+    let resp_src: Xyz<Swp, _> = multiply_xyz(&adapt.ma, s_wp);
+    let resp_dst: Xyz<Dwp, _> = multiply_xyz(&adapt.ma, t_wp);
+    let z = T::zero();
+    let resp = [
+        resp_dst.x / resp_src.x,
+        z,
+        z,
+        z,
+        resp_dst.y / resp_src.y,
+        z,
+        z,
+        z,
+        resp_dst.z / resp_src.z,
+    ];
+
+    let tmp = multiply_3x3(&resp, &adapt.ma);
+    multiply_3x3(&adapt.inv_ma, &tmp)
+}
+
 ///Geneartes to Srgb to Xyz transformation matrix for the given white point
 pub fn rgb_to_xyz_matrix<S: RgbSpace, T: Component + Float>() -> Mat3<T> {
     let r: Xyz<S::WhitePoint, T> = S::Primaries::red().into_xyz();
@@ -123,6 +400,21 @@ pub fn rgb_to_xyz_matrix<S: RgbSpace, T: Component + Float>() -> Mat3<T> {
     transform_matrix
 }
 
+///Generates the matrix that converts linear RGB in `Src` directly to linear
+///RGB in `Dst`, by fusing the XYZ matrix of `Src` with the inverse XYZ matrix
+///of `Dst`.
+pub fn rgb_to_rgb_matrix<Src, Dst, T>() -> Mat3<T>
+where
+    Src: RgbSpace,
+    Dst: RgbSpace<WhitePoint = Src::WhitePoint>,
+    T: Component + Float,
+{
+    multiply_3x3(
+        &matrix_inverse(&rgb_to_xyz_matrix::<Dst, T>()),
+        &rgb_to_xyz_matrix::<Src, T>(),
+    )
+}
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 fn mat3_from_primaries<T: Component + Float, Wp: WhitePoint>(r: Xyz<Wp, T>, g: Xyz<Wp, T>, b: Xyz<Wp, T>) -> Mat3<T> {
     [
@@ -139,7 +431,8 @@ mod test {
     use encoding::{Linear, Srgb};
     use chromatic_adaptation::AdaptInto;
     use white_point::D50;
-    use super::{matrix_inverse, multiply_xyz, rgb_to_xyz_matrix, multiply_3x3};
+    use super::{matrix_inverse, multiply_rgb, multiply_xyz, rgb_to_rgb_matrix, rgb_to_xyz_matrix, multiply_3x3};
+    use super::ColorMatrix;
 
     #[test]
     fn matrix_multiply_3x3() {
@@ -207,4 +500,82 @@ mod test {
         let computed: Rgb<Linear<(Srgb, D50)>> = input.adapt_into();
         assert_relative_eq!(expected, computed, epsilon = 0.000001);
     }
+
+    #[test]
+    fn rgb_to_rgb_skips_xyz() {
+        use encoding::itu::BT601_525;
+        use FromColorUnclamped;
+
+        let input: Rgb<Linear<BT601_525>, f64> = Rgb::new(0.8, 0.4, 0.2);
+        let matrix = rgb_to_rgb_matrix::<BT601_525, Srgb, f64>();
+        let computed: Rgb<Linear<Srgb>, f64> = multiply_rgb(&matrix, &input);
+
+        assert_relative_eq!(computed, Rgb::from_xyz(Xyz::from_rgb(input)), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn color_matrix_composition() {
+        use encoding::itu::BT601_525;
+
+        let to_xyz = ColorMatrix::<f64>::rgb_to_xyz::<BT601_525>();
+        let from_xyz = ColorMatrix::xyz_to_rgb::<Srgb>();
+        let fused = ColorMatrix::rgb_to_rgb::<BT601_525, Srgb>();
+
+        let composed = to_xyz.then(from_xyz);
+        assert_eq!(composed, from_xyz * to_xyz);
+        for (a, b) in composed.elements.iter().zip(&fused.elements) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+
+        let color = [0.8, 0.4, 0.2];
+        let expected = from_xyz.transform(to_xyz.transform(color));
+        let computed = composed * color;
+        for (a, b) in computed.iter().zip(&expected) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn color_matrix_inverse() {
+        let matrix = ColorMatrix::new([3.0, 0.0, 2.0, 2.0, 0.0, -2.0, 0.0, 1.0, 1.0]);
+        assert_eq!(matrix.determinant(), 10.0);
+
+        let product = matrix * matrix.inverse().unwrap();
+        for (a, b) in product.elements.iter().zip(&ColorMatrix::identity().elements) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+
+        let singular = ColorMatrix::new([1.0, 0.0, 0.0, 2.0, 0.0, 0.0, -4.0, 6.0, 1.0]);
+        assert_eq!(singular.inverse(), None);
+    }
+
+    #[test]
+    fn color_matrix_from_chromaticities() {
+        let computed = ColorMatrix::from_chromaticities(
+            (0.64, 0.33),
+            (0.30, 0.60),
+            (0.15, 0.06),
+            (0.3127266, 0.3290231),
+        )
+        .unwrap();
+        let expected = ColorMatrix::<f64>::rgb_to_xyz::<Srgb>();
+        for (a, b) in computed.elements.iter().zip(&expected.elements) {
+            assert_relative_eq!(a, b, epsilon = 1e-6);
+        }
+
+        let same = ColorMatrix::from_chromaticities((0.6, 0.3), (0.6, 0.3), (0.2, 0.1), (0.3, 0.3));
+        assert_eq!(same, None);
+    }
+
+    #[test]
+    fn color_matrix_layout() {
+        let matrix = ColorMatrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+
+        assert_eq!(matrix.elements, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(matrix.rows()[1], [4.0, 5.0, 6.0]);
+        assert_eq!(matrix.columns()[1], [2.0, 5.0, 8.0]);
+        assert_eq!(matrix.padded_columns()[2], [3.0, 6.0, 9.0, 0.0]);
+        assert_eq!(matrix.transpose().transpose(), matrix);
+        assert_eq!(matrix.cast::<f32>().elements[8], 9.0f32);
+    }
 }
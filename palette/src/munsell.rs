@@ -0,0 +1,852 @@
+//!Conversion between Munsell notation and CIE XYZ.
+//!
+//!The Munsell system describes a color by its hue, value and chroma, written
+//!as `5R 4/14` for the hue `5R`, the value `4` and the chroma `14`, or as
+//!`N 5/` for a neutral gray. It's still used in soil science, geology and art
+//!conservation, where the colors are matched against printed chips.
+//!
+//!The chips are defined by the Munsell renotation of 1943, which is a table of
+//!`(x, y)` chromaticities for illuminant [`C`](../white_point/struct.C.html)
+//!and the CIE 1931 2° observer. Palette doesn't distribute the table. A
+//![`Renotation`](struct.Renotation.html) is instead loaded from the text of
+//!the published `real.dat` or `all.dat` files, from the Munsell Color Science
+//!Laboratory at RIT, with one `h V C x y Y` sample per line. Colors between the
+//!samples are interpolated, and the value is converted to luminance with the
+//!ASTM D1535 polynomial.
+//!
+//!```
+//!use palette::munsell::{Munsell, Renotation};
+//!use palette::white_point::C;
+//!use palette::{IntoColor, Lab};
+//!
+//!//A few made up samples, in the format of real.dat. The published table
+//!//would be read from a file.
+//!let data = "h V C x y Y
+//!    2.5R 4 12 0.46 0.26 12.0
+//!    2.5R 4 14 0.49 0.25 12.0
+//!    5R 4 12 0.50 0.30 12.0
+//!    5R 4 14 0.53 0.29 12.0
+//!    7.5R 4 12 0.50 0.35 12.0
+//!    7.5R 4 14 0.53 0.35 12.0";
+//!let renotation = Renotation::<f64>::parse(data).unwrap();
+//!
+//!let color: Munsell<f64> = "5R 4/13".parse().unwrap();
+//!let xyz = renotation.to_xyz(&color).unwrap();
+//!let lab: Lab<C, f64> = xyz.into_color();
+//!assert!(lab.a > 0.0);
+//!
+//!let back = renotation.from_xyz(xyz).unwrap();
+//!assert!((back.chroma - 13.0).abs() < 1e-6);
+//!```
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::f64::consts::PI;
+use core::fmt;
+use core::str::FromStr;
+
+use float::Float;
+
+use white_point::{WhitePoint, C};
+use {cast, Component, Xyz, Yxy};
+
+///An error from parsing Munsell notation or a renotation table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    ///A number couldn't be parsed, or was out of range.
+    InvalidNumber,
+    ///The hue family isn't one of the ten Munsell hues.
+    UnknownHue,
+    ///The value isn't structured as expected, for example by missing the `/`
+    ///between value and chroma.
+    InvalidSyntax,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ParseError::InvalidNumber => "invalid number",
+            ParseError::UnknownHue => "unknown hue",
+            ParseError::InvalidSyntax => "invalid syntax",
+        };
+
+        fmt.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ParseError {}
+
+///The ten principal and intermediate hues of the Munsell system, in the
+///order of the hue circle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HueFamily {
+    ///Red.
+    R,
+    ///Yellow-red.
+    YR,
+    ///Yellow.
+    Y,
+    ///Green-yellow.
+    GY,
+    ///Green.
+    G,
+    ///Blue-green.
+    BG,
+    ///Blue.
+    B,
+    ///Purple-blue.
+    PB,
+    ///Purple.
+    P,
+    ///Red-purple.
+    RP,
+}
+
+const FAMILIES: [HueFamily; 10] = [
+    HueFamily::R,
+    HueFamily::YR,
+    HueFamily::Y,
+    HueFamily::GY,
+    HueFamily::G,
+    HueFamily::BG,
+    HueFamily::B,
+    HueFamily::PB,
+    HueFamily::P,
+    HueFamily::RP,
+];
+
+impl HueFamily {
+    ///The abbreviation of the family, such as `"YR"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            HueFamily::R => "R",
+            HueFamily::YR => "YR",
+            HueFamily::Y => "Y",
+            HueFamily::GY => "GY",
+            HueFamily::G => "G",
+            HueFamily::BG => "BG",
+            HueFamily::B => "B",
+            HueFamily::PB => "PB",
+            HueFamily::P => "P",
+            HueFamily::RP => "RP",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<HueFamily> {
+        FAMILIES
+            .iter()
+            .cloned()
+            .find(|family| family.name() == name)
+    }
+}
+
+///A Munsell hue, such as `2.5YR`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MunsellHue<T> {
+    ///The step within the family, above `0` and up to `10`. The principal
+    ///hue of the family is `5`.
+    pub step: T,
+
+    ///The hue family.
+    pub family: HueFamily,
+}
+
+impl<T: Component + Float> MunsellHue<T> {
+    ///Create a hue from its position on the hue circle, where `0` and `100`
+    ///are `10RP`, `5` is `5R` and `15` is `5YR`.
+    pub fn from_degrees(hue: T) -> MunsellHue<T> {
+        let hue = normalize_hue(hue);
+        let ten: T = cast(10.0);
+        let mut index = (hue / ten).floor();
+        let mut step = hue - index * ten;
+
+        if step <= T::zero() {
+            step = ten;
+            index = index - T::one();
+        }
+
+        let index: usize = cast(if index < T::zero() {
+            index + ten
+        } else {
+            index
+        });
+
+        MunsellHue {
+            step: step,
+            family: FAMILIES[index],
+        }
+    }
+
+    ///The position of the hue on the hue circle, from `0` to `100`, where
+    ///`0` is `10RP`.
+    pub fn to_degrees(&self) -> T {
+        let offset: T = cast(self.family as u8 as f64 * 10.0);
+        normalize_hue(offset + self.step)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MunsellHue<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}{}", self.step, self.family.name())
+    }
+}
+
+///A color in Munsell notation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Munsell<T = f32> {
+    ///The hue, or `None` for a neutral gray.
+    pub hue: Option<MunsellHue<T>>,
+
+    ///The lightness, from `0` for black to `10` for white.
+    pub value: T,
+
+    ///The colorfulness, from `0` for gray. It has no fixed maximum, since
+    ///the limit depends on hue and value.
+    pub chroma: T,
+}
+
+impl<T: Component + Float> Munsell<T> {
+    ///Create a chromatic color.
+    pub fn new(hue: MunsellHue<T>, value: T, chroma: T) -> Munsell<T> {
+        Munsell {
+            hue: Some(hue),
+            value: value,
+            chroma: chroma,
+        }
+    }
+
+    ///Create a neutral gray with the value `value`.
+    pub fn neutral(value: T) -> Munsell<T> {
+        Munsell {
+            hue: None,
+            value: value,
+            chroma: T::zero(),
+        }
+    }
+
+    ///Check if the color is a neutral gray.
+    pub fn is_neutral(&self) -> bool {
+        self.hue.is_none() || self.chroma <= T::zero()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Munsell<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.hue {
+            Some(ref hue) => write!(fmt, "{} {}/{}", hue, self.value, self.chroma),
+            None => write!(fmt, "N {}/", self.value),
+        }
+    }
+}
+
+impl<T: Component + Float> FromStr for Munsell<T> {
+    type Err = ParseError;
+
+    ///Parse notation such as `5R 4/14`, `2.5YR6/8` or `N 5/`.
+    fn from_str(s: &str) -> Result<Munsell<T>, ParseError> {
+        let s = s.trim();
+
+        if let Some(rest) = s.strip_prefix('N') {
+            let rest = rest.trim();
+            let (value, chroma) = match rest.find('/') {
+                Some(index) => (&rest[..index], rest[index + 1..].trim()),
+                None => (rest, ""),
+            };
+
+            if !chroma.is_empty() && parse_number(chroma)? != 0.0 {
+                return Err(ParseError::InvalidSyntax);
+            }
+
+            return Ok(Munsell::neutral(parse_value(value)?));
+        }
+
+        let (hue, rest) = parse_hue(s)?;
+        let slash = rest.find('/').ok_or(ParseError::InvalidSyntax)?;
+        let value = parse_value(&rest[..slash])?;
+        let chroma = parse_number(&rest[slash + 1..])?;
+        if chroma < 0.0 {
+            return Err(ParseError::InvalidNumber);
+        }
+
+        Ok(Munsell::new(hue, value, cast(chroma)))
+    }
+}
+
+fn parse_number(s: &str) -> Result<f64, ParseError> {
+    match s.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(ParseError::InvalidNumber),
+    }
+}
+
+fn parse_value<T: Component + Float>(s: &str) -> Result<T, ParseError> {
+    let value = parse_number(s)?;
+    if !(0.0..=10.0).contains(&value) {
+        return Err(ParseError::InvalidNumber);
+    }
+
+    Ok(cast(value))
+}
+
+///Parse a hue at the start of `s`, and return it with the rest of `s`.
+fn parse_hue<T: Component + Float>(s: &str) -> Result<(MunsellHue<T>, &str), ParseError> {
+    let letters = s
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or(ParseError::InvalidSyntax)?;
+    let end = s[letters..]
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .map_or(s.len(), |end| letters + end);
+
+    let step = parse_number(&s[..letters])?;
+    if step <= 0.0 || step > 10.0 {
+        return Err(ParseError::InvalidNumber);
+    }
+    let family = HueFamily::from_name(&s[letters..end]).ok_or(ParseError::UnknownHue)?;
+
+    let hue = MunsellHue {
+        step: cast(step),
+        family: family,
+    };
+    Ok((hue, &s[end..]))
+}
+
+///Convert a Munsell value into relative luminance, from `0` to `1`, using the
+///ASTM D1535 polynomial.
+pub fn luminance_from_value<T: Component + Float>(value: T) -> T {
+    let coefficients: [f64; 5] = [1.1914, -0.22533, 0.23352, -0.020484, 0.00081939];
+    let mut luminance = T::zero();
+    for &coefficient in coefficients.iter().rev() {
+        luminance = (luminance + cast(coefficient)) * value;
+    }
+
+    luminance / cast(100.0)
+}
+
+///Convert relative luminance, from `0` to `1`, into a Munsell value.
+///
+///This inverts the ASTM D1535 polynomial numerically, so it's accurate to
+///about `1e-9` for `f64`. Values outside `0` to `10` are clamped.
+pub fn value_from_luminance<T: Component + Float>(luminance: T) -> T {
+    let (mut low, mut high) = (T::zero(), cast::<T, _>(10.0));
+    if luminance <= T::zero() {
+        return low;
+    }
+    if luminance >= luminance_from_value(high) {
+        return high;
+    }
+
+    let two = T::one() + T::one();
+    for _ in 0..64 {
+        let middle = (low + high) / two;
+        if luminance_from_value(middle) < luminance {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    (low + high) / two
+}
+
+///A sample of the renotation table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Sample<T> {
+    value: T,
+    hue: T,
+    chroma: T,
+    x: T,
+    y: T,
+}
+
+impl<T: Float> Sample<T> {
+    fn key(&self) -> (T, T, T) {
+        (self.value, self.hue, self.chroma)
+    }
+}
+
+///The Munsell renotation table, for converting between Munsell notation and
+///XYZ relative to illuminant `C`.
+///
+///Colors are interpolated linearly between the samples, first between the
+///chroma steps, then between the hue pages and last between the values. The
+///hue pages have to be `2.5` steps apart, and the chromas have to be even, as
+///they are in the published tables. Colors outside the samples, such as too
+///high chromas, can't be converted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Renotation<T = f32> {
+    samples: Vec<Sample<T>>,
+    values: Vec<T>,
+}
+
+impl<T: Component + Float> Renotation<T> {
+    ///Parse a table in the format of the renotation data files, where each
+    ///line is a `h V C x y Y` sample, such as `2.5GY 4 6 0.3806 0.4462 12.0`.
+    ///
+    ///Empty lines and a header line starting with `h` are skipped. The `Y`
+    ///column is ignored, since the luminance is calculated from the value.
+    pub fn parse(data: &str) -> Result<Renotation<T>, ParseError> {
+        let mut samples = Vec::new();
+
+        for line in data.lines() {
+            let mut columns = line.split_whitespace();
+            let hue = match columns.next() {
+                Some("h") | None => continue,
+                Some(hue) => hue,
+            };
+
+            let (hue, rest) = parse_hue::<T>(hue)?;
+            if !rest.is_empty() {
+                return Err(ParseError::InvalidSyntax);
+            }
+
+            let mut number = || {
+                columns
+                    .next()
+                    .ok_or(ParseError::InvalidSyntax)
+                    .and_then(parse_number)
+                    .map(cast)
+            };
+
+            samples.push(Sample {
+                value: number()?,
+                hue: hue.to_degrees(),
+                chroma: number()?,
+                x: number()?,
+                y: number()?,
+            });
+        }
+
+        samples.sort_by(|a, b| compare(a.key(), b.key()));
+        let mut values: Vec<T> = samples.iter().map(|sample| sample.value).collect();
+        values.dedup();
+
+        Ok(Renotation {
+            samples: samples,
+            values: values,
+        })
+    }
+
+    ///The number of samples in the table.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    ///Check if the table has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    ///Convert Munsell notation into XYZ, or `None` if it's outside the
+    ///table.
+    pub fn to_xyz(&self, color: &Munsell<T>) -> Option<Xyz<C, T>> {
+        self.to_yxy(color).map(Xyz::from)
+    }
+
+    ///Convert Munsell notation into Yxy, or `None` if it's outside the
+    ///table.
+    pub fn to_yxy(&self, color: &Munsell<T>) -> Option<Yxy<C, T>> {
+        if color.value < T::zero() || color.value > cast(10.0) {
+            return None;
+        }
+
+        let luminance = luminance_from_value(color.value);
+        let hue = match color.hue {
+            Some(ref hue) if color.chroma > T::zero() => hue.to_degrees(),
+            _ => {
+                let (x, y) = white();
+                return Some(Yxy::with_wp(x, y, luminance));
+            }
+        };
+
+        let (x, y) = self.chromaticity(hue, color.value, color.chroma)?;
+        Some(Yxy::with_wp(x, y, luminance))
+    }
+
+    ///Convert XYZ into Munsell notation, or `None` if it's outside the
+    ///table.
+    ///
+    ///The hue and chroma are found by iterating until the interpolated
+    ///chromaticity matches the color.
+    pub fn from_xyz(&self, color: Xyz<C, T>) -> Option<Munsell<T>> {
+        self.from_yxy(Yxy::from(color))
+    }
+
+    ///Convert Yxy into Munsell notation, or `None` if it's outside the
+    ///table.
+    pub fn from_yxy(&self, color: Yxy<C, T>) -> Option<Munsell<T>> {
+        let value = value_from_luminance(color.luma);
+        let (white_x, white_y) = white();
+        let target = polar(color.x - white_x, color.y - white_y);
+
+        if target.1 < cast(1e-9) {
+            return Some(Munsell::neutral(value));
+        }
+
+        let (mut hue, mut chroma) = self.closest_sample(value, target)?;
+        let delta: T = cast(0.01);
+
+        for _ in 0..100 {
+            let (x, y) = self.chromaticity(hue, value, chroma)?;
+            let (angle, radius) = polar(x - white_x, y - white_y);
+
+            let (x, y) = self.chromaticity(normalize_hue(hue + delta), value, chroma)?;
+            let slope = wrap_angle(polar(x - white_x, y - white_y).0 - angle) / delta;
+            let hue_step = if slope > T::zero() {
+                wrap_angle(target.0 - angle) / slope
+            } else {
+                T::zero()
+            };
+
+            let new_chroma = chroma * target.1 / radius;
+            let converged = hue_step.abs() < cast(1e-9) && (new_chroma - chroma).abs() < cast(1e-9);
+            hue = normalize_hue(hue + hue_step);
+            chroma = new_chroma;
+
+            if converged {
+                break;
+            }
+        }
+
+        Some(Munsell::new(MunsellHue::from_degrees(hue), value, chroma))
+    }
+
+    ///Interpolate the chromaticity of a chromatic color.
+    fn chromaticity(&self, hue: T, value: T, chroma: T) -> Option<(T, T)> {
+        let index = self.values.iter().position(|&plane| plane >= value)?;
+        let upper = self.values[index];
+        if upper == value {
+            return self.plane_chromaticity(hue, upper, chroma);
+        }
+
+        let lower = *self.values.get(index.checked_sub(1)?)?;
+        let factor = (value - lower) / (upper - lower);
+        let (lower_x, lower_y) = self.plane_chromaticity(hue, lower, chroma)?;
+        let (upper_x, upper_y) = self.plane_chromaticity(hue, upper, chroma)?;
+
+        Some((
+            lerp(lower_x, upper_x, factor),
+            lerp(lower_y, upper_y, factor),
+        ))
+    }
+
+    ///Interpolate the chromaticity of a color on a value plane in the table.
+    fn plane_chromaticity(&self, hue: T, value: T, chroma: T) -> Option<(T, T)> {
+        let page: T = cast(2.5);
+        let lower = (hue / page).floor() * page;
+        let factor = (hue - lower) / page;
+
+        let lower_xy = self.page_chromaticity(normalize_hue(lower), value, chroma)?;
+        if factor == T::zero() {
+            return Some(lower_xy);
+        }
+
+        let upper_xy = self.page_chromaticity(normalize_hue(lower + page), value, chroma)?;
+        Some((
+            lerp(lower_xy.0, upper_xy.0, factor),
+            lerp(lower_xy.1, upper_xy.1, factor),
+        ))
+    }
+
+    ///Interpolate the chromaticity of a color on a hue page in the table.
+    fn page_chromaticity(&self, hue: T, value: T, chroma: T) -> Option<(T, T)> {
+        let two = T::one() + T::one();
+        let lower = (chroma / two).floor() * two;
+        let factor = (chroma - lower) / two;
+
+        let lower_xy = if lower == T::zero() {
+            white()
+        } else {
+            self.sample(value, hue, lower)?
+        };
+        if factor == T::zero() {
+            return Some(lower_xy);
+        }
+
+        let upper_xy = self.sample(value, hue, lower + two)?;
+        Some((
+            lerp(lower_xy.0, upper_xy.0, factor),
+            lerp(lower_xy.1, upper_xy.1, factor),
+        ))
+    }
+
+    fn sample(&self, value: T, hue: T, chroma: T) -> Option<(T, T)> {
+        let epsilon: T = cast(1e-6);
+        let same = |a: T, b: T| (a - b).abs() < epsilon;
+        let index = self
+            .samples
+            .binary_search_by(|sample| {
+                let (sample_value, sample_hue, sample_chroma) = sample.key();
+                if !same(sample_value, value) {
+                    compare_float(sample_value, value)
+                } else if !same(sample_hue, hue) {
+                    compare_float(sample_hue, hue)
+                } else if !same(sample_chroma, chroma) {
+                    compare_float(sample_chroma, chroma)
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()?;
+
+        let sample = &self.samples[index];
+        Some((sample.x, sample.y))
+    }
+
+    ///Find the hue and chroma of the sample with the closest chromaticity on
+    ///the value plane that is closest to `value`.
+    fn closest_sample(&self, value: T, (angle, radius): (T, T)) -> Option<(T, T)> {
+        let plane = self
+            .values
+            .iter()
+            .cloned()
+            .min_by(|&a, &b| compare_float((a - value).abs(), (b - value).abs()))?;
+        let (white_x, white_y) = white();
+
+        self.samples
+            .iter()
+            .filter(|sample| sample.value == plane)
+            .map(|sample| {
+                let (sample_angle, sample_radius) = polar(sample.x - white_x, sample.y - white_y);
+                let distance = (wrap_angle(sample_angle - angle) * radius).abs()
+                    + (sample_radius - radius).abs();
+                (distance, sample)
+            })
+            .min_by(|a, b| compare_float(a.0, b.0))
+            .map(|(_, sample)| (sample.hue, sample.chroma))
+    }
+}
+
+///The chromaticity of illuminant C.
+fn white<T: Component + Float>() -> (T, T) {
+    let white: Xyz<C, T> = C::get_xyz();
+    let sum = white.x + white.y + white.z;
+    (white.x / sum, white.y / sum)
+}
+
+fn normalize_hue<T: Component + Float>(hue: T) -> T {
+    let full: T = cast(100.0);
+    let hue = hue - (hue / full).floor() * full;
+    if hue >= full {
+        T::zero()
+    } else {
+        hue
+    }
+}
+
+///The angle and radius of a chromaticity difference.
+fn polar<T: Float>(x: T, y: T) -> (T, T) {
+    (y.atan2(x), (x * x + y * y).sqrt())
+}
+
+fn wrap_angle<T: Component + Float>(angle: T) -> T {
+    let pi: T = cast(PI);
+    let full = pi + pi;
+    if angle > pi {
+        angle - full
+    } else if angle < -pi {
+        angle + full
+    } else {
+        angle
+    }
+}
+
+fn lerp<T: Float>(a: T, b: T, factor: T) -> T {
+    a + (b - a) * factor
+}
+
+fn compare_float<T: Float>(a: T, b: T) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn compare<T: Float>(a: (T, T, T), b: (T, T, T)) -> Ordering {
+    compare_float(a.0, b.0)
+        .then(compare_float(a.1, b.1))
+        .then(compare_float(a.2, b.2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        luminance_from_value, value_from_luminance, HueFamily, Munsell, MunsellHue, ParseError,
+        Renotation,
+    };
+    use core::f64::consts::PI;
+    use float::Float;
+    use white_point::C;
+    use {IntoColor, Lab, Yxy};
+
+    ///A table where the chromaticity grows linearly with chroma, in the
+    ///direction of the hue.
+    fn renotation() -> Renotation<f64> {
+        let mut data = String::from("h V C x y Y\n");
+        for value in 1..10 {
+            for page in 1..=40 {
+                let hue = MunsellHue::from_degrees(page as f64 * 2.5);
+                let angle = page as f64 * 2.5 / 100.0 * 2.0 * PI;
+                for chroma in (2..=16).step_by(2) {
+                    let radius = chroma as f64 * (0.01 + value as f64 * 0.001);
+                    let x = 0.31006 + radius * Float::cos(angle);
+                    let y = 0.31616 + radius * Float::sin(angle);
+                    data.push_str(&format!(
+                        "{}{} {} {} {} {} 1.0\n",
+                        hue.step,
+                        hue.family.name(),
+                        value,
+                        chroma,
+                        x,
+                        y
+                    ));
+                }
+            }
+        }
+
+        Renotation::parse(&data).unwrap()
+    }
+
+    #[test]
+    fn parse_notation() {
+        let color: Munsell<f64> = "5R 4/14".parse().unwrap();
+        assert_eq!(
+            color,
+            Munsell::new(
+                MunsellHue {
+                    step: 5.0,
+                    family: HueFamily::R
+                },
+                4.0,
+                14.0
+            )
+        );
+
+        let color: Munsell<f64> = "2.5YR6/8".parse().unwrap();
+        assert_eq!(color.hue.unwrap().family, HueFamily::YR);
+        assert_eq!(color.value, 6.0);
+
+        let gray: Munsell<f64> = "N 5/".parse().unwrap();
+        assert_eq!(gray, Munsell::neutral(5.0));
+        assert!(gray.is_neutral());
+        assert_eq!("N 5/0".parse::<Munsell<f64>>(), Ok(Munsell::neutral(5.0)));
+
+        assert_eq!(
+            "5Q 4/14".parse::<Munsell<f64>>(),
+            Err(ParseError::UnknownHue)
+        );
+        assert_eq!(
+            "5R 4 14".parse::<Munsell<f64>>(),
+            Err(ParseError::InvalidSyntax)
+        );
+        assert_eq!(
+            "5R 11/2".parse::<Munsell<f64>>(),
+            Err(ParseError::InvalidNumber)
+        );
+        assert_eq!(
+            "12R 4/2".parse::<Munsell<f64>>(),
+            Err(ParseError::InvalidNumber)
+        );
+    }
+
+    #[test]
+    fn format_notation() {
+        let color: Munsell<f64> = "2.5PB 3/6".parse().unwrap();
+        assert_eq!(format!("{}", color), "2.5PB 3/6");
+        assert_eq!(format!("{}", Munsell::neutral(9.5f64)), "N 9.5/");
+    }
+
+    #[test]
+    fn hue_circle() {
+        let hue = MunsellHue::from_degrees(15.0f64);
+        assert_eq!((hue.step, hue.family), (5.0, HueFamily::YR));
+
+        let hue = MunsellHue::from_degrees(0.0f64);
+        assert_eq!((hue.step, hue.family), (10.0, HueFamily::RP));
+        assert_eq!(hue.to_degrees(), 0.0);
+
+        let hue = MunsellHue::from_degrees(-2.5f64);
+        assert_eq!((hue.step, hue.family), (7.5, HueFamily::RP));
+        assert_eq!(hue.to_degrees(), 97.5);
+    }
+
+    #[test]
+    fn value() {
+        assert_relative_eq!(luminance_from_value(10.0f64), 1.0, epsilon = 1e-4);
+        assert_relative_eq!(luminance_from_value(5.0f64), 0.1927, epsilon = 1e-4);
+        assert_eq!(luminance_from_value(0.0f64), 0.0);
+
+        for &value in &[0.5, 2.0, 5.0, 8.25, 9.9] {
+            let luminance = luminance_from_value(value);
+            assert_relative_eq!(value_from_luminance(luminance), value, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn samples() {
+        let renotation = renotation();
+        assert_eq!(renotation.len(), 9 * 40 * 8);
+
+        let color: Munsell<f64> = "5R 4/6".parse().unwrap();
+        let yxy = renotation.to_yxy(&color).unwrap();
+        let angle = 0.05 * 2.0 * PI;
+        assert_relative_eq!(
+            yxy.x,
+            0.31006 + 6.0 * 0.014 * Float::cos(angle),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            yxy.y,
+            0.31616 + 6.0 * 0.014 * Float::sin(angle),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(yxy.luma, luminance_from_value(4.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn neutral() {
+        let renotation = renotation();
+        let gray = renotation.to_yxy(&Munsell::neutral(5.0)).unwrap();
+        assert_relative_eq!(gray.x, 0.31006, epsilon = 1e-5);
+        assert_relative_eq!(gray.y, 0.31615, epsilon = 1e-5);
+
+        let back = renotation.from_yxy(gray).unwrap();
+        assert!(back.is_neutral());
+        assert_relative_eq!(back.value, 5.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let renotation = renotation();
+        for &notation in &["5R 4/6", "3.7GY 5.5/9", "9.9RP 2.2/1.5", "0.4B 8.9/13"] {
+            let color: Munsell<f64> = notation.parse().unwrap();
+            let xyz = renotation.to_xyz(&color).unwrap();
+            let back = renotation.from_xyz(xyz).unwrap();
+
+            let hue = color.hue.unwrap().to_degrees();
+            let back_hue = back.hue.unwrap().to_degrees();
+            assert_relative_eq!(back_hue, hue, epsilon = 1e-6);
+            assert_relative_eq!(back.value, color.value, epsilon = 1e-6);
+            assert_relative_eq!(back.chroma, color.chroma, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn lab() {
+        let renotation = renotation();
+        let color: Munsell<f64> = "5R 5/8".parse().unwrap();
+        let lab: Lab<C, f64> = renotation.to_xyz(&color).unwrap().into_color();
+        assert_relative_eq!(lab.l, 116.0 * 0.1927f64.cbrt() - 16.0, epsilon = 0.1);
+        assert!(lab.a > 0.0);
+    }
+
+    #[test]
+    fn outside_table() {
+        let renotation = renotation();
+        let too_strong: Munsell<f64> = "5R 5/20".parse().unwrap();
+        assert_eq!(renotation.to_xyz(&too_strong), None);
+
+        let too_dark: Munsell<f64> = "5R 0.5/2".parse().unwrap();
+        assert_eq!(renotation.to_xyz(&too_dark), None);
+
+        let saturated = Yxy::<C, f64>::with_wp(0.7, 0.3, 0.2);
+        assert_eq!(renotation.from_yxy(saturated), None);
+    }
+}
@@ -18,6 +18,8 @@
 #![cfg_attr(feature = "named_from_str", doc = "")]
 #![cfg_attr(feature = "named_from_str", doc = "assert_eq!(from_const, from_str);")]
 //!```
+//!
+//!All of the colors are also listed with their names in [`ALL`](static.ALL.html).
 
 include!(concat!(env!("OUT_DIR"), "/named.rs"));
 
@@ -29,3 +31,34 @@ include!(concat!(env!("OUT_DIR"), "/named.rs"));
 pub fn from_str(name: &str) -> Option<::Srgb<u8>> {
     COLORS.get(name).cloned()
 }
+
+///The X11 color names, from the `rgb.txt` file of the X Window System. Can
+///be toggled with the `"named_x11"` Cargo feature.
+///
+///The X11 colors are a superset of the SVG/CSS3 colors, with numbered
+///variants like `ANTIQUEWHITE1` to `ANTIQUEWHITE4` and `GRAY0` to `GRAY100`.
+///Some of the shared names have different values, such as `GRAY`, `GREEN`,
+///`MAROON` and `PURPLE`, so the CSS colors should be preferred for web
+///content.
+///
+///```
+///use palette::Srgb;
+///use palette::named::{self, x11};
+///
+///assert_eq!(x11::GRAY50, Srgb::new(127, 127, 127));
+///assert_eq!(x11::GREEN, Srgb::new(0, 255, 0));
+///assert_eq!(named::GREEN, Srgb::new(0, 128, 0));
+///```
+#[cfg(feature = "named_x11")]
+pub mod x11 {
+    include!(concat!(env!("OUT_DIR"), "/named_x11.rs"));
+
+    ///Get an X11 color by name. Can be toggled with the `"named_x11"` and
+    ///`"named_from_str"` Cargo features.
+    ///
+    ///The names are the same as the constants, but lower case.
+    #[cfg(feature = "named_from_str")]
+    pub fn from_str(name: &str) -> Option<::Srgb<u8>> {
+        COLORS.get(name).cloned()
+    }
+}
@@ -0,0 +1,535 @@
+//!Finding the closest color of a palette.
+//!
+//!The colors are compared in `Lab`, with one of the CIE color difference
+//!formulas, [`DeltaE`](enum.DeltaE.html). [`nearest`](fn.nearest.html)
+//!searches any palette, [`KdTree`](struct.KdTree.html) speeds up repeated
+//!searches in large palettes, and [`nearest_named`](fn.nearest_named.html)
+//!finds the closest SVG/CSS3 color name.
+//!
+//!```
+//!use palette::Srgb;
+//!use palette::nearest::{nearest, DeltaE};
+//!
+//!let palette = [
+//!    Srgb::new(0.0f32, 0.0, 0.0),
+//!    Srgb::new(1.0, 0.0, 0.0),
+//!    Srgb::new(1.0, 1.0, 1.0),
+//!];
+//!
+//!let closest = nearest(&palette, Srgb::new(0.8, 0.1, 0.1), DeltaE::Ciede2000).unwrap();
+//!assert_eq!(closest.index, 1);
+//!```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use float::Float;
+
+use white_point::WhitePoint;
+use {cast, Component, Lab};
+
+///A CIE color difference formula, for colors in `Lab`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeltaE {
+    ///The Euclidean distance in `Lab`, from CIE 1976. It's fast, but
+    ///overestimates the differences between saturated colors.
+    Cie76,
+    ///The CIE 1994 formula, with the weights for graphic arts. It's not
+    ///symmetric, and the first color is the reference.
+    Cie94,
+    ///The CIEDE2000 formula, which is the most accurate for small
+    ///differences.
+    Ciede2000,
+}
+
+impl DeltaE {
+    ///Calculate the difference between `reference` and `color`. A
+    ///difference of about 1 is just noticeable.
+    ///
+    ///```
+    ///use palette::Lab;
+    ///use palette::nearest::DeltaE;
+    ///
+    ///let a = Lab::new(50.0f64, 2.6772, -79.7751);
+    ///let b = Lab::new(50.0, 0.0, -82.7485);
+    ///assert!((DeltaE::Ciede2000.difference(a, b) - 2.0425).abs() < 1e-4);
+    ///```
+    pub fn difference<Wp, T>(self, reference: Lab<Wp, T>, color: Lab<Wp, T>) -> T
+    where
+        Wp: WhitePoint,
+        T: Component + Float,
+    {
+        let a = [cast(reference.l), cast(reference.a), cast(reference.b)];
+        let b = [cast(color.l), cast(color.a), cast(color.b)];
+
+        cast(match self {
+            DeltaE::Cie76 => distance_squared(&a, &b).sqrt(),
+            DeltaE::Cie94 => cie94(&a, &b),
+            DeltaE::Ciede2000 => ciede2000(&a, &b),
+        })
+    }
+}
+
+///The closest color of a palette.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Match<T> {
+    ///The index of the color in the palette.
+    pub index: usize,
+
+    ///The color difference between the color and the palette color.
+    pub distance: T,
+}
+
+///Find the color in `palette` that is closest to `color`, or `None` if the
+///palette is empty.
+///
+///The palette colors are converted to `Lab` for each search, so a palette
+///of `Lab` colors is faster for repeated searches. The palette colors are
+///the references for `DeltaE::Cie94`.
+pub fn nearest<Wp, T, P, C>(palette: &[P], color: C, delta_e: DeltaE) -> Option<Match<T>>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    P: Copy,
+    Lab<Wp, T>: From<P> + From<C>,
+{
+    let color = Lab::from(color);
+    let mut closest: Option<Match<T>> = None;
+    for (index, &candidate) in palette.iter().enumerate() {
+        let distance = delta_e.difference(Lab::from(candidate), color);
+        let closer = match closest {
+            Some(closest) => distance < closest.distance,
+            None => true,
+        };
+
+        if closer {
+            closest = Some(Match {
+                index: index,
+                distance: distance,
+            });
+        }
+    }
+
+    closest
+}
+
+///The closest SVG/CSS3 named color. Can be toggled with the `"named"`
+///Cargo feature.
+#[cfg(feature = "named")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NamedMatch<T> {
+    ///The lower case name of the color.
+    pub name: &'static str,
+
+    ///The value of the named color.
+    pub color: ::Srgb<u8>,
+
+    ///The color difference between the color and the named color.
+    pub distance: T,
+}
+
+///Find the SVG/CSS3 named color that is closest to `color`. Can be toggled
+///with the `"named"` Cargo feature.
+///
+///Some colors have two names, such as `"gray"` and `"grey"`, and the one
+///that comes first in [`named::ALL`](../named/static.ALL.html) is returned.
+///
+///```
+///use palette::Srgb;
+///use palette::nearest::{nearest_named, DeltaE};
+///
+///let color: Srgb = Srgb::new(250u8, 130, 110).into_format();
+///let closest = nearest_named(color, DeltaE::Ciede2000);
+///assert_eq!(closest.name, "salmon");
+///```
+#[cfg(feature = "named")]
+pub fn nearest_named<T, C>(color: C, delta_e: DeltaE) -> NamedMatch<T>
+where
+    T: Component + Float,
+    Lab<::white_point::D65, T>: From<::Srgb<T>> + From<C>,
+{
+    let colors = ::named::ALL;
+    let color = Lab::from(color);
+
+    let mut closest = (0, T::infinity());
+    for (index, &(_, candidate)) in colors.iter().enumerate() {
+        let distance = delta_e.difference(Lab::from(candidate.into_format()), color);
+        if distance < closest.1 {
+            closest = (index, distance);
+        }
+    }
+
+    let (name, value) = colors[closest.0];
+    NamedMatch {
+        name: name,
+        color: value,
+        distance: closest.1,
+    }
+}
+
+///A k-d tree of the colors of a palette in `Lab`, for finding the closest
+///color faster than searching the whole palette.
+///
+///The tree is searched with the `DeltaE::Cie76` distance, which is the
+///Euclidean distance in `Lab`. The search takes about logarithmic time in
+///the size of the palette, so the tree is best for large palettes and many
+///searches. This module is only available if the `alloc` feature is
+///enabled.
+///
+///```
+///use palette::Srgb;
+///use palette::nearest::KdTree;
+///
+///let palette: Vec<Srgb> = (0..=255u8)
+///    .map(|value| Srgb::new(value, value, value).into_format())
+///    .collect();
+///let tree = KdTree::new(&palette);
+///
+///let closest = tree.nearest(Srgb::new(0.39, 0.395, 0.385)).unwrap();
+///assert_eq!(closest.index, 100);
+///```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct KdTree<Wp, T> {
+    nodes: Vec<([f64; 3], usize)>,
+    white_point: ::core::marker::PhantomData<(Wp, T)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<Wp, T> KdTree<Wp, T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    ///Build a tree of the colors of `palette`.
+    pub fn new<P>(palette: &[P]) -> KdTree<Wp, T>
+    where
+        P: Copy,
+        Lab<Wp, T>: From<P>,
+    {
+        let mut nodes: Vec<_> = palette
+            .iter()
+            .enumerate()
+            .map(|(index, &color)| (lab_values(Lab::from(color)), index))
+            .collect();
+
+        build(&mut nodes, 0);
+
+        KdTree {
+            nodes: nodes,
+            white_point: ::core::marker::PhantomData,
+        }
+    }
+
+    ///The number of colors in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    ///Check if the tree has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    ///Find the color that is closest to `color`, or `None` if the tree is
+    ///empty. The distance is the `DeltaE::Cie76` difference.
+    pub fn nearest<C>(&self, color: C) -> Option<Match<T>>
+    where
+        Lab<Wp, T>: From<C>,
+    {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let target = lab_values(Lab::from(color));
+        let mut closest = (0, f64::INFINITY);
+        search(&self.nodes, 0, &target, 0, &mut closest);
+
+        Some(Match {
+            index: self.nodes[closest.0].1,
+            distance: cast(closest.1.sqrt()),
+        })
+    }
+}
+
+///Sort `nodes` into a balanced tree, where the middle node of each range
+///splits the rest of the range on the axis of its depth.
+#[cfg(feature = "alloc")]
+fn build(nodes: &mut [([f64; 3], usize)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    nodes.sort_by(|a, b| {
+        a.0[axis]
+            .partial_cmp(&b.0[axis])
+            .unwrap_or(::core::cmp::Ordering::Equal)
+    });
+
+    let middle = nodes.len() / 2;
+    let (before, after) = nodes.split_at_mut(middle);
+    build(before, depth + 1);
+    build(&mut after[1..], depth + 1);
+}
+
+///Find the node in `nodes` that is closest to `target`, where `offset` is
+///the position of `nodes` in the tree and `closest` is the position and
+///squared distance of the closest node so far.
+#[cfg(feature = "alloc")]
+fn search(
+    nodes: &[([f64; 3], usize)],
+    offset: usize,
+    target: &[f64; 3],
+    depth: usize,
+    closest: &mut (usize, f64),
+) {
+    if nodes.is_empty() {
+        return;
+    }
+
+    let middle = nodes.len() / 2;
+    let node = &nodes[middle].0;
+    let distance = distance_squared(node, target);
+    if distance < closest.1 {
+        *closest = (offset + middle, distance);
+    }
+
+    let axis = depth % 3;
+    let difference = target[axis] - node[axis];
+    let (before, after) = (&nodes[..middle], &nodes[middle + 1..]);
+    let after_offset = offset + middle + 1;
+
+    if difference < 0.0 {
+        search(before, offset, target, depth + 1, closest);
+        if difference * difference < closest.1 {
+            search(after, after_offset, target, depth + 1, closest);
+        }
+    } else {
+        search(after, after_offset, target, depth + 1, closest);
+        if difference * difference < closest.1 {
+            search(before, offset, target, depth + 1, closest);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn lab_values<Wp: WhitePoint, T: Component + Float>(color: Lab<Wp, T>) -> [f64; 3] {
+    [cast(color.l), cast(color.a), cast(color.b)]
+}
+
+fn distance_squared(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+fn cie94(reference: &[f64; 3], color: &[f64; 3]) -> f64 {
+    let c1 = reference[1].hypot(reference[2]);
+    let c2 = color[1].hypot(color[2]);
+
+    let delta_l = reference[0] - color[0];
+    let delta_c = c1 - c2;
+    let delta_h_squared = ((reference[1] - color[1]).powi(2) + (reference[2] - color[2]).powi(2)
+        - delta_c * delta_c)
+        .max(0.0);
+
+    let s_c = 1.0 + 0.045 * c1;
+    let s_h = 1.0 + 0.015 * c1;
+
+    (delta_l * delta_l + (delta_c / s_c).powi(2) + delta_h_squared / (s_h * s_h)).sqrt()
+}
+
+fn ciede2000(reference: &[f64; 3], color: &[f64; 3]) -> f64 {
+    let (l1, a1, b1) = (reference[0], reference[1], reference[2]);
+    let (l2, a2, b2) = (color[0], color[1], color[2]);
+
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let c_mean_7 = c_mean.powi(7);
+    let g = 0.5 * (1.0 - (c_mean_7 / (c_mean_7 + 25.0f64.powi(7))).sqrt());
+
+    let a1 = a1 * (1.0 + g);
+    let a2 = a2 * (1.0 + g);
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 <= h1 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1 - 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let c_mean_7 = c_mean.powi(7);
+    let r_c = 2.0 * (c_mean_7 / (c_mean_7 + 25.0f64.powi(7))).sqrt();
+    let l_offset = (l_mean - 50.0).powi(2);
+    let s_l = 1.0 + 0.015 * l_offset / (20.0 + l_offset).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+///The hue angle in degrees, from 0 to 360.
+fn hue(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        let hue = b.atan2(a).to_degrees();
+        if hue < 0.0 {
+            hue + 360.0
+        } else {
+            hue
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "alloc")]
+    use super::KdTree;
+    use super::{nearest, DeltaE};
+    use white_point::D65;
+    use {Lab, Srgb};
+
+    #[test]
+    fn ciede2000() {
+        //Test data from Sharma, Wu and Dalal, "The CIEDE2000 color-difference
+        //formula: Implementation notes, supplementary test data, and
+        //mathematical observations"
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+        ];
+
+        for &((l1, a1, b1), (l2, a2, b2), expected) in &pairs {
+            let a: Lab<D65, f64> = Lab::new(l1, a1, b1);
+            let b = Lab::new(l2, a2, b2);
+            assert_relative_eq!(DeltaE::Ciede2000.difference(a, b), expected, epsilon = 1e-4);
+            assert_relative_eq!(DeltaE::Ciede2000.difference(b, a), expected, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn cie76_and_cie94() {
+        let a: Lab<D65, f64> = Lab::new(50.0, 20.0, 0.0);
+        let b = Lab::new(53.0, 24.0, 0.0);
+        assert_relative_eq!(DeltaE::Cie76.difference(a, b), 5.0);
+
+        //Only the lightness and chroma differ, and the chroma difference is
+        //scaled by 1 + 0.045 * 20
+        let expected = (9.0f64 + (4.0f64 / 1.9).powi(2)).sqrt();
+        assert_relative_eq!(DeltaE::Cie94.difference(a, b), expected, epsilon = 1e-12);
+
+        assert_eq!(DeltaE::Cie94.difference(a, a), 0.0);
+        assert_eq!(DeltaE::Ciede2000.difference(b, b), 0.0);
+    }
+
+    #[test]
+    fn nearest_in_palette() {
+        let palette = [
+            Srgb::new(1.0, 1.0, 0.0),
+            Srgb::new(0.0, 0.5, 0.0),
+            Srgb::new(0.0, 0.0, 1.0),
+        ];
+
+        for &delta_e in &[DeltaE::Cie76, DeltaE::Cie94, DeltaE::Ciede2000] {
+            let closest = nearest::<D65, f64, _, _>(&palette, Srgb::new(0.1, 0.6, 0.1), delta_e);
+            assert_eq!(closest.unwrap().index, 1);
+
+            let exact = nearest::<D65, f64, _, _>(&palette, palette[2], delta_e).unwrap();
+            assert_eq!(exact.index, 2);
+            assert_relative_eq!(exact.distance, 0.0, epsilon = 1e-9);
+        }
+
+        let empty: [Srgb<f64>; 0] = [];
+        assert_eq!(
+            nearest::<D65, f64, _, _>(&empty, palette[0], DeltaE::Cie76),
+            None
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn kd_tree() {
+        let palette: Vec<_> = (0..500)
+            .map(|i| {
+                Srgb::new(
+                    (i % 7) as f64 / 6.0,
+                    (i % 11) as f64 / 10.0,
+                    (i % 13) as f64 / 12.0,
+                )
+            })
+            .collect();
+        let tree = KdTree::<D65, f64>::new(&palette);
+        assert_eq!(tree.len(), 500);
+
+        for i in 0..200 {
+            let color = Srgb::new(
+                (i % 17) as f64 / 16.0,
+                (i % 5) as f64 / 4.0,
+                (i % 19) as f64 / 18.0,
+            );
+
+            let expected = nearest::<D65, f64, _, _>(&palette, color, DeltaE::Cie76).unwrap();
+            let found = tree.nearest(color).unwrap();
+            assert_relative_eq!(found.distance, expected.distance, epsilon = 1e-9);
+            assert_eq!(
+                Lab::<D65, f64>::from(palette[found.index]),
+                Lab::from(palette[expected.index])
+            );
+        }
+
+        assert!(KdTree::<D65, f64>::new::<Srgb<f64>>(&[])
+            .nearest(Srgb::new(0.0, 0.0, 0.0))
+            .is_none());
+    }
+
+    #[cfg(feature = "named")]
+    #[test]
+    fn named() {
+        use super::nearest_named;
+        use named;
+
+        let closest = nearest_named(named::TOMATO.into_format::<f64>(), DeltaE::Ciede2000);
+        assert_eq!(closest.name, "tomato");
+        assert_eq!(closest.color, named::TOMATO);
+        assert_relative_eq!(closest.distance, 0.0, epsilon = 1e-9);
+
+        let closest = nearest_named::<f64, _>(Srgb::new(0.0f64, 0.0, 0.02), DeltaE::Cie76);
+        assert_eq!(closest.name, "black");
+    }
+}
@@ -0,0 +1,346 @@
+use alloc::vec::Vec;
+
+use super::{channels, closest, Quantized, MAX_CHANNELS};
+use {cast, clamp, Component, Pixel};
+
+///An error diffusion kernel, that describes how the error of each pixel is
+///spread to its neighbors.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Kernel<'a> {
+    ///The neighbors that get a part of the error, as `(x, y, weight)`. The
+    ///`x` offset is in the scanning direction of the row and `y` is the
+    ///number of rows below the pixel. Each neighbor gets `weight / divisor`
+    ///of the error.
+    pub weights: &'a [(isize, usize, u32)],
+
+    ///The divisor of the weights.
+    pub divisor: u32,
+}
+
+impl Kernel<'static> {
+    ///The Floyd–Steinberg kernel, that spreads the error to the four closest
+    ///unprocessed pixels.
+    pub const FLOYD_STEINBERG: Kernel<'static> = Kernel {
+        weights: &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)],
+        divisor: 16,
+    };
+
+    ///The Jarvis, Judice and Ninke kernel, that spreads the error over two
+    ///rows, for smoother results.
+    pub const JARVIS_JUDICE_NINKE: Kernel<'static> = Kernel {
+        weights: &[
+            (1, 0, 7),
+            (2, 0, 5),
+            (-2, 1, 3),
+            (-1, 1, 5),
+            (0, 1, 7),
+            (1, 1, 5),
+            (2, 1, 3),
+            (-2, 2, 1),
+            (-1, 2, 3),
+            (0, 2, 5),
+            (1, 2, 3),
+            (2, 2, 1),
+        ],
+        divisor: 48,
+    };
+
+    ///The Stucki kernel, a sharper variant of Jarvis, Judice and Ninke.
+    pub const STUCKI: Kernel<'static> = Kernel {
+        weights: &[
+            (1, 0, 8),
+            (2, 0, 4),
+            (-2, 1, 2),
+            (-1, 1, 4),
+            (0, 1, 8),
+            (1, 1, 4),
+            (2, 1, 2),
+            (-2, 2, 1),
+            (-1, 2, 2),
+            (0, 2, 4),
+            (1, 2, 2),
+            (2, 2, 1),
+        ],
+        divisor: 42,
+    };
+
+    ///The Burkes kernel, a one row simplification of Stucki.
+    pub const BURKES: Kernel<'static> = Kernel {
+        weights: &[
+            (1, 0, 8),
+            (2, 0, 4),
+            (-2, 1, 2),
+            (-1, 1, 4),
+            (0, 1, 8),
+            (1, 1, 4),
+            (2, 1, 2),
+        ],
+        divisor: 32,
+    };
+
+    ///The Sierra kernel, that spreads the error over two rows.
+    pub const SIERRA: Kernel<'static> = Kernel {
+        weights: &[
+            (1, 0, 5),
+            (2, 0, 3),
+            (-2, 1, 2),
+            (-1, 1, 4),
+            (0, 1, 5),
+            (1, 1, 4),
+            (2, 1, 2),
+            (-1, 2, 2),
+            (0, 2, 3),
+            (1, 2, 2),
+        ],
+        divisor: 32,
+    };
+
+    ///The Sierra Lite kernel, a fast kernel with three neighbors.
+    pub const SIERRA_LITE: Kernel<'static> = Kernel {
+        weights: &[(1, 0, 2), (-1, 1, 1), (0, 1, 1)],
+        divisor: 4,
+    };
+
+    ///The Atkinson kernel, that only spreads three quarters of the error,
+    ///for more contrast.
+    pub const ATKINSON: Kernel<'static> = Kernel {
+        weights: &[
+            (1, 0, 1),
+            (2, 0, 1),
+            (-1, 1, 1),
+            (0, 1, 1),
+            (1, 1, 1),
+            (0, 2, 1),
+        ],
+        divisor: 8,
+    };
+}
+
+impl<'a> Default for Kernel<'a> {
+    fn default() -> Kernel<'a> {
+        Kernel::FLOYD_STEINBERG
+    }
+}
+
+///Map the pixels of an image to the colors of `palette` with error
+///diffusion dithering.
+///
+///The image has rows of `width` pixels, that are processed from top to
+///bottom. The difference between each pixel and its closest palette color
+///is spread to the following pixels with `kernel`, so the average color of
+///an area stays close to the original. With `serpentine` scanning, every
+///other row is processed from right to left, which avoids the diagonal
+///patterns of always scanning in the same direction.
+///
+///The channels are compared and the error is spread in the color's own
+///space, with each channel limited to the range from 0 to the maximum
+///intensity. Linear RGB gives the most accurate average colors, while
+///encoded RGB, such as `Srgb`, is the common choice for images.
+///
+///```
+///use palette::Srgb;
+///use palette::quantize::{error_diffusion, Kernel};
+///
+///let gray = [Srgb::new(0.5f32, 0.5, 0.5); 16];
+///let palette = [Srgb::new(0.0, 0.0, 0.0), Srgb::new(1.0, 1.0, 1.0)];
+///
+///let dithered = error_diffusion(&gray, 4, &palette, Kernel::FLOYD_STEINBERG, true);
+///let white = dithered.indices.iter().filter(|&&index| index == 1).count();
+///assert_eq!(white, 8);
+///```
+///
+///Panics if `width` is 0 or doesn't divide the number of pixels, if
+///`palette` doesn't have between 1 and 256 colors, or if the color type has
+///more than four channels.
+pub fn error_diffusion<C, T>(
+    pixels: &[C],
+    width: usize,
+    palette: &[C],
+    kernel: Kernel,
+    serpentine: bool,
+) -> Quantized<C>
+where
+    C: Pixel<T> + Copy,
+    T: Component,
+{
+    assert!(
+        width > 0 && pixels.len().is_multiple_of(width),
+        "the number of pixels is not a multiple of the width"
+    );
+    assert!(
+        (1..=256).contains(&palette.len()),
+        "the palette must have between 1 and 256 colors"
+    );
+    assert!(
+        C::CHANNELS <= MAX_CHANNELS,
+        "the color type has too many channels"
+    );
+
+    let height = pixels.len() / width;
+    let max_intensity: f64 = cast(T::max_intensity());
+    let divisor = kernel.divisor as f64;
+    let palette_channels: Vec<_> = palette.iter().map(channels).collect();
+
+    //The errors of the rows that the kernel reaches, reused as a ring
+    let rows = kernel.weights.iter().map(|w| w.1).max().unwrap_or(0) + 1;
+    let mut errors: Vec<[f64; MAX_CHANNELS]> =
+        (0..rows * width).map(|_| [0.0; MAX_CHANNELS]).collect();
+
+    let mut indices: Vec<u8> = pixels.iter().map(|_| 0).collect();
+    for y in 0..height {
+        let reverse = serpentine && y % 2 == 1;
+        let row = (y % rows) * width;
+
+        for step in 0..width {
+            let x = if reverse { width - 1 - step } else { step };
+            let mut values = channels(&pixels[y * width + x]);
+            let error = errors[row + x];
+            errors[row + x] = [0.0; MAX_CHANNELS];
+            for (value, error) in values.iter_mut().zip(&error).take(C::CHANNELS) {
+                *value = clamp(*value + error, 0.0, max_intensity);
+            }
+
+            let index = closest(palette, &values);
+            indices[y * width + x] = index as u8;
+
+            let mut error = values;
+            for (error, target) in error.iter_mut().zip(&palette_channels[index]) {
+                *error -= target;
+            }
+
+            for &(dx, dy, weight) in kernel.weights {
+                let target_x = if reverse {
+                    x as isize - dx
+                } else {
+                    x as isize + dx
+                };
+                if target_x < 0 || target_x >= width as isize || y + dy >= height {
+                    continue;
+                }
+
+                let fraction = weight as f64 / divisor;
+                let target = &mut errors[((y + dy) % rows) * width + target_x as usize];
+                for (target, error) in target.iter_mut().zip(&error) {
+                    *target += error * fraction;
+                }
+            }
+        }
+    }
+
+    Quantized {
+        palette: palette.to_vec(),
+        indices: indices,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{error_diffusion, Kernel};
+    use Srgb;
+
+    const KERNELS: [Kernel<'static>; 7] = [
+        Kernel::FLOYD_STEINBERG,
+        Kernel::JARVIS_JUDICE_NINKE,
+        Kernel::STUCKI,
+        Kernel::BURKES,
+        Kernel::SIERRA,
+        Kernel::SIERRA_LITE,
+        Kernel::ATKINSON,
+    ];
+
+    #[test]
+    fn kernel_weights() {
+        for kernel in &KERNELS {
+            let sum: u32 = kernel.weights.iter().map(|w| w.2).sum();
+            if *kernel == Kernel::ATKINSON {
+                assert_eq!(sum * 4, kernel.divisor * 3);
+            } else {
+                assert_eq!(sum, kernel.divisor);
+            }
+
+            //The error may only go forward
+            for &(x, y, _) in kernel.weights {
+                assert!(y > 0 || x > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn palette_colors() {
+        let palette = [
+            Srgb::new(0u8, 0, 0),
+            Srgb::new(255, 0, 0),
+            Srgb::new(0, 0, 255),
+        ];
+        let pixels = [
+            palette[2], palette[0], palette[1], palette[1], palette[2], palette[0],
+        ];
+
+        for kernel in &KERNELS {
+            let dithered = error_diffusion(&pixels, 3, &palette, *kernel, true);
+            assert_eq!(dithered.indices, [2, 0, 1, 1, 2, 0]);
+            assert_eq!(dithered.palette, palette);
+        }
+    }
+
+    #[test]
+    fn average_color() {
+        let width = 32;
+        let pixels = [Srgb::new(0.25f64, 0.75, 0.5); 32 * 32];
+        let palette = [
+            Srgb::new(0.0, 0.0, 0.0),
+            Srgb::new(1.0, 1.0, 1.0),
+            Srgb::new(1.0, 0.0, 0.0),
+            Srgb::new(0.0, 1.0, 0.0),
+            Srgb::new(0.0, 0.0, 1.0),
+        ];
+
+        for kernel in &KERNELS {
+            for &serpentine in &[false, true] {
+                let dithered = error_diffusion(&pixels, width, &palette, *kernel, serpentine);
+                let mut sum = [0.0; 3];
+                for &index in &dithered.indices {
+                    let color = palette[index as usize];
+                    sum[0] += color.red;
+                    sum[1] += color.green;
+                    sum[2] += color.blue;
+                }
+
+                let count = pixels.len() as f64;
+                let mean = Srgb::new(sum[0] / count, sum[1] / count, sum[2] / count);
+                let tolerance = if *kernel == Kernel::ATKINSON {
+                    0.1
+                } else {
+                    0.02
+                };
+                assert_relative_eq!(mean, pixels[0], epsilon = tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn serpentine() {
+        let pixels: Vec<_> = (0..64)
+            .map(|i| Srgb::new((i % 8) as f32 / 10.0 + 0.1, 0.3, 0.3))
+            .collect();
+        let palette = [Srgb::new(0.0, 0.0, 0.0), Srgb::new(1.0, 1.0, 1.0)];
+
+        let forward = error_diffusion(&pixels, 8, &palette, Kernel::default(), false);
+        let serpentine = error_diffusion(&pixels, 8, &palette, Kernel::default(), true);
+        assert_eq!(forward.indices[..8], serpentine.indices[..8]);
+        assert_ne!(forward.indices, serpentine.indices);
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of pixels is not a multiple of the width")]
+    fn wrong_width() {
+        let palette = [Srgb::new(0u8, 0, 0)];
+        error_diffusion(
+            &[Srgb::new(0u8, 0, 0); 5],
+            2,
+            &palette,
+            Kernel::default(),
+            false,
+        );
+    }
+}
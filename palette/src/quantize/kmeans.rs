@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use float::Float;
+
+use super::{distance_squared, nearest, MAX_CHANNELS};
+use {cast, Pixel};
+
+///A color and how much of a collection of colors it represents.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Swatch<C, T> {
+    ///The color.
+    pub color: C,
+
+    ///The fraction of the colors that are closest to this color, from 0 to
+    ///1.
+    pub weight: T,
+}
+
+///Find the `k` dominant colors of `colors` with k-means clustering in the
+///color space `A`.
+///
+///The colors are converted to `A`, which should be a space where the
+///channels can be compared with Euclidean distances, such as `Lab` or
+///`Xyz`. Hues are compared as plain numbers. The clusters are refined until
+///they stop changing, or for at most `max_iterations` rounds, and are
+///returned as swatches, sorted from the highest weight to the lowest. There
+///may be fewer than `k` swatches if `colors` has fewer distinct colors.
+///
+///The initial clusters are picked with k-means++, with a fixed seed, so the
+///results are the same for the same input.
+///
+///Panics if `A` has more than four channels.
+pub fn kmeans<A, T, C>(colors: &[C], k: usize, max_iterations: usize) -> Vec<Swatch<A, T>>
+where
+    C: Copy,
+    A: Pixel<T> + Copy + From<C>,
+    T: Float,
+{
+    assert!(
+        A::CHANNELS <= MAX_CHANNELS,
+        "the color type has too many channels"
+    );
+
+    let points: Vec<A> = colors.iter().map(|&color| A::from(color)).collect();
+    if points.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let mut centers = initial_centers(&points, k);
+    let mut assignments: Vec<usize> = points.iter().map(|_| usize::MAX).collect();
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+        for (point, assignment) in points.iter().zip(&mut assignments) {
+            let nearest = nearest(&centers, point);
+            if nearest != *assignment {
+                *assignment = nearest;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        let mut sums: Vec<([T; MAX_CHANNELS], usize)> = centers
+            .iter()
+            .map(|_| ([T::zero(); MAX_CHANNELS], 0))
+            .collect();
+        for (point, &assignment) in points.iter().zip(&assignments) {
+            let sum = &mut sums[assignment];
+            for (sum, &value) in sum.0.iter_mut().zip(point.as_raw::<[T]>()) {
+                *sum = *sum + value;
+            }
+            sum.1 += 1;
+        }
+
+        for (center, &(sum, count)) in centers.iter_mut().zip(&sums) {
+            if count > 0 {
+                let count: T = cast(count);
+                for (channel, &sum) in center.as_raw_mut::<[T]>().iter_mut().zip(&sum) {
+                    *channel = sum / count;
+                }
+            }
+        }
+    }
+
+    let mut counts: Vec<usize> = centers.iter().map(|_| 0).collect();
+    for point in &points {
+        counts[nearest(&centers, point)] += 1;
+    }
+
+    let total: T = cast(points.len());
+    let mut swatches: Vec<_> = centers
+        .into_iter()
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .map(|(color, count)| Swatch {
+            color: color,
+            weight: cast::<T, _>(count) / total,
+        })
+        .collect();
+
+    swatches.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap_or(Ordering::Equal));
+    swatches
+}
+
+///Pick up to `k` initial centers with k-means++, which makes it more likely
+///for colors that are far from the already picked centers to be picked.
+fn initial_centers<A, T>(points: &[A], k: usize) -> Vec<A>
+where
+    A: Pixel<T> + Copy,
+    T: Float,
+{
+    let mut random = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut centers = Vec::with_capacity(k);
+    centers.push(points[random.next_index(points.len())]);
+
+    let mut distances: Vec<T> = points
+        .iter()
+        .map(|point| distance_squared(point, &centers[0]))
+        .collect();
+
+    while centers.len() < k {
+        let total = distances
+            .iter()
+            .fold(T::zero(), |sum, &distance| sum + distance);
+        if total <= T::zero() {
+            //All of the colors are already centers
+            break;
+        }
+
+        let mut target = cast::<T, _>(random.next_fraction()) * total;
+        let mut index = 0;
+        for (i, &distance) in distances.iter().enumerate() {
+            if distance > T::zero() {
+                index = i;
+                if target < distance {
+                    break;
+                }
+                target = target - distance;
+            }
+        }
+
+        let center = points[index];
+        centers.push(center);
+        for (distance, point) in distances.iter_mut().zip(points) {
+            *distance = distance.min(distance_squared(point, &center));
+        }
+    }
+
+    centers
+}
+
+///A small, deterministic pseudo-random number generator.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    ///A number in the range `[0.0, 1.0)`.
+    fn next_fraction(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_index(&mut self, length: usize) -> usize {
+        (self.next() % length as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::kmeans;
+    use white_point::D65;
+    use {Lab, Srgb};
+
+    #[test]
+    fn clusters() {
+        let mut colors = Vec::new();
+        for i in 0..30 {
+            let offset = i as f64 / 300.0;
+            colors.push(Srgb::new(0.8 + offset, 0.1, 0.1));
+            colors.push(Srgb::new(0.1, 0.7 + offset, 0.2));
+            if i % 3 == 0 {
+                colors.push(Srgb::new(0.1, 0.1, 0.8 + offset));
+            }
+        }
+
+        let swatches = kmeans::<Lab<D65, f64>, _, _>(&colors, 3, 100);
+        assert_eq!(swatches.len(), 3);
+
+        let weights: Vec<_> = swatches.iter().map(|swatch| swatch.weight).collect();
+        assert_relative_eq!(weights[0], 30.0 / 70.0);
+        assert_relative_eq!(weights[1], 30.0 / 70.0);
+        assert_relative_eq!(weights[2], 10.0 / 70.0);
+
+        let blue = Srgb::from(swatches[2].color);
+        assert!(blue.blue > 0.8 && blue.red < 0.2);
+    }
+
+    #[test]
+    fn few_colors() {
+        let colors = [Srgb::new(1.0f32, 0.0, 0.0), Srgb::new(0.0, 1.0, 0.0)];
+        let swatches = kmeans::<Lab, _, _>(&colors, 5, 10);
+        assert_eq!(swatches.len(), 2);
+        assert_eq!(swatches[0].weight, 0.5);
+
+        assert!(kmeans::<Lab, f32, Srgb>(&[], 5, 10).is_empty());
+        assert!(kmeans::<Lab, _, _>(&colors, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn deterministic() {
+        let colors: Vec<_> = (0..100)
+            .map(|i| Srgb::new((i % 7) as f32 / 7.0, (i % 11) as f32 / 11.0, 0.5))
+            .collect();
+
+        assert_eq!(
+            kmeans::<Lab, _, _>(&colors, 4, 50),
+            kmeans::<Lab, _, _>(&colors, 4, 50)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use super::{average, channels, Quantized, MAX_CHANNELS};
+use {Component, Pixel};
+
+///Reduce `colors` to at most `max_colors` colors with median cut.
+///
+///The colors are put in a box that is repeatedly split in two, at the median
+///of its widest channel, until there are `max_colors` boxes or no box has
+///more than one distinct color. Each box becomes the average of its colors
+///in the palette. The channels are compared in the color's own space, so
+///the colors should be in a space where the channels have similar scales,
+///such as RGB.
+///
+///```
+///use palette::Srgb;
+///use palette::quantize::median_cut;
+///
+///let image = [
+///    Srgb::new(250u8, 10, 10),
+///    Srgb::new(240, 20, 10),
+///    Srgb::new(10, 10, 250),
+///    Srgb::new(20, 10, 240),
+///];
+///
+///let quantized = median_cut(&image, 2);
+///assert_eq!(quantized.indices, [1, 1, 0, 0]);
+///assert_eq!(quantized.palette[0], Srgb::new(15, 10, 245));
+///```
+///
+///Panics if `max_colors` is not between 1 and 256, or if the color type has
+///more than four channels.
+pub fn median_cut<C, T>(colors: &[C], max_colors: usize) -> Quantized<C>
+where
+    C: Pixel<T> + Copy,
+    T: Component,
+{
+    assert!(
+        (1..=256).contains(&max_colors),
+        "the palette must have between 1 and 256 colors"
+    );
+    assert!(
+        C::CHANNELS <= MAX_CHANNELS,
+        "the color type has too many channels"
+    );
+
+    let mut points: Vec<([f64; MAX_CHANNELS], usize)> = colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| (channels(color), index))
+        .collect();
+
+    let mut boxes = Vec::with_capacity(max_colors);
+    if !points.is_empty() {
+        boxes.push(ColorBox::new(&points, 0, points.len()));
+    }
+
+    while boxes.len() < max_colors {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|&(_, color_box)| color_box.range > 0.0)
+            .max_by(|&(_, a), &(_, b)| a.range.partial_cmp(&b.range).unwrap_or(Ordering::Equal))
+            .map(|(index, _)| index);
+
+        let index = match widest {
+            Some(index) => index,
+            None => break,
+        };
+
+        let ColorBox {
+            start,
+            end,
+            channel,
+            ..
+        } = boxes[index];
+        let slice = &mut points[start..end];
+        slice.sort_by(|a, b| {
+            a.0[channel]
+                .partial_cmp(&b.0[channel])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        //Split at the median, but keep equal values in the same box
+        let median = slice[slice.len() / 2].0[channel];
+        let mut split = slice
+            .iter()
+            .position(|point| point.0[channel] >= median)
+            .unwrap_or(0);
+        if split == 0 {
+            split = slice
+                .iter()
+                .position(|point| point.0[channel] > median)
+                .unwrap_or(slice.len());
+        }
+
+        boxes[index] = ColorBox::new(&points, start, start + split);
+        boxes.push(ColorBox::new(&points, start + split, end));
+    }
+
+    let mut palette = Vec::with_capacity(boxes.len());
+    let mut indices: Vec<u8> = points.iter().map(|_| 0).collect();
+    for (palette_index, color_box) in boxes.iter().enumerate() {
+        let points = &points[color_box.start..color_box.end];
+
+        let mut sum = [0.0; MAX_CHANNELS];
+        for &(values, color_index) in points {
+            for (sum, value) in sum.iter_mut().zip(&values) {
+                *sum += value;
+            }
+            indices[color_index] = palette_index as u8;
+        }
+
+        palette.push(average(colors[points[0].1], &sum, points.len()));
+    }
+
+    Quantized {
+        palette: palette,
+        indices: indices,
+    }
+}
+
+///A range of points, and its widest channel.
+#[derive(Copy, Clone)]
+struct ColorBox {
+    start: usize,
+    end: usize,
+    channel: usize,
+    range: f64,
+}
+
+impl ColorBox {
+    fn new(points: &[([f64; MAX_CHANNELS], usize)], start: usize, end: usize) -> ColorBox {
+        let mut min = [f64::INFINITY; MAX_CHANNELS];
+        let mut max = [f64::NEG_INFINITY; MAX_CHANNELS];
+        for &(values, _) in &points[start..end] {
+            for i in 0..MAX_CHANNELS {
+                min[i] = min[i].min(values[i]);
+                max[i] = max[i].max(values[i]);
+            }
+        }
+
+        let mut color_box = ColorBox {
+            start: start,
+            end: end,
+            channel: 0,
+            range: 0.0,
+        };
+        for i in 0..MAX_CHANNELS {
+            if max[i] - min[i] > color_box.range {
+                color_box.channel = i;
+                color_box.range = max[i] - min[i];
+            }
+        }
+
+        color_box
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::median_cut;
+    use Srgb;
+
+    #[test]
+    fn distinct_colors() {
+        let colors = [
+            Srgb::new(0.0f32, 0.0, 0.0),
+            Srgb::new(1.0, 1.0, 1.0),
+            Srgb::new(0.0, 0.0, 0.0),
+            Srgb::new(1.0, 0.0, 0.0),
+        ];
+
+        let quantized = median_cut(&colors, 16);
+        assert_eq!(quantized.palette.len(), 3);
+        for (color, &index) in colors.iter().zip(&quantized.indices) {
+            assert_eq!(quantized.palette[index as usize], *color);
+        }
+    }
+
+    #[test]
+    fn reduced() {
+        let colors: Vec<_> = (0..=255u8)
+            .map(|value| Srgb::new(value, 255 - value, 0))
+            .collect();
+
+        let quantized = median_cut(&colors, 4);
+        assert_eq!(quantized.palette.len(), 4);
+        assert_eq!(quantized.indices.len(), 256);
+
+        //Each box gets a quarter of the colors
+        for index in 0..4 {
+            let count = quantized.indices.iter().filter(|&&i| i == index).count();
+            assert_eq!(count, 64);
+        }
+
+        let error = colors
+            .iter()
+            .zip(&quantized.indices)
+            .map(|(color, &index)| {
+                let quantized = quantized.palette[index as usize];
+                (color.red as i32 - quantized.red as i32).abs()
+            })
+            .max()
+            .unwrap();
+        assert!(error <= 32);
+    }
+
+    #[test]
+    fn empty() {
+        let quantized = median_cut::<Srgb<u8>, u8>(&[], 8);
+        assert!(quantized.palette.is_empty());
+        assert!(quantized.indices.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the palette must have between 1 and 256 colors")]
+    fn too_many_colors() {
+        median_cut(&[Srgb::new(0u8, 0, 0)], 257);
+    }
+}
@@ -0,0 +1,149 @@
+//!Color quantization and palette extraction.
+//!
+//![`kmeans`](fn.kmeans.html) finds the dominant colors of a collection of
+//!colors, such as the pixels of an image, by clustering them in a color
+//!space where distances match perceived differences, like `Lab`. The
+//!clusters are returned as swatches, with the fraction of the colors that
+//!belong to them.
+//!
+//!```
+//!use palette::{Lab, Srgb};
+//!use palette::quantize::kmeans;
+//!
+//!let image = [
+//!    Srgb::new(0.9f32, 0.1, 0.1),
+//!    Srgb::new(0.85, 0.15, 0.1),
+//!    Srgb::new(0.9, 0.15, 0.05),
+//!    Srgb::new(0.1, 0.2, 0.8),
+//!];
+//!
+//!let swatches = kmeans::<Lab, _, _>(&image, 2, 20);
+//!assert_eq!(swatches.len(), 2);
+//!assert_eq!(swatches[0].weight, 0.75);
+//!
+//!let dominant = Srgb::from(swatches[0].color);
+//!assert!(dominant.red > 0.8);
+//!```
+//!
+//![`median_cut`](fn.median_cut.html) reduces the colors to a palette of at
+//!most 256 colors, and gives the index of the palette color for each of the
+//!original colors, as in the indexed images of GIF and PNG.
+//!
+//![`octree`](fn.octree.html) gives the same kind of result with an octree,
+//!which uses less memory and can be built from a stream of colors with
+//![`Octree`](struct.Octree.html).
+//!
+//!The colors of an image can also be mapped to a palette, from one of the
+//!quantizers or any other source, with dithering that hides the steps
+//!between the palette colors. [`error_diffusion`](fn.error_diffusion.html)
+//!spreads the error of each pixel to its neighbors, with a configurable
+//![`Kernel`](struct.Kernel.html).
+//!
+//!This module is only available if the `alloc` feature is enabled, which is
+//!implied by the default `std` feature.
+
+use alloc::vec::Vec;
+
+use float::Float;
+
+use {cast, Component, Pixel};
+
+pub use self::dither::{error_diffusion, Kernel};
+pub use self::kmeans::{kmeans, Swatch};
+pub use self::median_cut::median_cut;
+pub use self::octree::{octree, Octree};
+
+mod dither;
+mod kmeans;
+mod median_cut;
+mod octree;
+
+///Colors that have been reduced to a palette.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantized<C> {
+    ///The colors of the palette.
+    pub palette: Vec<C>,
+
+    ///The index of the palette color for each of the original colors.
+    pub indices: Vec<u8>,
+}
+
+impl<C: Copy> Quantized<C> {
+    ///Get the palette color that replaces the original color with the index
+    ///`index`, if it exists.
+    pub fn color(&self, index: usize) -> Option<C> {
+        self.indices
+            .get(index)
+            .map(|&palette_index| self.palette[palette_index as usize])
+    }
+}
+
+///The highest number of channels of the colors that can be quantized.
+const MAX_CHANNELS: usize = 4;
+
+///The squared Euclidean distance between the channels of two colors.
+fn distance_squared<A: Pixel<T>, T: Float>(a: &A, b: &A) -> T {
+    a.as_raw::<[T]>()
+        .iter()
+        .zip(b.as_raw::<[T]>())
+        .fold(T::zero(), |sum, (&a, &b)| sum + (a - b) * (a - b))
+}
+
+///The channels of `color`, as `f64`.
+fn channels<C: Pixel<T>, T: Component>(color: &C) -> [f64; MAX_CHANNELS] {
+    let mut values = [0.0; MAX_CHANNELS];
+    for (value, &channel) in values.iter_mut().zip(color.as_raw::<[T]>()) {
+        *value = cast(channel);
+    }
+
+    values
+}
+
+///The index of the color in `palette` that has the channels that are
+///closest to `values`.
+fn closest<C: Pixel<T>, T: Component>(palette: &[C], values: &[f64; MAX_CHANNELS]) -> usize {
+    let mut closest = 0;
+    let mut closest_distance = f64::INFINITY;
+    for (index, candidate) in palette.iter().enumerate() {
+        let distance = channels(candidate)
+            .iter()
+            .zip(values)
+            .fold(0.0, |sum, (&a, &b)| sum + (a - b) * (a - b));
+        if distance < closest_distance {
+            closest = index;
+            closest_distance = distance;
+        }
+    }
+
+    closest
+}
+
+///Replace the channels of `color` with the average of `count` colors, where
+///`sum` is the sum of their channels.
+fn average<C: Pixel<T>, T: Component>(mut color: C, sum: &[f64; MAX_CHANNELS], count: usize) -> C {
+    for (channel, &sum) in color.as_raw_mut::<[T]>().iter_mut().zip(sum) {
+        let average = sum / count as f64;
+        *channel = if T::LIMITED {
+            cast(average.round())
+        } else {
+            cast(average)
+        };
+    }
+
+    color
+}
+
+///The index of the color in `colors` that is closest to `color`.
+fn nearest<A: Pixel<T>, T: Float>(colors: &[A], color: &A) -> usize {
+    let mut nearest = 0;
+    let mut nearest_distance = T::infinity();
+    for (index, candidate) in colors.iter().enumerate() {
+        let distance = distance_squared(candidate, color);
+        if distance < nearest_distance {
+            nearest = index;
+            nearest_distance = distance;
+        }
+    }
+
+    nearest
+}
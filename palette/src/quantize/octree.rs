@@ -0,0 +1,413 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use super::{average, channels, closest, Quantized, MAX_CHANNELS};
+use {Component, Pixel};
+
+///The number of levels below the root, one for each bit of an 8 bit channel.
+const DEPTH: usize = 8;
+
+///Reduce `colors` to at most `max_colors` colors with an octree.
+///
+///This is a shortcut for adding all of `colors` to an
+///[`Octree`](struct.Octree.html) and quantizing them with it.
+///
+///```
+///use palette::Srgb;
+///use palette::quantize::octree;
+///
+///let image = [
+///    Srgb::new(250u8, 10, 10),
+///    Srgb::new(240, 20, 10),
+///    Srgb::new(10, 10, 250),
+///    Srgb::new(20, 10, 240),
+///];
+///
+///let quantized = octree(&image, 2);
+///assert_eq!(quantized.indices, [0, 0, 1, 1]);
+///assert_eq!(quantized.palette[1], Srgb::new(15, 10, 245));
+///```
+///
+///Panics if `max_colors` is not between 1 and 256, or if the color type has
+///more than four channels.
+pub fn octree<C, T>(colors: &[C], max_colors: usize) -> Quantized<C>
+where
+    C: Pixel<T> + Copy,
+    T: Component,
+{
+    let mut octree = Octree::new(max_colors);
+    octree.extend(colors.iter().cloned());
+    octree.quantize(colors)
+}
+
+///An octree that reduces a stream of colors to a palette.
+///
+///Each level of the tree splits the colors by one more bit of each channel,
+///after scaling the channels to 8 bits, so a node has up to 8 children for
+///RGB colors and up to 16 for RGBA colors. The deepest nodes that are
+///closest to the leaves are merged into their parent whenever there are
+///more than `max_colors` leaves, so the tree never grows beyond a few
+///thousand nodes, no matter how many colors are added. The channels are
+///scaled by their maximum intensity, so the colors should be in a space
+///where each channel goes from 0 to the maximum intensity, such as RGB.
+///
+///The colors can be added one at a time, or from an iterator with `extend`,
+///and the palette is the average of the colors in each leaf.
+///
+///```
+///use palette::Srgb;
+///use palette::quantize::Octree;
+///
+///let rows = [
+///    [Srgb::new(1.0f32, 0.0, 0.0), Srgb::new(0.9, 0.0, 0.0)],
+///    [Srgb::new(0.0, 0.0, 1.0), Srgb::new(0.0, 0.0, 0.9)],
+///];
+///
+///let mut octree = Octree::new(2);
+///for row in &rows {
+///    octree.extend(row.iter().cloned());
+///}
+///
+///assert_eq!(octree.palette().len(), 2);
+///for row in &rows {
+///    let quantized = octree.quantize(row);
+///    assert_eq!(quantized.indices[0], quantized.indices[1]);
+///}
+///```
+#[derive(Clone, Debug)]
+pub struct Octree<C, T> {
+    nodes: Vec<Node>,
+    free: Vec<u32>,
+    reducible: [Vec<u32>; DEPTH],
+    leaves: usize,
+    max_colors: usize,
+    color: PhantomData<(C, T)>,
+}
+
+impl<C, T> Octree<C, T>
+where
+    C: Pixel<T> + Copy,
+    T: Component,
+{
+    ///Create an empty octree that reduces the colors to at most
+    ///`max_colors` colors.
+    ///
+    ///Panics if `max_colors` is not between 1 and 256, or if the color type
+    ///has more than four channels.
+    pub fn new(max_colors: usize) -> Octree<C, T> {
+        assert!(
+            (1..=256).contains(&max_colors),
+            "the palette must have between 1 and 256 colors"
+        );
+        assert!(
+            C::CHANNELS <= MAX_CHANNELS,
+            "the color type has too many channels"
+        );
+
+        let mut reducible: [Vec<u32>; DEPTH] = Default::default();
+        reducible[0].push(0);
+
+        Octree {
+            nodes: Some(Node::new(false)).into_iter().collect(),
+            free: Vec::new(),
+            reducible: reducible,
+            leaves: 0,
+            max_colors: max_colors,
+            color: PhantomData,
+        }
+    }
+
+    ///Add `color` to the octree.
+    pub fn add(&mut self, color: C) {
+        let bits = Bits::new(&color);
+        let values = channels(&color);
+
+        let mut index = 0;
+        for level in 0..DEPTH + 1 {
+            {
+                let node = &mut self.nodes[index];
+                node.count += 1;
+                if node.leaf {
+                    for (sum, value) in node.sum.iter_mut().zip(&values) {
+                        *sum += value;
+                    }
+                    break;
+                }
+            }
+
+            let child = bits.child(level);
+            index = match self.nodes[index].children[child] {
+                0 => {
+                    let new_child = self.allocate(level + 1);
+                    self.nodes[index].children[child] = new_child;
+                    new_child as usize
+                }
+                existing => existing as usize,
+            };
+        }
+
+        while self.leaves > self.max_colors {
+            self.reduce();
+        }
+    }
+
+    ///Get the colors of the palette, in the same order as they are indexed
+    ///by `quantize`.
+    pub fn palette(&self) -> Vec<C> {
+        let mut palette = Vec::with_capacity(self.leaves);
+        self.visit_leaves(0, &mut |_, node| palette.push(node.average()));
+        palette
+    }
+
+    ///Replace `colors` with the colors of the palette. Colors that have not
+    ///been added to the octree are replaced with the palette color that is
+    ///closest to them.
+    pub fn quantize(&self, colors: &[C]) -> Quantized<C> {
+        let mut palette = Vec::with_capacity(self.leaves);
+        let mut palette_indices: Vec<u8> = self.nodes.iter().map(|_| 0).collect();
+        self.visit_leaves(0, &mut |index, node| {
+            palette_indices[index] = palette.len() as u8;
+            palette.push(node.average());
+        });
+
+        let indices = colors
+            .iter()
+            .map(|color| match self.leaf(color) {
+                Some(leaf) => palette_indices[leaf],
+                None => closest(&palette, &channels(color)) as u8,
+            })
+            .collect();
+
+        Quantized {
+            palette: palette,
+            indices: indices,
+        }
+    }
+
+    ///Find the leaf that `color` belongs to, if it has been added.
+    fn leaf(&self, color: &C) -> Option<usize> {
+        let bits = Bits::new(color);
+        let mut index = 0;
+        for level in 0..DEPTH + 1 {
+            if self.nodes[index].leaf {
+                return Some(index);
+            }
+
+            match self.nodes[index].children[bits.child(level)] {
+                0 => return None,
+                child => index = child as usize,
+            }
+        }
+
+        None
+    }
+
+    fn visit_leaves<F: FnMut(usize, &Node)>(&self, index: usize, f: &mut F) {
+        let node = &self.nodes[index];
+        if node.leaf {
+            if node.count > 0 {
+                f(index, node);
+            }
+        } else {
+            for &child in &node.children {
+                if child != 0 {
+                    self.visit_leaves(child as usize, f);
+                }
+            }
+        }
+    }
+
+    ///Add a node at `level`, reusing a removed node if there is one.
+    fn allocate(&mut self, level: usize) -> u32 {
+        let leaf = level == DEPTH;
+        let node = Node::new(leaf);
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index as usize] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        };
+
+        if leaf {
+            self.leaves += 1;
+        } else {
+            self.reducible[level].push(index);
+        }
+
+        index
+    }
+
+    ///Merge the children of the least common of the deepest inner nodes
+    ///into it. The children of the deepest inner nodes are always leaves.
+    fn reduce(&mut self) {
+        let level = match self.reducible.iter().rposition(|nodes| !nodes.is_empty()) {
+            Some(level) => level,
+            None => return,
+        };
+
+        let position = {
+            let nodes = &self.nodes;
+            let reducible = &self.reducible[level];
+            (0..reducible.len())
+                .min_by_key(|&position| nodes[reducible[position] as usize].count)
+                .unwrap_or(0)
+        };
+        let index = self.reducible[level].swap_remove(position) as usize;
+
+        let children = self.nodes[index].children;
+        let mut sum = [0.0; MAX_CHANNELS];
+        let mut merged = 0;
+        for &child in children.iter().filter(|&&child| child != 0) {
+            let child_node = &self.nodes[child as usize];
+            for (sum, value) in sum.iter_mut().zip(&child_node.sum) {
+                *sum += value;
+            }
+            merged += 1;
+            self.free.push(child);
+        }
+
+        let node = &mut self.nodes[index];
+        node.children = [0; 1 << MAX_CHANNELS];
+        node.sum = sum;
+        node.leaf = true;
+        self.leaves = self.leaves + 1 - merged;
+    }
+}
+
+impl<C, T> Extend<C> for Octree<C, T>
+where
+    C: Pixel<T> + Copy,
+    T: Component,
+{
+    fn extend<I: IntoIterator<Item = C>>(&mut self, colors: I) {
+        for color in colors {
+            self.add(color);
+        }
+    }
+}
+
+///The 8 bit channels of a color, for finding its place in the octree.
+struct Bits([u8; MAX_CHANNELS]);
+
+impl Bits {
+    fn new<C: Pixel<T>, T: Component>(color: &C) -> Bits {
+        let mut bits = [0; MAX_CHANNELS];
+        for (bits, channel) in bits.iter_mut().zip(color.as_raw::<[T]>()) {
+            *bits = channel.convert();
+        }
+        Bits(bits)
+    }
+
+    ///The index of the child at `level + 1`, from one bit of each channel.
+    fn child(&self, level: usize) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(0, |child, (channel, &bits)| {
+                child | (((bits >> (DEPTH - 1 - level)) & 1) as usize) << channel
+            })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Node {
+    children: [u32; 1 << MAX_CHANNELS],
+    sum: [f64; MAX_CHANNELS],
+    count: usize,
+    leaf: bool,
+}
+
+impl Node {
+    fn new(leaf: bool) -> Node {
+        Node {
+            children: [0; 1 << MAX_CHANNELS],
+            sum: [0.0; MAX_CHANNELS],
+            count: 0,
+            leaf: leaf,
+        }
+    }
+
+    fn average<C: Pixel<T> + Copy, T: Component>(&self) -> C {
+        let zero = [T::zero(); MAX_CHANNELS];
+        average(*C::from_raw(&zero[..]), &self.sum, self.count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{octree, Octree};
+    use {Srgb, Srgba};
+
+    #[test]
+    fn distinct_colors() {
+        let colors = [
+            Srgb::new(0.0f32, 0.0, 0.0),
+            Srgb::new(1.0, 1.0, 1.0),
+            Srgb::new(0.0, 0.0, 0.0),
+            Srgb::new(1.0, 0.0, 0.0),
+        ];
+
+        let quantized = octree(&colors, 16);
+        assert_eq!(quantized.palette.len(), 3);
+        for (color, &index) in colors.iter().zip(&quantized.indices) {
+            assert_eq!(quantized.palette[index as usize], *color);
+        }
+    }
+
+    #[test]
+    fn reduced() {
+        let colors: Vec<_> = (0..=255u8)
+            .flat_map(|red| (0..=255u8).map(move |green| Srgb::new(red, green, 128)))
+            .collect();
+
+        let mut octree = Octree::new(64);
+        octree.extend(colors.iter().cloned());
+        assert!(octree.nodes.len() < 2000);
+
+        let quantized = octree.quantize(&colors);
+        assert_eq!(quantized.palette, octree.palette());
+        assert!(quantized.palette.len() <= 64);
+        assert!(quantized.palette.len() >= 16);
+
+        for (color, &index) in colors.iter().zip(&quantized.indices) {
+            let quantized = quantized.palette[index as usize];
+            assert!((color.red as i32 - quantized.red as i32).abs() <= 64);
+            assert!((color.green as i32 - quantized.green as i32).abs() <= 64);
+            assert_eq!(quantized.blue, 128);
+        }
+    }
+
+    #[test]
+    fn single_color() {
+        let colors = [
+            Srgba::new(0u8, 0, 0, 255),
+            Srgba::new(255, 255, 255, 0),
+            Srgba::new(255, 255, 255, 255),
+        ];
+
+        let quantized = octree(&colors, 1);
+        assert_eq!(quantized.palette, [Srgba::new(170, 170, 170, 170)]);
+        assert_eq!(quantized.indices, [0, 0, 0]);
+    }
+
+    #[test]
+    fn unknown_colors() {
+        let mut octree = Octree::new(4);
+        octree.add(Srgb::new(0.0f64, 0.0, 0.0));
+        octree.add(Srgb::new(1.0, 0.0, 0.0));
+
+        let quantized = octree.quantize(&[Srgb::new(0.9, 0.1, 0.0), Srgb::new(0.1, 0.0, 0.1)]);
+        assert_eq!(quantized.indices, [1, 0]);
+    }
+
+    #[test]
+    fn empty() {
+        let quantized = octree::<Srgb<u8>, u8>(&[], 8);
+        assert!(quantized.palette.is_empty());
+        assert!(quantized.indices.is_empty());
+    }
+}
@@ -8,7 +8,7 @@ use white_point::WhitePoint;
 
 use encoding::{Linear, TransferFn};
 
-pub use self::rgb::{Rgb, Rgba};
+pub use self::rgb::{FromHexError, Rgb, Rgba};
 
 //mod linear;
 mod rgb;
@@ -2,22 +2,23 @@ use core::any::TypeId;
 use core::fmt;
 use core::marker::PhantomData;
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::str::FromStr;
 
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 use float::Float;
 
 use alpha::Alpha;
-use blend::PreAlpha;
-use convert::{FromColor, IntoColor};
+use blend::{BlendFunction, PreAlpha};
+use convert::{FromColorUnclamped, IntoColorUnclamped};
 use encoding::linear::LinearFn;
 use encoding::pixel::RawPixel;
 use encoding::{Linear, Srgb};
 use luma::LumaStandard;
-use matrix::{matrix_inverse, multiply_xyz_to_rgb, rgb_to_xyz_matrix};
+use matrix::{matrix_inverse, multiply_rgb, multiply_xyz_to_rgb, rgb_to_rgb_matrix, rgb_to_xyz_matrix};
 use rgb::{RgbSpace, RgbStandard, TransferFn};
 use white_point::WhitePoint;
 use {cast, clamp};
-use {Blend, Component, ComponentWise, GetHue, Limited, Mix, Pixel, Shade};
+use {Blend, Component, ComponentWise, ConstComponent, GetHue, Invert, Limited, Mix, Pixel, Shade};
 use {Hsl, Hsv, Hwb, Lab, Lch, Luma, RgbHue, Xyz, Yxy};
 
 /// Generic RGB with an alpha component. See the [`Rgba` implementation in
@@ -35,7 +36,7 @@ pub type Rgba<S = Srgb, T = f32> = Alpha<Rgb<S, T>, T>;
 /// linear, meaning that gamma correction is required when converting to and
 /// from a displayable RGB, such as sRGB. See the [`pixel`](pixel/index.html)
 /// module for encoding formats.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_rgb_space = "S::Space"]
@@ -64,12 +65,70 @@ pub struct Rgb<S: RgbStandard = Srgb, T: Component = f32> {
 
 impl<S: RgbStandard, T: Component> Copy for Rgb<S, T> {}
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + ::bytemuck::Zeroable,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Rgb<S, T>
+where
+    S: RgbStandard + 'static,
+    T: Component + ::bytemuck::Pod,
+{
+}
+
 impl<S: RgbStandard, T: Component> Clone for Rgb<S, T> {
     fn clone(&self) -> Rgb<S, T> {
         *self
     }
 }
 
+impl<S: RgbStandard, T: ConstComponent> Rgb<S, T> {
+    /// Black, with all components at zero.
+    pub const BLACK: Rgb<S, T> = Rgb {
+        red: T::ZERO,
+        green: T::ZERO,
+        blue: T::ZERO,
+        standard: PhantomData,
+    };
+
+    /// White, with all components at their maximum intensity.
+    pub const WHITE: Rgb<S, T> = Rgb {
+        red: T::MAX_INTENSITY,
+        green: T::MAX_INTENSITY,
+        blue: T::MAX_INTENSITY,
+        standard: PhantomData,
+    };
+
+    /// The red primary of the RGB space.
+    pub const RED: Rgb<S, T> = Rgb {
+        red: T::MAX_INTENSITY,
+        green: T::ZERO,
+        blue: T::ZERO,
+        standard: PhantomData,
+    };
+
+    /// The green primary of the RGB space.
+    pub const GREEN: Rgb<S, T> = Rgb {
+        red: T::ZERO,
+        green: T::MAX_INTENSITY,
+        blue: T::ZERO,
+        standard: PhantomData,
+    };
+
+    /// The blue primary of the RGB space.
+    pub const BLUE: Rgb<S, T> = Rgb {
+        red: T::ZERO,
+        green: T::ZERO,
+        blue: T::MAX_INTENSITY,
+        standard: PhantomData,
+    };
+}
+
 impl<S: RgbStandard, T: Component> Rgb<S, T> {
     /// Create an RGB color.
     pub fn new(red: T, green: T, blue: T) -> Rgb<S, T> {
@@ -151,11 +210,48 @@ impl<S: RgbStandard, T: Component + Float> Rgb<S, T> {
         if TypeId::of::<Sp::Primaries>() == TypeId::of::<<S::Space as RgbSpace>::Primaries>() {
             Self::from_linear(rgb.reinterpret_as())
         } else {
-            Self::from_xyz(Xyz::from_rgb(rgb))
+            let matrix = rgb_to_rgb_matrix::<Sp, S::Space, T>();
+            Self::from_linear(multiply_rgb(&matrix, &rgb))
         }
     }
 }
 
+impl<S: RgbStandard, T: Component + Float> Rgb<S, T> {
+    /// Blend the color with `destination` in the `St` encoding of the same RGB
+    /// space, instead of the color's own encoding. The colors are converted
+    /// to `St` before blending, and the result is converted back.
+    ///
+    /// This makes it possible to choose between blending the linear values,
+    /// which is physically more accurate, and the encoded values, which
+    /// matches web browsers and most image editors, regardless of how the
+    /// colors are stored.
+    ///
+    /// ```
+    /// use palette::{Blend, Srgb};
+    /// use palette::blend::BlendMode;
+    /// use palette::encoding::Linear;
+    ///
+    /// let a = Srgb::new(0.5, 0.5, 0.5);
+    /// let b = Srgb::new(0.5, 0.25, 1.0);
+    ///
+    /// let encoded = a.blend(b, BlendMode::Multiply);
+    /// let linear = a.blend_in::<Linear<_>, _>(b, BlendMode::Multiply);
+    ///
+    /// assert_eq!(encoded, Srgb::new(0.25, 0.125, 0.5));
+    /// assert!(linear.red < encoded.red);
+    /// ```
+    pub fn blend_in<St, F>(self, destination: Self, blend_function: F) -> Self
+    where
+        St: RgbStandard<Space = S::Space>,
+        F: BlendFunction<Rgb<St, T>>,
+    {
+        Self::from_encoding(
+            self.into_encoding::<St>()
+                .blend(destination.into_encoding(), blend_function),
+        )
+    }
+}
+
 impl<S: RgbStandard<TransferFn = LinearFn>, T: Component> Rgb<S, T> {
     #[inline]
     fn reinterpret_as<St: RgbStandard<TransferFn = LinearFn>>(self) -> Rgb<St, T>
@@ -250,6 +346,23 @@ impl<S: RgbStandard, T: Component + Float, A: Component> Alpha<Rgb<S, T>, A> {
     }
 }
 
+impl<S: RgbStandard, T: Component + Float> Alpha<Rgb<S, T>, T> {
+    /// Blend the color with `destination` in the `St` encoding of the same RGB
+    /// space, instead of the color's own encoding. The colors are converted
+    /// to `St` before blending, and the result is converted back. See
+    /// [`Rgb::blend_in`](struct.Rgb.html#method.blend_in) for more details.
+    pub fn blend_in<St, F>(self, destination: Self, blend_function: F) -> Self
+    where
+        St: RgbStandard<Space = S::Space>,
+        F: BlendFunction<Rgb<St, T>>,
+    {
+        Self::from_encoding(
+            self.into_encoding::<St>()
+                .blend(destination.into_encoding(), blend_function),
+        )
+    }
+}
+
 impl<S, T> Limited for Rgb<S, T>
 where
     S: RgbStandard,
@@ -334,7 +447,7 @@ where
 
 impl<S, T> Blend for Rgb<S, T>
 where
-    S: RgbStandard<TransferFn = LinearFn>,
+    S: RgbStandard,
     T: Component + Float,
 {
     type Color = Rgb<S, T>;
@@ -374,6 +487,23 @@ where
     }
 }
 
+impl<S, T> Invert for Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Sub<Output = T>,
+{
+    fn invert(&self) -> Rgb<S, T> {
+        let max = T::max_intensity();
+
+        Rgb {
+            red: max - self.red,
+            green: max - self.green,
+            blue: max - self.blue,
+            standard: PhantomData,
+        }
+    }
+}
+
 impl<S, T> Default for Rgb<S, T>
 where
     T: Component,
@@ -753,13 +883,59 @@ impl<S: RgbStandard, T: Component, A: Component> From<(T, T, T, A)> for Alpha<Rg
     }
 }
 
+/// Decodes the components with a lookup table, which is faster than
+/// `color.into_format().into_linear()`.
+impl From<Rgb<Srgb, u8>> for Rgb<Linear<Srgb>, f32> {
+    fn from(color: Rgb<Srgb, u8>) -> Self {
+        Rgb::new(
+            ::encoding::srgb::u8_into_linear(color.red),
+            ::encoding::srgb::u8_into_linear(color.green),
+            ::encoding::srgb::u8_into_linear(color.blue),
+        )
+    }
+}
+
+/// Decodes the components with a lookup table, which is faster than
+/// `color.into_format().into_linear()`.
+impl From<Rgb<Srgb, u16>> for Rgb<Linear<Srgb>, f32> {
+    fn from(color: Rgb<Srgb, u16>) -> Self {
+        Rgb::new(
+            ::encoding::srgb::u16_into_linear(color.red),
+            ::encoding::srgb::u16_into_linear(color.green),
+            ::encoding::srgb::u16_into_linear(color.blue),
+        )
+    }
+}
+
+/// Decodes the color components with a lookup table, which is faster than
+/// `color.into_format().into_linear()`.
+impl From<Alpha<Rgb<Srgb, u8>, u8>> for Alpha<Rgb<Linear<Srgb>, f32>, f32> {
+    fn from(color: Alpha<Rgb<Srgb, u8>, u8>) -> Self {
+        Alpha {
+            color: color.color.into(),
+            alpha: color.alpha.convert(),
+        }
+    }
+}
+
+/// Decodes the color components with a lookup table, which is faster than
+/// `color.into_format().into_linear()`.
+impl From<Alpha<Rgb<Srgb, u16>, u16>> for Alpha<Rgb<Linear<Srgb>, f32>, f32> {
+    fn from(color: Alpha<Rgb<Srgb, u16>, u16>) -> Self {
+        Alpha {
+            color: color.color.into(),
+            alpha: color.alpha.convert(),
+        }
+    }
+}
+
 impl<S: RgbStandard, T: Component, A: Component> Into<(T, T, T, A)> for Alpha<Rgb<S, T>, A> {
     fn into(self) -> (T, T, T, A) {
         self.into_components()
     }
 }
 
-impl<S, T, Wp> IntoColor<Wp, T> for Rgb<S, T>
+impl<S, T, Wp> IntoColorUnclamped<Wp, T> for Rgb<S, T>
 where
     S: RgbStandard,
     T: Component + Float,
@@ -917,12 +1093,26 @@ where
     }
 }
 
+///Formats the components as hexadecimal numbers, each padded to the size of
+///the component type, or to the width in the format string. The alternate
+///flag adds a leading `#`.
+///
+///```
+///use palette::Srgb;
+///
+///assert_eq!(format!("{:x}", Srgb::new(255u8, 136, 0)), "ff8800");
+///assert_eq!(format!("{:#X}", Srgb::new(255u8, 136, 0)), "#FF8800");
+///```
 impl<S, T> fmt::LowerHex for Rgb<S, T>
 where
     T: Component + fmt::LowerHex,
     S: RgbStandard,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(
             f,
@@ -941,6 +1131,10 @@ where
     S: RgbStandard,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
+
         let size = f.width().unwrap_or(::core::mem::size_of::<T>() * 2);
         write!(
             f,
@@ -953,10 +1147,122 @@ where
     }
 }
 
+///An error from parsing a hexadecimal RGB color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromHexError {
+    ///The string contains a character that isn't a hexadecimal digit.
+    InvalidDigit,
+    ///The string doesn't have a supported number of digits.
+    InvalidLength,
+}
+
+impl fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FromHexError::InvalidDigit => f.write_str("invalid hexadecimal digit"),
+            FromHexError::InvalidLength => f.write_str("invalid number of hexadecimal digits"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for FromHexError {
+    fn description(&self) -> &str {
+        "could not parse hexadecimal color"
+    }
+}
+
+///Parse the components of a hexadecimal color, with an optional leading `#`.
+///The alpha component is only accepted if `with_alpha` is `true` and is 255
+///if it's missing.
+fn parse_hex(hex: &str, with_alpha: bool) -> Result<[u8; 4], FromHexError> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    let mut digits = [0u8; 8];
+
+    if hex.len() > digits.len() {
+        return Err(FromHexError::InvalidLength);
+    }
+
+    for (digit, c) in digits.iter_mut().zip(hex.chars()) {
+        *digit = c.to_digit(16).ok_or(FromHexError::InvalidDigit)? as u8;
+    }
+
+    let single = |index: usize| digits[index] * 17;
+    let pair = |index: usize| digits[index] * 16 + digits[index + 1];
+
+    match hex.len() {
+        3 => Ok([single(0), single(1), single(2), 255]),
+        4 if with_alpha => Ok([single(0), single(1), single(2), single(3)]),
+        6 => Ok([pair(0), pair(2), pair(4), 255]),
+        8 if with_alpha => Ok([pair(0), pair(2), pair(4), pair(6)]),
+        _ => Err(FromHexError::InvalidLength),
+    }
+}
+
+///Parses hexadecimal colors in the `#rgb` and `#rrggbb` formats, where the
+///`#` is optional.
+///
+///```
+///use palette::Srgb;
+///
+///let orange: Srgb<u8> = "#ff8000".parse().unwrap();
+///assert_eq!(orange, Srgb::new(255, 128, 0));
+///assert_eq!("f80".parse(), Ok(Srgb::new(255u8, 136, 0)));
+///```
+impl<S: RgbStandard> FromStr for Rgb<S, u8> {
+    type Err = FromHexError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        let [red, green, blue, _] = parse_hex(hex, false)?;
+        Ok(Rgb::new(red, green, blue))
+    }
+}
+
+///Parses hexadecimal colors in the `#rgb`, `#rgba`, `#rrggbb` and
+///`#rrggbbaa` formats, where the `#` is optional. The alpha component is 255
+///if it's missing.
+///
+///```
+///use palette::Srgba;
+///
+///let orange: Srgba<u8> = "#ff800080".parse().unwrap();
+///assert_eq!(orange, Srgba::new(255, 128, 0, 128));
+///assert_eq!("f80".parse(), Ok(Srgba::new(255u8, 136, 0, 255)));
+///```
+impl<S: RgbStandard> FromStr for Rgba<S, u8> {
+    type Err = FromHexError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        let [red, green, blue, alpha] = parse_hex(hex, true)?;
+        Ok(Rgba::new(red, green, blue, alpha))
+    }
+}
+
+///Samples each component uniformly, which covers the whole gamut of the RGB
+///space. Floating point components are in `[0.0, 1.0)` and integer
+///components cover their full range.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Rgb<S, T>> for ::rand::distributions::Standard
+where
+    S: RgbStandard,
+    T: Component,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Rgb<S, T> {
+        Rgb {
+            red: rng.gen(),
+            green: rng.gen(),
+            blue: rng.gen(),
+            standard: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Rgb;
+    use super::{Rgb, Rgba};
     use encoding::Srgb;
+    use {LinSrgb, LinSrgba};
 
     #[test]
     fn ranges() {
@@ -975,6 +1281,26 @@ mod test {
     raw_pixel_conversion_tests!(Rgb<Srgb>: red, green, blue);
     raw_pixel_conversion_fail_tests!(Rgb<Srgb>: red, green, blue);
 
+    #[test]
+    fn from_str() {
+        use super::FromHexError;
+        use rgb::Rgba;
+
+        assert_eq!("#a1b2c3".parse(), Ok(Rgb::<Srgb, u8>::new(161, 178, 195)));
+        assert_eq!("A1B2C3".parse(), Ok(Rgb::<Srgb, u8>::new(161, 178, 195)));
+        assert_eq!("#f0c".parse(), Ok(Rgb::<Srgb, u8>::new(255, 0, 204)));
+        assert_eq!("#a1b2c3d4".parse(), Ok(Rgba::<Srgb, u8>::new(161, 178, 195, 212)));
+        assert_eq!("f0c8".parse(), Ok(Rgba::<Srgb, u8>::new(255, 0, 204, 136)));
+        assert_eq!("#a1b2c3".parse(), Ok(Rgba::<Srgb, u8>::new(161, 178, 195, 255)));
+
+        assert_eq!("#a1b2c3d4".parse::<Rgb<Srgb, u8>>(), Err(FromHexError::InvalidLength));
+        assert_eq!("#a1b2c".parse::<Rgb<Srgb, u8>>(), Err(FromHexError::InvalidLength));
+        assert_eq!("".parse::<Rgba<Srgb, u8>>(), Err(FromHexError::InvalidLength));
+        assert_eq!("#a1b2cg".parse::<Rgb<Srgb, u8>>(), Err(FromHexError::InvalidDigit));
+        assert_eq!("#+1b2c3".parse::<Rgb<Srgb, u8>>(), Err(FromHexError::InvalidDigit));
+        assert_eq!("#ééé".parse::<Rgb<Srgb, u8>>(), Err(FromHexError::InvalidDigit));
+    }
+
     #[test]
     fn lower_hex() {
         assert_eq!(
@@ -1020,6 +1346,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn hex_alternate() {
+        assert_eq!(format!("{:#x}", Rgb::<Srgb, u8>::new(171, 193, 35)), "#abc123");
+        assert_eq!(format!("{:#X}", Rgb::<Srgb, u8>::new(171, 193, 35)), "#ABC123");
+        assert_eq!(format!("{:#03x}", Rgb::<Srgb, u8>::new(1, 2, 3)), "#001002003");
+    }
+
+    #[test]
+    fn integer_into_linear() {
+        let color = Rgb::<Srgb, u8>::new(171, 193, 35);
+        let linear: LinSrgb = color.into();
+        assert_relative_eq!(linear, color.into_format::<f32>().into_linear());
+
+        let color = Rgba::<Srgb, u16>::new(4369, 65535, 0, 32768);
+        let linear: LinSrgba = color.into();
+        assert_relative_eq!(linear, color.into_format::<f32, f32>().into_linear());
+    }
+
     #[test]
     fn upper_hex() {
         assert_eq!(
@@ -1081,4 +1425,18 @@ mod test {
 
         assert_eq!(deserialized, Rgb::<Srgb>::new(0.3, 0.8, 0.1));
     }
+
+    #[test]
+    fn constants() {
+        use Limited;
+
+        assert_eq!(Rgb::<Srgb, u8>::WHITE, Rgb::new(255, 255, 255));
+        assert_eq!(Rgb::<Srgb, u16>::RED, Rgb::new(65535, 0, 0));
+        assert_eq!(LinSrgb::BLUE, LinSrgb::new(0.0, 0.0, 1.0));
+        assert_eq!(Rgb::<Srgb, f64>::BLACK, Rgb::new(0.0, 0.0, 0.0));
+
+        let white: LinSrgb = Rgb::<Srgb, f32>::WHITE.into_linear();
+        assert_eq!(white, LinSrgb::WHITE);
+        assert!(LinSrgb::<f32>::GREEN.is_valid());
+    }
 }
@@ -0,0 +1,248 @@
+//!Scales of tints, shades and tones.
+//!
+//!A tint is a color mixed with white, a shade is a color mixed with black and
+//!a tone is a color mixed with gray. The [`ColorScale`](trait.ColorScale.html)
+//!trait makes evenly spaced scales of them, from a base color to white, black
+//!or gray, which is useful for the lighter and darker variants of a color in
+//!a design system.
+//!
+//!The colors are mixed in the color space of the base color, so the space is
+//!selected by converting the base color before making the scale. Linear RGB
+//!mixes the light of the colors, CIE L\*a\*b\* and L\*C\*h° give more even
+//!steps of perceived lightness, and the hue of L\*C\*h°, HSL, HSV and HWB is
+//!kept for all of the colors.
+//!
+//!```
+//!use palette::{Lab, LinSrgb, Srgb};
+//!use palette::scale::ColorScale;
+//!
+//!let base = Srgb::new(0.2, 0.4, 0.8).into_linear();
+//!
+//!let linear: Vec<Srgb> = base
+//!    .tints(5)
+//!    .map(|color| Srgb::from_linear(color))
+//!    .collect();
+//!
+//!let perceptual: Vec<Srgb> = Lab::from(base)
+//!    .tints(5)
+//!    .map(|color| Srgb::from_linear(color.into()))
+//!    .collect();
+//!
+//!assert_eq!(linear.len(), 5);
+//!assert!(perceptual[1].red < linear[1].red);
+//!```
+
+use float::Float;
+use num_traits::Zero;
+
+use encoding::linear::LinearFn;
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {cast, Component, FromColorUnclamped, Hsl, Hsv, Hwb, Lab, Lch, Mix, Xyz};
+
+///Make scales of tints, shades and tones from a color.
+///
+///Each scale has `steps` colors, where the first is the color itself and the
+///last is white, black or gray. The colors are mixed in the color space of
+///`Self`, and the color spaces with a hue keep the hue of the base color.
+pub trait ColorScale: Mix + Sized {
+    ///Mix the color with white.
+    fn tints(&self, steps: usize) -> Steps<Self>;
+
+    ///Mix the color with black.
+    fn shades(&self, steps: usize) -> Steps<Self>;
+
+    ///Mix the color with a middle gray, with the CIE L\* lightness `50.0`.
+    fn tones(&self, steps: usize) -> Steps<Self>;
+}
+
+///An iterator over evenly spaced mixes of two colors.
+#[derive(Clone, Debug)]
+pub struct Steps<C> {
+    from: C,
+    to: C,
+    len: usize,
+    current: usize,
+}
+
+impl<C: Mix> Steps<C> {
+    ///Create an iterator over `len` colors from `from` to `to`, including
+    ///both of them.
+    pub fn new(from: C, to: C, len: usize) -> Steps<C> {
+        Steps {
+            from: from,
+            to: to,
+            len: len,
+            current: 0,
+        }
+    }
+
+    fn get(&self, index: usize) -> C {
+        let factor = if self.len > 1 {
+            cast::<C::Scalar, _>(index) / cast(self.len - 1)
+        } else {
+            C::Scalar::zero()
+        };
+
+        self.from.mix(&self.to, factor)
+    }
+}
+
+impl<C: Mix> Iterator for Steps<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.current < self.len {
+            let color = self.get(self.current);
+            self.current += 1;
+            Some(color)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.current, Some(self.len - self.current))
+    }
+}
+
+impl<C: Mix> DoubleEndedIterator for Steps<C> {
+    fn next_back(&mut self) -> Option<C> {
+        if self.current < self.len {
+            self.len -= 1;
+            Some(self.get(self.len))
+        } else {
+            None
+        }
+    }
+}
+
+impl<C: Mix> ExactSizeIterator for Steps<C> {}
+
+fn white<C, Wp, T>() -> C
+where
+    C: FromColorUnclamped<Wp, T>,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    C::from_xyz(Wp::get_xyz())
+}
+
+fn black<C, Wp, T>() -> C
+where
+    C: FromColorUnclamped<Wp, T>,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    C::from_xyz(Xyz::with_wp(T::zero(), T::zero(), T::zero()))
+}
+
+fn gray<C, Wp, T>() -> C
+where
+    C: FromColorUnclamped<Wp, T>,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    C::from_lab(Lab::with_wp(cast(50.0), T::zero(), T::zero()))
+}
+
+macro_rules! impl_color_scale {
+    ($ty:ident<$param:ident: $bound:path>, $wp:ty) => {
+        impl_color_scale!($ty<$param: $bound>, $wp, |color: Self, _: &Self| color);
+    };
+    (hue $ty:ident<$param:ident: $bound:path>, $wp:ty) => {
+        //The end colors are gray, so they get the hue of the base color
+        impl_color_scale!($ty<$param: $bound>, $wp, |color: Self, base: &Self| {
+            Self { hue: base.hue, ..color }
+        });
+    };
+    ($ty:ident<$param:ident: $bound:path>, $wp:ty, $adapt:expr) => {
+        impl<$param, T> ColorScale for $ty<$param, T>
+        where
+            T: Component + Float,
+            $param: $bound,
+        {
+            fn tints(&self, steps: usize) -> Steps<Self> {
+                let white = ($adapt)(white::<Self, $wp, T>(), self);
+                Steps::new(*self, white, steps)
+            }
+
+            fn shades(&self, steps: usize) -> Steps<Self> {
+                let black = ($adapt)(black::<Self, $wp, T>(), self);
+                Steps::new(*self, black, steps)
+            }
+
+            fn tones(&self, steps: usize) -> Steps<Self> {
+                let gray = ($adapt)(gray::<Self, $wp, T>(), self);
+                Steps::new(*self, gray, steps)
+            }
+        }
+    };
+}
+
+impl_color_scale!(Rgb<S: RgbStandard<TransferFn = LinearFn>>, <S::Space as RgbSpace>::WhitePoint);
+impl_color_scale!(Xyz<Wp: WhitePoint>, Wp);
+impl_color_scale!(Lab<Wp: WhitePoint>, Wp);
+impl_color_scale!(hue Lch<Wp: WhitePoint>, Wp);
+impl_color_scale!(hue Hsl<S: RgbSpace>, S::WhitePoint);
+impl_color_scale!(hue Hsv<S: RgbSpace>, S::WhitePoint);
+impl_color_scale!(hue Hwb<S: RgbSpace>, S::WhitePoint);
+
+#[cfg(test)]
+mod test {
+    use super::{ColorScale, Steps};
+    use {Hsl, Lab, Lch, LinSrgb};
+
+    #[test]
+    fn steps() {
+        let steps = Steps::new(LinSrgb::new(0.0, 0.0, 0.0), LinSrgb::new(1.0, 0.5, 0.0), 3);
+        assert_eq!(steps.len(), 3);
+
+        let colors: Vec<_> = steps.clone().collect();
+        assert_relative_eq!(colors[0], LinSrgb::new(0.0, 0.0, 0.0));
+        assert_relative_eq!(colors[1], LinSrgb::new(0.5, 0.25, 0.0));
+        assert_relative_eq!(colors[2], LinSrgb::new(1.0, 0.5, 0.0));
+
+        let reversed: Vec<_> = steps.rev().collect();
+        assert_relative_eq!(reversed[0], colors[2]);
+        assert_relative_eq!(reversed[2], colors[0]);
+
+        let mut single = Steps::new(LinSrgb::new(0.2, 0.2, 0.2), LinSrgb::new(1.0, 1.0, 1.0), 1);
+        assert_relative_eq!(single.next().unwrap(), LinSrgb::new(0.2, 0.2, 0.2));
+        assert_eq!(single.next(), None);
+    }
+
+    #[test]
+    fn rgb_scales() {
+        let base = LinSrgb::new(0.2f64, 0.4, 0.6);
+
+        let tints: Vec<_> = base.tints(5).collect();
+        assert_relative_eq!(tints[0], base);
+        assert_relative_eq!(tints[2], LinSrgb::new(0.6, 0.7, 0.8), epsilon = 1e-6);
+        assert_relative_eq!(tints[4], LinSrgb::new(1.0, 1.0, 1.0), epsilon = 1e-6);
+
+        let shades: Vec<_> = base.shades(3).collect();
+        assert_relative_eq!(shades[1], LinSrgb::new(0.1, 0.2, 0.3), epsilon = 1e-6);
+        assert_relative_eq!(shades[2], LinSrgb::new(0.0, 0.0, 0.0), epsilon = 1e-6);
+
+        let tones: Vec<_> = base.tones(2).collect();
+        let gray = Lab::from(tones[1]);
+        assert_relative_eq!(gray, Lab::new(50.0, 0.0, 0.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn keeps_hue() {
+        let base = Lch::new(60.0f64, 50.0, 120.0);
+        for color in base.tints(4).chain(base.shades(4)).chain(base.tones(4)) {
+            assert_relative_eq!(color.hue, base.hue);
+        }
+
+        let tones: Vec<_> = base.tones(3).collect();
+        assert_relative_eq!(tones[1], Lch::new(55.0, 25.0, 120.0), epsilon = 1e-6);
+
+        let base = Hsl::new(200.0f64, 0.8, 0.4);
+        let shades: Vec<_> = base.shades(3).collect();
+        assert_relative_eq!(shades[1].hue, base.hue);
+        assert_relative_eq!(shades[2].lightness, 0.0, epsilon = 1e-6);
+    }
+}
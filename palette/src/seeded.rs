@@ -0,0 +1,237 @@
+//!Seeded palettes, for giving categories consistent colors.
+//!
+//!A [`SeededPalette`](struct.SeededPalette.html) makes a sequence of colors
+//!with distinct hues, from a `u64` seed. The same seed always gives the same
+//!colors, also in later versions of this crate, so it's suitable for giving
+//!the series of a chart, the users of a chat or the labels of an issue
+//!tracker colors that stay the same between runs and releases.
+//!
+//!The hues follow the golden angle, starting from a hue that depends on the
+//!seed, which keeps the colors that are close in the sequence far apart on the
+//!color wheel. The lightness and chroma are picked at random, in CIE L\*C\*h°,
+//!from ranges that can be changed to fit a light or dark background. The
+//!chroma is reduced where it's needed to keep the colors in the RGB gamut.
+//!
+//!```
+//!use palette::{encoding, Srgb};
+//!use palette::seeded::SeededPalette;
+//!
+//!let palette = SeededPalette::<encoding::Srgb>::new(42).with_lightness(40.0, 60.0);
+//!
+//!let series: Vec<Srgb> = palette
+//!    .colors()
+//!    .take(5)
+//!    .map(|color| Srgb::from_linear(color.into()))
+//!    .collect();
+//!
+//!//The colors can also be looked up by index
+//!assert_eq!(Srgb::from_linear(palette.color(3).into()), series[3]);
+//!```
+
+use core::marker::PhantomData;
+
+use float::Float;
+
+use encoding;
+use rgb::RgbSpace;
+use tonal::TonalPalette;
+use {cast, Component, LabHue, Lch};
+
+///The golden angle, in degrees.
+const GOLDEN_ANGLE: f64 = 137.507_764_050_037_85;
+
+///A deterministic sequence of colors with distinct hues.
+///
+///The colors are kept inside the gamut of the RGB space `S`. The algorithm is
+///part of the stability guarantee, so the colors for a seed, index, lightness
+///range and chroma range don't change between versions.
+#[derive(Debug, PartialEq)]
+pub struct SeededPalette<S: RgbSpace = encoding::Srgb, T: Float = f32> {
+    seed: u64,
+
+    ///The range of the CIE L\* lightness of the colors. The default is
+    ///`(50.0, 75.0)`.
+    pub lightness: (T, T),
+
+    ///The range of the CIE C\*ab chroma of the colors, before they are fitted
+    ///into the RGB gamut. The default is `(35.0, 60.0)`.
+    pub chroma: (T, T),
+
+    space: PhantomData<S>,
+}
+
+impl<S: RgbSpace, T: Float> Copy for SeededPalette<S, T> {}
+
+impl<S: RgbSpace, T: Float> Clone for SeededPalette<S, T> {
+    fn clone(&self) -> SeededPalette<S, T> {
+        *self
+    }
+}
+
+impl<S, T> SeededPalette<S, T>
+where
+    S: RgbSpace,
+    T: Component + Float,
+{
+    ///Create a palette from a seed, with the default lightness and chroma
+    ///ranges.
+    pub fn new(seed: u64) -> SeededPalette<S, T> {
+        SeededPalette {
+            seed: seed,
+            lightness: (cast(50.0), cast(75.0)),
+            chroma: (cast(35.0), cast(60.0)),
+            space: PhantomData,
+        }
+    }
+
+    ///Change the range of the lightness.
+    pub fn with_lightness(mut self, min: T, max: T) -> SeededPalette<S, T> {
+        self.lightness = (min, max);
+        self
+    }
+
+    ///Change the range of the chroma.
+    pub fn with_chroma(mut self, min: T, max: T) -> SeededPalette<S, T> {
+        self.chroma = (min, max);
+        self
+    }
+
+    ///The seed of the palette.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    ///The color with the index `index` in the sequence.
+    pub fn color(&self, index: usize) -> Lch<S::WhitePoint, T> {
+        let start = fraction(split_mix(self.seed) >> 11, 53) * 360.0;
+        let hue = (start + index as f64 * GOLDEN_ANGLE) % 360.0;
+
+        let random = split_mix(self.seed ^ split_mix(index as u64));
+        let lightness = lerp(self.lightness, fraction(random >> 32, 32));
+        let chroma = lerp(self.chroma, fraction(random & 0xFFFF_FFFF, 32));
+
+        TonalPalette::<S, T>::new(LabHue::from_degrees(cast(hue)), chroma).tone(lightness)
+    }
+
+    ///An iterator over the colors of the palette, starting with index `0`.
+    ///It never ends, so it's usually limited with `take`.
+    pub fn colors(&self) -> Colors<S, T> {
+        Colors {
+            palette: *self,
+            index: 0,
+        }
+    }
+}
+
+///An endless iterator over the colors of a seeded palette.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Colors<S: RgbSpace = encoding::Srgb, T: Float = f32> {
+    palette: SeededPalette<S, T>,
+    index: usize,
+}
+
+impl<S, T> Iterator for Colors<S, T>
+where
+    S: RgbSpace,
+    T: Component + Float,
+{
+    type Item = Lch<S::WhitePoint, T>;
+
+    fn next(&mut self) -> Option<Lch<S::WhitePoint, T>> {
+        let color = self.palette.color(self.index);
+        self.index = self.index.wrapping_add(1);
+        Some(color)
+    }
+}
+
+///The SplitMix64 hash function.
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+///Turn the lowest `bits` bits of `value` into a number in `[0.0, 1.0)`.
+fn fraction(value: u64, bits: i32) -> f64 {
+    value as f64 / 2.0f64.powi(bits)
+}
+
+fn lerp<T: Component + Float>((min, max): (T, T), factor: f64) -> T {
+    min + (max - min) * cast(factor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::SeededPalette;
+    use encoding::Srgb;
+    use LinSrgb;
+
+    #[test]
+    fn deterministic() {
+        let a = SeededPalette::<Srgb, f64>::new(7);
+        let b = SeededPalette::<Srgb, f64>::new(7);
+        let c = SeededPalette::<Srgb, f64>::new(8);
+
+        for (index, (color, other)) in a.colors().zip(b.colors()).take(20).enumerate() {
+            assert_eq!(color, other);
+            assert_eq!(color, a.color(index));
+        }
+
+        assert!(a.color(0) != c.color(0));
+    }
+
+    #[test]
+    fn ranges() {
+        let palette = SeededPalette::<Srgb, f64>::new(1234)
+            .with_lightness(30.0, 40.0)
+            .with_chroma(20.0, 30.0);
+
+        for color in palette.colors().take(50) {
+            assert!((30.0..=40.0).contains(&color.l));
+            assert!(color.chroma <= 30.0);
+
+            let rgb = LinSrgb::from(color);
+            for &value in &[rgb.red, rgb.green, rgb.blue] {
+                assert!((-1e-4..=1.0 + 1e-4).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn distinct_hues() {
+        let palette = SeededPalette::<Srgb, f64>::new(99);
+        let hues: Vec<_> = palette
+            .colors()
+            .take(8)
+            .map(|color| color.hue.to_positive_degrees())
+            .collect();
+
+        for (index, &a) in hues.iter().enumerate() {
+            for &b in &hues[index + 1..] {
+                let difference = (a - b).abs();
+                assert!(difference.min(360.0 - difference) > 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn stable() {
+        //These values must never change
+        let palette = SeededPalette::<Srgb, f64>::new(0);
+        let color = palette.color(0);
+        assert_relative_eq!(color.l, 66.311212, epsilon = 1e-6);
+        assert_relative_eq!(color.chroma, 42.529256, epsilon = 1e-6);
+        assert_relative_eq!(color.hue.to_positive_degrees(), 317.991891, epsilon = 1e-6);
+
+        let color = palette.color(5);
+        assert_relative_eq!(color.l, 74.496034, epsilon = 1e-6);
+        assert_relative_eq!(color.chroma, 40.617845, epsilon = 1e-6);
+        assert_relative_eq!(color.hue.to_positive_degrees(), 285.530711, epsilon = 1e-6);
+
+        let color = SeededPalette::<Srgb, f32>::new(0xDEAD_BEEF).color(3);
+        assert_relative_eq!(color.l, 63.882076, epsilon = 1e-4);
+        assert_relative_eq!(color.chroma, 43.735992, epsilon = 1e-4);
+        assert_relative_eq!(color.hue.to_positive_degrees(), 157.81474, epsilon = 1e-4);
+    }
+}
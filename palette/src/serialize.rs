@@ -0,0 +1,162 @@
+//!Alternative serialization formats, for use with `#[serde(with = "...")]`.
+//!
+//!The color types are serialized as structs with named fields by default,
+//!such as `{"red":1.0,"green":0.5,"blue":0.0}`. The modules in here can be
+//!used to choose a more compact format for individual fields:
+//!
+//! * [`as_array`](as_array/index.html) serializes any color as an array of
+//!   its components, in the same order as in memory, such as `[1.0,0.5,0.0]`.
+//! * [`as_hex`](as_hex/index.html) serializes `Srgb<u8>` as a hexadecimal
+//!   string, such as `"#ff8000"`.
+//!
+//!```
+//!#[macro_use]
+//!extern crate serde_derive;
+//!extern crate serde_json;
+//!extern crate palette;
+//!
+//!use palette::{Lch, Srgb};
+//!
+//!#[derive(Serialize, Deserialize)]
+//!struct Theme {
+//!    #[serde(with = "palette::serialize::as_hex")]
+//!    background: Srgb<u8>,
+//!    #[serde(with = "palette::serialize::as_array")]
+//!    accent: Lch,
+//!}
+//!
+//!fn main() {
+//!    let theme = Theme {
+//!        background: Srgb::new(255, 128, 0),
+//!        accent: Lch::new(50.0, 30.0, 120.0),
+//!    };
+//!
+//!    let json = serde_json::to_string(&theme).unwrap();
+//!    assert_eq!(json, r##"{"background":"#ff8000","accent":[50.0,30.0,120.0]}"##);
+//!}
+//!```
+
+///Serialize colors as arrays of their components.
+///
+///Works for every color that implements [`Pixel`](../../trait.Pixel.html),
+///including colors with an alpha component. Deserializing fails if the array
+///doesn't have exactly one element per channel.
+pub mod as_array {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::Serialize;
+
+    use Pixel;
+
+    ///Serialize `color` as an array of its components.
+    pub fn serialize<C, T, S>(color: &C, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: Pixel<T>,
+        T: Serialize,
+        S: Serializer,
+    {
+        let components: &[T] = color.as_raw();
+        serializer.collect_seq(components)
+    }
+
+    ///Deserialize a color from an array of its components.
+    pub fn deserialize<'de, C, T, D>(deserializer: D) -> Result<C, D::Error>
+    where
+        C: Pixel<T> + Clone,
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let components: Vec<T> = Vec::deserialize(deserializer)?;
+
+        if components.len() != C::CHANNELS {
+            return Err(D::Error::invalid_length(
+                components.len(),
+                &"one component per color channel",
+            ));
+        }
+
+        Ok(C::from_raw::<[T]>(&components).clone())
+    }
+}
+
+///Serialize `Srgb<u8>` as hexadecimal strings, such as `"#ff8000"`.
+///
+///Deserializing requires a leading `#` and exactly six hexadecimal digits,
+///but accepts both lower and upper case letters.
+pub mod as_hex {
+    use serde::de::{Error, Unexpected};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use Srgb;
+
+    ///Serialize `color` as a hexadecimal string.
+    pub fn serialize<S: Serializer>(color: &Srgb<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("#{:x}", color))
+    }
+
+    ///Deserialize a color from a hexadecimal string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Srgb<u8>, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        let invalid = || D::Error::invalid_value(Unexpected::Str(&input), &"a color like #ff8000");
+
+        let digits = match input.strip_prefix('#') {
+            Some(digits) if digits.len() == 6 && digits.is_ascii() => digits,
+            _ => return Err(invalid()),
+        };
+
+        let component = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16);
+        match (component(0), component(2), component(4)) {
+            (Ok(red), Ok(green), Ok(blue)) => Ok(Srgb::new(red, green, blue)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json;
+
+    use {Hsva, Srgb};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Formats {
+        #[serde(with = "::serialize::as_array")]
+        array: Hsva,
+        #[serde(with = "::serialize::as_hex")]
+        hex: Srgb<u8>,
+        named: Srgb<u8>,
+    }
+
+    #[test]
+    fn round_trip() {
+        let formats = Formats {
+            array: Hsva::new(90.0, 0.5, 0.25, 1.0),
+            hex: Srgb::new(171, 193, 35),
+            named: Srgb::new(1, 2, 3),
+        };
+
+        let json = serde_json::to_string(&formats).unwrap();
+        assert_eq!(
+            json,
+            r##"{"array":[90.0,0.5,0.25,1.0],"hex":"#abc123","named":{"red":1,"green":2,"blue":3}}"##
+        );
+
+        let deserialized: Formats = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, formats);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let wrong_length = r##"{"array":[90.0,0.5,0.25],"hex":"#abc123","named":{"red":1,"green":2,"blue":3}}"##;
+        assert!(serde_json::from_str::<Formats>(wrong_length).is_err());
+
+        let no_hash = r##"{"array":[90.0,0.5,0.25,1.0],"hex":"abc123","named":{"red":1,"green":2,"blue":3}}"##;
+        assert!(serde_json::from_str::<Formats>(no_hash).is_err());
+
+        let not_hex = r##"{"array":[90.0,0.5,0.25,1.0],"hex":"#abcxyz","named":{"red":1,"green":2,"blue":3}}"##;
+        assert!(serde_json::from_str::<Formats>(not_hex).is_err());
+
+        let upper_case = r##"{"array":[90.0,0.5,0.25,1.0],"hex":"#ABC123","named":{"red":1,"green":2,"blue":3}}"##;
+        assert!(serde_json::from_str::<Formats>(upper_case).is_ok());
+    }
+}
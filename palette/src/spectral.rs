@@ -0,0 +1,599 @@
+//!Spectral power distributions, and their conversion to XYZ.
+//!
+//!A spectral power distribution (SPD) describes how much power a light
+//!source emits, or a surface reflects, at each wavelength. It's converted to
+//!XYZ by integrating it against the color matching functions of a standard
+//!observer. The CIE 1931 2° and the CIE 1964 10° observers are available, as
+//!tables with 10 nm intervals between 380 nm and 780 nm. The integration is
+//!done in 1 nm steps, with linear interpolation, so spectra with narrow peaks
+//!can be sampled at any wavelengths.
+//!
+//!The white points know which observer they are for, so
+//![`surface_to_xyz`](fn.surface_to_xyz.html) picks the 10° observer for a
+//!white point such as
+//![`D65Degree10`](../white_point/struct.D65Degree10.html).
+//!
+//!Besides the standard illuminants, there are the spectra of black body
+//!radiators and of CIE daylight at any temperature, in
+//![`Planckian`](struct.Planckian.html) and [`Daylight`](struct.Daylight.html).
+//!
+//!```
+//!use palette::Xyz;
+//!use palette::spectral::{spectrum_to_xyz, Illuminant, Observer};
+//!
+//!let d65: Xyz = spectrum_to_xyz(&Illuminant::D65, Observer::Cie1931);
+//!let white = d65 / d65.y;
+//!assert!((white.x - 0.9505).abs() < 1e-3);
+//!assert!((white.z - 1.089).abs() < 5e-3);
+//!
+//!//A green LED, sampled at a few wavelengths
+//!let led: &[(f32, f32)] = &[(500.0, 0.0), (525.0, 1.0), (550.0, 0.0)];
+//!let green: Xyz = spectrum_to_xyz(led, Observer::Cie1931);
+//!assert!(green.y > green.x && green.y > green.z);
+//!```
+
+use float::Float;
+
+use white_point::WhitePoint;
+use {cast, clamp, Component, Xyz};
+
+///A spectral power distribution.
+pub trait Spectrum<T> {
+    ///Get the spectral power at the wavelength `wavelength`, in nanometers.
+    fn power(&self, wavelength: T) -> T;
+}
+
+///Samples of `(wavelength, power)`, sorted by wavelength in nanometers. The
+///power is linearly interpolated between the samples, and is 0 outside of
+///them.
+impl<T: Float> Spectrum<T> for [(T, T)] {
+    fn power(&self, wavelength: T) -> T {
+        match self.iter().position(|&(sample, _)| sample >= wavelength) {
+            Some(0) if self[0].0 == wavelength => self[0].1,
+            Some(0) | None => T::zero(),
+            Some(index) => {
+                let (low_wavelength, low_power) = self[index - 1];
+                let (high_wavelength, high_power) = self[index];
+                let factor = (wavelength - low_wavelength) / (high_wavelength - low_wavelength);
+                low_power + (high_power - low_power) * factor
+            }
+        }
+    }
+}
+
+impl<T, S: Spectrum<T> + ?Sized> Spectrum<T> for &S {
+    fn power(&self, wavelength: T) -> T {
+        (**self).power(wavelength)
+    }
+}
+
+///The spectrum of a surface with the reflectance spectrum `R`, lit by a
+///light source with the spectrum `S`.
+pub struct Reflection<'a, R: 'a + ?Sized, S: 'a + ?Sized> {
+    ///The reflectance of the surface, from 0 to 1.
+    pub reflectance: &'a R,
+
+    ///The spectrum of the light source.
+    pub light: &'a S,
+}
+
+impl<'a, R: ?Sized, S: ?Sized> Reflection<'a, R, S> {
+    ///Light a surface with the reflectance `reflectance` with `light`.
+    pub fn new(reflectance: &'a R, light: &'a S) -> Reflection<'a, R, S> {
+        Reflection {
+            reflectance: reflectance,
+            light: light,
+        }
+    }
+}
+
+impl<'a, T, R, S> Spectrum<T> for Reflection<'a, R, S>
+where
+    T: Float,
+    R: Spectrum<T> + ?Sized,
+    S: Spectrum<T> + ?Sized,
+{
+    fn power(&self, wavelength: T) -> T {
+        self.reflectance.power(wavelength) * self.light.power(wavelength)
+    }
+}
+
+///A CIE standard illuminant, with a relative power of 100 at 560 nm.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Illuminant {
+    ///Incandescent light, a black body radiator at about 2856 K.
+    A,
+    ///Average daylight, at about 6504 K.
+    D65,
+    ///The equal energy illuminant, with the same power at all wavelengths.
+    E,
+}
+
+impl<T: Float> Spectrum<T> for Illuminant {
+    fn power(&self, wavelength: T) -> T {
+        let wavelength: f64 = cast(wavelength);
+
+        let power = match *self {
+            Illuminant::A => {
+                //The definition of illuminant A, from CIE 15
+                let c2: f64 = 1.435e7 / 2848.0;
+                100.0 * (560.0 / wavelength).powi(5) * ((c2 / 560.0).exp() - 1.0)
+                    / ((c2 / wavelength).exp() - 1.0)
+            }
+            Illuminant::D65 => match table_position(wavelength) {
+                Some((index, factor)) => D65[index] + (D65[index + 1] - D65[index]) * factor,
+                None => 0.0,
+            },
+            Illuminant::E => 100.0,
+        };
+
+        cast(power)
+    }
+}
+
+///The spectrum of a black body radiator, with a relative power of 100 at
+///560 nm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Planckian<T> {
+    ///The temperature of the radiator, in kelvin.
+    pub temperature: T,
+}
+
+impl<T> Planckian<T> {
+    ///Create the spectrum of a black body radiator at `temperature` kelvin.
+    pub fn new(temperature: T) -> Planckian<T> {
+        Planckian {
+            temperature: temperature,
+        }
+    }
+}
+
+impl<T: Float> Spectrum<T> for Planckian<T> {
+    fn power(&self, wavelength: T) -> T {
+        let wavelength: f64 = cast(wavelength);
+        let c2 = 1.4388e7 / cast::<f64, _>(self.temperature);
+
+        cast(
+            100.0 * (560.0 / wavelength).powi(5) * ((c2 / 560.0).exp() - 1.0)
+                / ((c2 / wavelength).exp() - 1.0),
+        )
+    }
+}
+
+///The spectrum of CIE daylight, with a relative power of 100 at 560 nm.
+///
+///The temperature is clamped to the defined range, from 4000 K to 25000 K.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Daylight<T> {
+    ///The correlated color temperature of the daylight, in kelvin.
+    pub temperature: T,
+}
+
+impl<T> Daylight<T> {
+    ///Create the spectrum of daylight at `temperature` kelvin.
+    pub fn new(temperature: T) -> Daylight<T> {
+        Daylight {
+            temperature: temperature,
+        }
+    }
+}
+
+impl<T: Float> Spectrum<T> for Daylight<T> {
+    fn power(&self, wavelength: T) -> T {
+        let t: f64 = clamp(cast(self.temperature), 4000.0, 25000.0);
+        let x = if t <= 7000.0 {
+            -4.607e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244063
+        } else {
+            -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.23704
+        };
+        let y = -3.0 * x * x + 2.87 * x - 0.275;
+
+        let m = 0.0241 + 0.2562 * x - 0.7341 * y;
+        let m1 = (-1.3515 - 1.7703 * x + 5.9114 * y) / m;
+        let m2 = (0.03 - 31.4424 * x + 30.0717 * y) / m;
+
+        match table_position(cast(wavelength)) {
+            Some((index, factor)) => {
+                let (low, high) = (DAYLIGHT[index], DAYLIGHT[index + 1]);
+                let interpolate = |i: usize| low[i] + (high[i] - low[i]) * factor;
+                cast(interpolate(0) + m1 * interpolate(1) + m2 * interpolate(2))
+            }
+            None => T::zero(),
+        }
+    }
+}
+
+///A CIE standard colorimetric observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Observer {
+    ///The CIE 1931 2° standard observer, which is the one that XYZ and most
+    ///color spaces are based on.
+    Cie1931,
+    ///The CIE 1964 10° supplementary standard observer, for color matching
+    ///over larger fields of view.
+    Cie1964,
+}
+
+impl Observer {
+    ///Get the values of the color matching functions at the wavelength
+    ///`wavelength`, in nanometers. They are 0 outside of the tabulated range.
+    pub fn color_matching<T: Float>(self, wavelength: T) -> (T, T, T) {
+        let table = match self {
+            Observer::Cie1931 => &CIE_1931,
+            Observer::Cie1964 => &CIE_1964,
+        };
+
+        match table_position(cast(wavelength)) {
+            Some((index, factor)) => {
+                let (low, high) = (table[index], table[index + 1]);
+                let interpolate = |i: usize| cast(low[i] + (high[i] - low[i]) * factor);
+                (interpolate(0), interpolate(1), interpolate(2))
+            }
+            None => (T::zero(), T::zero(), T::zero()),
+        }
+    }
+}
+
+///Convert a spectral power distribution to XYZ, as seen by `observer`.
+///
+///The result is scaled so that the equal energy spectrum with a power of 1
+///has a luminance of 1. Divide by `y` to normalize the luminance of a light
+///source.
+pub fn spectrum_to_xyz<Wp, T, S>(spectrum: &S, observer: Observer) -> Xyz<Wp, T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    S: Spectrum<T> + ?Sized,
+{
+    let mut x = T::zero();
+    let mut y = T::zero();
+    let mut z = T::zero();
+    let mut y_sum = T::zero();
+
+    for wavelength in FIRST_WAVELENGTH..=LAST_WAVELENGTH {
+        let wavelength: T = cast(wavelength);
+        let power = spectrum.power(wavelength);
+        let (x_bar, y_bar, z_bar) = observer.color_matching(wavelength);
+
+        x = x + power * x_bar;
+        y = y + power * y_bar;
+        z = z + power * z_bar;
+        y_sum = y_sum + y_bar;
+    }
+
+    Xyz::with_wp(x / y_sum, y / y_sum, z / y_sum)
+}
+
+///Convert the reflectance spectrum of a surface, lit by `light`, to XYZ, as
+///seen by the observer of the white point `Wp`.
+///
+///The result is relative to a perfect white surface, which gets a luminance
+///of 1. Using a white point for the same illuminant as `light`, such as
+///[`D65Degree10`](../white_point/struct.D65Degree10.html) with
+///[`Illuminant::D65`](enum.Illuminant.html#variant.D65), makes the white
+///surface match the white point, as expected by the color spaces that are
+///relative to it.
+///
+///```
+///use palette::Lab;
+///use palette::spectral::{surface_to_xyz, Illuminant};
+///use palette::white_point::D65Degree10;
+///
+///let paint: &[(f64, f64)] = &[(380.0, 0.1), (550.0, 0.3), (780.0, 0.8)];
+///let color: Lab<D65Degree10, f64> = surface_to_xyz(paint, &Illuminant::D65).into();
+///assert!(color.a > 0.0);
+///```
+pub fn surface_to_xyz<Wp, T, R, S>(reflectance: &R, light: &S) -> Xyz<Wp, T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    R: Spectrum<T> + ?Sized,
+    S: Spectrum<T> + ?Sized,
+{
+    let observer = Wp::observer();
+    let white: Xyz<Wp, T> = spectrum_to_xyz(light, observer);
+    let color: Xyz<Wp, T> = spectrum_to_xyz(&Reflection::new(reflectance, light), observer);
+
+    color / white.y
+}
+
+const FIRST_WAVELENGTH: u32 = 380;
+const LAST_WAVELENGTH: u32 = 780;
+const INTERVAL: f64 = 10.0;
+const TABLE_LENGTH: usize = 41;
+
+///Find the index of the table row before `wavelength`, and how far it is
+///towards the next row.
+fn table_position(wavelength: f64) -> Option<(usize, f64)> {
+    let position = (wavelength - FIRST_WAVELENGTH as f64) / INTERVAL;
+    let last = (TABLE_LENGTH - 1) as f64;
+    if position < 0.0 || position > last {
+        return None;
+    }
+
+    let index = ::core::cmp::min(position as usize, TABLE_LENGTH - 2);
+    Some((index, position - index as f64))
+}
+
+///The CIE 1931 2° color matching functions, from 380 nm to 780 nm.
+const CIE_1931: [[f64; 3]; TABLE_LENGTH] = [
+    [0.001368, 0.000039, 0.006450],
+    [0.004243, 0.000120, 0.020050],
+    [0.014310, 0.000396, 0.067850],
+    [0.043510, 0.001210, 0.207400],
+    [0.134380, 0.004000, 0.645600],
+    [0.283900, 0.011600, 1.385600],
+    [0.348280, 0.023000, 1.747060],
+    [0.336200, 0.038000, 1.772110],
+    [0.290800, 0.060000, 1.669200],
+    [0.195360, 0.090980, 1.287640],
+    [0.095640, 0.139020, 0.812950],
+    [0.032010, 0.208020, 0.465180],
+    [0.004900, 0.323000, 0.272000],
+    [0.009300, 0.503000, 0.158200],
+    [0.063270, 0.710000, 0.078250],
+    [0.165500, 0.862000, 0.042160],
+    [0.290400, 0.954000, 0.020300],
+    [0.433450, 0.994950, 0.008750],
+    [0.594500, 0.995000, 0.003900],
+    [0.762100, 0.952000, 0.002100],
+    [0.916300, 0.870000, 0.001650],
+    [1.026300, 0.757000, 0.001100],
+    [1.062200, 0.631000, 0.000800],
+    [1.002600, 0.503000, 0.000340],
+    [0.854450, 0.381000, 0.000190],
+    [0.642400, 0.265000, 0.000050],
+    [0.447900, 0.175000, 0.000020],
+    [0.283500, 0.107000, 0.000000],
+    [0.164900, 0.061000, 0.000000],
+    [0.087400, 0.032000, 0.000000],
+    [0.046770, 0.017000, 0.000000],
+    [0.022700, 0.008210, 0.000000],
+    [0.011359, 0.004102, 0.000000],
+    [0.005790, 0.002091, 0.000000],
+    [0.002899, 0.001047, 0.000000],
+    [0.001440, 0.000520, 0.000000],
+    [0.000690, 0.000249, 0.000000],
+    [0.000332, 0.000120, 0.000000],
+    [0.000166, 0.000060, 0.000000],
+    [0.000083, 0.000030, 0.000000],
+    [0.000042, 0.000015, 0.000000],
+];
+
+///The CIE 1964 10° color matching functions, from 380 nm to 780 nm.
+const CIE_1964: [[f64; 3]; TABLE_LENGTH] = [
+    [0.000160, 0.000017, 0.000705],
+    [0.002362, 0.000253, 0.010482],
+    [0.019110, 0.002004, 0.086011],
+    [0.084736, 0.008756, 0.389366],
+    [0.204492, 0.021391, 0.972542],
+    [0.314679, 0.038676, 1.553480],
+    [0.383734, 0.062077, 1.967280],
+    [0.370702, 0.089456, 1.994800],
+    [0.302273, 0.128201, 1.745370],
+    [0.195618, 0.185190, 1.317560],
+    [0.080507, 0.253589, 0.772125],
+    [0.016172, 0.339133, 0.415254],
+    [0.003816, 0.460777, 0.218502],
+    [0.037465, 0.606741, 0.112044],
+    [0.117749, 0.761757, 0.060709],
+    [0.236491, 0.875211, 0.030451],
+    [0.376772, 0.961988, 0.013676],
+    [0.529826, 0.991761, 0.003988],
+    [0.705224, 0.997340, 0.000000],
+    [0.878655, 0.955552, 0.000000],
+    [1.014160, 0.868934, 0.000000],
+    [1.118520, 0.777405, 0.000000],
+    [1.123990, 0.658341, 0.000000],
+    [1.030480, 0.527963, 0.000000],
+    [0.856297, 0.398057, 0.000000],
+    [0.647467, 0.283493, 0.000000],
+    [0.431567, 0.179828, 0.000000],
+    [0.268329, 0.107633, 0.000000],
+    [0.152568, 0.060281, 0.000000],
+    [0.081261, 0.031800, 0.000000],
+    [0.040851, 0.015905, 0.000000],
+    [0.019941, 0.007749, 0.000000],
+    [0.009577, 0.003718, 0.000000],
+    [0.004553, 0.001768, 0.000000],
+    [0.002175, 0.000846, 0.000000],
+    [0.001045, 0.000407, 0.000000],
+    [0.000508, 0.000199, 0.000000],
+    [0.000251, 0.000098, 0.000000],
+    [0.000126, 0.000050, 0.000000],
+    [0.000065, 0.000025, 0.000000],
+    [0.000033, 0.000013, 0.000000],
+];
+
+///The relative spectral power of illuminant D65, from 380 nm to 780 nm.
+const D65: [f64; TABLE_LENGTH] = [
+    49.9755, 54.6482, 82.7549, 91.486, 93.4318, 86.6823, 104.865, 117.008, 117.812, 114.861,
+    115.923, 108.811, 109.354, 107.802, 104.79, 107.689, 104.405, 104.046, 100.0, 96.3342, 95.788,
+    88.6856, 90.0062, 89.5991, 87.6987, 83.2886, 83.6992, 80.0268, 80.2146, 82.2778, 78.2842,
+    69.7213, 71.6091, 74.349, 61.604, 69.8856, 75.087, 63.5927, 46.4182, 66.8054, 63.3828,
+];
+
+///The S0, S1 and S2 components of CIE daylight, from 380 nm to 780 nm.
+const DAYLIGHT: [[f64; 3]; TABLE_LENGTH] = [
+    [63.4, 38.5, 3.0],
+    [65.8, 35.0, 1.2],
+    [94.8, 43.4, -1.1],
+    [104.8, 46.3, -0.5],
+    [105.9, 43.9, -0.7],
+    [96.8, 37.1, -1.2],
+    [113.9, 36.7, -2.6],
+    [125.6, 35.9, -2.9],
+    [125.5, 32.6, -2.8],
+    [121.3, 27.9, -2.6],
+    [121.3, 24.3, -2.6],
+    [113.5, 20.1, -1.8],
+    [113.1, 16.2, -1.5],
+    [110.8, 13.2, -1.3],
+    [106.5, 8.6, -1.2],
+    [108.8, 6.1, -1.0],
+    [105.3, 4.2, -0.5],
+    [104.4, 1.9, -0.3],
+    [100.0, 0.0, 0.0],
+    [96.0, -1.6, 0.2],
+    [95.1, -3.5, 0.5],
+    [89.1, -3.5, 2.1],
+    [90.5, -5.8, 3.2],
+    [90.3, -7.2, 4.1],
+    [88.4, -8.6, 4.7],
+    [84.0, -9.5, 5.1],
+    [85.1, -10.9, 6.7],
+    [81.9, -10.7, 7.3],
+    [82.6, -12.0, 8.6],
+    [84.9, -14.0, 9.8],
+    [81.3, -13.6, 10.2],
+    [71.9, -12.0, 8.3],
+    [74.3, -13.3, 9.6],
+    [76.4, -12.9, 8.5],
+    [63.3, -10.6, 7.0],
+    [71.7, -11.6, 7.6],
+    [77.0, -12.2, 8.0],
+    [65.2, -10.2, 6.7],
+    [47.7, -7.8, 5.2],
+    [68.6, -11.2, 7.4],
+    [65.0, -10.4, 6.8],
+];
+
+///Spectra that are shared by the tests of the color rendering metrics.
+#[cfg(test)]
+pub mod fixtures {
+    use super::Spectrum;
+
+    ///Smooth reflectances of a few different hues.
+    pub const SAMPLES: [&[(f64, f64)]; 6] = [
+        &[(380.0, 0.2), (580.0, 0.2), (780.0, 0.6)],
+        &[(380.0, 0.1), (520.0, 0.5), (620.0, 0.1), (780.0, 0.1)],
+        &[(380.0, 0.6), (480.0, 0.4), (580.0, 0.2), (780.0, 0.2)],
+        &[(380.0, 0.1), (560.0, 0.6), (780.0, 0.7)],
+        &[
+            (380.0, 0.5),
+            (450.0, 0.5),
+            (550.0, 0.1),
+            (650.0, 0.5),
+            (780.0, 0.5),
+        ],
+        &[(380.0, 0.1), (490.0, 0.5), (560.0, 0.2), (780.0, 0.1)],
+    ];
+
+    ///Three narrow peaks, like a cheap white LED.
+    pub struct Peaks;
+
+    impl Spectrum<f64> for Peaks {
+        fn power(&self, wavelength: f64) -> f64 {
+            [(450.0, 0.9), (540.0, 1.0), (610.0, 1.1)]
+                .iter()
+                .map(|&(peak, height)| height * (-((wavelength - peak) / 10.0).powi(2)).exp())
+                .sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        spectrum_to_xyz, surface_to_xyz, Daylight, Illuminant, Observer, Planckian, Spectrum,
+    };
+    use white_point::{ADegree10, WhitePoint, D65, D65Degree10};
+    use {Xyz, Yxy};
+
+    fn chromaticity<S: Spectrum<f64>>(spectrum: &S, observer: Observer) -> (f64, f64) {
+        let xyz: Xyz<D65, f64> = spectrum_to_xyz(spectrum, observer);
+        let yxy = Yxy::from(xyz);
+        (yxy.x, yxy.y)
+    }
+
+    fn assert_chromaticity(result: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (result.0 - expected.0).abs() < 3e-4 && (result.1 - expected.1).abs() < 3e-4,
+            "{:?} should be {:?}",
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    fn illuminants() {
+        let cie1931 = Observer::Cie1931;
+        assert_chromaticity(chromaticity(&Illuminant::A, cie1931), (0.44757, 0.40745));
+        assert_chromaticity(chromaticity(&Illuminant::D65, cie1931), (0.31271, 0.32902));
+        assert_chromaticity(
+            chromaticity(&Illuminant::E, cie1931),
+            (1.0 / 3.0, 1.0 / 3.0),
+        );
+
+        let cie1964 = Observer::Cie1964;
+        assert_chromaticity(chromaticity(&Illuminant::A, cie1964), (0.45117, 0.40594));
+        assert_chromaticity(chromaticity(&Illuminant::D65, cie1964), (0.31382, 0.33100));
+        assert_chromaticity(
+            chromaticity(&Illuminant::E, cie1964),
+            (1.0 / 3.0, 1.0 / 3.0),
+        );
+    }
+
+    #[test]
+    fn temperatures() {
+        let d65 = Daylight::new(6504.0);
+        for wavelength in (380..781).map(f64::from) {
+            assert!((d65.power(wavelength) - Illuminant::D65.power(wavelength)).abs() < 0.05);
+        }
+
+        let cie1931 = Observer::Cie1931;
+        assert_chromaticity(
+            chromaticity(&Daylight::new(5003.0), cie1931),
+            (0.34567, 0.35850),
+        );
+        assert_chromaticity(
+            chromaticity(&Planckian::new(2856.0), cie1931),
+            (0.44757, 0.40745),
+        );
+        assert_eq!(Planckian::new(3000.0).power(560.0), 100.0);
+    }
+
+    #[test]
+    fn equal_energy() {
+        let flat: &[(f64, f64)] = &[(300.0, 1.0), (900.0, 1.0)];
+        let xyz: Xyz<D65, f64> = spectrum_to_xyz(flat, Observer::Cie1931);
+        assert!((xyz.y - 1.0).abs() < 1e-12);
+        assert!((xyz.x - 1.0).abs() < 2e-3);
+        assert!((xyz.z - 1.0).abs() < 2e-3);
+    }
+
+    #[test]
+    fn samples() {
+        let samples: &[(f32, f32)] = &[(400.0, 1.0), (500.0, 3.0), (600.0, 2.0)];
+        assert_eq!(samples.power(399.0), 0.0);
+        assert_eq!(samples.power(400.0), 1.0);
+        assert_eq!(samples.power(450.0), 2.0);
+        assert_eq!(samples.power(575.0), 2.25);
+        assert_eq!(samples.power(600.0), 2.0);
+        assert_eq!(samples.power(601.0), 0.0);
+    }
+
+    #[test]
+    fn monochromatic() {
+        let (x, y, z) = Observer::Cie1931.color_matching(555.0f64);
+        assert!((x - 0.512).abs() < 3e-3 && (y - 0.995).abs() < 1e-3 && z < 0.01);
+        assert_eq!(Observer::Cie1964.color_matching(800.0), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn matched_white_points() {
+        assert_eq!(D65::observer(), Observer::Cie1931);
+        assert_eq!(D65Degree10::observer(), Observer::Cie1964);
+
+        let white: &[(f64, f64)] = &[(300.0, 1.0), (900.0, 1.0)];
+        let d65: Xyz<D65Degree10, f64> = surface_to_xyz(white, &Illuminant::D65);
+        assert_relative_eq!(d65, D65Degree10::get_xyz(), epsilon = 1e-3);
+
+        let a: Xyz<ADegree10, f64> = surface_to_xyz(white, &Illuminant::A);
+        assert_relative_eq!(a, ADegree10::get_xyz(), epsilon = 1e-3);
+
+        //The 10 nm tables are a bit less accurate for the 2° observer
+        let d65: Xyz<D65, f64> = surface_to_xyz(white, &Illuminant::D65);
+        assert_relative_eq!(d65, D65::get_xyz(), epsilon = 3e-3);
+    }
+}
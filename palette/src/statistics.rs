@@ -0,0 +1,296 @@
+//!Per-channel statistics of collections of colors.
+//!
+//!The colors are converted one at a time to an analysis color space, such as
+//!`Lab`, and the statistics are updated as they come, so the converted colors
+//!are never stored. [`Statistics`](struct.Statistics.html) keeps track of
+//!the mean, variance and range of each channel, and
+//![`histogram`](fn.histogram.html) counts the values of a single channel.
+//!
+//!Hues are treated as plain numbers of degrees, so their means and variances
+//!are not meaningful across the 0° boundary. Use a space like `Lab` instead
+//!of `Lch` when the hues are needed.
+//!
+//!```
+//!use palette::{Lab, Srgb};
+//!use palette::statistics::{histogram, Statistics};
+//!
+//!let image = [
+//!    Srgb::new(0.9f32, 0.2, 0.1),
+//!    Srgb::new(0.8, 0.3, 0.1),
+//!    Srgb::new(0.1, 0.2, 0.9),
+//!];
+//!
+//!let statistics: Statistics<Lab, f32> = image.iter().cloned().collect();
+//!let mean = statistics.mean().unwrap();
+//!assert!(mean.a > 0.0);
+//!
+//!//Count the lightness values in four bins
+//!let mut bins = [0; 4];
+//!histogram::<Lab, _, _>(image.iter().cloned(), 0, 0.0, 100.0, &mut bins);
+//!assert_eq!(bins, [0, 2, 1, 0]);
+//!```
+
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+
+use float::Float;
+
+use {cast, Pixel};
+
+const MAX_CHANNELS: usize = 4;
+
+///Running statistics of the channels of colors, in the color space `A`.
+///
+///Colors of any type that `A` can be converted from can be added, one at a
+///time with `add`, or from an iterator with `extend` or `collect`. The
+///variance is updated with Welford's algorithm, which stays accurate for
+///long streams of colors.
+#[derive(Clone, Debug)]
+pub struct Statistics<A, T> {
+    count: usize,
+    mean: [T; MAX_CHANNELS],
+    squared_deviations: [T; MAX_CHANNELS],
+    min: [T; MAX_CHANNELS],
+    max: [T; MAX_CHANNELS],
+    color: PhantomData<A>,
+}
+
+impl<A, T> Statistics<A, T>
+where
+    A: Pixel<T> + Copy,
+    T: Float,
+{
+    ///Create empty statistics.
+    ///
+    ///Panics if `A` has more than four channels.
+    pub fn new() -> Statistics<A, T> {
+        assert!(
+            A::CHANNELS <= MAX_CHANNELS,
+            "the color type has too many channels"
+        );
+
+        Statistics {
+            count: 0,
+            mean: [T::zero(); MAX_CHANNELS],
+            squared_deviations: [T::zero(); MAX_CHANNELS],
+            min: [T::infinity(); MAX_CHANNELS],
+            max: [T::neg_infinity(); MAX_CHANNELS],
+            color: PhantomData,
+        }
+    }
+
+    ///Convert `color` to `A` and add it to the statistics.
+    pub fn add<C>(&mut self, color: C)
+    where
+        A: From<C>,
+    {
+        let color = A::from(color);
+        let channels: &[T] = color.as_raw();
+
+        self.count += 1;
+        let count: T = cast(self.count);
+        for (i, &value) in channels.iter().enumerate() {
+            let delta = value - self.mean[i];
+            self.mean[i] = self.mean[i] + delta / count;
+            self.squared_deviations[i] =
+                self.squared_deviations[i] + delta * (value - self.mean[i]);
+            self.min[i] = self.min[i].min(value);
+            self.max[i] = self.max[i].max(value);
+        }
+    }
+
+    ///The number of colors that have been added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///The mean of each channel, or `None` if no colors have been added.
+    pub fn mean(&self) -> Option<A> {
+        self.get(&self.mean)
+    }
+
+    ///The population variance of each channel, or `None` if no colors have
+    ///been added.
+    pub fn variance(&self) -> Option<A> {
+        let count: T = cast(self.count);
+        let mut variance = self.squared_deviations;
+        for value in &mut variance {
+            *value = *value / count;
+        }
+
+        self.get(&variance)
+    }
+
+    ///The standard deviation of each channel, or `None` if no colors have
+    ///been added.
+    pub fn standard_deviation(&self) -> Option<A> {
+        self.variance().map(|mut variance| {
+            for value in variance.as_raw_mut::<[T]>() {
+                *value = value.sqrt();
+            }
+            variance
+        })
+    }
+
+    ///The lowest value of each channel, or `None` if no colors have been
+    ///added.
+    pub fn min(&self) -> Option<A> {
+        self.get(&self.min)
+    }
+
+    ///The highest value of each channel, or `None` if no colors have been
+    ///added.
+    pub fn max(&self) -> Option<A> {
+        self.get(&self.max)
+    }
+
+    fn get(&self, channels: &[T; MAX_CHANNELS]) -> Option<A> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(*A::from_raw(&channels[..]))
+        }
+    }
+}
+
+impl<A, T> Default for Statistics<A, T>
+where
+    A: Pixel<T> + Copy,
+    T: Float,
+{
+    fn default() -> Statistics<A, T> {
+        Statistics::new()
+    }
+}
+
+impl<A, T, C> Extend<C> for Statistics<A, T>
+where
+    A: Pixel<T> + Copy + From<C>,
+    T: Float,
+{
+    fn extend<I: IntoIterator<Item = C>>(&mut self, colors: I) {
+        for color in colors {
+            self.add(color);
+        }
+    }
+}
+
+impl<A, T, C> FromIterator<C> for Statistics<A, T>
+where
+    A: Pixel<T> + Copy + From<C>,
+    T: Float,
+{
+    fn from_iter<I: IntoIterator<Item = C>>(colors: I) -> Statistics<A, T> {
+        let mut statistics = Statistics::new();
+        statistics.extend(colors);
+        statistics
+    }
+}
+
+///Count the values of the channel with the index `channel` of `colors`,
+///after converting them to `A`, in equally wide bins from `min` to `max`.
+///
+///The counts are added to `bins`, so a histogram can be built from multiple
+///collections of colors. Values outside of the range, and NaN, are not
+///counted, and `max` is counted in the last bin. The channel index can be
+///found by name with [`Channels`](../trait.Channels.html).
+///
+///Panics if `channel` is not a channel of `A`.
+pub fn histogram<A, T, I>(colors: I, channel: usize, min: T, max: T, bins: &mut [usize])
+where
+    I: IntoIterator,
+    A: Pixel<T> + From<I::Item>,
+    T: Float,
+{
+    assert!(channel < A::CHANNELS, "the channel index is out of bounds");
+    if bins.is_empty() {
+        return;
+    }
+
+    let last = bins.len() - 1;
+    let scale = cast::<T, _>(bins.len()) / (max - min);
+    for color in colors {
+        let value = A::from(color).as_raw::<[T]>()[channel];
+        if value >= min && value <= max {
+            let index: usize = cast((value - min) * scale);
+            bins[::core::cmp::min(index, last)] += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{histogram, Statistics};
+    use encoding::Srgb;
+    use white_point::D65;
+    use {Hsv, Lab};
+
+    #[test]
+    fn mean_and_variance() {
+        let colors = [
+            Hsv::new(10.0, 0.5, 0.2),
+            Hsv::new(20.0, 0.5, 0.4),
+            Hsv::new(60.0, 0.5, 0.9),
+        ];
+
+        let statistics: Statistics<Hsv<Srgb, f64>, f64> = colors.iter().cloned().collect();
+        assert_eq!(statistics.count(), 3);
+
+        let mean = statistics.mean().unwrap();
+        assert_relative_eq!(mean, Hsv::new(30.0, 0.5, 0.5), epsilon = 1e-12);
+
+        let variance = statistics.variance().unwrap();
+        assert_relative_eq!(variance.saturation, 0.0);
+        assert_relative_eq!(variance.value, 0.26 / 3.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            statistics.standard_deviation().unwrap().value,
+            (0.26f64 / 3.0).sqrt(),
+            epsilon = 1e-12
+        );
+
+        assert_eq!(statistics.min().unwrap(), colors[0]);
+        assert_eq!(statistics.max().unwrap(), colors[2]);
+    }
+
+    #[test]
+    fn converted() {
+        let colors = [::Srgb::new(1.0f64, 0.5, 0.0), ::Srgb::new(0.0, 0.5, 1.0)];
+
+        let mut statistics = Statistics::<Lab<D65, f64>, f64>::new();
+        statistics.extend(colors.iter().cloned());
+
+        let first = Lab::from(colors[0]);
+        let second = Lab::from(colors[1]);
+        let mean = statistics.mean().unwrap();
+        assert_relative_eq!(mean.l, (first.l + second.l) / 2.0, epsilon = 1e-9);
+        assert_relative_eq!(mean.b, (first.b + second.b) / 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn empty() {
+        let statistics = Statistics::<Hsv, f32>::default();
+        assert_eq!(statistics.count(), 0);
+        assert_eq!(statistics.mean(), None);
+        assert_eq!(statistics.variance(), None);
+        assert_eq!(statistics.max(), None);
+    }
+
+    #[test]
+    fn histogram_bins() {
+        let values = [0.0, 0.1, 0.3, 0.5, 0.99, 1.0, 1.5, -0.1];
+        let colors = values.iter().map(|&value| Hsv::new(0.0, value, 0.0));
+
+        let mut bins = [0; 4];
+        histogram::<Hsv, _, _>(colors.clone(), 1, 0.0, 1.0, &mut bins);
+        assert_eq!(bins, [2, 1, 1, 2]);
+
+        histogram::<Hsv, _, _>(colors, 1, 0.0, 1.0, &mut bins);
+        assert_eq!(bins, [4, 2, 2, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the channel index is out of bounds")]
+    fn histogram_channel() {
+        histogram::<Hsv, f32, _>(Some(Hsv::default()), 3, 0.0, 1.0, &mut [0; 2]);
+    }
+}
@@ -0,0 +1,399 @@
+//!Correlated color temperature, for describing light sources.
+//!
+//!The color of a light source near white is commonly described by its
+//!correlated color temperature (CCT), which is the temperature of the black
+//!body radiator with the most similar chromaticity, and its Duv, which is the
+//!signed distance from the chromaticity to the Planckian locus in the CIE
+//!1960 UCS. A positive Duv is above the locus, towards green, and a negative
+//!Duv is below it, towards magenta.
+//!
+//!The Planckian locus is approximated with Krystek's rational functions, and
+//!the closest point on it is searched for in the same way as in Ohno's
+//!method. This is accurate to about a kelvin between 1000 K and 15000 K,
+//!which is the supported range. The CCT is only meaningful for
+//!chromaticities that are close to the locus, usually within a Duv of
+//!±0.05.
+//!
+//!The other way around, [`planckian`](fn.planckian.html) gives the
+//!chromaticity of a black body radiator and
+//![`blackbody`](fn.blackbody.html) gives its color in an RGB space, for
+//!rendering incandescent light sources.
+//![`from_temperature`](fn.from_temperature.html) can instead use a faster
+//!curve fit, when the color only has to look right, such as in a lighting
+//!UI.
+//!
+//!```
+//!use palette::Yxy;
+//!use palette::temperature::cct;
+//!
+//!let d65 = cct(Yxy::new(0.31271f64, 0.32902, 1.0));
+//!assert!((d65.temperature - 6504.0).abs() < 5.0);
+//!assert!((d65.duv - 0.0032).abs() < 1e-4);
+//!```
+
+use float::Float;
+
+use encoding::{self, Linear};
+use rgb::{Rgb, RgbSpace, RgbStandard};
+use white_point::WhitePoint;
+use {cast, clamp, ColorMatrix, Component, Xyz, Yxy};
+
+///The lowest supported temperature, in kelvin.
+pub const MIN_TEMPERATURE: f64 = 1000.0;
+
+///The highest supported temperature, in kelvin.
+pub const MAX_TEMPERATURE: f64 = 15000.0;
+
+///A correlated color temperature and its distance from the Planckian locus.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cct<T> {
+    ///The correlated color temperature, in kelvin.
+    pub temperature: T,
+
+    ///The signed distance from the Planckian locus, in the CIE 1960 UCS.
+    pub duv: T,
+}
+
+///Calculate the correlated color temperature and Duv of a color. The
+///luminance is ignored.
+///
+///Temperatures outside the supported range are clamped to it.
+pub fn cct<Wp, T>(color: Yxy<Wp, T>) -> Cct<T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    let denominator = color.y * cast(12.0) - color.x * cast(2.0) + cast(3.0);
+    cct_from_uv(
+        color.x * cast(4.0) / denominator,
+        color.y * cast(9.0) / denominator,
+    )
+}
+
+///Calculate the correlated color temperature and Duv of a chromaticity in
+///the CIE 1976 u′v′ coordinates.
+///
+///Temperatures outside the supported range are clamped to it.
+pub fn cct_from_uv<T: Float>(u: T, v: T) -> Cct<T> {
+    //The CIE 1960 v is 2/3 of the 1976 v′
+    let (u, v): (f64, f64) = (cast(u), cast::<f64, _>(v) / 1.5);
+    let distance = |mired: f64| {
+        let (locus_u, locus_v) = planckian_uv(1e6 / mired);
+        (u - locus_u).powi(2) + (v - locus_v).powi(2)
+    };
+
+    //The locus is searched in reciprocal temperature, where it's closer to
+    //evenly spaced. A coarse table finds the closest segment, which is then
+    //narrowed down with a golden section search.
+    const STEPS: usize = 64;
+    let min = 1e6 / MAX_TEMPERATURE;
+    let max = 1e6 / MIN_TEMPERATURE;
+    let step = (max - min) / STEPS as f64;
+
+    let closest = (0..=STEPS)
+        .map(|i| (i, distance(min + step * i as f64)))
+        .fold((0, f64::INFINITY), |closest, current| {
+            if current.1 < closest.1 {
+                current
+            } else {
+                closest
+            }
+        })
+        .0;
+
+    let mut low = min + step * closest.saturating_sub(1) as f64;
+    let mut high = min + step * ::core::cmp::min(closest + 1, STEPS) as f64;
+    let ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+    for _ in 0..40 {
+        let a = high - ratio * (high - low);
+        let b = low + ratio * (high - low);
+        if distance(a) < distance(b) {
+            high = b;
+        } else {
+            low = a;
+        }
+    }
+
+    let temperature = 2e6 / (low + high);
+    let (locus_u, locus_v) = planckian_uv(temperature);
+    let duv = ((u - locus_u).powi(2) + (v - locus_v).powi(2)).sqrt();
+
+    Cct {
+        temperature: cast(temperature),
+        duv: cast(if v < locus_v { -duv } else { duv }),
+    }
+}
+
+///Get the chromaticity of a black body radiator with the temperature
+///`temperature`, in kelvin. The luminance is 1.
+///
+///The temperature is clamped to the supported range.
+///
+///```
+///use palette::Yxy;
+///use palette::temperature::planckian;
+///
+///let a: Yxy = planckian(2856.0);
+///assert!((a.x - 0.4476).abs() < 1e-3);
+///assert!((a.y - 0.4074).abs() < 1e-3);
+///```
+pub fn planckian<Wp, T>(temperature: T) -> Yxy<Wp, T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    let temperature = clamp(cast(temperature), MIN_TEMPERATURE, MAX_TEMPERATURE);
+    let (u, v) = planckian_uv(temperature);
+    let denominator = u * 2.0 - v * 8.0 + 4.0;
+
+    Yxy::with_wp(
+        cast(u * 3.0 / denominator),
+        cast(v * 2.0 / denominator),
+        T::one(),
+    )
+}
+
+///Get the color of a black body radiator with the temperature
+///`temperature`, in kelvin, in the RGB standard `S`.
+///
+///The color is not chromatically adapted, so a temperature close to the
+///white point of `S` is close to white. Temperatures that are outside the
+///gamut of `S` are desaturated until they fit, and the color is scaled to
+///make its brightest component 1.
+///
+///```
+///use palette::Srgb;
+///use palette::temperature::blackbody;
+///
+///let candle: Srgb = blackbody(1900.0);
+///assert!(candle.red > 0.999);
+///assert!(candle.green > candle.blue);
+///```
+pub fn blackbody<S, T>(temperature: T) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let chromaticity: Yxy<<S::Space as RgbSpace>::WhitePoint, T> = planckian(temperature);
+    normalize(Rgb::<Linear<S::Space>, T>::from(Xyz::from(chromaticity)))
+}
+
+///How [`from_temperature`](fn.from_temperature.html) finds the color of a
+///temperature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Method {
+    ///Tanner Helland's curve fit of black body colors in sRGB. It's cheap,
+    ///but it's only accurate to a few percent, and it's white at about
+    ///6600 K instead of at the white point.
+    Fast,
+
+    ///The chromaticity on the Planckian locus, as in
+    ///[`blackbody`](fn.blackbody.html).
+    Locus,
+}
+
+///Get the color of light with the temperature `temperature`, in kelvin, in
+///the RGB standard `S`, using `method`.
+///
+///The color is scaled to make its brightest component 1, and the
+///temperature is clamped to the supported range. The fast method is fitted
+///to sRGB, so it's converted through XYZ for other standards, without
+///chromatic adaptation, which takes away most of its advantage.
+///
+///```
+///use palette::Srgb;
+///use palette::temperature::{from_temperature, Method};
+///
+///let warm: Srgb = from_temperature(2700.0, Method::Fast);
+///let accurate: Srgb = from_temperature(2700.0, Method::Locus);
+///assert!((warm.green - accurate.green).abs() < 0.05);
+///```
+pub fn from_temperature<S, T>(temperature: T, method: Method) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    match method {
+        Method::Fast => {
+            let srgb =
+                Rgb::<encoding::Srgb, T>::from_components(fast_srgb(temperature)).into_linear();
+            let matrix =
+                ColorMatrix::xyz_to_rgb::<S::Space>() * ColorMatrix::rgb_to_xyz::<encoding::Srgb>();
+            let [red, green, blue] = matrix.transform([srgb.red, srgb.green, srgb.blue]);
+            normalize(Rgb::<Linear<S::Space>, T>::new(red, green, blue))
+        }
+        Method::Locus => blackbody(temperature),
+    }
+}
+
+///Desaturate a linear color until it's inside the gamut, and scale it to
+///make its brightest component 1.
+fn normalize<S, T>(linear: Rgb<Linear<S::Space>, T>) -> Rgb<S, T>
+where
+    S: RgbStandard,
+    T: Component + Float,
+{
+    let min = linear.red.min(linear.green).min(linear.blue).min(T::zero());
+    let max = linear.red.max(linear.green).max(linear.blue) - min;
+
+    Rgb::from_linear(Rgb::new(
+        (linear.red - min) / max,
+        (linear.green - min) / max,
+        (linear.blue - min) / max,
+    ))
+}
+
+///Tanner Helland's fit of the sRGB color of a black body radiator, with the
+///components from 0 to 1.
+fn fast_srgb<T: Component + Float>(temperature: T) -> (T, T, T) {
+    let t = clamp(
+        cast::<f64, _>(temperature),
+        MIN_TEMPERATURE,
+        MAX_TEMPERATURE,
+    ) / 100.0;
+
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.698_727_446 * (t - 60.0).powf(-0.133_204_759_2)
+    };
+    let green = if t <= 66.0 {
+        99.470_802_586_1 * t.ln() - 161.119_568_166_1
+    } else {
+        288.122_169_528_3 * (t - 60.0).powf(-0.075_514_849_2)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_731_223_1 * (t - 10.0).ln() - 305.044_792_730_7
+    };
+
+    let component = |value: f64| cast(clamp(value, 0.0, 255.0) / 255.0);
+    (component(red), component(green), component(blue))
+}
+
+///The point on the Planckian locus with the temperature `temperature`, in
+///the CIE 1960 UCS, using Krystek's approximation.
+fn planckian_uv(temperature: f64) -> (f64, f64) {
+    let t = temperature;
+    let t2 = t * t;
+
+    let u = (0.860_117_757 + 1.541_182_54e-4 * t + 1.286_412_12e-7 * t2)
+        / (1.0 + 8.424_202_35e-4 * t + 7.081_451_63e-7 * t2);
+    let v = (0.317_398_726 + 4.228_062_45e-5 * t + 4.204_816_91e-8 * t2)
+        / (1.0 - 2.897_418_16e-5 * t + 1.614_560_53e-7 * t2);
+
+    (u, v)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{blackbody, cct, cct_from_uv, from_temperature, planckian, planckian_uv, Method};
+    use white_point::D65;
+    use {LinSrgb, Srgb, Yxy};
+
+    #[test]
+    fn standard_illuminants() {
+        let d65 = cct(Yxy::new(0.31271f64, 0.32902, 1.0));
+        assert!((d65.temperature - 6504.0).abs() < 5.0);
+        assert!((d65.duv - 0.0032).abs() < 1e-4);
+
+        let d50 = cct(Yxy::new(0.34567f64, 0.35850, 0.5));
+        assert!((d50.temperature - 5003.0).abs() < 5.0);
+        assert!((d50.duv - 0.0033).abs() < 1e-4);
+
+        let a = cct(Yxy::new(0.44757f32, 0.40745, 1.0));
+        assert!((a.temperature - 2856.0).abs() < 5.0);
+        assert!(a.duv.abs() < 2e-4);
+    }
+
+    #[test]
+    fn on_the_locus() {
+        for &temperature in &[1500.0, 2700.0, 4000.0, 6500.0, 10000.0] {
+            let (u, v) = planckian_uv(temperature);
+            let result = cct_from_uv(u, v * 1.5);
+            assert!((result.temperature - temperature).abs() < 0.01);
+            assert!(result.duv.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn sign_of_duv() {
+        let (u, v) = planckian_uv(3000.0);
+        let green = cct_from_uv(u, (v + 0.01) * 1.5);
+        let magenta = cct_from_uv(u, (v - 0.01) * 1.5);
+
+        assert!((green.duv - 0.01).abs() < 1e-3);
+        assert!((magenta.duv + 0.01).abs() < 1e-3);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let (u, v) = planckian_uv(15000.0);
+        let result = cct_from_uv(u - 0.01, v * 1.5);
+        assert!((result.temperature - 15000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn planckian_round_trip() {
+        for &temperature in &[1000.0, 2000.0, 3500.0, 5000.0, 9000.0, 15000.0] {
+            let chromaticity: Yxy<D65, f64> = planckian(temperature);
+            let result = cct(chromaticity);
+            assert!((result.temperature - temperature).abs() < 0.01);
+            assert!(result.duv.abs() < 1e-9);
+        }
+
+        let clamped: Yxy<D65, f64> = planckian(100_000.0);
+        assert_eq!(clamped, planckian(15000.0));
+    }
+
+    #[test]
+    fn blackbody_colors() {
+        let warm: LinSrgb<f64> = blackbody(2700.0);
+        assert_eq!(warm.red, 1.0);
+        assert!(warm.red > warm.green && warm.green > warm.blue);
+
+        let cold: LinSrgb<f64> = blackbody(10000.0);
+        assert_eq!(cold.blue, 1.0);
+        assert!(cold.blue > cold.green && cold.green > cold.red);
+
+        let white: LinSrgb<f64> = blackbody(6504.0);
+        assert!(white.red > 0.9 && white.green > 0.9 && white.blue > 0.9);
+
+        let deep: LinSrgb<f64> = blackbody(1000.0);
+        assert_eq!(deep.blue, 0.0);
+    }
+
+    #[test]
+    fn fast_colors() {
+        let white: Srgb<f64> = from_temperature(6600.0, Method::Fast);
+        assert_relative_eq!(white, Srgb::new(1.0, 1.0, 1.0), epsilon = 1e-9);
+
+        let candle: Srgb<f64> = from_temperature(1900.0, Method::Fast);
+        assert_relative_eq!(candle.red, 1.0, epsilon = 1e-9);
+        assert_relative_eq!(candle.blue, 0.0, epsilon = 1e-9);
+
+        let sky: Srgb<f64> = from_temperature(12000.0, Method::Fast);
+        assert_relative_eq!(sky.blue, 1.0, epsilon = 1e-9);
+        assert!(sky.blue > sky.green && sky.green > sky.red);
+    }
+
+    #[test]
+    fn fast_and_locus() {
+        for &temperature in &[1500.0, 2700.0, 4000.0, 5500.0, 8000.0, 12000.0] {
+            let fast: Srgb<f64> = from_temperature(temperature, Method::Fast);
+            let locus: Srgb<f64> = from_temperature(temperature, Method::Locus);
+            assert_eq!(locus, blackbody(temperature));
+            assert_relative_eq!(fast, locus, epsilon = 0.1);
+        }
+    }
+
+    #[test]
+    fn fast_in_other_spaces() {
+        let srgb: Srgb<f64> = from_temperature(3000.0, Method::Fast);
+        let linear: LinSrgb<f64> = from_temperature(3000.0, Method::Fast);
+        assert_relative_eq!(srgb.into_linear(), linear, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,424 @@
+//!The IES TM-30 fidelity and gamut indices of light sources.
+//!
+//!TM-30 is a more accurate alternative to the [color rendering
+//!index](../cri/index.html). The colors of a set of samples under the test
+//!light source are compared to their colors under a reference illuminant with
+//!the same correlated color temperature, in the CAM02-UCS color space with
+//!the CIE 1964 10° observer. The fidelity index `Rf` describes how close the
+//!colors are, from 0 to 100, and the gamut index `Rg` describes whether the
+//!test light source makes the colors more or less saturated, with 100 being
+//!the same as the reference. The reference is a black body radiator below
+//!4000 K, CIE daylight above 5000 K, and a mix of both in between.
+//!
+//!The calculation follows TM-30-20. The samples are given as reflectance
+//!spectra, and the indices are only comparable to other TM-30 results if the
+//!99 color evaluation samples (CES) of the standard are used. Their
+//!reflectances are not included, and can be taken from the data that is
+//!published with TM-30.
+//!
+//!```
+//!use palette::spectral::Daylight;
+//!use palette::tm30::Tm30;
+//!
+//!let red: &[(f64, f64)] = &[(380.0, 0.2), (580.0, 0.2), (780.0, 0.6)];
+//!let green: &[(f64, f64)] = &[(380.0, 0.1), (520.0, 0.5), (620.0, 0.1), (780.0, 0.1)];
+//!let blue: &[(f64, f64)] = &[(380.0, 0.6), (480.0, 0.4), (580.0, 0.2), (780.0, 0.2)];
+//!let samples = [red, green, blue];
+//!
+//!let daylight = Daylight::new(6500.0f64);
+//!let tm30 = Tm30::new(&daylight);
+//!assert!(tm30.fidelity_index(&samples) > 99.9);
+//!assert!((tm30.gamut_index(&samples) - 100.0).abs() < 0.1);
+//!```
+
+use core::f64::consts::PI;
+
+use float::Float;
+
+use matrix::{multiply_3x3, Mat3};
+use spectral::{spectrum_to_xyz, Daylight, Observer, Planckian, Reflection, Spectrum};
+use temperature::{cct, Cct};
+use white_point::E;
+use {cast, clamp, Component, Xyz, Yxy};
+
+const HUE_BINS: usize = 16;
+
+///The TM-30 color rendering of a light source, compared to its reference
+///illuminant.
+pub struct Tm30<'a, T, S: 'a + ?Sized> {
+    test: &'a S,
+    reference: Reference<T>,
+    cct: Cct<T>,
+    test_luminance: f64,
+    reference_luminance: f64,
+    test_appearance: Cam02,
+    reference_appearance: Cam02,
+}
+
+impl<'a, T, S> Tm30<'a, T, S>
+where
+    T: Component + Float,
+    S: Spectrum<T> + ?Sized,
+{
+    ///Prepare the TM-30 calculations for the light source with the spectrum
+    ///`test`.
+    pub fn new(test: &'a S) -> Tm30<'a, T, S> {
+        let chromaticity: Xyz<E, T> = spectrum_to_xyz(test, Observer::Cie1931);
+        let cct = cct(Yxy::from(chromaticity));
+        let temperature: f64 = cast(cct.temperature);
+
+        //The reference spectra are mixed after normalizing their luminance
+        let daylight_fraction = clamp((temperature - 4000.0) / 1000.0, 0.0, 1.0);
+        let planckian = Planckian::new(cct.temperature);
+        let daylight = Daylight::new(cct.temperature);
+        let reference = Reference {
+            planckian: planckian,
+            daylight: daylight,
+            planckian_weight: cast((1.0 - daylight_fraction) / luminance(&planckian)),
+            daylight_weight: cast(daylight_fraction / luminance(&daylight)),
+        };
+
+        let test_white = xyz_10(test);
+        let reference_white = xyz_10(&reference);
+        let test_luminance = test_white[1];
+        let reference_luminance = reference_white[1];
+
+        Tm30 {
+            test: test,
+            reference: reference,
+            cct: cct,
+            test_luminance: test_luminance,
+            reference_luminance: reference_luminance,
+            test_appearance: Cam02::new(scale(test_white, test_luminance), 100.0, 20.0, 1.0),
+            reference_appearance: Cam02::new(
+                scale(reference_white, reference_luminance),
+                100.0,
+                20.0,
+                1.0,
+            ),
+        }
+    }
+
+    ///Get the correlated color temperature and Duv of the light source.
+    pub fn cct(&self) -> Cct<T> {
+        self.cct
+    }
+
+    ///Calculate the fidelity index `Rf` of the light source, over all of
+    ///`samples`.
+    ///
+    ///# Panics
+    ///
+    ///Panics if `samples` is empty.
+    pub fn fidelity_index<R: Spectrum<T>>(&self, samples: &[R]) -> T {
+        assert!(!samples.is_empty(), "there must be at least one sample");
+
+        let sum = samples.iter().fold(0.0, |sum, sample| {
+            let (test, reference) = self.sample_coordinates(sample);
+            sum + distance(test, reference)
+        });
+
+        cast(fidelity(sum / samples.len() as f64))
+    }
+
+    ///Calculate the fidelity index of the light source for a single sample.
+    pub fn sample_fidelity_index<R: Spectrum<T> + ?Sized>(&self, sample: &R) -> T {
+        let (test, reference) = self.sample_coordinates(sample);
+        cast(fidelity(distance(test, reference)))
+    }
+
+    ///Calculate the gamut index `Rg` of the light source, over all of
+    ///`samples`.
+    ///
+    ///The samples are grouped into 16 bins by their hue under the reference
+    ///illuminant, and the index is the ratio between the areas of the
+    ///polygons that the average colors of the bins form under the test and
+    ///the reference light sources. Empty bins are left out of the polygons.
+    ///
+    ///# Panics
+    ///
+    ///Panics if `samples` is empty.
+    pub fn gamut_index<R: Spectrum<T>>(&self, samples: &[R]) -> T {
+        assert!(!samples.is_empty(), "there must be at least one sample");
+
+        let mut bins = [([0.0; 2], [0.0; 2], 0usize); HUE_BINS];
+
+        for sample in samples {
+            let (test, reference) = self.sample_coordinates(sample);
+            let hue = reference[2].atan2(reference[1]);
+            let hue = if hue < 0.0 { hue + 2.0 * PI } else { hue };
+            let index =
+                ::core::cmp::min((hue / (2.0 * PI) * HUE_BINS as f64) as usize, HUE_BINS - 1);
+
+            let bin = &mut bins[index];
+            bin.0[0] += test[1];
+            bin.0[1] += test[2];
+            bin.1[0] += reference[1];
+            bin.1[1] += reference[2];
+            bin.2 += 1;
+        }
+
+        let mut test_area = 0.0;
+        let mut reference_area = 0.0;
+        let mut filled = bins
+            .iter()
+            .filter(|bin| bin.2 > 0)
+            .map(|&(test, reference, count)| {
+                let count = count as f64;
+                (
+                    [test[0] / count, test[1] / count],
+                    [reference[0] / count, reference[1] / count],
+                )
+            });
+
+        if let Some(first) = filled.next() {
+            let mut previous = first;
+            for current in filled.chain(Some(first)) {
+                test_area += previous.0[0] * current.0[1] - current.0[0] * previous.0[1];
+                reference_area += previous.1[0] * current.1[1] - current.1[0] * previous.1[1];
+                previous = current;
+            }
+        }
+
+        cast(100.0 * test_area / reference_area)
+    }
+
+    ///The CAM02-UCS coordinates of `sample`, under the test light source and
+    ///the reference illuminant.
+    fn sample_coordinates<R: Spectrum<T> + ?Sized>(&self, sample: &R) -> ([f64; 3], [f64; 3]) {
+        let test = xyz_10(&Reflection::new(sample, self.test));
+        let reference = xyz_10(&Reflection::new(sample, &self.reference));
+
+        (
+            self.test_appearance.ucs(scale(test, self.test_luminance)),
+            self.reference_appearance
+                .ucs(scale(reference, self.reference_luminance)),
+        )
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Reference<T> {
+    planckian: Planckian<T>,
+    daylight: Daylight<T>,
+    planckian_weight: T,
+    daylight_weight: T,
+}
+
+impl<T: Float> Spectrum<T> for Reference<T> {
+    fn power(&self, wavelength: T) -> T {
+        self.planckian.power(wavelength) * self.planckian_weight
+            + self.daylight.power(wavelength) * self.daylight_weight
+    }
+}
+
+///The CIECAM02 color appearance model, for a white point and viewing
+///conditions with an average surround.
+struct Cam02 {
+    white: [f64; 3],
+    white_luminance: f64,
+    adaptation: f64,
+    luminance_factor: f64,
+    induction: f64,
+    exponent: f64,
+    white_achromatic: f64,
+    background: f64,
+}
+
+const CAT02: Mat3<f64> = [
+    0.7328, 0.4296, -0.1624, -0.7036, 1.6975, 0.0061, 0.0030, 0.0136, 0.9834,
+];
+
+const CAT02_INVERSE: Mat3<f64> = [
+    1.096124, -0.278869, 0.182745, 0.454369, 0.473533, 0.072098, -0.009628, -0.005698, 1.015326,
+];
+
+const HUNT_POINTER_ESTEVEZ: Mat3<f64> = [
+    0.38971, 0.68898, -0.07868, -0.22981, 1.18340, 0.04641, 0.0, 0.0, 1.0,
+];
+
+impl Cam02 {
+    ///Set up the model for the white point `white`, the adapting luminance
+    ///`adapting_luminance` in cd/m², the relative luminance of the background
+    ///`background` and the degree of adaptation `adaptation`.
+    fn new(white: [f64; 3], adapting_luminance: f64, background: f64, adaptation: f64) -> Cam02 {
+        let k = 1.0 / (5.0 * adapting_luminance + 1.0);
+        let k4 = k.powi(4);
+        let luminance_factor = 0.2 * k4 * 5.0 * adapting_luminance
+            + 0.1 * (1.0 - k4).powi(2) * (5.0 * adapting_luminance).cbrt();
+
+        let n = background / white[1];
+        let mut model = Cam02 {
+            white: multiply(&CAT02, white),
+            white_luminance: white[1],
+            adaptation: adaptation,
+            luminance_factor: luminance_factor,
+            induction: 0.725 * n.recip().powf(0.2),
+            exponent: 0.69 * (1.48 + n.sqrt()),
+            white_achromatic: 0.0,
+            background: n,
+        };
+
+        let white = model.compressed(white);
+        model.white_achromatic = model.achromatic(white);
+        model
+    }
+
+    ///The adapted and compressed cone responses of `xyz`.
+    fn compressed(&self, xyz: [f64; 3]) -> [f64; 3] {
+        let rgb = multiply(&CAT02, xyz);
+        let mut adapted = [0.0; 3];
+        for i in 0..3 {
+            let factor =
+                self.adaptation * self.white_luminance / self.white[i] + 1.0 - self.adaptation;
+            adapted[i] = rgb[i] * factor;
+        }
+
+        let cone = multiply(
+            &multiply_3x3(&HUNT_POINTER_ESTEVEZ, &CAT02_INVERSE),
+            adapted,
+        );
+        let mut compressed = [0.0; 3];
+        for i in 0..3 {
+            let scaled = (self.luminance_factor * cone[i].abs() / 100.0).powf(0.42);
+            let response = 400.0 * scaled / (scaled + 27.13);
+            compressed[i] = if cone[i] < 0.0 { -response } else { response } + 0.1;
+        }
+
+        compressed
+    }
+
+    fn achromatic(&self, compressed: [f64; 3]) -> f64 {
+        (2.0 * compressed[0] + compressed[1] + compressed[2] / 20.0 - 0.305) * self.induction
+    }
+
+    ///The lightness `J`, chroma `C` and hue angle `h`, in radians, of `xyz`.
+    fn jch(&self, xyz: [f64; 3]) -> [f64; 3] {
+        let [r, g, b] = self.compressed(xyz);
+        let a = r - 12.0 * g / 11.0 + b / 11.0;
+        let b_ = (r + g - 2.0 * b) / 9.0;
+        let hue = b_.atan2(a);
+
+        let eccentricity = 0.25 * ((hue + 2.0).cos() + 3.8);
+        let lightness =
+            100.0 * (self.achromatic([r, g, b]) / self.white_achromatic).powf(self.exponent);
+        let t = (50000.0 / 13.0 * self.induction * eccentricity * (a * a + b_ * b_).sqrt())
+            / (r + g + 21.0 / 20.0 * b);
+        let chroma = t.powf(0.9)
+            * (lightness / 100.0).sqrt()
+            * (1.64 - 0.29f64.powf(self.background)).powf(0.73);
+
+        [lightness, chroma, hue]
+    }
+
+    ///The CAM02-UCS coordinates `J'`, `a'` and `b'` of `xyz`.
+    fn ucs(&self, xyz: [f64; 3]) -> [f64; 3] {
+        let [lightness, chroma, hue] = self.jch(xyz);
+        let colorfulness = chroma * self.luminance_factor.powf(0.25);
+        let m = (1.0 + 0.0228 * colorfulness).ln() / 0.0228;
+
+        [
+            1.7 * lightness / (1.0 + 0.007 * lightness),
+            m * hue.cos(),
+            m * hue.sin(),
+        ]
+    }
+}
+
+fn multiply(matrix: &Mat3<f64>, vector: [f64; 3]) -> [f64; 3] {
+    [
+        matrix[0] * vector[0] + matrix[1] * vector[1] + matrix[2] * vector[2],
+        matrix[3] * vector[0] + matrix[4] * vector[1] + matrix[5] * vector[2],
+        matrix[6] * vector[0] + matrix[7] * vector[1] + matrix[8] * vector[2],
+    ]
+}
+
+fn xyz_10<T, S>(spectrum: &S) -> [f64; 3]
+where
+    T: Component + Float,
+    S: Spectrum<T> + ?Sized,
+{
+    let xyz: Xyz<E, T> = spectrum_to_xyz(spectrum, Observer::Cie1964);
+    [cast(xyz.x), cast(xyz.y), cast(xyz.z)]
+}
+
+fn luminance<T: Component + Float, S: Spectrum<T>>(spectrum: &S) -> f64 {
+    xyz_10(spectrum)[1]
+}
+
+///Scale `xyz` to a light source with the luminance `luminance` having a
+///luminance of 100.
+fn scale(xyz: [f64; 3], luminance: f64) -> [f64; 3] {
+    let factor = 100.0 / luminance;
+    [xyz[0] * factor, xyz[1] * factor, xyz[2] * factor]
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+///Map an average color difference to a fidelity index.
+fn fidelity(difference: f64) -> f64 {
+    10.0 * (((100.0 - 6.73 * difference) / 10.0).exp() + 1.0).ln()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cam02, Tm30};
+    use spectral::fixtures::{Peaks, SAMPLES};
+    use spectral::{Daylight, Planckian};
+
+    #[test]
+    fn cam02() {
+        //The worked example from CIE 159, with the default degree of
+        //adaptation for 200 cd/m²
+        let adaptation = 1.0 - (-(200.0f64 + 42.0) / 92.0).exp() / 3.6;
+        let model = Cam02::new([98.88, 90.0, 32.03], 200.0, 18.0, adaptation);
+        let [j, c, h] = model.jch([19.31, 23.93, 10.14]);
+
+        assert!((j - 48.0314).abs() < 1e-3);
+        assert!((c - 38.7789).abs() < 1e-3);
+        assert!((h.to_degrees() + 360.0 - 191.0452).abs() < 1e-3);
+    }
+
+    #[test]
+    fn reference_illuminants() {
+        for &temperature in &[2700.0f64, 4500.0] {
+            let planckian = Planckian::new(temperature);
+            let tm30 = Tm30::new(&planckian);
+            assert!((tm30.cct().temperature - temperature).abs() < 5.0);
+
+            //Between 4000 K and 5000 K, the reference is partly daylight
+            let fidelity = tm30.fidelity_index(&SAMPLES);
+            if temperature < 4000.0 {
+                assert!(fidelity > 99.9);
+            } else {
+                assert!(fidelity < 99.9 && fidelity > 95.0, "{}", fidelity);
+            }
+        }
+
+        let daylight = Daylight::new(6500.0);
+        let tm30 = Tm30::new(&daylight);
+        assert!(tm30.fidelity_index(&SAMPLES) > 99.9);
+        assert!((tm30.gamut_index(&SAMPLES) - 100.0).abs() < 0.01);
+        for sample in &SAMPLES {
+            assert!(tm30.sample_fidelity_index(sample) > 99.9);
+        }
+    }
+
+    #[test]
+    fn narrow_band() {
+        let tm30 = Tm30::new(&Peaks);
+        assert!(tm30.fidelity_index(&SAMPLES) < 95.0);
+
+        //Narrow peaks make colors more saturated
+        assert!(tm30.gamut_index(&SAMPLES) > 100.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "there must be at least one sample")]
+    fn no_samples() {
+        let samples: [&[(f64, f64)]; 0] = [];
+        Tm30::new(&Planckian::new(2700.0f64)).gamut_index(&samples);
+    }
+}
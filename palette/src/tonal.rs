@@ -0,0 +1,202 @@
+//!Tonal palettes, for the color schemes of design systems.
+//!
+//!A tonal palette is a set of colors with the same hue and different tones,
+//!from black at tone 0 to white at tone 100, in the style of Material Design.
+//!The tone is the CIE L\* lightness, so colors with the same tone have the
+//!same perceived lightness and contrast ratios against each other, no matter
+//!which palette they come from. A color scheme can then be made by picking the
+//!tones for each role, such as tone 40 for a primary color on a light
+//!background and tone 90 for its container.
+//!
+//!The hue and chroma are taken from a seed color in CIE L\*C\*h°. Not every
+//!tone can have the chroma of the seed color, so the chroma is reduced as much
+//!as needed for each tone to be inside the gamut of an RGB space.
+//!
+//!```
+//!use palette::{Lch, Srgb};
+//!use palette::encoding;
+//!use palette::tonal::TonalPalette;
+//!
+//!let seed = Srgb::new(0.4f32, 0.3, 0.7).into_linear();
+//!let palette = TonalPalette::<encoding::Srgb>::from_color(seed);
+//!
+//!let primary = Srgb::from_linear(palette.tone(40.0).into());
+//!let container = Srgb::from_linear(palette.tone(90.0).into());
+//!
+//!assert!((Lch::from(primary.into_linear()).l - 40.0).abs() < 0.01);
+//!assert!(container.red > primary.red);
+//!```
+
+use core::marker::PhantomData;
+
+use float::Float;
+
+use encoding::{self, Linear};
+use rgb::{Rgb, RgbSpace};
+use {cast, Component, FromColorUnclamped, IntoColorUnclamped, LabHue, Lch};
+
+///The tones of a Material Design tonal palette.
+pub const TONES: [u8; 13] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99, 100];
+
+///A palette of colors with the same hue and different tones.
+///
+///The colors are kept inside the gamut of the RGB space `S`.
+#[derive(Debug, PartialEq)]
+pub struct TonalPalette<S: RgbSpace = encoding::Srgb, T: Float = f32> {
+    ///The hue of the colors.
+    pub hue: LabHue<T>,
+
+    ///The highest chroma of the colors. The tones that can't have this
+    ///chroma get as much of it as the gamut allows.
+    pub chroma: T,
+
+    space: PhantomData<S>,
+}
+
+impl<S: RgbSpace, T: Float> Copy for TonalPalette<S, T> {}
+
+impl<S: RgbSpace, T: Float> Clone for TonalPalette<S, T> {
+    fn clone(&self) -> TonalPalette<S, T> {
+        *self
+    }
+}
+
+impl<S, T> TonalPalette<S, T>
+where
+    S: RgbSpace,
+    T: Component + Float,
+{
+    ///Create a palette from a hue and chroma in CIE L\*C\*h°.
+    pub fn new<H: Into<LabHue<T>>>(hue: H, chroma: T) -> TonalPalette<S, T> {
+        TonalPalette {
+            hue: hue.into(),
+            chroma: chroma,
+            space: PhantomData,
+        }
+    }
+
+    ///Create a palette with the hue and chroma of a seed color.
+    pub fn from_color<C>(seed: C) -> TonalPalette<S, T>
+    where
+        C: IntoColorUnclamped<S::WhitePoint, T>,
+    {
+        let seed = seed.into_lch();
+        TonalPalette::new(seed.hue, seed.chroma)
+    }
+
+    ///The color with the lightness `tone`, from `0.0` to `100.0`.
+    ///
+    ///The chroma is as close to the chroma of the palette as it can be,
+    ///while keeping the color inside the gamut of `S`.
+    pub fn tone(&self, tone: T) -> Lch<S::WhitePoint, T> {
+        let color = |chroma| Lch::with_wp(tone, chroma, self.hue);
+        if is_in_gamut::<S, T>(color(self.chroma)) {
+            return color(self.chroma);
+        }
+
+        let mut low = T::zero();
+        let mut high = self.chroma;
+        for _ in 0..24 {
+            let middle = (low + high) / (T::one() + T::one());
+            if is_in_gamut::<S, T>(color(middle)) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        color(low)
+    }
+
+    ///The colors with the tones in [`TONES`](constant.TONES.html).
+    pub fn tones(&self) -> [Lch<S::WhitePoint, T>; 13] {
+        let mut colors = [self.tone(T::zero()); 13];
+        for (color, &tone) in colors.iter_mut().zip(&TONES).skip(1) {
+            *color = self.tone(cast(tone));
+        }
+
+        colors
+    }
+}
+
+///Check if a color is inside the RGB gamut, with a small margin for rounding
+///errors.
+fn is_in_gamut<S, T>(color: Lch<S::WhitePoint, T>) -> bool
+where
+    S: RgbSpace,
+    T: Component + Float,
+{
+    let margin: T = cast(1e-5);
+    let rgb = Rgb::<Linear<S>, T>::from_lch(color);
+    [rgb.red, rgb.green, rgb.blue]
+        .iter()
+        .all(|&value| value >= -margin && value <= T::one() + margin)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TonalPalette, TONES};
+    use encoding::Srgb;
+    use {Lch, LinSrgb};
+
+    #[test]
+    fn tones() {
+        let palette = TonalPalette::<Srgb, f64>::from_color(LinSrgb::new(0.1, 0.3, 0.8));
+        let colors = palette.tones();
+
+        for (color, &tone) in colors.iter().zip(&TONES) {
+            assert_relative_eq!(color.l, tone as f64);
+            assert!(color.chroma <= palette.chroma);
+
+            let rgb = LinSrgb::from(*color);
+            for &value in &[rgb.red, rgb.green, rgb.blue] {
+                assert!(
+                    (-1e-4..=1.0 + 1e-4).contains(&value),
+                    "{:?} for {}",
+                    rgb,
+                    tone
+                );
+            }
+        }
+
+        assert_relative_eq!(
+            LinSrgb::from(colors[0]),
+            LinSrgb::new(0.0, 0.0, 0.0),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            LinSrgb::from(colors[12]),
+            LinSrgb::new(1.0, 1.0, 1.0),
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn keeps_hue() {
+        let palette = TonalPalette::<Srgb, f64>::new(250.0, 40.0);
+        for color in palette.tones().iter().skip(1).take(11) {
+            assert_relative_eq!(color.hue, palette.hue);
+        }
+    }
+
+    #[test]
+    fn chroma_when_possible() {
+        let seed = Lch::new(50.0, 20.0, 120.0);
+        let palette = TonalPalette::<Srgb, f64>::from_color(seed);
+
+        assert_relative_eq!(palette.tone(50.0), seed, epsilon = 1e-9);
+        assert_relative_eq!(palette.tone(30.0).chroma, 20.0);
+    }
+
+    #[test]
+    fn gamut_limits_chroma() {
+        let palette = TonalPalette::<Srgb, f64>::new(40.0, 120.0);
+
+        let color = palette.tone(50.0);
+        assert!(color.chroma < 120.0);
+
+        //A little more chroma is outside the gamut
+        let outside = LinSrgb::from(Lch::new(50.0, color.chroma + 0.1, 40.0));
+        assert!(outside.red > 1.0 || outside.green < 0.0 || outside.blue < 0.0);
+    }
+}
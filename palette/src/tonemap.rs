@@ -0,0 +1,506 @@
+//!Tone mapping operators for high dynamic range colors.
+//!
+//!Tone mapping compresses the unbounded range of HDR scene or display light
+//!into the `[0.0, 1.0]` range of an SDR display. The operators in this module
+//!work on linear RGB, where `1.0` is the SDR reference white, and are
+//!parameterized by the peak luminance of the source, relative to that same
+//!reference white. The peak luminance is always mapped to `1.0`.
+//!
+//!Mapping each RGB channel individually shifts the hue of bright, saturated
+//!colors, since the channels are compressed by different amounts. The
+//![`tonemap_luminance`](trait.ToneMap.html#method.tonemap_luminance) method
+//!compresses only the luminance and rescales the color to match, which keeps
+//!its chromaticity, and therefore its hue, unchanged.
+//!
+//![`Bt2390`](struct.Bt2390.html) implements the EETF of ITU-R BT.2390 and,
+//!with [`tonemap_ictcp`](struct.Bt2390.html#method.tonemap_ictcp), applies it
+//!the way the report recommends: to the intensity of ICtCp, with the
+//!chroma components rescaled to match.
+//!
+//!```
+//!use palette::LinSrgb;
+//!use palette::tonemap::{Aces, ToneMap};
+//!
+//!// A 1000 nit HDR source, with the reference white at 203 nits.
+//!let operator = Aces::from_nits(1000.0, 203.0);
+//!
+//!let hdr = LinSrgb::new(3.2, 1.5, 0.4);
+//!let sdr = operator.tonemap(hdr);
+//!assert!(sdr.red <= 1.0 && sdr.green <= 1.0 && sdr.blue <= 1.0);
+//!```
+
+use float::Float;
+
+use encoding::itu::{TransferPq, BT2020};
+use encoding::{Linear, TransferFn};
+use matrix::matrix_inverse;
+use rgb::{Rgb, RgbSpace};
+use white_point::D65;
+use {cast, clamp, ColorMatrix, Component, IntoColorUnclamped};
+
+///The luminance of the linear value `1.0` of the perceptual quantizer, in
+///cd/m².
+const PQ_PEAK: f64 = 10000.0;
+
+///The reference white that is assumed when the peak luminance is relative,
+///in cd/m², as recommended in ITU-R BT.2408.
+const REFERENCE_WHITE: f64 = 203.0;
+
+///The BT.2020 to LMS matrix of ITU-R BT.2100, in units of 1/4096.
+const RGB_TO_LMS: [f64; 9] = [
+    1688.0, 2146.0, 262.0, 683.0, 2951.0, 462.0, 99.0, 309.0, 3688.0,
+];
+
+///The L'M'S' to ICtCp matrix of ITU-R BT.2100, in units of 1/4096.
+const LMS_TO_ICTCP: [f64; 9] = [
+    2048.0, 2048.0, 0.0, 6610.0, -13613.0, 7003.0, 17933.0, -17390.0, -543.0,
+];
+
+///A tone mapping operator.
+///
+///The operator can either be applied to each of the linear RGB channels
+///individually, or to the luminance of the color.
+pub trait ToneMap<T: Float> {
+    ///Map a single linear component value. Values between `0.0` and the peak
+    ///luminance are mapped to `[0.0, 1.0]`.
+    fn map(&self, value: T) -> T;
+
+    ///Tone map a linear RGB color. The result is clamped to `[0.0, 1.0]`.
+    fn tonemap<S>(&self, color: Rgb<Linear<S>, T>) -> Rgb<Linear<S>, T>
+    where
+        S: RgbSpace,
+        T: Component,
+    {
+        Rgb::new(
+            clamp(self.map(color.red), T::zero(), T::one()),
+            clamp(self.map(color.green), T::zero(), T::one()),
+            clamp(self.map(color.blue), T::zero(), T::one()),
+        )
+    }
+
+    ///Tone map the luminance of a linear RGB color, while preserving its hue.
+    ///
+    ///The color is scaled so that its luminance matches the mapped luminance.
+    ///Any components that would end up above `1.0` are brought back by
+    ///scaling the whole color down, rather than clamping them individually.
+    ///
+    ///This works on the CIE luminance, rather than the ICtCp intensity that
+    ///ITU-R BT.2390 recommends. See
+    ///[`Bt2390::tonemap_ictcp`](struct.Bt2390.html#method.tonemap_ictcp) for
+    ///that.
+    fn tonemap_luminance<S>(&self, color: Rgb<Linear<S>, T>) -> Rgb<Linear<S>, T>
+    where
+        S: RgbSpace,
+        T: Component,
+    {
+        let color = Rgb::new(
+            color.red.max(T::zero()),
+            color.green.max(T::zero()),
+            color.blue.max(T::zero()),
+        );
+        let luminance = color.into_xyz().y;
+
+        if luminance <= T::zero() {
+            return Rgb::new(T::zero(), T::zero(), T::zero());
+        }
+
+        let mapped = color * (self.map(luminance) / luminance);
+        let max = mapped.red.max(mapped.green).max(mapped.blue);
+
+        if max > T::one() {
+            mapped / max
+        } else {
+            mapped
+        }
+    }
+}
+
+///The simple Reinhard operator, `x / (1 + x)`, scaled so that the peak
+///luminance becomes `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reinhard<T: Float = f32> {
+    scale: T,
+}
+
+impl<T: Float> Reinhard<T> {
+    ///Create a Reinhard operator for the peak luminance `peak`, relative to
+    ///the reference white.
+    pub fn new(peak: T) -> Reinhard<T> {
+        let peak = peak.max(T::epsilon());
+
+        Reinhard {
+            scale: (T::one() + peak) / peak,
+        }
+    }
+
+    ///Create a Reinhard operator from a peak luminance and a reference white,
+    ///both in cd/m².
+    pub fn from_nits(peak: T, reference_white: T) -> Reinhard<T> {
+        Reinhard::new(peak / reference_white)
+    }
+}
+
+impl<T: Float> ToneMap<T> for Reinhard<T> {
+    fn map(&self, value: T) -> T {
+        let value = value.max(T::zero());
+        self.scale * value / (T::one() + value)
+    }
+}
+
+///The extended Reinhard operator, `x * (1 + x / w²) / (1 + x)`, where the white
+///point `w` is the peak luminance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtendedReinhard<T: Float = f32> {
+    white_squared: T,
+}
+
+impl<T: Float> ExtendedReinhard<T> {
+    ///Create an extended Reinhard operator for the peak luminance `peak`,
+    ///relative to the reference white.
+    pub fn new(peak: T) -> ExtendedReinhard<T> {
+        let peak = peak.max(T::epsilon());
+
+        ExtendedReinhard {
+            white_squared: peak * peak,
+        }
+    }
+
+    ///Create an extended Reinhard operator from a peak luminance and a
+    ///reference white, both in cd/m².
+    pub fn from_nits(peak: T, reference_white: T) -> ExtendedReinhard<T> {
+        ExtendedReinhard::new(peak / reference_white)
+    }
+}
+
+impl<T: Float> ToneMap<T> for ExtendedReinhard<T> {
+    fn map(&self, value: T) -> T {
+        let value = value.max(T::zero());
+        value * (T::one() + value / self.white_squared) / (T::one() + value)
+    }
+}
+
+///Krzysztof Narkowicz' fit of the ACES filmic curve, scaled so that the peak
+///luminance becomes `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aces<T: Float = f32> {
+    scale: T,
+}
+
+impl<T: Float> Aces<T> {
+    ///Create an ACES operator for the peak luminance `peak`, relative to the
+    ///reference white.
+    pub fn new(peak: T) -> Aces<T> {
+        let peak = peak.max(T::epsilon());
+
+        Aces {
+            scale: T::one() / aces_fitted(peak),
+        }
+    }
+
+    ///Create an ACES operator from a peak luminance and a reference white,
+    ///both in cd/m².
+    pub fn from_nits(peak: T, reference_white: T) -> Aces<T> {
+        Aces::new(peak / reference_white)
+    }
+}
+
+impl<T: Float> ToneMap<T> for Aces<T> {
+    fn map(&self, value: T) -> T {
+        self.scale * aces_fitted(value.max(T::zero()))
+    }
+}
+
+fn aces_fitted<T: Float>(x: T) -> T {
+    let a: T = cast(2.51);
+    let b: T = cast(0.03);
+    let c: T = cast(2.43);
+    let d: T = cast(0.59);
+    let e: T = cast(0.14);
+
+    let x = x * cast(0.6);
+    (x * (a * x + b)) / (x * (c * x + d) + e)
+}
+
+///The EETF of ITU-R BT.2390, which rolls off the perceptual quantizer
+///encoded luminance above a knee, so that the peak luminance of the source
+///lands on the peak luminance of the target.
+///
+///The target is an SDR display, with its peak at the reference white, and
+///the black levels of both the source and the target are `0` cd/m². Values
+///up to the knee are kept as they are, while the values above it are
+///compressed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bt2390<T: Float = f32> {
+    reference_white: T,
+    source_peak: T,
+    target_peak: T,
+    knee: T,
+}
+
+impl<T: Float> Bt2390<T> {
+    ///Create a BT.2390 operator for the peak luminance `peak`, relative to
+    ///the reference white, which is placed at `203` cd/m².
+    ///
+    ///Peaks below `1.0` are treated as `1.0`.
+    pub fn new(peak: T) -> Bt2390<T> {
+        let reference_white = cast(REFERENCE_WHITE);
+        Bt2390::from_nits(peak * reference_white, reference_white)
+    }
+
+    ///Create a BT.2390 operator from a peak luminance and a reference white,
+    ///both in cd/m².
+    ///
+    ///Peaks below the reference white are treated as the reference white.
+    pub fn from_nits(peak: T, reference_white: T) -> Bt2390<T> {
+        let reference_white = reference_white.max(T::epsilon());
+        let peak = peak.max(reference_white);
+        let source_peak = TransferPq::from_linear(peak / cast(PQ_PEAK));
+        let target_peak = TransferPq::from_linear(reference_white / cast(PQ_PEAK)) / source_peak;
+
+        Bt2390 {
+            reference_white: reference_white,
+            source_peak: source_peak,
+            target_peak: target_peak,
+            knee: cast::<T, _>(1.5) * target_peak - cast(0.5),
+        }
+    }
+
+    ///Apply the EETF to a perceptual quantizer encoded value.
+    fn eetf(&self, encoded: T) -> T {
+        let normalized = clamp(encoded / self.source_peak, T::zero(), T::one());
+
+        if normalized <= self.knee {
+            return normalized * self.source_peak;
+        }
+
+        let two: T = cast(2.0);
+        let three: T = cast(3.0);
+        let t = (normalized - self.knee) / (T::one() - self.knee);
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let spline = (two * t3 - three * t2 + T::one()) * self.knee
+            + (t3 - two * t2 + t) * (T::one() - self.knee)
+            + (three * t2 - two * t3) * self.target_peak;
+
+        spline * self.source_peak
+    }
+
+    ///Tone map a linear RGB color by applying the EETF to its ICtCp
+    ///intensity, as recommended in ITU-R BT.2390.
+    ///
+    ///The color is converted to ICtCp through the BT.2020 primaries, and the
+    ///chroma components are scaled by the ratio of the mapped and the
+    ///original intensity, whichever way keeps it below `1.0`. The result is
+    ///clamped to `[0.0, 1.0]`.
+    pub fn tonemap_ictcp<S>(&self, color: Rgb<Linear<S>, T>) -> Rgb<Linear<S>, T>
+    where
+        S: RgbSpace<WhitePoint = D65>,
+        T: Component,
+    {
+        let scale = self.reference_white / cast(PQ_PEAK);
+        let [red, green, blue] =
+            ColorMatrix::rgb_to_rgb::<S, BT2020>().transform([color.red, color.green, color.blue]);
+
+        let [intensity, ct, cp] = rgb_to_ictcp([red * scale, green * scale, blue * scale]);
+        let mapped = self.eetf(intensity);
+
+        if intensity <= T::zero() || mapped <= T::zero() {
+            return Rgb::new(T::zero(), T::zero(), T::zero());
+        }
+
+        let ratio = (intensity / mapped).min(mapped / intensity);
+        let [red, green, blue] = ictcp_to_rgb([mapped, ct * ratio, cp * ratio]);
+        let [red, green, blue] = ColorMatrix::rgb_to_rgb::<BT2020, S>().transform([
+            red / scale,
+            green / scale,
+            blue / scale,
+        ]);
+
+        Rgb::new(
+            clamp(red, T::zero(), T::one()),
+            clamp(green, T::zero(), T::one()),
+            clamp(blue, T::zero(), T::one()),
+        )
+    }
+}
+
+impl<T: Float> ToneMap<T> for Bt2390<T> {
+    fn map(&self, value: T) -> T {
+        let scale = self.reference_white / cast(PQ_PEAK);
+        let encoded = TransferPq::from_linear(value.max(T::zero()) * scale);
+        TransferPq::into_linear(self.eetf(encoded)) / scale
+    }
+}
+
+///Convert linear BT.2020 RGB, where `1.0` is `10000` cd/m², to ICtCp.
+fn rgb_to_ictcp<T: Float>(rgb: [T; 3]) -> [T; 3] {
+    let [l, m, s] = ictcp_matrix(RGB_TO_LMS).transform(rgb);
+    let encoded = [
+        TransferPq::from_linear(l),
+        TransferPq::from_linear(m),
+        TransferPq::from_linear(s),
+    ];
+    ictcp_matrix(LMS_TO_ICTCP).transform(encoded)
+}
+
+///Convert ICtCp to linear BT.2020 RGB, where `1.0` is `10000` cd/m².
+fn ictcp_to_rgb<T: Float>(ictcp: [T; 3]) -> [T; 3] {
+    let [l, m, s] = inverse_ictcp_matrix(LMS_TO_ICTCP).transform(ictcp);
+    let decoded = [
+        TransferPq::into_linear(l),
+        TransferPq::into_linear(m),
+        TransferPq::into_linear(s),
+    ];
+    inverse_ictcp_matrix(RGB_TO_LMS).transform(decoded)
+}
+
+fn ictcp_matrix<T: Float>(elements: [f64; 9]) -> ColorMatrix<T> {
+    let mut matrix = [T::zero(); 9];
+    for (element, &value) in matrix.iter_mut().zip(&elements) {
+        *element = cast(value / 4096.0);
+    }
+
+    ColorMatrix::new(matrix)
+}
+
+fn inverse_ictcp_matrix<T: Float>(elements: [f64; 9]) -> ColorMatrix<T> {
+    ColorMatrix::new(matrix_inverse(&ictcp_matrix(elements).into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ictcp_to_rgb, rgb_to_ictcp, Aces, Bt2390, ExtendedReinhard, Reinhard, ToneMap};
+    use encoding::itu::BT2020;
+    use encoding::Srgb;
+    use {ColorMatrix, IntoColorUnclamped, LinSrgb};
+
+    #[test]
+    fn peak_maps_to_one() {
+        assert_relative_eq!(Reinhard::new(4.0f64).map(4.0), 1.0);
+        assert_relative_eq!(ExtendedReinhard::new(4.0f64).map(4.0), 1.0);
+        assert_relative_eq!(Aces::new(4.0f64).map(4.0), 1.0);
+        assert_relative_eq!(Bt2390::new(4.0f64).map(4.0), 1.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn black_stays_black() {
+        assert_relative_eq!(Reinhard::new(4.0f64).map(0.0), 0.0);
+        assert_relative_eq!(ExtendedReinhard::new(4.0f64).map(0.0), 0.0);
+        assert_relative_eq!(Aces::new(4.0f64).map(0.0), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(Bt2390::new(4.0f64).map(0.0), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn monotonic() {
+        let reinhard = Reinhard::new(10.0f64);
+        let extended = ExtendedReinhard::new(10.0f64);
+        let aces = Aces::new(10.0f64);
+        let bt2390 = Bt2390::new(10.0f64);
+
+        let mut previous = (0.0, 0.0, 0.0, 0.0);
+        for i in 1..100 {
+            let x = i as f64 * 0.1;
+            let current = (reinhard.map(x), extended.map(x), aces.map(x), bt2390.map(x));
+            assert!(current.0 > previous.0);
+            assert!(current.1 > previous.1);
+            assert!(current.2 > previous.2);
+            assert!(current.3 > previous.3);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn from_nits() {
+        assert_eq!(Aces::from_nits(1000.0, 100.0), Aces::new(10.0));
+        assert_eq!(
+            ExtendedReinhard::from_nits(1000.0, 100.0),
+            ExtendedReinhard::new(10.0)
+        );
+    }
+
+    #[test]
+    fn tonemap_clamps() {
+        let sdr = ExtendedReinhard::new(2.0).tonemap(LinSrgb::new(8.0, 1.0, -0.5));
+        assert_relative_eq!(sdr, LinSrgb::new(1.0, 0.625, 0.0));
+    }
+
+    #[test]
+    fn tonemap_luminance_preserves_hue() {
+        let operator = Reinhard::new(10.0f64);
+        let hdr = LinSrgb::new(2.0, 1.5, 1.0);
+        let sdr = operator.tonemap_luminance(hdr);
+
+        assert_relative_eq!(sdr.green / sdr.red, 0.75);
+        assert_relative_eq!(sdr.blue / sdr.red, 0.5);
+        assert_relative_eq!(
+            sdr.into_xyz().y,
+            operator.map(hdr.into_xyz().y),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn tonemap_luminance_stays_in_range() {
+        let sdr = Aces::new(4.0f64).tonemap_luminance(LinSrgb::new(0.0, 0.0, 4.0));
+        assert_relative_eq!(sdr, LinSrgb::new(0.0, 0.0, 1.0));
+
+        let black = Aces::new(4.0f64).tonemap_luminance(LinSrgb::new(0.0, 0.0, 0.0));
+        assert_relative_eq!(black, LinSrgb::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn bt2390_below_knee() {
+        let operator = Bt2390::from_nits(1000.0f64, 203.0);
+        assert_relative_eq!(operator.map(0.1), 0.1, epsilon = 1e-9);
+        assert_relative_eq!(operator.map(20.0), 1.0, epsilon = 1e-9);
+        assert!(operator.map(1.0) < 1.0);
+    }
+
+    #[test]
+    fn ictcp_round_trip() {
+        let rgb = [0.02, 0.01, 0.005];
+        let ictcp = rgb_to_ictcp(rgb);
+        let [red, green, blue] = ictcp_to_rgb(ictcp);
+
+        assert_relative_eq!(red, rgb[0], epsilon = 1e-12);
+        assert_relative_eq!(green, rgb[1], epsilon = 1e-12);
+        assert_relative_eq!(blue, rgb[2], epsilon = 1e-12);
+    }
+
+    #[test]
+    fn tonemap_ictcp_keeps_gray() {
+        let operator = Bt2390::new(10.0f64);
+        let sdr = operator.tonemap_ictcp(LinSrgb::new(4.0, 4.0, 4.0));
+        let expected = operator.map(4.0);
+
+        assert_relative_eq!(
+            sdr,
+            LinSrgb::new(expected, expected, expected),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn tonemap_ictcp_preserves_hue() {
+        let operator = Bt2390::new(10.0f64);
+        let hdr = LinSrgb::new(1.2, 0.8, 0.6);
+        let sdr = operator.tonemap_ictcp(hdr);
+
+        let hue = |color: LinSrgb<f64>| {
+            let rgb = ColorMatrix::rgb_to_rgb::<Srgb, BT2020>().transform([
+                color.red,
+                color.green,
+                color.blue,
+            ]);
+            let scale = 203.0 / 10000.0;
+            let [_, ct, cp] = rgb_to_ictcp([rgb[0] * scale, rgb[1] * scale, rgb[2] * scale]);
+            cp.atan2(ct)
+        };
+        assert!(sdr.red < 1.0);
+        assert_relative_eq!(hue(sdr), hue(hdr), epsilon = 1e-6);
+
+        let black = operator.tonemap_ictcp(LinSrgb::new(0.0, 0.0, 0.0));
+        assert_relative_eq!(black, LinSrgb::new(0.0, 0.0, 0.0));
+    }
+}
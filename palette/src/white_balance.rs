@@ -0,0 +1,169 @@
+//!Automatic white balance, for removing color casts from images.
+//!
+//!White balancing is done in two steps. The illuminant of the scene is first
+//!estimated from the colors of the image, and the colors are then
+//!chromatically adapted from the estimated illuminant to the white point of
+//!the color space. The estimators work on linear RGB and assume that the
+//!image has some neutral content, so they may be fooled by images that are
+//!dominated by a single color.
+//!
+//!```
+//!use palette::LinSrgb;
+//!use palette::white_balance::{correct, Estimator};
+//!
+//!//A gray scene, lit by a yellow light
+//!let mut image = vec![
+//!    LinSrgb::new(0.9f32, 0.8, 0.5),
+//!    LinSrgb::new(0.45, 0.4, 0.25),
+//!    LinSrgb::new(0.18, 0.16, 0.1),
+//!];
+//!
+//!let illuminant = Estimator::GrayWorld.estimate(&image).unwrap();
+//!correct(&mut image, illuminant);
+//!
+//!let gray = image[1];
+//!assert!((gray.red - gray.blue).abs() < 1e-3);
+//!```
+
+use float::Float;
+
+use chromatic_adaptation::{Method, TransformMatrix};
+use encoding::Linear;
+use matrix::{adaptation_matrix, multiply_xyz};
+use rgb::{Rgb, RgbSpace};
+use white_point::WhitePoint;
+use {cast, Component, FromColorUnclamped, IntoColorUnclamped, Xyz};
+
+///A method for estimating the illuminant of a scene.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Estimator {
+    ///Assumes that the average color of the scene is gray, so the average of
+    ///all colors is the color of the illuminant.
+    GrayWorld,
+    ///Assumes that the scene contains something white, so the highest value
+    ///of each channel is the color of the illuminant. It's sensitive to
+    ///noise and clipped highlights.
+    WhitePatch,
+}
+
+impl Estimator {
+    ///Estimate the illuminant of the scene in `colors`.
+    ///
+    ///The estimated illuminant is normalized to have a luminance, `Y`, of
+    ///`1.0`, just like the white points. It can be converted to `Yxy` to get
+    ///its chromaticity. `None` is returned if `colors` is empty or has no
+    ///light in it.
+    pub fn estimate<S, T>(self, colors: &[Rgb<Linear<S>, T>]) -> Option<Xyz<S::WhitePoint, T>>
+    where
+        S: RgbSpace,
+        T: Component + Float,
+    {
+        if colors.is_empty() {
+            return None;
+        }
+
+        let estimate = match self {
+            Estimator::GrayWorld => {
+                let mut sum = Rgb::new(T::zero(), T::zero(), T::zero());
+                for color in colors {
+                    sum = sum + *color;
+                }
+
+                sum / cast::<T, _>(colors.len())
+            }
+            Estimator::WhitePatch => {
+                let mut max = Rgb::new(T::zero(), T::zero(), T::zero());
+                for color in colors {
+                    max = Rgb::new(
+                        max.red.max(color.red),
+                        max.green.max(color.green),
+                        max.blue.max(color.blue),
+                    );
+                }
+
+                max
+            }
+        };
+
+        let xyz: Xyz<S::WhitePoint, T> = estimate.into_xyz();
+        if xyz.y > T::zero() {
+            Some(xyz / xyz.y)
+        } else {
+            None
+        }
+    }
+}
+
+///Correct the white balance of `colors`, by adapting them from the estimated
+///`illuminant` to the white point of their color space, using the bradford
+///method.
+pub fn correct<C, Wp, T>(colors: &mut [C], illuminant: Xyz<Wp, T>)
+where
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    correct_using(colors, illuminant, Method::Bradford)
+}
+
+///Correct the white balance of `colors`, by adapting them from the estimated
+///`illuminant` to the white point of their color space, using the specified
+///method.
+pub fn correct_using<C, Wp, T, M>(colors: &mut [C], illuminant: Xyz<Wp, T>, method: M)
+where
+    C: IntoColorUnclamped<Wp, T> + FromColorUnclamped<Wp, T> + Clone,
+    Wp: WhitePoint,
+    T: Component + Float,
+    M: TransformMatrix<Wp, Wp, T>,
+{
+    let white: Xyz<Wp, T> = Wp::get_xyz();
+    let transform_matrix = adaptation_matrix(method.get_cone_response(), &illuminant, &white);
+
+    for color in colors {
+        let xyz: Xyz<Wp, T> = color.clone().into_xyz();
+        *color = C::from_xyz(multiply_xyz(&transform_matrix, &xyz));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{correct, Estimator};
+    use white_point::{WhitePoint, A, D65};
+    use {LinSrgb, Xyz};
+
+    #[test]
+    fn empty_and_black() {
+        let empty: [LinSrgb<f64>; 0] = [];
+        assert_eq!(Estimator::GrayWorld.estimate(&empty), None);
+        assert_eq!(
+            Estimator::WhitePatch.estimate(&[LinSrgb::new(0.0f64, 0.0, 0.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn neutral_scene() {
+        let colors = [
+            LinSrgb::new(0.2f64, 0.2, 0.2),
+            LinSrgb::new(0.8, 0.1, 0.1),
+            LinSrgb::new(0.1, 0.8, 0.8),
+        ];
+
+        let d65: Xyz<D65, f64> = D65::get_xyz();
+        assert_relative_eq!(Estimator::GrayWorld.estimate(&colors).unwrap(), d65, epsilon = 1e-6);
+        assert_relative_eq!(Estimator::WhitePatch.estimate(&colors).unwrap(), d65, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn correct_tungsten() {
+        let illuminant: Xyz<D65, f64> = A::get_xyz();
+        let mut colors = [illuminant * 0.5];
+
+        let rgb: LinSrgb<f64> = colors[0].into();
+        let estimate = Estimator::WhitePatch.estimate(&[rgb]).unwrap();
+        assert_relative_eq!(estimate, illuminant, epsilon = 1e-6);
+
+        correct(&mut colors, estimate);
+        assert_relative_eq!(colors[0], D65::get_xyz::<D65, f64>() * 0.5, epsilon = 1e-6);
+    }
+}
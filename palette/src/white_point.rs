@@ -5,10 +5,20 @@
 //! which are relatively orange compared to daylight. Defining "white" as daylight will give
 //! unacceptable results when attempting to color-correct a photograph taken with incandescent
 //! lighting.
+//!
+//! The fluorescent and LED illuminants are defined by their spectra in CIE 15. Those spectra are
+//! not included in [`spectral::Illuminant`](../spectral/enum.Illuminant.html), which only has A,
+//! D65 and E, so the F and LED white points can't be used where a spectrum is needed, such as in
+//! the color rendering calculations. The tristimulus values of the LED illuminants and of the
+//! fluorescent illuminants other than F2, F7 and F11 are derived from their chromaticities in
+//! CIE 15:2018 instead.
 
 use float::Float;
 
-use {cast, Component, Xyz};
+use chromatic_adaptation::{Method, TransformMatrix};
+use matrix::adaptation_matrix;
+use spectral::Observer;
+use {cast, ColorMatrix, Component, Xyz};
 
 ///WhitePoint defines the Xyz color co-ordinates for a given white point.
 ///
@@ -18,9 +28,19 @@ use {cast, Component, Xyz};
 ///
 ///Custom white points can be easily defined on an empty struct with the tristimulus values
 ///and can be used in place of the ones defined in this library.
+///
+///The tristimulus values depend on the standard observer they are calculated for. Most white
+///points are for the CIE 1931 2° observer, while the ones ending in `Degree10` are for the
+///CIE 1964 10° observer, which is required by some industries, such as paints and textiles.
 pub trait WhitePoint {
     ///Get the Xyz chromacity co-ordinates for the white point.
     fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T>;
+
+    ///The standard observer that the tristimulus values are for. The default is the CIE 1931 2°
+    ///observer.
+    fn observer() -> Observer {
+        Observer::Cie1931
+    }
 }
 
 /// CIE standard illuminant A
@@ -112,6 +132,16 @@ impl WhitePoint for E {
         Xyz::with_wp(T::one(), T::one(), T::one())
     }
 }
+///CIE fluorescent illuminant series - F1
+///
+///F1 represents a daylight fluorescent lamp of 6430K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F1;
+impl WhitePoint for F1 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.92834), T::one(), cast(1.03665))
+    }
+}
 ///CIE fluorescent illuminant series - F2
 ///
 ///F2 represents a semi-broadband fluorescent lamp for 2° Standard Observer.
@@ -122,6 +152,46 @@ impl WhitePoint for F2 {
         Xyz::with_wp(cast(0.99186), T::one(), cast(0.67393))
     }
 }
+///CIE fluorescent illuminant series - F3
+///
+///F3 represents a white fluorescent lamp of 3450K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F3;
+impl WhitePoint for F3 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.03753), T::one(), cast(0.49861))
+    }
+}
+///CIE fluorescent illuminant series - F4
+///
+///F4 represents a warm white fluorescent lamp of 2940K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F4;
+impl WhitePoint for F4 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.09147), T::one(), cast(0.38813))
+    }
+}
+///CIE fluorescent illuminant series - F5
+///
+///F5 represents a daylight fluorescent lamp of 6350K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F5;
+impl WhitePoint for F5 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.90872), T::one(), cast(0.98723))
+    }
+}
+///CIE fluorescent illuminant series - F6
+///
+///F6 represents a lite white fluorescent lamp of 4150K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F6;
+impl WhitePoint for F6 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.97309), T::one(), cast(0.60191))
+    }
+}
 ///CIE fluorescent illuminant series - F7
 ///
 ///F7 represents a broadband fluorescent lamp for 2° Standard Observer.
@@ -132,6 +202,36 @@ impl WhitePoint for F7 {
         Xyz::with_wp(cast(0.95041), T::one(), cast(1.08747))
     }
 }
+///CIE fluorescent illuminant series - F8
+///
+///F8 represents a broadband fluorescent lamp of 5000K, simulating D50, for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F8;
+impl WhitePoint for F8 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.96413), T::one(), cast(0.82333))
+    }
+}
+///CIE fluorescent illuminant series - F9
+///
+///F9 represents a broadband cool white deluxe fluorescent lamp of 4150K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F9;
+impl WhitePoint for F9 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.00365), T::one(), cast(0.67868))
+    }
+}
+///CIE fluorescent illuminant series - F10
+///
+///F10 represents a narrowband fluorescent lamp of 5000K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F10;
+impl WhitePoint for F10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.96174), T::one(), cast(0.81712))
+    }
+}
 ///CIE fluorescent illuminant series - F11
 ///
 ///F11 represents a narrowband fluorescent lamp for 2° Standard Observer.
@@ -142,6 +242,106 @@ impl WhitePoint for F11 {
         Xyz::with_wp(cast(1.00962), T::one(), cast(0.64350))
     }
 }
+///CIE fluorescent illuminant series - F12
+///
+///F12 represents a narrowband fluorescent lamp of 3000K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F12;
+impl WhitePoint for F12 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.08046), T::one(), cast(0.39228))
+    }
+}
+///CIE LED illuminant series - LED-B1
+///
+///LED-B1 represents a phosphor-converted blue LED of 2733K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedB1;
+impl WhitePoint for LedB1 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.11820), T::one(), cast(0.33399))
+    }
+}
+///CIE LED illuminant series - LED-B2
+///
+///LED-B2 represents a phosphor-converted blue LED of 2998K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedB2;
+impl WhitePoint for LedB2 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.08599), T::one(), cast(0.40653))
+    }
+}
+///CIE LED illuminant series - LED-B3
+///
+///LED-B3 represents a phosphor-converted blue LED of 4103K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedB3;
+impl WhitePoint for LedB3 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.00886), T::one(), cast(0.67714))
+    }
+}
+///CIE LED illuminant series - LED-B4
+///
+///LED-B4 represents a phosphor-converted blue LED of 5109K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedB4;
+impl WhitePoint for LedB4 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.97716), T::one(), cast(0.87836))
+    }
+}
+///CIE LED illuminant series - LED-B5
+///
+///LED-B5 represents a phosphor-converted blue LED of 6598K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedB5;
+impl WhitePoint for LedB5 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.96354), T::one(), cast(1.12670))
+    }
+}
+///CIE LED illuminant series - LED-BH1
+///
+///LED-BH1 represents a mix of phosphor-converted blue and red LEDs of 2851K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedBh1;
+impl WhitePoint for LedBh1 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.10034), T::one(), cast(0.35908))
+    }
+}
+///CIE LED illuminant series - LED-RGB1
+///
+///LED-RGB1 represents a mix of red, green and blue LEDs of 2840K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedRgb1;
+impl WhitePoint for LedRgb1 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.08217), T::one(), cast(0.29257))
+    }
+}
+///CIE LED illuminant series - LED-V1
+///
+///LED-V1 represents a phosphor-converted violet LED of 2724K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedV1;
+impl WhitePoint for LedV1 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.00264), T::one(), cast(0.19613))
+    }
+}
+///CIE LED illuminant series - LED-V2
+///
+///LED-V2 represents a phosphor-converted violet LED of 4070K for 2° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedV2;
+impl WhitePoint for LedV2 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.00159), T::one(), cast(0.64742))
+    }
+}
 ///CIE D series standard illuminant - D50
 ///
 ///D50 White Point is the natural daylight with a color temperature of around 5000K
@@ -152,6 +352,9 @@ impl WhitePoint for D50Degree10 {
     fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
         Xyz::with_wp(cast(0.9672), T::one(), cast(0.8143))
     }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
 }
 ///CIE D series standard illuminant - D55
 ///
@@ -163,6 +366,9 @@ impl WhitePoint for D55Degree10 {
     fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
         Xyz::with_wp(cast(0.958), T::one(), cast(0.9093))
     }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
 }
 ///CIE D series standard illuminant - D65
 ///
@@ -174,6 +380,9 @@ impl WhitePoint for D65Degree10 {
     fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
         Xyz::with_wp(cast(0.9481), T::one(), cast(1.073))
     }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
 }
 ///CIE D series standard illuminant - D75
 ///
@@ -185,4 +394,225 @@ impl WhitePoint for D75Degree10 {
     fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
         Xyz::with_wp(cast(0.94416), T::one(), cast(1.2064))
     }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE standard illuminant A
+///
+///CIE standard illuminant A represents typical, domestic, tungsten-filament lighting
+///for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ADegree10;
+impl WhitePoint for ADegree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.11144), T::one(), cast(0.35200))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE standard illuminant B
+///
+///CIE standard illuminant B represents noon sunlight
+///for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BDegree10;
+impl WhitePoint for BDegree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.99178), T::one(), cast(0.84349))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE standard illuminant C
+///
+///CIE standard illuminant C represents the average day light
+///for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CDegree10;
+impl WhitePoint for CDegree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.97285), T::one(), cast(1.16145))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE fluorescent illuminant series - F2
+///
+///F2 represents a semi-broadband fluorescent lamp for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F2Degree10;
+impl WhitePoint for F2Degree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.03280), T::one(), cast(0.69026))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE fluorescent illuminant series - F7
+///
+///F7 represents a broadband fluorescent lamp for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F7Degree10;
+impl WhitePoint for F7Degree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(0.95792), T::one(), cast(1.07687))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+///CIE fluorescent illuminant series - F11
+///
+///F11 represents a narrowband fluorescent lamp for 10° Standard Observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct F11Degree10;
+impl WhitePoint for F11Degree10 {
+    fn get_xyz<Wp: WhitePoint, T: Component + Float>() -> Xyz<Wp, T> {
+        Xyz::with_wp(cast(1.03866), T::one(), cast(0.65627))
+    }
+    fn observer() -> Observer {
+        Observer::Cie1964
+    }
+}
+
+///A white point that is only known at runtime, such as an illuminant that is read from an
+///instrument.
+///
+///It can't be used as the white point of a color type, so colors that are measured relative to
+///it are adapted to one of the white point types before they are converted. The tristimulus
+///values are stored with a luminance, `Y`, of `1.0`.
+///
+///```
+///use palette::{Lab, Xyz};
+///use palette::chromatic_adaptation::Method;
+///use palette::white_point::{RuntimeWhitePoint, D50};
+///
+///let illuminant = RuntimeWhitePoint::from_chromaticity(0.4476, 0.4074);
+///
+/////A sample that was measured under the illuminant
+///let sample = Xyz::<D50, f64>::with_wp(0.3, 0.25, 0.05);
+///let adapted = illuminant.adapt_to_wp(sample, Method::Bradford);
+///let lab = Lab::from(adapted);
+///
+/////The illuminant itself becomes the white point
+///let white = illuminant.adapt_to_wp(illuminant.xyz::<D50>(), Method::Bradford);
+///assert!((white.x - 0.96422).abs() < 1e-6);
+///```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RuntimeWhitePoint<T: Float = f32> {
+    x: T,
+    z: T,
+}
+
+impl<T: Component + Float> RuntimeWhitePoint<T> {
+    ///Create a white point from its tristimulus values. They are scaled to a luminance of `1.0`.
+    pub fn from_xyz<Wp: WhitePoint>(xyz: Xyz<Wp, T>) -> RuntimeWhitePoint<T> {
+        RuntimeWhitePoint {
+            x: xyz.x / xyz.y,
+            z: xyz.z / xyz.y,
+        }
+    }
+
+    ///Create a white point from its `(x, y)` chromaticity.
+    pub fn from_chromaticity(x: T, y: T) -> RuntimeWhitePoint<T> {
+        RuntimeWhitePoint {
+            x: x / y,
+            z: (T::one() - x - y) / y,
+        }
+    }
+
+    ///Create a white point from one of the white point types.
+    pub fn from_white_point<Wp: WhitePoint>() -> RuntimeWhitePoint<T> {
+        RuntimeWhitePoint::from_xyz(Wp::get_xyz::<Wp, T>())
+    }
+
+    ///The tristimulus values of the white point, with a luminance of `1.0`.
+    pub fn xyz<Wp: WhitePoint>(&self) -> Xyz<Wp, T> {
+        Xyz::with_wp(self.x, T::one(), self.z)
+    }
+
+    ///The `(x, y)` chromaticity of the white point.
+    pub fn chromaticity(&self) -> (T, T) {
+        let sum = self.x + T::one() + self.z;
+        (self.x / sum, T::one() / sum)
+    }
+
+    ///The matrix that adapts XYZ colors from this white point to `destination`, using the
+    ///chromatic adaptation `method`. It's the identity matrix if the white points are the same.
+    pub fn adaptation_to(
+        &self,
+        destination: &RuntimeWhitePoint<T>,
+        method: Method,
+    ) -> ColorMatrix<T> {
+        if self == destination {
+            return ColorMatrix::identity();
+        }
+
+        let cone_response = TransformMatrix::<D65, D65, T>::get_cone_response(&method);
+        ColorMatrix::new(adaptation_matrix(
+            cone_response,
+            &self.xyz::<D65>(),
+            &destination.xyz::<D65>(),
+        ))
+    }
+
+    ///Adapt a color that is measured relative to this white point to the white point `Wp`, using
+    ///the chromatic adaptation `method`.
+    pub fn adapt_to_wp<Wp: WhitePoint>(&self, color: Xyz<Wp, T>, method: Method) -> Xyz<Wp, T> {
+        let matrix = self.adaptation_to(&RuntimeWhitePoint::from_white_point::<Wp>(), method);
+        let [x, y, z] = matrix.transform([color.x, color.y, color.z]);
+        Xyz::with_wp(x, y, z)
+    }
+
+    ///Adapt a color from the white point `Wp` to this white point, using the chromatic adaptation
+    ///`method`.
+    pub fn adapt_from_wp<Wp: WhitePoint>(&self, color: Xyz<Wp, T>, method: Method) -> Xyz<Wp, T> {
+        let matrix = RuntimeWhitePoint::from_white_point::<Wp>().adaptation_to(self, method);
+        let [x, y, z] = matrix.transform([color.x, color.y, color.z]);
+        Xyz::with_wp(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RuntimeWhitePoint, WhitePoint, A, D50, D65};
+    use chromatic_adaptation::{AdaptInto, Method};
+    use {ColorMatrix, Xyz};
+
+    #[test]
+    fn runtime_white_point() {
+        let d65 = RuntimeWhitePoint::<f64>::from_white_point::<D65>();
+        assert_relative_eq!(d65.xyz(), D65::get_xyz::<D65, f64>());
+
+        let (x, y) = d65.chromaticity();
+        let from_chromaticity = RuntimeWhitePoint::from_chromaticity(x, y);
+        assert_relative_eq!(from_chromaticity.xyz(), d65.xyz::<D65>(), epsilon = 1e-12);
+
+        let scaled = Xyz::<D50, f64>::with_wp(0.95047, 1.0, 1.08883) * 2.0;
+        let from_xyz = RuntimeWhitePoint::from_xyz(scaled);
+        assert_relative_eq!(from_xyz.xyz(), d65.xyz::<D65>(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn runtime_adaptation() {
+        let a = RuntimeWhitePoint::<f64>::from_white_point::<A>();
+        let color = Xyz::<A, f64>::with_wp(0.315756, 0.162732, 0.015905);
+
+        let expected: Xyz<D65, f64> = color.adapt_into();
+        let measured = Xyz::<D65, f64>::with_wp(color.x, color.y, color.z);
+        let adapted = a.adapt_to_wp(measured, Method::Bradford);
+        assert_relative_eq!(adapted, expected, epsilon = 1e-9);
+
+        let back = a.adapt_from_wp(adapted, Method::Bradford);
+        assert_relative_eq!(back.x, color.x, epsilon = 1e-6);
+        assert_relative_eq!(back.z, color.z, epsilon = 1e-6);
+
+        let identity = a.adaptation_to(&a, Method::VonKries);
+        assert_eq!(identity, ColorMatrix::identity());
+    }
 }
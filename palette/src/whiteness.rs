@@ -0,0 +1,151 @@
+//!The CIE whiteness and tint of white materials.
+//!
+//!The whiteness index `W` of CIE 15 (and ISO 11475) describes how white a
+//!sample, such as paper or a textile, looks compared to a perfect white
+//!diffuser, which has a whiteness of `100`. Bluish whites look whiter, so
+//!samples with optical brighteners often have a whiteness well above `100`.
+//!The tint index `Tw` describes the hue of the white, where positive values
+//!are greenish and negative values are reddish.
+//!
+//!The indices are calculated from the XYZ values of the sample relative to
+//!the white point of the illuminant, which is usually
+//![`D65`](../white_point/struct.D65.html) or
+//![`D65Degree10`](../white_point/struct.D65Degree10.html). The observer of
+//!the white point selects the coefficients of the tint formula.
+//!
+//!```
+//!use palette::Xyz;
+//!use palette::white_point::D65Degree10;
+//!use palette::whiteness::cie_whiteness;
+//!
+//!let paper = Xyz::<D65Degree10, f64>::with_wp(0.8610, 0.8980, 1.0750);
+//!let whiteness = cie_whiteness(paper);
+//!
+//!assert!(whiteness.is_valid());
+//!assert!(whiteness.whiteness > 100.0);
+//!```
+
+use float::Float;
+
+use spectral::Observer;
+use white_point::WhitePoint;
+use {cast, Component, Xyz};
+
+///The CIE whiteness and tint of a sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Whiteness<T> {
+    ///The whiteness index, `W`. A perfect white diffuser has a whiteness
+    ///of `100`.
+    pub whiteness: T,
+
+    ///The tint index, `Tw`. Positive values are greenish and negative values
+    ///are reddish.
+    pub tint: T,
+
+    luminance: T,
+}
+
+impl<T: Component + Float> Whiteness<T> {
+    ///Check if the sample is white enough for the indices to be meaningful.
+    ///
+    ///CIE 15 only considers the indices valid when `W` is above `40` and
+    ///below `5Y - 280`, and `Tw` is between `-4` and `2`, where `Y` is the
+    ///luminance from `0` to `100`.
+    pub fn is_valid(&self) -> bool {
+        self.whiteness > cast(40.0)
+            && self.whiteness < self.luminance * cast(5.0) - cast(280.0)
+            && self.tint > cast(-4.0)
+            && self.tint < cast(2.0)
+    }
+}
+
+///Calculate the CIE whiteness and tint of a sample, relative to the white
+///point `Wp`.
+///
+///The tint uses the coefficient `1000` for the CIE 1931 2° observer and
+///`900` for the CIE 1964 10° observer, as given by the
+///[`observer`](../white_point/trait.WhitePoint.html#method.observer) of
+///`Wp`.
+pub fn cie_whiteness<Wp, T>(color: Xyz<Wp, T>) -> Whiteness<T>
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+{
+    let white: Xyz<Wp, T> = Wp::get_xyz();
+    let (white_x, white_y) = chromaticity(white);
+    let (x, y) = chromaticity(color);
+
+    let luminance = color.y * cast(100.0);
+    let tint_factor: T = match Wp::observer() {
+        Observer::Cie1931 => cast(1000.0),
+        Observer::Cie1964 => cast(900.0),
+    };
+
+    Whiteness {
+        whiteness: luminance
+            + cast::<T, _>(800.0) * (white_x - x)
+            + cast::<T, _>(1700.0) * (white_y - y),
+        tint: tint_factor * (white_x - x) - cast::<T, _>(650.0) * (white_y - y),
+        luminance: luminance,
+    }
+}
+
+fn chromaticity<Wp: WhitePoint, T: Component + Float>(color: Xyz<Wp, T>) -> (T, T) {
+    let sum = color.x + color.y + color.z;
+    (color.x / sum, color.y / sum)
+}
+
+#[cfg(test)]
+mod test {
+    use super::cie_whiteness;
+    use white_point::{D65Degree10, WhitePoint, D65};
+    use Xyz;
+
+    #[test]
+    fn perfect_diffuser() {
+        let whiteness = cie_whiteness(D65::get_xyz::<D65, f64>());
+        assert_relative_eq!(whiteness.whiteness, 100.0, epsilon = 1e-9);
+        assert_relative_eq!(whiteness.tint, 0.0, epsilon = 1e-9);
+        assert!(whiteness.is_valid());
+    }
+
+    #[test]
+    fn indices() {
+        //x = 0.3100 and y = 0.3250, with Y = 90
+        let sample = Xyz::<D65, f64>::with_wp(0.3100 / 0.3250 * 0.9, 0.9, 0.365 / 0.3250 * 0.9);
+        let white = D65::get_xyz::<D65, f64>();
+        let sum = white.x + white.y + white.z;
+        let (dx, dy) = (white.x / sum - 0.3100, white.y / sum - 0.3250);
+
+        let whiteness = cie_whiteness(sample);
+        assert_relative_eq!(
+            whiteness.whiteness,
+            90.0 + 800.0 * dx + 1700.0 * dy,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(whiteness.tint, 1000.0 * dx - 650.0 * dy, epsilon = 1e-9);
+
+        let sample = Xyz::<D65Degree10, f64>::with_wp(sample.x, sample.y, sample.z);
+        let white = D65Degree10::get_xyz::<D65Degree10, f64>();
+        let sum = white.x + white.y + white.z;
+        let (dx, dy) = (white.x / sum - 0.3100, white.y / sum - 0.3250);
+        assert_relative_eq!(
+            cie_whiteness(sample).tint,
+            900.0 * dx - 650.0 * dy,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn validity() {
+        //A yellowish white
+        let sample = Xyz::<D65, f64>::with_wp(0.85, 0.88, 0.75);
+        let whiteness = cie_whiteness(sample);
+        assert!(whiteness.whiteness < 40.0);
+        assert!(!whiteness.is_valid());
+
+        //A gray, that is too dark
+        let gray = D65::get_xyz::<D65, f64>() * 0.5;
+        assert!(!cie_whiteness(gray).is_valid());
+    }
+}
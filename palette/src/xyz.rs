@@ -25,7 +25,7 @@ pub type Xyza<Wp = D65, T = f32> = Alpha<Xyz<Wp, T>, T>;
 ///
 ///Conversions and operations on this color space depend on the defined white
 /// point
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "Wp"]
@@ -64,6 +64,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Zeroable for Xyz<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    Wp: WhitePoint,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Pod for Xyz<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    Wp: WhitePoint + 'static,
+{
+}
+
 impl<Wp, T> Clone for Xyz<Wp, T>
 where
     T: Component + Float,
@@ -115,6 +131,23 @@ where
     }
 }
 
+macro_rules! impl_constants {
+    ($($component: ty),+) => {$(
+        impl<Wp: WhitePoint> Xyz<Wp, $component> {
+            ///Black, with all components at zero. The white point is
+            ///available from [`WhitePoint::get_xyz`](white_point/trait.WhitePoint.html).
+            pub const BLACK: Xyz<Wp, $component> = Xyz {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                white_point: PhantomData,
+            };
+        }
+    )+};
+}
+
+impl_constants!(f32, f64);
+
 ///<span id="Xyza"></span>[`Xyza`](type.Xyza.html) implementations.
 impl<T, A> Alpha<Xyz<D65, T>, A>
 where
@@ -606,6 +639,27 @@ where
     }
 }
 
+///Samples each component uniformly, between `0.0` and the corresponding
+///component of the white point.
+#[cfg(feature = "rand")]
+impl<Wp, T> ::rand::distributions::Distribution<Xyz<Wp, T>> for ::rand::distributions::Standard
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Xyz<Wp, T> {
+        let white: Xyz<Wp, T> = Wp::get_xyz();
+
+        Xyz {
+            x: rng.gen::<T>() * white.x,
+            y: rng.gen::<T>() * white.y,
+            z: rng.gen::<T>() * white.z,
+            white_point: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Xyz;
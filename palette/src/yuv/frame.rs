@@ -0,0 +1,280 @@
+//! Conversion of quantized Y'CbCr frames between standards, ranges and bit depths.
+//!
+//! Converting a frame from one YUV standard to another, for example from BT.601 to BT.709,
+//! involves dequantizing the codes, converting them to R'G'B', decoding the transfer function,
+//! converting the primaries and doing all of it in reverse for the other standard. A
+//! [`FrameConverter`](struct.FrameConverter.html) precomputes the matrices and levels of both
+//! formats, so each pixel is converted in a single pass, without intermediate buffers.
+//!
+//! ```
+//! use palette::encoding::itu::{BT601_625, BT709};
+//! use palette::yuv::frame::{Format, FrameConverter};
+//! use palette::yuv::integer::Range;
+//!
+//! let converter = FrameConverter::<BT601_625, BT709>::new(
+//!     Format::new(8, Range::Limited),
+//!     Format::new(10, Range::Full),
+//! );
+//!
+//! let frame = [[16, 128, 128], [235, 128, 128], [81, 90, 240]];
+//! let mut converted = [[0; 3]; 3];
+//! converter.convert_frame(&frame, &mut converted);
+//!
+//! assert_eq!(converted[0], [0, 512, 512]);
+//! assert_eq!(converted[1], [1023, 512, 512]);
+//! ```
+
+use float::Float;
+
+use converter::Converter;
+use yuv::integer::Range;
+use yuv::{Yuv, YuvStandard};
+use {cast, clamp, Component};
+
+/// The bit depth and range of quantized Y'CbCr codes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Format {
+    /// The number of bits of each component, from 8 to 16.
+    pub depth: u32,
+
+    /// The range of the codes.
+    pub range: Range,
+}
+
+impl Format {
+    /// Create a format with `depth` bits and the range `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is not between 8 and 16 bits.
+    pub fn new(depth: u32, range: Range) -> Format {
+        assert!(
+            (8..=16).contains(&depth),
+            "the bit depth must be between 8 and 16"
+        );
+
+        Format {
+            depth: depth,
+            range: range,
+        }
+    }
+
+    /// The scale and offset of the luminance and chroma codes, as in BT.2100.
+    fn levels<T: Component + Float>(&self) -> Levels<T> {
+        let shift = f64::from(1u32 << (self.depth - 8));
+        let max = f64::from((1u32 << self.depth) - 1);
+
+        let (luma, chroma) = match self.range {
+            Range::Full => ((max, 0.0), (max, shift * 128.0)),
+            Range::Limited => (
+                (shift * 219.0, shift * 16.0),
+                (shift * 224.0, shift * 128.0),
+            ),
+        };
+
+        Levels {
+            luma: (cast(luma.0), cast(luma.1)),
+            chroma: (cast(chroma.0), cast(chroma.1)),
+            max: cast(max),
+        }
+    }
+}
+
+struct Levels<T> {
+    luma: (T, T),
+    chroma: (T, T),
+    max: T,
+}
+
+/// A precomputed conversion of quantized Y'CbCr codes from the standard `Src` to the standard
+/// `Dst`.
+///
+/// The codes are in the order `[Y', Cb, Cr]`. The colors are converted through linear RGB, with
+/// the white points adapted with the Bradford method when they are different, and the matrix is
+/// skipped when the standards have the same primaries and white point.
+pub struct FrameConverter<Src, Dst, T = f32>
+where
+    Src: YuvStandard,
+    Dst: YuvStandard,
+    T: Component + Float,
+{
+    source: Levels<T>,
+    destination: Levels<T>,
+    converter: Converter<Yuv<Src, T>, Yuv<Dst, T>>,
+}
+
+impl<Src, Dst, T> FrameConverter<Src, Dst, T>
+where
+    Src: YuvStandard,
+    Dst: YuvStandard,
+    T: Component + Float,
+{
+    /// Create a converter from codes in the format `source` to codes in the format
+    /// `destination`.
+    pub fn new(source: Format, destination: Format) -> FrameConverter<Src, Dst, T> {
+        FrameConverter {
+            source: source.levels(),
+            destination: destination.levels(),
+            converter: Converter::new(),
+        }
+    }
+
+    /// Convert the codes of a pixel.
+    ///
+    /// Colors that are outside the range of the destination are clamped to the lowest and highest
+    /// codes of its bit depth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a code doesn't fit in the bit depth of the source format.
+    pub fn convert(&self, codes: [u16; 3]) -> [u16; 3] {
+        let source = &self.source;
+        for &code in &codes {
+            assert!(
+                cast::<T, _>(code) <= source.max,
+                "the value must fit in the bit depth"
+            );
+        }
+
+        let dequantize = |code: u16, (scale, offset): (T, T)| (cast::<T, _>(code) - offset) / scale;
+        let color = Yuv::new(
+            dequantize(codes[0], source.luma),
+            dequantize(codes[1], source.chroma),
+            dequantize(codes[2], source.chroma),
+        );
+
+        let color = self.converter.convert(color);
+
+        let destination = &self.destination;
+        let quantize = |value: T, (scale, offset): (T, T)| {
+            cast(clamp(
+                (value * scale + offset).round(),
+                T::zero(),
+                destination.max,
+            ))
+        };
+
+        [
+            quantize(color.luminance, destination.luma),
+            quantize(color.blue_diff, destination.chroma),
+            quantize(color.red_diff, destination.chroma),
+        ]
+    }
+
+    /// Convert each pixel in `input` and write it to `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frames don't have the same number of pixels, or if a code doesn't fit in the
+    /// bit depth of the source format.
+    pub fn convert_frame(&self, input: &[[u16; 3]], output: &mut [[u16; 3]]) {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "the frames must have the same number of pixels"
+        );
+
+        for (&codes, result) in input.iter().zip(output) {
+            *result = self.convert(codes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, FrameConverter};
+    use converter::Converter;
+    use encoding::itu::{BT601_625, BT709};
+    use rgb::Rgb;
+    use yuv::integer::{ycbcr_into_range, ycbcr_shift_depth, Range};
+    use yuv::Yuv;
+
+    #[test]
+    fn same_standard() {
+        for &(from, to) in &[
+            (Range::Limited, Range::Full),
+            (Range::Full, Range::Limited),
+            (Range::Limited, Range::Limited),
+        ] {
+            let converter = FrameConverter::<BT709, BT709, f64>::new(
+                Format::new(10, from),
+                Format::new(10, to),
+            );
+
+            for &codes in &[
+                [64, 512, 512],
+                [940, 64, 960],
+                [500, 300, 700],
+                [0, 1023, 4],
+            ] {
+                assert_eq!(
+                    converter.convert(codes),
+                    ycbcr_into_range(codes, 10, from, to)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn depths() {
+        let converter = FrameConverter::<BT709, BT709, f64>::new(
+            Format::new(8, Range::Limited),
+            Format::new(10, Range::Limited),
+        );
+
+        for &codes in &[[16, 128, 128], [235, 16, 240], [100, 50, 200]] {
+            assert_eq!(converter.convert(codes), ycbcr_shift_depth(codes, 8, 10));
+        }
+    }
+
+    #[test]
+    fn standards() {
+        let converter = FrameConverter::<BT601_625, BT709, f64>::new(
+            Format::new(10, Range::Limited),
+            Format::new(10, Range::Limited),
+        );
+
+        // Grays keep their codes, since the white points are the same.
+        for &luma in &[64, 300, 940] {
+            assert_eq!(converter.convert([luma, 512, 512]), [luma, 512, 512]);
+        }
+
+        let rgb = Rgb::<BT601_625, f64>::new(0.8, 0.3, 0.1);
+        let yuv: Yuv<BT601_625, f64> = Converter::new().convert(rgb);
+        let codes = [
+            (yuv.luminance * 876.0 + 64.0).round() as u16,
+            (yuv.blue_diff * 896.0 + 512.0).round() as u16,
+            (yuv.red_diff * 896.0 + 512.0).round() as u16,
+        ];
+
+        let expected: Yuv<BT709, f64> = Converter::new().convert(rgb);
+        let converted = converter.convert(codes);
+        let expected = [
+            expected.luminance * 876.0 + 64.0,
+            expected.blue_diff * 896.0 + 512.0,
+            expected.red_diff * 896.0 + 512.0,
+        ];
+        for (&code, &expected) in converted.iter().zip(&expected) {
+            assert!((f64::from(code) - expected).abs() <= 1.5);
+        }
+    }
+
+    #[test]
+    fn clamped() {
+        let converter = FrameConverter::<BT709, BT709, f32>::new(
+            Format::new(10, Range::Full),
+            Format::new(8, Range::Full),
+        );
+        assert_eq!(converter.convert([1023, 0, 1023]), [255, 0, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value must fit in the bit depth")]
+    fn overflow() {
+        let converter = FrameConverter::<BT709, BT709, f32>::new(
+            Format::new(8, Range::Full),
+            Format::new(8, Range::Limited),
+        );
+        converter.convert([256, 0, 0]);
+    }
+}
@@ -0,0 +1,576 @@
+//! Integer-only conversions of 8 bit and deeper components.
+//!
+//! These functions only use integer arithmetic, so they can be used on targets without a floating
+//! point unit. The YCbCr conversions use the fixed point weights of an
+//! [`IntegerDifferenceFn`](../trait.IntegerDifferenceFn.html), while the range and bit depth
+//! conversions are exact.
+//!
+//! Mixing up limited and full range values is a common mistake, that makes video look washed out
+//! or crushes its shadows and highlights. The `*_into_range` functions convert between them, for
+//! both RGB and Y'CbCr components of any bit depth from 8 to 16 bits.
+//!
+//! ```
+//! use palette::encoding::itu::BT601_625;
+//! use palette::yuv::integer::{rgb_to_ycbcr, ycbcr_to_rgb, Range};
+//!
+//! let ycbcr = rgb_to_ycbcr::<BT601_625>([255, 128, 0], Range::Full);
+//! assert_eq!(ycbcr, [151, 43, 202]);
+//!
+//! // Not every RGB color has an exact 8 bit YCbCr representation.
+//! let rgb = ycbcr_to_rgb::<BT601_625>(ycbcr, Range::Full);
+//! assert_eq!(rgb, [255, 127, 0]);
+//! ```
+
+use depth::{convert_depth, Scaling};
+use yuv::{IntegerDifferenceFn, YuvStandard};
+
+/// The range of the digital values of a component.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Range {
+    /// All values from `0` to `255` are used. This is common for images, such as JPEG.
+    Full,
+
+    /// Luminance values from `16` to `235` and chroma values from `16` to `240` are used, while
+    /// the values outside of them are head- and footroom. This is common for video. Deeper
+    /// components have the same limits, multiplied by `2^(depth - 8)`, so 10 bit luminance goes
+    /// from `64` to `940`.
+    Limited,
+}
+
+/// Convert 8 bit R'G'B' components to Y'CbCr, in the order `[Y', Cb, Cr]`.
+///
+/// The RGB components are used as they are, without changing their transfer function. This
+/// makes it possible to convert sRGB components directly, as is done in JPEG images. The result
+/// is at most 1 away from the rounded result of the floating point conversion.
+pub fn rgb_to_ycbcr<S>(rgb: [u8; 3], range: Range) -> [u8; 3]
+where
+    S: YuvStandard,
+    S::DifferenceFn: IntegerDifferenceFn,
+{
+    let rgb = [i32::from(rgb[0]), i32::from(rgb[1]), i32::from(rgb[2])];
+
+    let ([y, cb, cr], y_offset) = match range {
+        Range::Full => (multiply(&S::DifferenceFn::INTO_FULL, rgb), 0),
+        Range::Limited => (multiply(&S::DifferenceFn::INTO_LIMITED, rgb), 16),
+    };
+
+    [
+        clamp_u8(y + y_offset),
+        clamp_u8(cb + 128),
+        clamp_u8(cr + 128),
+    ]
+}
+
+/// Convert 8 bit Y'CbCr components, in the order `[Y', Cb, Cr]`, to R'G'B'.
+///
+/// The RGB components have the same transfer function as the components that were converted to
+/// YCbCr, and are clamped to `[0, 255]`. The result is at most 1 away from the rounded result of
+/// the floating point conversion.
+pub fn ycbcr_to_rgb<S>(ycbcr: [u8; 3], range: Range) -> [u8; 3]
+where
+    S: YuvStandard,
+    S::DifferenceFn: IntegerDifferenceFn,
+{
+    let [y, cb, cr] = [
+        i32::from(ycbcr[0]),
+        i32::from(ycbcr[1]),
+        i32::from(ycbcr[2]),
+    ];
+
+    let [red, green, blue] = match range {
+        Range::Full => multiply(&S::DifferenceFn::FROM_FULL, [y, cb - 128, cr - 128]),
+        Range::Limited => multiply(&S::DifferenceFn::FROM_LIMITED, [y - 16, cb - 128, cr - 128]),
+    };
+
+    [clamp_u8(red), clamp_u8(green), clamp_u8(blue)]
+}
+
+/// Convert a full range luminance or RGB value to limited range, rounded to the nearest value.
+pub fn luma_into_limited(value: u8) -> u8 {
+    (divide_rounded(i64::from(value) * 219, 255) + 16) as u8
+}
+
+/// Convert a limited range luminance or RGB value to full range, rounded to the nearest value.
+/// Values in the head- and footroom are clamped.
+pub fn luma_into_full(value: u8) -> u8 {
+    clamp_u8(divide_rounded((i64::from(value) - 16) * 255, 219) as i32)
+}
+
+/// Convert a full range chroma value to limited range, rounded to the nearest value.
+pub fn chroma_into_limited(value: u8) -> u8 {
+    divide_rounded((i64::from(value) - 128) * 224 + 128 * 255, 255) as u8
+}
+
+/// Convert a limited range chroma value to full range, rounded to the nearest value. Values in
+/// the head- and footroom are clamped.
+pub fn chroma_into_full(value: u8) -> u8 {
+    clamp_u8(divide_rounded((i64::from(value) - 128) * 255 + 128 * 224, 224) as i32)
+}
+
+/// Convert a luminance or R'G'B' value with `depth` bits from the range `from` to the range `to`,
+/// rounded to the nearest value. Values in the head- and footroom are clamped when they are
+/// converted to full range.
+///
+/// ```
+/// use palette::yuv::integer::{luma_into_range, Range};
+///
+/// assert_eq!(luma_into_range(64, 10, Range::Limited, Range::Full), 0);
+/// assert_eq!(luma_into_range(940, 10, Range::Limited, Range::Full), 1023);
+/// assert_eq!(luma_into_range(65535, 16, Range::Full, Range::Limited), 60160);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `depth` is not between 8 and 16 bits, or if `value` doesn't fit in `depth` bits.
+pub fn luma_into_range(value: u16, depth: u32, from: Range, to: Range) -> u16 {
+    into_range(value, depth, from, to, luma_levels)
+}
+
+/// Convert a Cb or Cr value with `depth` bits from the range `from` to the range `to`, rounded
+/// to the nearest value. Values in the head- and footroom are clamped when they are converted to
+/// full range.
+///
+/// # Panics
+///
+/// Panics if `depth` is not between 8 and 16 bits, or if `value` doesn't fit in `depth` bits.
+pub fn chroma_into_range(value: u16, depth: u32, from: Range, to: Range) -> u16 {
+    into_range(value, depth, from, to, chroma_levels)
+}
+
+/// Convert R'G'B' components with `depth` bits from the range `from` to the range `to`.
+///
+/// # Panics
+///
+/// Panics if `depth` is not between 8 and 16 bits, or if a component doesn't fit in `depth`
+/// bits.
+pub fn rgb_into_range(rgb: [u16; 3], depth: u32, from: Range, to: Range) -> [u16; 3] {
+    [
+        luma_into_range(rgb[0], depth, from, to),
+        luma_into_range(rgb[1], depth, from, to),
+        luma_into_range(rgb[2], depth, from, to),
+    ]
+}
+
+/// Convert Y'CbCr components with `depth` bits, in the order `[Y', Cb, Cr]`, from the range
+/// `from` to the range `to`.
+///
+/// ```
+/// use palette::yuv::integer::{ycbcr_into_range, Range};
+///
+/// let black = ycbcr_into_range([0, 512, 512], 10, Range::Full, Range::Limited);
+/// assert_eq!(black, [64, 512, 512]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `depth` is not between 8 and 16 bits, or if a component doesn't fit in `depth`
+/// bits.
+pub fn ycbcr_into_range(ycbcr: [u16; 3], depth: u32, from: Range, to: Range) -> [u16; 3] {
+    [
+        luma_into_range(ycbcr[0], depth, from, to),
+        chroma_into_range(ycbcr[1], depth, from, to),
+        chroma_into_range(ycbcr[2], depth, from, to),
+    ]
+}
+
+/// Change the bit depth of a component from `from` bits to `to` bits, rounded to the nearest
+/// value. The maximum value of one depth is mapped to the maximum value of the other, as with
+/// [`Scaling::Rescale`](../../depth/enum.Scaling.html).
+///
+/// ```
+/// use palette::yuv::integer::change_depth;
+///
+/// assert_eq!(change_depth(255, 8, 16), 65535);
+/// assert_eq!(change_depth(512, 10, 8), 128);
+/// ```
+///
+/// # Panics
+///
+/// Panics if either depth is not between 1 and 16 bits, or if `value` doesn't fit in `from` bits.
+pub fn change_depth(value: u16, from: u32, to: u32) -> u16 {
+    convert_depth(value, from, to, Scaling::Rescale)
+}
+
+/// Change the bit depth of a video code value from `from` bits to `to` bits, the way video
+/// formats do, as specified in BT.2100. Deeper values are shifted to the left, and shallower
+/// values are divided by a power of two and rounded to the nearest value, with halfway cases
+/// rounded up. This keeps limited range values on their levels, so 8 bit black and white, `16`
+/// and `235`, become `64` and `940` in 10 bits, while [`change_depth`](fn.change_depth.html) is
+/// the convention for photographic images.
+///
+/// ```
+/// use palette::yuv::integer::shift_depth;
+///
+/// assert_eq!(shift_depth(235, 8, 10), 940);
+/// assert_eq!(shift_depth(942, 10, 8), 236);
+/// ```
+///
+/// Values that would round past the highest value of the new depth are clamped.
+///
+/// # Panics
+///
+/// Panics if either depth is not between 1 and 16 bits, or if `value` doesn't fit in `from` bits.
+pub fn shift_depth(value: u16, from: u32, to: u32) -> u16 {
+    convert_depth(value, from, to, Scaling::Shift)
+}
+
+/// Change the bit depth of Y'CbCr components, in the order `[Y', Cb, Cr]`, from `from` bits to
+/// `to` bits, with [`shift_depth`](fn.shift_depth.html).
+///
+/// ```
+/// use palette::yuv::integer::ycbcr_shift_depth;
+///
+/// assert_eq!(ycbcr_shift_depth([16, 128, 240], 8, 10), [64, 512, 960]);
+/// assert_eq!(ycbcr_shift_depth([3760, 2048, 257], 12, 8), [235, 128, 16]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if either depth is not between 1 and 16 bits, or if a component doesn't fit in `from`
+/// bits.
+pub fn ycbcr_shift_depth(ycbcr: [u16; 3], from: u32, to: u32) -> [u16; 3] {
+    [
+        shift_depth(ycbcr[0], from, to),
+        shift_depth(ycbcr[1], from, to),
+        shift_depth(ycbcr[2], from, to),
+    ]
+}
+
+/// The scale and offset of luminance or R'G'B' values with `depth` bits, as in BT.2100.
+fn luma_levels(depth: u32, range: Range) -> (i64, i64) {
+    match range {
+        Range::Full => ((1 << depth) - 1, 0),
+        Range::Limited => (219 << (depth - 8), 16 << (depth - 8)),
+    }
+}
+
+/// The scale and offset of chroma values with `depth` bits, as in BT.2100.
+fn chroma_levels(depth: u32, range: Range) -> (i64, i64) {
+    match range {
+        Range::Full => ((1 << depth) - 1, 1 << (depth - 1)),
+        Range::Limited => (224 << (depth - 8), 128 << (depth - 8)),
+    }
+}
+
+fn into_range(
+    value: u16,
+    depth: u32,
+    from: Range,
+    to: Range,
+    levels: fn(u32, Range) -> (i64, i64),
+) -> u16 {
+    assert!(
+        (8..=16).contains(&depth),
+        "the bit depth must be between 8 and 16"
+    );
+    assert!(
+        u32::from(value) < 1 << depth,
+        "the value must fit in the bit depth"
+    );
+
+    let (from_scale, from_offset) = levels(depth, from);
+    let (to_scale, to_offset) = levels(depth, to);
+    let value = divide_rounded(
+        (i64::from(value) - from_offset) * to_scale + to_offset * from_scale,
+        from_scale,
+    );
+
+    if value < 0 {
+        0
+    } else if value >= 1 << depth {
+        ((1 << depth) - 1) as u16
+    } else {
+        value as u16
+    }
+}
+
+/// Multiply with a matrix with 16 fractional bits and round the result.
+fn multiply(m: &[i32; 9], [a, b, c]: [i32; 3]) -> [i32; 3] {
+    let round = |x: i32| (x + (1 << 15)) >> 16;
+
+    [
+        round(m[0] * a + m[1] * b + m[2] * c),
+        round(m[3] * a + m[4] * b + m[5] * c),
+        round(m[6] * a + m[7] * b + m[8] * c),
+    ]
+}
+
+/// Divide and round halfway cases away from zero.
+fn divide_rounded(numerator: i64, denominator: i64) -> i64 {
+    if numerator < 0 {
+        (numerator - denominator / 2) / denominator
+    } else {
+        (numerator + denominator / 2) / denominator
+    }
+}
+
+fn clamp_u8(value: i32) -> u8 {
+    if value < 0 {
+        0
+    } else if value > 255 {
+        255
+    } else {
+        value as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        change_depth, chroma_into_full, chroma_into_limited, chroma_into_range, luma_into_full,
+        luma_into_limited, luma_into_range, rgb_into_range, rgb_to_ycbcr, shift_depth,
+        ycbcr_into_range, ycbcr_shift_depth, ycbcr_to_rgb, Range,
+    };
+    use encoding::itu::{BT601_625, BT709};
+    use yuv::{DifferenceFn, IntegerDifferenceFn, YuvStandard};
+
+    fn scales(range: Range) -> (f64, f64, f64) {
+        match range {
+            Range::Full => (255.0, 255.0, 0.0),
+            Range::Limited => (219.0, 224.0, 16.0),
+        }
+    }
+
+    fn reference_ycbcr<D: DifferenceFn>(rgb: [u8; 3], range: Range) -> [f64; 3] {
+        let (luma_scale, chroma_scale, offset) = scales(range);
+        let weights = D::luminance::<f64>();
+        let [r, g, b] = [
+            rgb[0] as f64 / 255.0,
+            rgb[1] as f64 / 255.0,
+            rgb[2] as f64 / 255.0,
+        ];
+        let y = weights[0] * r + weights[1] * g + weights[2] * b;
+
+        [
+            y * luma_scale + offset,
+            D::normalize_blue(b - y) * chroma_scale + 128.0,
+            D::normalize_red(r - y) * chroma_scale + 128.0,
+        ]
+    }
+
+    fn reference_rgb<D: DifferenceFn>(ycbcr: [u8; 3], range: Range) -> [f64; 3] {
+        let (luma_scale, chroma_scale, offset) = scales(range);
+        let weights = D::luminance::<f64>();
+        let y = (ycbcr[0] as f64 - offset) / luma_scale;
+        let b = y + D::denormalize_blue((ycbcr[1] as f64 - 128.0) / chroma_scale);
+        let r = y + D::denormalize_red((ycbcr[2] as f64 - 128.0) / chroma_scale);
+        let g = (y - weights[0] * r - weights[2] * b) / weights[1];
+
+        [r * 255.0, g * 255.0, b * 255.0]
+    }
+
+    fn assert_close(computed: [u8; 3], reference: [f64; 3], input: [u8; 3]) {
+        for (&computed, &reference) in computed.iter().zip(&reference) {
+            let reference = reference.round().clamp(0.0, 255.0);
+            assert!(
+                (computed as f64 - reference).abs() <= 1.0,
+                "{:?} became {:?}, expected {:?}",
+                input,
+                computed,
+                reference
+            );
+        }
+    }
+
+    fn check_standard<S>()
+    where
+        S: YuvStandard,
+        S::DifferenceFn: IntegerDifferenceFn,
+    {
+        for &range in &[Range::Full, Range::Limited] {
+            for r in (0..256).step_by(5) {
+                for g in (0..256).step_by(5) {
+                    for b in (0..256).step_by(5) {
+                        let input = [r as u8, g as u8, b as u8];
+
+                        assert_close(
+                            rgb_to_ycbcr::<S>(input, range),
+                            reference_ycbcr::<S::DifferenceFn>(input, range),
+                            input,
+                        );
+                        assert_close(
+                            ycbcr_to_rgb::<S>(input, range),
+                            reference_rgb::<S::DifferenceFn>(input, range),
+                            input,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bt601() {
+        check_standard::<BT601_625>();
+    }
+
+    #[test]
+    fn bt709() {
+        check_standard::<BT709>();
+    }
+
+    #[test]
+    fn white_and_black() {
+        assert_eq!(
+            rgb_to_ycbcr::<BT601_625>([255; 3], Range::Full),
+            [255, 128, 128]
+        );
+        assert_eq!(
+            rgb_to_ycbcr::<BT601_625>([0; 3], Range::Limited),
+            [16, 128, 128]
+        );
+        assert_eq!(
+            rgb_to_ycbcr::<BT601_625>([255; 3], Range::Limited),
+            [235, 128, 128]
+        );
+        assert_eq!(
+            ycbcr_to_rgb::<BT601_625>([235, 128, 128], Range::Limited),
+            [255; 3]
+        );
+        assert_eq!(
+            ycbcr_to_rgb::<BT601_625>([16, 128, 128], Range::Limited),
+            [0; 3]
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        for value in 0..256 {
+            let full = value as f64;
+            let limited = value as u8;
+
+            assert_eq!(
+                luma_into_limited(value as u8) as f64,
+                (full * 219.0 / 255.0).round() + 16.0
+            );
+            assert_eq!(
+                chroma_into_limited(value as u8) as f64,
+                ((full - 128.0) * 224.0 / 255.0).round() + 128.0
+            );
+            assert_eq!(
+                luma_into_full(limited) as f64,
+                ((full - 16.0) * 255.0 / 219.0).round().clamp(0.0, 255.0)
+            );
+            assert_eq!(
+                chroma_into_full(limited) as f64,
+                ((full - 128.0) * 255.0 / 224.0 + 128.0)
+                    .round()
+                    .clamp(0.0, 255.0)
+            );
+        }
+    }
+
+    #[test]
+    fn deep_ranges() {
+        for value in 0..256u16 {
+            assert_eq!(
+                luma_into_range(value, 8, Range::Full, Range::Limited),
+                u16::from(luma_into_limited(value as u8))
+            );
+            assert_eq!(
+                luma_into_range(value, 8, Range::Limited, Range::Full),
+                u16::from(luma_into_full(value as u8))
+            );
+            assert_eq!(
+                chroma_into_range(value, 8, Range::Full, Range::Limited),
+                u16::from(chroma_into_limited(value as u8))
+            );
+            assert_eq!(
+                chroma_into_range(value, 8, Range::Limited, Range::Full),
+                u16::from(chroma_into_full(value as u8))
+            );
+        }
+
+        for &depth in &[10, 12, 16] {
+            let max = ((1u32 << depth) - 1) as f64;
+            let step = 1 << (depth - 8);
+
+            for value in (0..(1u32 << depth)).step_by(7) {
+                let full = value as f64;
+                let value = value as u16;
+
+                assert_eq!(
+                    luma_into_range(value, depth, Range::Full, Range::Limited) as f64,
+                    (full * 219.0 * step as f64 / max).round() + 16.0 * step as f64
+                );
+                assert_eq!(
+                    luma_into_range(value, depth, Range::Limited, Range::Full) as f64,
+                    ((full - 16.0 * step as f64) * max / (219.0 * step as f64))
+                        .round()
+                        .clamp(0.0, max)
+                );
+                assert_eq!(
+                    chroma_into_range(value, depth, Range::Limited, Range::Full) as f64,
+                    ((full - 128.0 * step as f64) * max / (224.0 * step as f64)
+                        + (max + 1.0) / 2.0)
+                        .round()
+                        .clamp(0.0, max)
+                );
+                assert_eq!(
+                    luma_into_range(value, depth, Range::Full, Range::Full),
+                    value
+                );
+            }
+        }
+
+        assert_eq!(
+            rgb_into_range([0, 512, 1023], 10, Range::Full, Range::Limited),
+            [64, 502, 940]
+        );
+        assert_eq!(
+            ycbcr_into_range([940, 64, 960], 10, Range::Limited, Range::Full),
+            [1023, 1, 1023]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the bit depth must be between 8 and 16")]
+    fn shallow_range() {
+        luma_into_range(0, 6, Range::Full, Range::Limited);
+    }
+
+    #[test]
+    fn depths() {
+        for &(from, to) in &[(8, 16), (16, 8), (10, 8), (8, 10), (12, 16), (1, 8)] {
+            let from_max = ((1u32 << from) - 1) as f64;
+            let to_max = ((1u32 << to) - 1) as f64;
+
+            for value in 0..(1u32 << from) {
+                assert_eq!(
+                    change_depth(value as u16, from, to) as f64,
+                    (value as f64 * to_max / from_max).round()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shifted_depths() {
+        for value in 0..256u16 {
+            let promoted = shift_depth(value, 8, 10);
+            assert_eq!(promoted, value * 4);
+            assert_eq!(shift_depth(promoted, 10, 8), value);
+            assert_eq!(shift_depth(value, 8, 16), value << 8);
+        }
+
+        assert_eq!(shift_depth(941, 10, 8), 235);
+        assert_eq!(shift_depth(942, 10, 8), 236);
+        assert_eq!(shift_depth(1023, 10, 8), 255);
+        assert_eq!(shift_depth(65535, 16, 10), 1023);
+
+        // Limited range levels stay in place, unlike with rescaling
+        let black_and_white = [64, 940, 512];
+        assert_eq!(
+            ycbcr_shift_depth(black_and_white, 10, 12),
+            [256, 3760, 2048]
+        );
+        assert_eq!(ycbcr_shift_depth(black_and_white, 10, 8), [16, 235, 128]);
+        assert_eq!(change_depth(940, 10, 8), 234);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value must fit in the bit depth")]
+    fn depth_overflow() {
+        change_depth(256, 8, 16);
+    }
+}
@@ -0,0 +1,272 @@
+//! Chroma keying, for making the background of green or blue screen footage transparent.
+//!
+//! A [`ChromaKey`](struct.ChromaKey.html) compares the chroma components of each color with the
+//! chroma of a key color, while the luminance is ignored, so the shadows and highlights of the
+//! background are keyed just like the rest of it. Colors that are within the tolerance of the key
+//! become transparent, colors that are farther away than the tolerance and the softness stay
+//! opaque, and the colors in between become partially transparent.
+//!
+//! The edges of the foreground are often tinted by light that is reflected from the background.
+//! This spill can be removed with [`suppress_spill`](struct.ChromaKey.html#method.suppress_spill).
+//!
+//! ```
+//! use palette::encoding::itu::BT709;
+//! use palette::yuv::key::ChromaKey;
+//! use palette::rgb::Rgb;
+//! use palette::yuv::Yuv;
+//!
+//! let screen = Yuv::<BT709>::from(Rgb::<BT709>::new(0.1, 0.8, 0.2));
+//! let key = ChromaKey::new(screen, 0.1, 0.1);
+//!
+//! let background = Yuv::<BT709>::from(Rgb::<BT709>::new(0.05, 0.6, 0.15));
+//! let skin = Yuv::<BT709>::from(Rgb::<BT709>::new(0.9, 0.7, 0.6));
+//! assert_eq!(key.matte(&background), 0.0);
+//! assert_eq!(key.matte(&skin), 1.0);
+//!
+//! let keyed = key.key(skin, 0.5);
+//! assert_eq!(keyed.alpha, 1.0);
+//! ```
+
+use float::Float;
+
+use yuv::{Yuv, YuvStandard};
+use {cast, clamp, Alpha, Component};
+
+/// The chroma plane in which the distance to the key color is measured.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyPlane {
+    /// The straight line distance between the `(Cb, Cr)` coordinates of the colors. The tolerance
+    /// and softness are in the same unit as the chroma components.
+    Cartesian,
+
+    /// The difference between the hue angles of the colors, in the polar form of the `(Cb, Cr)`
+    /// plane. The tolerance and softness are in degrees.
+    ///
+    /// Only the hue is compared, so a color has to be at least as saturated as the key to be
+    /// fully transparent. Less saturated colors keep a part of their opacity, and gray colors are
+    /// always opaque.
+    Polar,
+}
+
+/// A chroma keyer, that computes an alpha matte from the distance to a key color.
+#[derive(Debug, PartialEq)]
+pub struct ChromaKey<S: YuvStandard, T: Float = f32> {
+    /// The color of the background that should become transparent.
+    pub key: Yuv<S, T>,
+
+    /// The greatest distance from the key where colors are fully transparent.
+    pub tolerance: T,
+
+    /// The width of the transition from fully transparent to fully opaque, beyond the tolerance.
+    pub softness: T,
+
+    /// The plane where the distance is measured.
+    pub plane: KeyPlane,
+}
+
+impl<S: YuvStandard, T: Float> Copy for ChromaKey<S, T> {}
+
+impl<S: YuvStandard, T: Float> Clone for ChromaKey<S, T> {
+    fn clone(&self) -> ChromaKey<S, T> {
+        *self
+    }
+}
+
+impl<S: YuvStandard, T: Component + Float> ChromaKey<S, T> {
+    /// Create a keyer that measures the distance in the `(Cb, Cr)` plane.
+    pub fn new(key: Yuv<S, T>, tolerance: T, softness: T) -> ChromaKey<S, T> {
+        ChromaKey {
+            key,
+            tolerance,
+            softness,
+            plane: KeyPlane::Cartesian,
+        }
+    }
+
+    /// Create a keyer that measures the hue difference, in degrees, in the polar form of the
+    /// `(Cb, Cr)` plane.
+    pub fn polar(key: Yuv<S, T>, tolerance: T, softness: T) -> ChromaKey<S, T> {
+        ChromaKey {
+            key,
+            tolerance,
+            softness,
+            plane: KeyPlane::Polar,
+        }
+    }
+
+    /// Compute the alpha of `color`, where `0.0` is fully transparent and `1.0` is fully opaque.
+    pub fn matte(&self, color: &Yuv<S, T>) -> T {
+        match self.plane {
+            KeyPlane::Cartesian => {
+                let blue = color.blue_diff - self.key.blue_diff;
+                let red = color.red_diff - self.key.red_diff;
+                self.ramp((blue * blue + red * red).sqrt())
+            }
+            KeyPlane::Polar => {
+                let key_chroma = chroma(&self.key);
+                let color_chroma = chroma(color);
+                if key_chroma <= T::zero() || color_chroma <= T::zero() {
+                    return T::one();
+                }
+
+                let mut difference = (hue(color) - hue(&self.key)).to_degrees().abs();
+                if difference > cast(180.0) {
+                    difference = cast::<T, _>(360.0) - difference;
+                }
+
+                let alpha = self.ramp(difference);
+                let saturation = clamp(color_chroma / key_chroma, T::zero(), T::one());
+                alpha.max(T::one() - saturation)
+            }
+        }
+    }
+
+    /// Remove the key color that has spilled onto `color`.
+    ///
+    /// The part of the chroma of `color` that points in the direction of the key's chroma is
+    /// reduced by `amount`, where `0.0` keeps the color as it is and `1.0` removes all of it.
+    /// Colors with a chroma in any other direction are not changed, and neither is the
+    /// luminance.
+    pub fn suppress_spill(&self, color: Yuv<S, T>, amount: T) -> Yuv<S, T> {
+        let key_chroma = chroma(&self.key);
+        if key_chroma <= T::zero() {
+            return color;
+        }
+
+        let direction_blue = self.key.blue_diff / key_chroma;
+        let direction_red = self.key.red_diff / key_chroma;
+        let spill = color.blue_diff * direction_blue + color.red_diff * direction_red;
+        if spill <= T::zero() {
+            return color;
+        }
+
+        let removed = spill * clamp(amount, T::zero(), T::one());
+        Yuv::new(
+            color.luminance,
+            color.blue_diff - removed * direction_blue,
+            color.red_diff - removed * direction_red,
+        )
+    }
+
+    /// Key `color`, by computing its matte and suppressing the spill with `spill` as the amount.
+    pub fn key(&self, color: Yuv<S, T>, spill: T) -> Alpha<Yuv<S, T>, T> {
+        Alpha {
+            color: self.suppress_spill(color, spill),
+            alpha: self.matte(&color),
+        }
+    }
+
+    /// Map a distance to an alpha value, with a linear transition that starts at the tolerance
+    /// and is as wide as the softness.
+    fn ramp(&self, distance: T) -> T {
+        if distance <= self.tolerance {
+            T::zero()
+        } else if self.softness <= T::zero() || distance >= self.tolerance + self.softness {
+            T::one()
+        } else {
+            (distance - self.tolerance) / self.softness
+        }
+    }
+}
+
+fn chroma<S: YuvStandard, T: Float>(color: &Yuv<S, T>) -> T {
+    (color.blue_diff * color.blue_diff + color.red_diff * color.red_diff).sqrt()
+}
+
+fn hue<S: YuvStandard, T: Float>(color: &Yuv<S, T>) -> T {
+    color.red_diff.atan2(color.blue_diff)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChromaKey;
+    use encoding::itu::BT709;
+    use rgb::Rgb;
+    use yuv::Yuv;
+
+    fn yuv(red: f64, green: f64, blue: f64) -> Yuv<BT709, f64> {
+        Yuv::from(Rgb::<BT709, f64>::new(red, green, blue))
+    }
+
+    #[test]
+    fn cartesian_matte() {
+        let key = ChromaKey::new(Yuv::<BT709, f64>::new(0.5, -0.3, -0.3), 0.1, 0.2);
+
+        assert_eq!(key.matte(&Yuv::new(0.2, -0.3, -0.3)), 0.0);
+        assert_eq!(key.matte(&Yuv::new(0.9, -0.25, -0.3)), 0.0);
+        assert_relative_eq!(key.matte(&Yuv::new(0.5, -0.1, -0.3)), 0.5, epsilon = 1e-12);
+        assert_eq!(key.matte(&Yuv::new(0.5, 0.0, 0.0)), 1.0);
+    }
+
+    #[test]
+    fn hard_edge() {
+        let key = ChromaKey::new(Yuv::<BT709, f64>::new(0.5, -0.3, -0.3), 0.1, 0.0);
+
+        assert_eq!(key.matte(&Yuv::new(0.5, -0.3, -0.25)), 0.0);
+        assert_eq!(key.matte(&Yuv::new(0.5, -0.3, -0.15)), 1.0);
+    }
+
+    #[test]
+    fn polar_matte() {
+        let key = ChromaKey::polar(Yuv::<BT709, f64>::new(0.5, -0.2, -0.2), 10.0, 20.0);
+
+        //Same hue, but darker and more saturated
+        assert_eq!(key.matte(&Yuv::new(0.3, -0.3, -0.3)), 0.0);
+        //Same hue, but half as saturated
+        assert_relative_eq!(key.matte(&Yuv::new(0.5, -0.1, -0.1)), 0.5, epsilon = 1e-12);
+        //Gray
+        assert_eq!(key.matte(&Yuv::new(0.5, 0.0, 0.0)), 1.0);
+        //Opposite hue
+        assert_eq!(key.matte(&Yuv::new(0.5, 0.2, 0.2)), 1.0);
+
+        //Rotated by 20 degrees
+        let angle = (225.0f64 + 20.0).to_radians();
+        let rotated = Yuv::new(0.5, 0.3 * angle.cos(), 0.3 * angle.sin());
+        assert_relative_eq!(key.matte(&rotated), 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn polar_wraps_around() {
+        let angle = 175.0f64.to_radians();
+        let key = ChromaKey::polar(
+            Yuv::<BT709, f64>::new(0.5, 0.2 * angle.cos(), 0.2 * angle.sin()),
+            15.0,
+            0.0,
+        );
+
+        let angle = (-175.0f64).to_radians();
+        assert_eq!(
+            key.matte(&Yuv::new(0.5, 0.2 * angle.cos(), 0.2 * angle.sin())),
+            0.0
+        );
+    }
+
+    #[test]
+    fn spill_suppression() {
+        let key = ChromaKey::new(yuv(0.0, 1.0, 0.0), 0.1, 0.1);
+
+        let green_tint = yuv(0.6, 0.8, 0.5);
+        let suppressed = key.suppress_spill(green_tint, 1.0);
+        assert_eq!(suppressed.luminance, green_tint.luminance);
+        let remaining =
+            suppressed.blue_diff * key.key.blue_diff + suppressed.red_diff * key.key.red_diff;
+        assert_relative_eq!(remaining, 0.0, epsilon = 1e-12);
+
+        let magenta = yuv(0.8, 0.2, 0.8);
+        assert_eq!(key.suppress_spill(magenta, 1.0), magenta);
+        assert_eq!(key.suppress_spill(green_tint, 0.0), green_tint);
+    }
+
+    #[test]
+    fn key() {
+        let key = ChromaKey::new(yuv(0.0, 1.0, 0.0), 0.1, 0.1);
+
+        let keyed = key.key(yuv(0.0, 0.9, 0.1), 1.0);
+        assert_eq!(keyed.alpha, 0.0);
+
+        let color = yuv(0.9, 0.2, 0.3);
+        let keyed = key.key(color, 1.0);
+        assert_eq!(keyed.alpha, 1.0);
+        assert_eq!(keyed.color, color);
+    }
+}
@@ -5,6 +5,12 @@ use encoding::{TransferFn};
 use rgb::RgbSpace;
 use {Component};
 
+pub use self::yuv::Yuv;
+
+pub mod frame;
+pub mod integer;
+pub mod key;
+pub mod sdi;
 mod quant;
 mod yuv;
 
@@ -52,6 +58,26 @@ pub trait DifferenceFn {
     fn denormalize_red<T: Float>(norm: T) -> T;
 }
 
+/// Fixed point versions of the `DifferenceFn` weights, for conversions without floating point
+/// arithmetic.
+///
+/// The matrices have 16 fractional bits and work on 8 bit components, where the chroma components
+/// are centered around `0` instead of `128` and the limited range luminance starts at `0` instead
+/// of `16`. They are used by the functions in the [`integer`](integer/index.html) module.
+pub trait IntegerDifferenceFn: DifferenceFn {
+    /// Converts full range R'G'B' to full range Y'CbCr.
+    const INTO_FULL: [i32; 9];
+
+    /// Converts full range R'G'B' to limited range Y'CbCr.
+    const INTO_LIMITED: [i32; 9];
+
+    /// Converts full range Y'CbCr to full range R'G'B'.
+    const FROM_FULL: [i32; 9];
+
+    /// Converts limited range Y'CbCr to full range R'G'B'.
+    const FROM_LIMITED: [i32; 9];
+}
+
 /// A digital encoding of a YUV color model.
 ///
 /// This is not a mere type conversion. Instead, it is a standardized encoding depending on the bit
@@ -0,0 +1,222 @@
+//! Packing of 10 bit Y'CbCr 4:2:2 streams, as they come from SDI capture cards.
+//!
+//! An SDI stream carries the components of two neighboring pixels as four 10 bit words, in the
+//! order `Cb, Y'0, Cr, Y'1`, so each pair of pixels shares its chroma. The words `0` to `3` and
+//! `1020` to `1023` are reserved for the timing references of the stream, and never appear in
+//! video data. Values that would end up on them, for example after processing, have to be clipped
+//! with [`clip_reserved`](fn.clip_reserved.html) before they are sent.
+//!
+//! The words are usually stored in memory with 4 words in 5 bytes, with the most significant bit
+//! first, as in SMPTE ST 2110-20.
+//!
+//! ```
+//! use palette::yuv::sdi::{interleave, pack, unpack, deinterleave};
+//!
+//! // Black and white
+//! let luma = [64, 940];
+//! let cb = [512];
+//! let cr = [512];
+//!
+//! let mut words = [0; 4];
+//! interleave(&luma, &cb, &cr, &mut words);
+//! assert_eq!(words, [512, 64, 512, 940]);
+//!
+//! let mut bytes = [0; 5];
+//! pack(&words, &mut bytes);
+//!
+//! let mut unpacked = [0; 4];
+//! unpack(&bytes, &mut unpacked);
+//! assert_eq!(unpacked, words);
+//! ```
+
+/// The lowest word that can be used for video data.
+pub const MIN_CODE: u16 = 4;
+
+/// The highest word that can be used for video data.
+pub const MAX_CODE: u16 = 1019;
+
+/// Check if a word is reserved for timing references.
+pub fn is_reserved(word: u16) -> bool {
+    !(MIN_CODE..=MAX_CODE).contains(&word)
+}
+
+/// Clip a 10 bit word to the range that can be used for video data, from `4` to `1019`.
+pub fn clip_reserved(word: u16) -> u16 {
+    word.clamp(MIN_CODE, MAX_CODE)
+}
+
+/// Clip each word in `words` to the range that can be used for video data.
+pub fn clip_reserved_slice(words: &mut [u16]) {
+    for word in words {
+        *word = clip_reserved(*word);
+    }
+}
+
+/// Interleave the components of a 4:2:2 line into a stream of words, in the order
+/// `Cb, Y'0, Cr, Y'1`.
+///
+/// # Panics
+///
+/// Panics if `luma` doesn't have twice as many values as `cb` and `cr`, or if `words` doesn't
+/// have as many words as there are values in total.
+pub fn interleave(luma: &[u16], cb: &[u16], cr: &[u16], words: &mut [u16]) {
+    assert_422(luma, cb, cr, words);
+
+    for (index, group) in words.chunks_mut(4).enumerate() {
+        group[0] = cb[index];
+        group[1] = luma[index * 2];
+        group[2] = cr[index];
+        group[3] = luma[index * 2 + 1];
+    }
+}
+
+/// Split a stream of words, in the order `Cb, Y'0, Cr, Y'1`, into the components of a 4:2:2
+/// line.
+///
+/// # Panics
+///
+/// Panics if `luma` doesn't have twice as many values as `cb` and `cr`, or if `words` doesn't
+/// have as many words as there are values in total.
+pub fn deinterleave(words: &[u16], luma: &mut [u16], cb: &mut [u16], cr: &mut [u16]) {
+    assert_422(luma, cb, cr, words);
+
+    for (index, group) in words.chunks(4).enumerate() {
+        cb[index] = group[0];
+        luma[index * 2] = group[1];
+        cr[index] = group[2];
+        luma[index * 2 + 1] = group[3];
+    }
+}
+
+/// Pack 10 bit words into bytes, with 4 words in 5 bytes and the most significant bit first.
+///
+/// # Panics
+///
+/// Panics if the number of words isn't a multiple of 4, if `bytes` doesn't have 5 bytes for every
+/// 4 words, or if a word doesn't fit in 10 bits.
+pub fn pack(words: &[u16], bytes: &mut [u8]) {
+    assert_packed(words.len(), bytes.len());
+
+    for (words, bytes) in words.chunks(4).zip(bytes.chunks_mut(5)) {
+        let mut bits = 0u64;
+        for &word in words {
+            assert!(word < 1 << 10, "the value must fit in the bit depth");
+            bits = bits << 10 | u64::from(word);
+        }
+
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = (bits >> (32 - index * 8)) as u8;
+        }
+    }
+}
+
+/// Unpack bytes with 4 words in 5 bytes, and the most significant bit first, into 10 bit words.
+///
+/// The words are unpacked as they are, including reserved words.
+///
+/// # Panics
+///
+/// Panics if the number of words isn't a multiple of 4, or if `bytes` doesn't have 5 bytes for
+/// every 4 words.
+pub fn unpack(bytes: &[u8], words: &mut [u16]) {
+    assert_packed(words.len(), bytes.len());
+
+    for (bytes, words) in bytes.chunks(5).zip(words.chunks_mut(4)) {
+        let bits = bytes
+            .iter()
+            .fold(0u64, |bits, &byte| bits << 8 | u64::from(byte));
+
+        for (index, word) in words.iter_mut().enumerate() {
+            *word = (bits >> (30 - index * 10)) as u16 & 0x3FF;
+        }
+    }
+}
+
+fn assert_422(luma: &[u16], cb: &[u16], cr: &[u16], words: &[u16]) {
+    assert!(
+        cb.len() == cr.len() && luma.len() == cb.len() * 2,
+        "there must be two luma values for each pair of chroma values"
+    );
+    assert_eq!(
+        words.len(),
+        luma.len() * 2,
+        "there must be a word for each value"
+    );
+}
+
+fn assert_packed(words: usize, bytes: usize) {
+    assert_eq!(words % 4, 0, "the number of words must be a multiple of 4");
+    assert_eq!(
+        bytes,
+        words / 4 * 5,
+        "there must be 5 bytes for every 4 words"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clip_reserved, clip_reserved_slice, deinterleave, interleave, is_reserved, pack, unpack,
+    };
+
+    #[test]
+    fn reserved() {
+        for word in 0..1024 {
+            assert_eq!(is_reserved(word), word <= 3 || word >= 1020);
+            assert!(!is_reserved(clip_reserved(word)));
+        }
+
+        let mut words = [0, 3, 4, 512, 1019, 1020, 1023];
+        clip_reserved_slice(&mut words);
+        assert_eq!(words, [4, 4, 4, 512, 1019, 1019, 1019]);
+    }
+
+    #[test]
+    fn packing() {
+        let words = [0x3FF, 0, 0x155, 0x2AA, 1, 2, 3, 4];
+        let mut bytes = [0; 10];
+        pack(&words, &mut bytes);
+        assert_eq!(
+            bytes,
+            [0xFF, 0xC0, 0x05, 0x56, 0xAA, 0x00, 0x40, 0x20, 0x0C, 0x04]
+        );
+
+        let mut unpacked = [0; 8];
+        unpack(&bytes, &mut unpacked);
+        assert_eq!(unpacked, words);
+
+        let all: Vec<u16> = (0..1024).collect();
+        let mut bytes = vec![0; 1280];
+        let mut unpacked = vec![0; 1024];
+        pack(&all, &mut bytes);
+        unpack(&bytes, &mut unpacked);
+        assert_eq!(unpacked, all);
+    }
+
+    #[test]
+    fn interleaving() {
+        let luma = [64, 100, 200, 940];
+        let cb = [512, 300];
+        let cr = [512, 700];
+
+        let mut words = [0; 8];
+        interleave(&luma, &cb, &cr, &mut words);
+        assert_eq!(words, [512, 64, 512, 100, 300, 200, 700, 940]);
+
+        let mut line = ([0; 4], [0; 2], [0; 2]);
+        deinterleave(&words, &mut line.0, &mut line.1, &mut line.2);
+        assert_eq!(line, (luma, cb, cr));
+    }
+
+    #[test]
+    #[should_panic(expected = "the value must fit in the bit depth")]
+    fn pack_overflow() {
+        pack(&[1024, 0, 0, 0], &mut [0; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "there must be 5 bytes for every 4 words")]
+    fn pack_length() {
+        pack(&[0; 8], &mut [0; 5]);
+    }
+}
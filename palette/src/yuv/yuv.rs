@@ -3,18 +3,19 @@ use core::marker::PhantomData;
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 use float::Float;
 
+use converter::Endpoint;
 use encoding::Linear;
 use luma::{Luma, LumaStandard};
 use rgb::{Rgb, RgbSpace};
 use yuv::{DifferenceFn, YuvStandard};
 use {clamp};
-use {Component, FromColor, Limited, Pixel};
+use {Component, FromColorUnclamped, Limited, Mat3, Pixel};
 
 /// Generic YUV.
 ///
 /// YUV is an alternate representation for an RGB color space with a focus on separating luminance
 /// from chroma components.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_rgb_space = "S::RgbSpace"]
@@ -43,6 +44,22 @@ pub struct Yuv<S: YuvStandard, T: Float = f32> {
 
 impl<S: YuvStandard, T: Float> Copy for Yuv<S, T> {}
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Zeroable for Yuv<S, T>
+where
+    S: YuvStandard,
+    T: Float + ::bytemuck::Zeroable,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<S, T> ::bytemuck::Pod for Yuv<S, T>
+where
+    S: YuvStandard + 'static,
+    T: Float + ::bytemuck::Pod,
+{
+}
+
 impl<S: YuvStandard, T: Float> Clone for Yuv<S, T> {
     fn clone(&self) -> Yuv<S, T> {
         *self
@@ -80,6 +97,47 @@ impl<S: YuvStandard, T: Float> Yuv<S, T> {
     }
 }
 
+/// YUV is decoded into linear RGB of the underlying space, so a `Converter` between RGB and YUV
+/// fuses their RGB matrices without going through XYZ.
+impl<S, T> Endpoint for Yuv<S, T>
+where
+    S: YuvStandard,
+    T: Component + Float,
+{
+    type Scalar = T;
+    type Space = ();
+
+    fn to_xyz_matrix(space: &()) -> Mat3<f64> {
+        Rgb::<(S::RgbSpace, S::TransferFn), T>::to_xyz_matrix(space)
+    }
+
+    fn white_point(space: &()) -> [f64; 3] {
+        Rgb::<(S::RgbSpace, S::TransferFn), T>::white_point(space)
+    }
+
+    fn into_linear(self, space: &()) -> [T; 3] {
+        let weights = S::DifferenceFn::luminance::<T>();
+        let red = self.luminance + S::DifferenceFn::denormalize_red(self.red_diff);
+        let blue = self.luminance + S::DifferenceFn::denormalize_blue(self.blue_diff);
+        let green = (self.luminance - weights[0]*red - weights[2]*blue) / weights[1];
+
+        let rgb = Rgb::<(S::RgbSpace, S::TransferFn), T>::new(red, green, blue);
+        Endpoint::into_linear(rgb, space)
+    }
+
+    fn from_linear(linear: [T; 3], space: &()) -> Self {
+        let rgb = <Rgb<(S::RgbSpace, S::TransferFn), T> as Endpoint>::from_linear(linear, space);
+        let weights = S::DifferenceFn::luminance::<T>();
+        let luminance = weights[0]*rgb.red + weights[1]*rgb.green + weights[2]*rgb.blue;
+
+        Yuv::new(
+            luminance,
+            S::DifferenceFn::normalize_blue(rgb.blue - luminance),
+            S::DifferenceFn::normalize_red(rgb.red - luminance),
+        )
+    }
+}
+
 impl<S, T> Limited for Yuv<S, T>
 where
     S: YuvStandard,
@@ -188,6 +246,27 @@ where
     }
 }
 
+///Samples the luminance uniformly in `[0.0, 1.0)` and the color differences
+///uniformly in `[-0.5, 0.5)`.
+#[cfg(feature = "rand")]
+impl<S, T> ::rand::distributions::Distribution<Yuv<S, T>> for ::rand::distributions::Standard
+where
+    S: YuvStandard,
+    T: Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Yuv<S, T> {
+        let half = T::one() / (T::one() + T::one());
+
+        Yuv {
+            luminance: rng.gen(),
+            blue_diff: rng.gen::<T>() - half,
+            red_diff: rng.gen::<T>() - half,
+            standard: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Yuv};
@@ -249,6 +328,20 @@ mod tests {
         assert_yuv_eq_rgb!(BT601_625, (0.413, 0.587, 0.587), (1.0, 0.0, 1.0), ulps);
     }
 
+    #[test]
+    fn converter() {
+        use converter::Converter;
+        use {FromColorUnclamped, Srgb};
+
+        let to_yuv = Converter::<Srgb<f64>, Yuv<BT601_525, f64>>::new();
+        let from_yuv = Converter::<Yuv<BT601_525, f64>, Srgb<f64>>::new();
+
+        let color = Srgb::new(0.8, 0.4, 0.2);
+        let yuv = to_yuv.convert(color);
+        assert_relative_eq!(yuv, Yuv::from_rgb(color.into_linear()), epsilon = 1e-12);
+        assert_relative_eq!(from_yuv.convert(yuv), color, epsilon = 1e-12);
+    }
+
     #[test]
     fn bt709_baseline() {
         // Otherwise we trust the table tests from the other encodings and the hardcoded constants.
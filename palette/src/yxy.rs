@@ -8,7 +8,7 @@ use encoding::pixel::RawPixel;
 use luma::LumaStandard;
 use white_point::{D65, WhitePoint};
 use {Alpha, Luma, Xyz};
-use {Component, ComponentWise, IntoColor, Limited, Mix, Pixel, Shade};
+use {Component, ComponentWise, IntoColorUnclamped, Limited, Mix, Pixel, Shade};
 
 /// CIE 1931 Yxy (xyY) with an alpha component. See the [`Yxya` implementation
 /// in `Alpha`](struct.Alpha.html#Yxya).
@@ -21,7 +21,7 @@ pub type Yxya<Wp = D65, T = f32> = Alpha<Yxy<Wp, T>, T>;
 ///for the color spaces are a plot of this color space's x and y coordiantes.
 ///
 ///Conversions and operations on this color space depend on the white point.
-#[derive(Debug, PartialEq, FromColor, Pixel)]
+#[derive(Debug, PartialEq, FromColorUnclamped, Pixel, WithAlpha)]
 #[cfg_attr(feature = "serializing", derive(Serialize, Deserialize))]
 #[palette_internal]
 #[palette_white_point = "Wp"]
@@ -60,6 +60,22 @@ where
 {
 }
 
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Zeroable for Yxy<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Zeroable,
+    Wp: WhitePoint,
+{
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<Wp, T> ::bytemuck::Pod for Yxy<Wp, T>
+where
+    T: Component + Float + ::bytemuck::Pod,
+    Wp: WhitePoint + 'static,
+{
+}
+
 impl<Wp, T> Clone for Yxy<Wp, T>
 where
     T: Component + Float,
@@ -572,6 +588,24 @@ where
     }
 }
 
+///Samples each component uniformly, in `[0.0, 1.0)`.
+#[cfg(feature = "rand")]
+impl<Wp, T> ::rand::distributions::Distribution<Yxy<Wp, T>> for ::rand::distributions::Standard
+where
+    Wp: WhitePoint,
+    T: Component + Float,
+    ::rand::distributions::Standard: ::rand::distributions::Distribution<T>,
+{
+    fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> Yxy<Wp, T> {
+        Yxy {
+            x: rng.gen(),
+            y: rng.gen(),
+            luma: rng.gen(),
+            white_point: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Yxy;
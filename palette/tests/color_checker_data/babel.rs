@@ -6,7 +6,7 @@ The Rgb colors in this data appear to be adapted to the D50 white_point from the
 
 */
 
-use palette::{Xyz, Yxy, Lab, IntoColor};
+use palette::{Xyz, Yxy, Lab, IntoColorUnclamped};
 use palette::white_point::D50;
 
 use super::load_data::{ColorCheckerRaw, load_babel};
@@ -8,7 +8,7 @@ Tests XYZ and YXY conversion
 
 
 use csv;
-use palette::{Xyz, Yxy,IntoColor};
+use palette::{Xyz, Yxy,IntoColorUnclamped};
 use palette::white_point::D65;
 
 #[derive(Deserialize, PartialEq)]
@@ -2,7 +2,7 @@
 List of color from www.colormine.org
 */
 use csv;
-use palette::{Hsl, Hsv, Hwb, IntoColor, Lab, Lch, LinSrgb, Srgb, Xyz, Yxy};
+use palette::{Hsl, Hsv, Hwb, IntoColorUnclamped, Lab, Lch, LinSrgb, Srgb, Xyz, Yxy};
 use palette::white_point::D65;
 
 #[derive(Deserialize, PartialEq)]
@@ -1,4 +1,4 @@
-use palette::{IntoColor, Lab, Lch};
+use palette::{IntoColorUnclamped, Lab, Lch};
 
 #[test]
 fn lab_lch_green() {
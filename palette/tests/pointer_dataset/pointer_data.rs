@@ -14,7 +14,7 @@ Note: The xyz and yxy conversions do not use the updated conversion formula. So
 use num_traits::{NumCast, ToPrimitive};
 use palette::float::Float;
 use csv;
-use palette::{Component, IntoColor, Lab, Lch, Xyz};
+use palette::{Component, IntoColorUnclamped, Lab, Lch, Xyz};
 use palette::white_point::WhitePoint;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -0,0 +1,3 @@
+pub use self::with_alpha::derive as derive_with_alpha;
+
+mod with_alpha;
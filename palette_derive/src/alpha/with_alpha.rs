@@ -0,0 +1,122 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, Type};
+
+use meta::{self, DataMetaParser, IdentOrIndex, MetaParser};
+use util;
+
+pub fn derive(tokens: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident,
+        attrs,
+        generics: original_generics,
+        data,
+        ..
+    } = parse_macro_input!(tokens);
+    let mut generics = original_generics.clone();
+
+    let meta: WithAlphaMeta = meta::parse_attributes(attrs);
+    let item_meta: WithAlphaItemMeta = meta::parse_data_attributes(data);
+
+    let trait_path = util::path(&["WithAlpha"], meta.internal);
+    let component_trait_path = util::path(&["Component"], meta.internal);
+
+    let implementation = if let Some((alpha_property, alpha_type)) = item_meta.alpha_property {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#alpha_type: #component_trait_path));
+        let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #trait_path<#alpha_type> for #ident #type_generics #where_clause {
+                type Color = Self;
+                type WithAlpha = Self;
+
+                fn with_alpha(mut self, alpha: #alpha_type) -> Self::WithAlpha {
+                    self.#alpha_property = alpha;
+                    self
+                }
+
+                fn without_alpha(mut self) -> Self::Color {
+                    self.#alpha_property = <#alpha_type as #component_trait_path>::max_intensity();
+                    self
+                }
+
+                fn split(mut self) -> (Self::Color, #alpha_type) {
+                    let alpha = self.#alpha_property;
+                    self.#alpha_property = <#alpha_type as #component_trait_path>::max_intensity();
+                    (self, alpha)
+                }
+            }
+        }
+    } else {
+        let (_, type_generics, _) = original_generics.split_for_impl();
+        let alpha_path = util::path(&["Alpha"], meta.internal);
+        let alpha_type: Type = parse_quote!(_A);
+        generics
+            .params
+            .push(parse_quote!(_A: #component_trait_path));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #trait_path<#alpha_type> for #ident #type_generics #where_clause {
+                type Color = Self;
+                type WithAlpha = #alpha_path<Self, #alpha_type>;
+
+                fn with_alpha(self, alpha: #alpha_type) -> Self::WithAlpha {
+                    #alpha_path {
+                        color: self,
+                        alpha: alpha,
+                    }
+                }
+
+                fn without_alpha(self) -> Self::Color {
+                    self
+                }
+
+                fn split(self) -> (Self::Color, #alpha_type) {
+                    (self, <#alpha_type as #component_trait_path>::max_intensity())
+                }
+            }
+        }
+    };
+
+    let result = util::bundle_impl("WithAlpha", ident, meta.internal, implementation);
+    result.into()
+}
+
+#[derive(Default)]
+struct WithAlphaMeta {
+    internal: bool,
+}
+
+impl MetaParser for WithAlphaMeta {
+    fn internal(&mut self) {
+        self.internal = true;
+    }
+
+    fn parse_attribute(&mut self, _attribute_name: Ident, _attribute_tts: TokenStream2) {}
+}
+
+#[derive(Default)]
+struct WithAlphaItemMeta {
+    alpha_property: Option<(IdentOrIndex, Type)>,
+}
+
+impl DataMetaParser for WithAlphaItemMeta {
+    fn parse_struct_field_attribute(
+        &mut self,
+        field_name: IdentOrIndex,
+        ty: Type,
+        attribute_name: Ident,
+        attribute_tts: TokenStream2,
+    ) {
+        if attribute_name == "palette_alpha" {
+            meta::assert_empty_attribute(&attribute_name, attribute_tts);
+            self.alpha_property = Some((field_name, ty));
+        }
+    }
+}
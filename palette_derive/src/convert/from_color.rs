@@ -129,7 +129,7 @@ pub fn derive(tokens: TokenStream) -> TokenStream {
         })
         .collect();
 
-    let trait_path = util::path(&["FromColor"], meta.internal);
+    let trait_path = util::path(&["FromColorUnclamped"], meta.internal);
     let from_color_impl = quote!{
         #[automatically_derived]
         impl #impl_generics #trait_path<#white_point, #component> for #ident #type_generics #where_clause {
@@ -138,7 +138,7 @@ pub fn derive(tokens: TokenStream) -> TokenStream {
     };
 
     let result = util::bundle_impl(
-        "FromColor",
+        "FromColorUnclamped",
         ident,
         meta.internal,
         quote! {
@@ -308,7 +308,7 @@ fn prepare_from_impl(
 
     let method_name = Ident::new(&format!("from_{}", color.to_lowercase()), Span::call_site());
 
-    let trait_path = util::path(&["FromColor"], meta.internal);
+    let trait_path = util::path(&["FromColorUnclamped"], meta.internal);
     let alpha_path = util::path(&["Alpha"], meta.internal);
 
     let white_point = shared::white_point_type(meta.white_point.clone(), meta.internal);
@@ -64,7 +64,7 @@ pub fn derive(tokens: TokenStream) -> TokenStream {
         meta.internal,
     );
 
-    let trait_path = util::path(&["IntoColor"], meta.internal);
+    let trait_path = util::path(&["IntoColorUnclamped"], meta.internal);
     let into_color_impl = quote!{
         #[automatically_derived]
         impl #impl_generics #trait_path<#white_point, #component> for #ident #type_generics #where_clause {
@@ -108,7 +108,7 @@ pub fn derive(tokens: TokenStream) -> TokenStream {
     });
 
     let result = util::bundle_impl(
-        "IntoColor",
+        "IntoColorUnclamped",
         ident.clone(),
         meta.internal,
         quote! {
@@ -214,7 +214,7 @@ fn prepare_into_impl(
 
     let method_name = Ident::new(&format!("into_{}", color.to_lowercase()), Span::call_site());
 
-    let trait_path = util::path(&["IntoColor"], meta.internal);
+    let trait_path = util::path(&["IntoColorUnclamped"], meta.internal);
     let alpha_path = util::path(&["Alpha"], meta.internal);
 
     let white_point = shared::white_point_type(meta.white_point.clone(), meta.internal);
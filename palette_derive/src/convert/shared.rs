@@ -176,7 +176,7 @@ pub fn generate_methods(
         let color_path = util::path(&["Xyz"], internal);
         let method_name = Ident::new(&format!("{}_xyz", convert_direction), Span::call_site());
         let into_temporary_name = Ident::new(&format!("into_{}", xyz_convert), Span::call_site());
-        let into_color_trait_path = util::path(&["IntoColor"], internal);
+        let into_color_trait_path = util::path(&["IntoColorUnclamped"], internal);
         let convert_function = Ident::new(
             &format!("{}_{}", convert_direction, xyz_convert),
             Span::call_site(),
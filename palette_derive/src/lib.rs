@@ -15,25 +15,31 @@ mod util;
 mod meta;
 mod convert;
 mod encoding;
+mod alpha;
 
 const COLOR_TYPES: &[&str] = &[
     "Rgb", "Luma", "Hsl", "Hsv", "Hwb", "Lab", "Lch", "Xyz", "Yxy"
 ];
 
-#[proc_macro_derive(FromColor,
+#[proc_macro_derive(FromColorUnclamped,
                     attributes(palette_internal, palette_white_point, palette_component,
                                palette_manual_from, palette_rgb_space, palette_alpha))]
 pub fn derive_from_color(tokens: TokenStream) -> TokenStream {
     convert::derive_from_color(tokens)
 }
 
-#[proc_macro_derive(IntoColor,
+#[proc_macro_derive(IntoColorUnclamped,
                     attributes(palette_internal, palette_white_point, palette_component,
                                palette_manual_into, palette_rgb_space, palette_alpha))]
 pub fn derive_into_color(tokens: TokenStream) -> TokenStream {
     convert::derive_into_color(tokens)
 }
 
+#[proc_macro_derive(WithAlpha, attributes(palette_internal, palette_alpha))]
+pub fn derive_with_alpha(tokens: TokenStream) -> TokenStream {
+    alpha::derive_with_alpha(tokens)
+}
+
 #[proc_macro_derive(Pixel,
                     attributes(palette_internal, palette_unsafe_same_layout_as,
                                palette_unsafe_zero_sized))]